@@ -0,0 +1,140 @@
+//! Compares finding the overlapping faces of a large net by testing every pair of triangles against
+//! the sweep and prune used by `find_overlapping_triangles()`. The nets are unfolded from spheres
+//! that are made by subdividing an icosahedron, and both are benchmarked in the same group (named
+//! after the number of faces of each net), so that criterion reports them side by side. Run with:
+//!
+//! ```shell
+//! cargo bench --no-default-features --bench overlaps
+//! ```
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use durer::utils::{find_overlapping_triangles, find_overlapping_triangles_brute_force};
+use durer::{GoalMesh, LoadOptions, UnfoldStrategy};
+
+use glam::Vec3;
+
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::io::Cursor;
+use std::path::Path;
+
+/// The numbers of times that the faces of the icosahedron are subdivided: each subdivision splits
+/// every triangle into 4.
+const SUBDIVISIONS: [usize; 3] = [3, 4, 5];
+
+/// Returns the contents of an .obj file with a unit sphere that is made by subdividing the faces of
+/// an icosahedron the specified number of times, which has `20 * 4^subdivisions` triangular faces.
+fn icosphere(subdivisions: usize) -> String {
+    let t = (1.0 + 5.0f32.sqrt()) / 2.0;
+    let mut vertices = vec![
+        Vec3::new(-1.0, t, 0.0),
+        Vec3::new(1.0, t, 0.0),
+        Vec3::new(-1.0, -t, 0.0),
+        Vec3::new(1.0, -t, 0.0),
+        Vec3::new(0.0, -1.0, t),
+        Vec3::new(0.0, 1.0, t),
+        Vec3::new(0.0, -1.0, -t),
+        Vec3::new(0.0, 1.0, -t),
+        Vec3::new(t, 0.0, -1.0),
+        Vec3::new(t, 0.0, 1.0),
+        Vec3::new(-t, 0.0, -1.0),
+        Vec3::new(-t, 0.0, 1.0),
+    ]
+    .into_iter()
+    .map(|vertex| vertex.normalize())
+    .collect::<Vec<_>>();
+    let mut faces = vec![
+        [0, 11, 5],
+        [0, 5, 1],
+        [0, 1, 7],
+        [0, 7, 10],
+        [0, 10, 11],
+        [1, 5, 9],
+        [5, 11, 4],
+        [11, 10, 2],
+        [10, 7, 6],
+        [7, 1, 8],
+        [3, 9, 4],
+        [3, 4, 2],
+        [3, 2, 6],
+        [3, 6, 8],
+        [3, 8, 9],
+        [4, 9, 5],
+        [2, 4, 11],
+        [6, 2, 10],
+        [8, 6, 7],
+        [9, 8, 1],
+    ];
+
+    for _ in 0..subdivisions {
+        // The midpoint of each edge is shared by the two triangles on either side of it
+        let mut midpoints: HashMap<(usize, usize), usize> = HashMap::new();
+        let mut midpoint = |a: usize, b: usize| {
+            *midpoints.entry((a.min(b), a.max(b))).or_insert_with(|| {
+                vertices.push(((vertices[a] + vertices[b]) * 0.5).normalize());
+                vertices.len() - 1
+            })
+        };
+
+        faces = faces
+            .iter()
+            .flat_map(|&[a, b, c]| {
+                let (ab, bc, ca) = (midpoint(a, b), midpoint(b, c), midpoint(c, a));
+                vec![[a, ab, ca], [b, bc, ab], [c, ca, bc], [ab, bc, ca]]
+            })
+            .collect();
+    }
+
+    // Vertex indices in .obj files start at 1
+    let mut obj = String::new();
+    for vertex in vertices.iter() {
+        writeln!(obj, "v {} {} {}", vertex.x(), vertex.y(), vertex.z()).unwrap();
+    }
+    for [a, b, c] in faces.iter() {
+        writeln!(obj, "f {} {} {}", a + 1, b + 1, c + 1).unwrap();
+    }
+    obj
+}
+
+fn overlaps(c: &mut Criterion) {
+    // Testing every pair of faces of the largest net takes a few seconds, so only take the
+    // minimum number of samples
+    let mut group = c.benchmark_group("overlaps");
+    group.sample_size(10);
+
+    for &subdivisions in SUBDIVISIONS.iter() {
+        let goal_mesh = GoalMesh::from_obj_reader(
+            &mut Cursor::new(icosphere(subdivisions)),
+            Path::new("."),
+            &LoadOptions::default(),
+        )
+        .expect("Failed to load goal mesh");
+        let net = goal_mesh
+            .unfold(0, UnfoldStrategy::BreadthFirst)
+            .expect("Failed to unfold goal mesh");
+
+        // Both have to find the same pairs of faces for the comparison to be fair
+        let tolerance = goal_mesh.tolerances().overlap;
+        assert_eq!(
+            find_overlapping_triangles(&net.positions, tolerance),
+            find_overlapping_triangles_brute_force(&net.positions, tolerance)
+        );
+
+        let faces = goal_mesh.face_count();
+        group.bench_with_input(
+            BenchmarkId::new("brute_force", faces),
+            &net.positions,
+            |b, positions| b.iter(|| find_overlapping_triangles_brute_force(positions, tolerance)),
+        );
+        group.bench_with_input(
+            BenchmarkId::new("sweep_and_prune", faces),
+            &net.positions,
+            |b, positions| b.iter(|| find_overlapping_triangles(positions, tolerance)),
+        );
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, overlaps);
+criterion_main!(benches);
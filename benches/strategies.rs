@@ -0,0 +1,129 @@
+//! Compares how often the steepest-edge and minimum-weight spanning tree strategies (among others)
+//! produce a net with overlaps, by unfolding each of the Platonic solids (and stretched copies of
+//! them, whose edges have different lengths) from every one of their faces, along with how long
+//! each strategy takes to unfold all of them. Run with:
+//!
+//! ```shell
+//! cargo bench --no-default-features --bench strategies
+//! ```
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use durer::{GoalMesh, LoadOptions, UnfoldStrategy};
+
+use std::path::Path;
+
+/// The goal meshes that are unfolded, from the `goal_meshes` folder.
+const SOLIDS: [&str; 5] = [
+    "tetrahedron",
+    "hexahedron",
+    "octahedron",
+    "dodecahedron",
+    "icosahedron",
+];
+
+/// The strategies that are compared (breadth-first and depth-first traversals are included as a
+/// baseline).
+const STRATEGIES: [UnfoldStrategy; 5] = [
+    UnfoldStrategy::BreadthFirst,
+    UnfoldStrategy::DepthFirst,
+    UnfoldStrategy::SteepestEdge,
+    UnfoldStrategy::MinWeightSpanningTree,
+    UnfoldStrategy::Strip,
+];
+
+/// The factors that the x-, y-, and z-coordinates of the stretched copies are multiplied by.
+const STRETCH: [f32; 3] = [1.0, 2.5, 0.6];
+
+/// Multiplies the coordinates of every vertex of the .obj file by `STRETCH`.
+fn stretch(obj: &str) -> String {
+    obj.lines()
+        .map(|line| match line.strip_prefix("v ") {
+            Some(coordinates) => {
+                let stretched = coordinates
+                    .split_whitespace()
+                    .zip(STRETCH.iter())
+                    .map(|(coordinate, factor)| {
+                        (coordinate.parse::<f32>().unwrap() * factor).to_string()
+                    })
+                    .collect::<Vec<_>>();
+                format!("v {}\n", stretched.join(" "))
+            }
+            None => format!("{}\n", line),
+        })
+        .collect()
+}
+
+fn strategies(c: &mut Criterion) {
+    let mut goal_meshes = vec![];
+    for name in SOLIDS.iter() {
+        let path = Path::new("goal_meshes").join(format!("{}.obj", name));
+        let obj = std::fs::read_to_string(&path).expect("Failed to read goal mesh");
+
+        for (label, obj) in [
+            (name.to_string(), obj.clone()),
+            (format!("{} (stretched)", name), stretch(&obj)),
+        ]
+        .iter()
+        {
+            let goal_mesh = GoalMesh::from_obj_reader(
+                &mut obj.as_bytes(),
+                Path::new("goal_meshes"),
+                &LoadOptions::default(),
+            )
+            .expect("Failed to load goal mesh");
+            goal_meshes.push((label.clone(), goal_mesh));
+        }
+    }
+
+    // The number of overlapping nets doesn't change between runs, so it is only reported once
+    // (criterion only reports the times)
+    println!(
+        "{:<24}  {:<26}  {:>5}  {:>11}",
+        "goal mesh", "strategy", "faces", "overlapping"
+    );
+    let mut totals = vec![(0, 0); STRATEGIES.len()];
+    for (label, goal_mesh) in goal_meshes.iter() {
+        for (strategy, total) in STRATEGIES.iter().zip(totals.iter_mut()) {
+            let overlapping = goal_mesh
+                .count_overlaps_by_reference_face(*strategy)
+                .iter()
+                .filter(|&&overlaps| overlaps > 0)
+                .count();
+
+            println!(
+                "{:<24}  {:<26}  {:>5}  {:>11}",
+                label,
+                format!("{:?}", strategy),
+                goal_mesh.face_count(),
+                overlapping
+            );
+            total.0 += overlapping;
+            total.1 += goal_mesh.face_count();
+        }
+    }
+    for (strategy, (overlapping, faces)) in STRATEGIES.iter().zip(totals.iter()) {
+        println!(
+            "{:?}: {} of {} root faces ({:.1}%) result in a net with overlaps",
+            strategy,
+            overlapping,
+            faces,
+            100.0 * *overlapping as f32 / *faces as f32
+        );
+    }
+
+    // Each strategy unfolds every goal mesh from every one of its faces
+    let mut group = c.benchmark_group("strategies");
+    for strategy in STRATEGIES.iter() {
+        group.bench_function(format!("{:?}", strategy), |b| {
+            b.iter(|| {
+                for (_, goal_mesh) in goal_meshes.iter() {
+                    goal_mesh.count_overlaps_by_reference_face(*strategy);
+                }
+            })
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, strategies);
+criterion_main!(benches);
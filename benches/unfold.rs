@@ -0,0 +1,112 @@
+//! Compares unfolding a large goal mesh from several reference faces by reloading the .obj file
+//! each time (which rebuilds the half-edge mesh) against loading it once and reusing it, along with
+//! how long loading it takes on its own. Run with:
+//!
+//! ```shell
+//! cargo bench --no-default-features
+//! ```
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use durer::{GoalMesh, LoadOptions, UnfoldStrategy};
+
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+/// The number of reference faces that the goal mesh is unfolded from.
+const UNFOLDS: usize = 5;
+
+/// Writes a UV sphere with the specified number of rings and segments to an .obj file, which has
+/// `2 * segments * (rings - 1)` triangular faces.
+fn write_sphere(path: &Path, rings: usize, segments: usize) -> std::io::Result<()> {
+    let mut file = BufWriter::new(File::create(path)?);
+
+    // The poles, followed by the vertices of every ring (from top to bottom)
+    writeln!(file, "v 0 1 0")?;
+    writeln!(file, "v 0 -1 0")?;
+    for ring in 1..rings {
+        let polar = std::f32::consts::PI * ring as f32 / rings as f32;
+        for segment in 0..segments {
+            let azimuth = std::f32::consts::PI * 2.0 * segment as f32 / segments as f32;
+            writeln!(
+                file,
+                "v {} {} {}",
+                polar.sin() * azimuth.cos(),
+                polar.cos(),
+                -polar.sin() * azimuth.sin()
+            )?;
+        }
+    }
+
+    // Vertex indices in .obj files start at 1
+    let vertex = |ring: usize, segment: usize| 3 + (ring - 1) * segments + segment % segments;
+    for segment in 0..segments {
+        writeln!(
+            file,
+            "f 1 {} {}",
+            vertex(1, segment),
+            vertex(1, segment + 1)
+        )?;
+        writeln!(
+            file,
+            "f 2 {} {}",
+            vertex(rings - 1, segment + 1),
+            vertex(rings - 1, segment)
+        )?;
+    }
+    for ring in 1..rings - 1 {
+        for segment in 0..segments {
+            let (a, b) = (vertex(ring, segment), vertex(ring, segment + 1));
+            let (c, d) = (vertex(ring + 1, segment + 1), vertex(ring + 1, segment));
+            writeln!(file, "f {} {} {}", a, d, c)?;
+            writeln!(file, "f {} {} {}", a, c, b)?;
+        }
+    }
+
+    Ok(())
+}
+
+fn unfold(c: &mut Criterion) {
+    let path = std::env::temp_dir().join("durer_bench_sphere.obj");
+    write_sphere(&path, 51, 100).expect("Failed to write goal mesh");
+
+    let goal_mesh =
+        GoalMesh::from_obj(&path, &LoadOptions::default()).expect("Failed to load goal mesh");
+    let faces = goal_mesh.face_count();
+    let roots = (0..UNFOLDS)
+        .map(|i| i * faces / UNFOLDS)
+        .collect::<Vec<_>>();
+
+    // Loading the goal mesh takes about half a second, so only take the minimum number of samples
+    let mut group = c.benchmark_group(format!("unfold_{}_faces_{}_times", faces, UNFOLDS));
+    group.sample_size(10);
+
+    group.bench_function("load", |b| {
+        b.iter(|| GoalMesh::from_obj(&path, &LoadOptions::default()).unwrap())
+    });
+    group.bench_function("reload_before_every_unfold", |b| {
+        b.iter(|| {
+            for &root in roots.iter() {
+                let goal_mesh = GoalMesh::from_obj(&path, &LoadOptions::default()).unwrap();
+                goal_mesh
+                    .unfold(root, UnfoldStrategy::BreadthFirst)
+                    .unwrap();
+            }
+        })
+    });
+    group.bench_function("reuse_loaded_goal_mesh", |b| {
+        b.iter(|| {
+            for &root in roots.iter() {
+                goal_mesh
+                    .unfold(root, UnfoldStrategy::BreadthFirst)
+                    .unwrap();
+            }
+        })
+    });
+
+    group.finish();
+    std::fs::remove_file(&path).ok();
+}
+
+criterion_group!(benches, unfold);
+criterion_main!(benches);
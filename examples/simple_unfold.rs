@@ -0,0 +1,43 @@
+//! Unfolds a goal mesh and writes the net (with glue tabs) to an .svg file, using only the library
+//! (i.e. without Bevy). Run with:
+//!
+//! ```shell
+//! cargo run --no-default-features --example simple_unfold -- goal_meshes/icosahedron.obj net.svg
+//! ```
+
+use durer::export::svg::{write_svg, SvgOptions};
+use durer::export::NetStyle;
+use durer::{generate_tabs, unfold_obj, TabOptions, UnfoldOptions, DEFAULT_TAB_WIDTH};
+
+use glam::Vec3;
+
+use std::path::Path;
+
+/// The number of units of the .svg file (i.e. pixels) that make up a single unit of the goal mesh.
+const SCALE: f32 = 100.0;
+
+fn main() {
+    let args = std::env::args().collect::<Vec<_>>();
+    let (input, output) = match args.as_slice() {
+        [_, input, output] => (Path::new(input), Path::new(output)),
+        _ => panic!("Usage: simple_unfold <INPUT.obj> <OUTPUT.svg>"),
+    };
+
+    let mut net = unfold_obj(input, &UnfoldOptions::default()).expect("Failed to unfold goal mesh");
+    net.positions.iter_mut().for_each(|point| *point *= SCALE);
+    let tab_options = TabOptions {
+        width: DEFAULT_TAB_WIDTH * SCALE,
+        ..TabOptions::default()
+    };
+    let tabs = generate_tabs(&net.positions, &net.edges, &tab_options);
+
+    let colors = vec![Vec3::new(0.55, 0.79, 0.9); net.polygons.len()];
+    write_svg(
+        output,
+        &net,
+        &tabs,
+        &NetStyle::new(&colors),
+        &SvgOptions::default(),
+    )
+    .expect("Failed to write .svg file");
+}
@@ -1,4 +1,9 @@
+use crate::utils::{hsv_to_rgb, lch_to_srgb, rgb_to_hsv, srgb_to_lch};
+
 use glam::Vec3;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
 use serde::{Deserialize, Serialize};
 
 /// A struct representing a color palette used for rendering an unfolded net.
@@ -9,6 +14,11 @@ pub struct ColorPalette {
 
     // A list of colors that will be applied to the faces of the net
     pub polygons: Vec<Vec3>,
+
+    // An (optional) list of evenly spaced colors that make up the gradient used by
+    // `ColorMode::Gradient` - if this is empty, the polygon colors are used instead
+    #[serde(default)]
+    pub gradient: Vec<Vec3>,
 }
 
 impl ColorPalette {
@@ -16,6 +26,449 @@ impl ColorPalette {
         ColorPalette {
             background: *background,
             polygons: polygons.clone(),
+            gradient: vec![],
+        }
+    }
+
+    /// Returns the gradient described by this palette, which is interpolated in the specified
+    /// color space.
+    pub fn gradient(&self, color_space: ColorSpace) -> Gradient {
+        if self.gradient.is_empty() {
+            Gradient::linear_spacing(&self.polygons, color_space)
+        } else {
+            Gradient::linear_spacing(&self.gradient, color_space)
+        }
+    }
+}
+
+/// The different ways in which the faces of the net can be colored.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ColorMode {
+    // Cycle through the colors of the palette
+    #[default]
+    Palette,
+
+    // Color each face based on its distance from the reference face in the spanning tree
+    Gradient,
+
+    // Give each face a random color (that differs from the colors of its neighbors)
+    Random,
+
+    // Use the diffuse color of each face's material in the .obj file (faces without a material
+    // cycle through the colors of the palette instead)
+    Material,
+
+    // Color each face based on the direction that it faces on the goal mesh, by mapping the x-, y-,
+    // and z-components of its normal to red, green, and blue
+    Normal,
+
+    // Use the average color of each face's vertices in the .obj file (faces with any vertices
+    // without a color cycle through the colors of the palette instead)
+    Vertex,
+}
+
+impl std::str::FromStr for ColorMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "palette" => Ok(ColorMode::Palette),
+            "gradient" => Ok(ColorMode::Gradient),
+            "random" => Ok(ColorMode::Random),
+            "material" => Ok(ColorMode::Material),
+            "normal" => Ok(ColorMode::Normal),
+            "vertex" => Ok(ColorMode::Vertex),
+            _ => Err(format!("Unknown color mode: {:?}", s)),
+        }
+    }
+}
+
+/// The color spaces that a gradient can be interpolated in.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum ColorSpace {
+    // Interpolate each (SRGB) channel separately, which can produce muddy colors halfway between
+    // two very different hues
+    #[default]
+    Rgb,
+
+    // Interpolate the hue (the shorter way around the color wheel), saturation, and value
+    Hsv,
+
+    // Interpolate the lightness, chroma, and hue of CIE LCh, which is perceptually uniform
+    Lch,
+}
+
+impl std::str::FromStr for ColorSpace {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "rgb" => Ok(ColorSpace::Rgb),
+            "hsv" => Ok(ColorSpace::Hsv),
+            "lch" => Ok(ColorSpace::Lch),
+            _ => Err(format!("Unknown color space: {:?}", s)),
+        }
+    }
+}
+
+impl ColorSpace {
+    /// Interpolates between two (SRGB) colors in this color space, where `t` is in the range `0..1`.
+    pub fn lerp(&self, from: &Vec3, to: &Vec3, t: f32) -> Vec3 {
+        // Hues wrap around, so they are interpolated along the shorter arc between them. The hue
+        // of a gray is meaningless, so it takes on the hue of the other color instead
+        const ACHROMATIC: f32 = 1.0e-4;
+        let lerp_hue = |from: (f32, f32), to: (f32, f32)| {
+            let (mut from_hue, from_chroma) = from;
+            let (mut to_hue, to_chroma) = to;
+            if from_chroma < ACHROMATIC {
+                from_hue = to_hue;
+            } else if to_chroma < ACHROMATIC {
+                to_hue = from_hue;
+            }
+            let delta = (to_hue - from_hue + 0.5).rem_euclid(1.0) - 0.5;
+            (from_hue + delta * t).rem_euclid(1.0)
+        };
+
+        match self {
+            ColorSpace::Rgb => from.lerp(*to, t),
+            ColorSpace::Hsv => {
+                let (from_hue, from_saturation, from_value) = rgb_to_hsv(from);
+                let (to_hue, to_saturation, to_value) = rgb_to_hsv(to);
+                hsv_to_rgb(
+                    lerp_hue((from_hue, from_saturation), (to_hue, to_saturation)),
+                    from_saturation + (to_saturation - from_saturation) * t,
+                    from_value + (to_value - from_value) * t,
+                )
+            }
+            ColorSpace::Lch => {
+                let (from, to) = (srgb_to_lch(from), srgb_to_lch(to));
+                let mixed = from.lerp(to, t);
+                lch_to_srgb(&Vec3::new(
+                    mixed.x(),
+                    mixed.y(),
+                    lerp_hue((from.z(), from.y()), (to.z(), to.y())),
+                ))
+            }
+        }
+    }
+}
+
+/// A piecewise linear gradient between a list of (RGB) colors, or "stops."
+#[derive(Clone, Debug)]
+pub struct Gradient {
+    // Each stop's position along the gradient (in the range `0..1`, in ascending order) and color
+    stops: Vec<(f32, Vec3)>,
+
+    // The color space that neighboring stops are interpolated in
+    color_space: ColorSpace,
+}
+
+impl Gradient {
+    /// Creates a gradient with the specified stops, which will be sorted by their positions. The
+    /// positions are clamped to the range `0..1`, and stops without a position (i.e. NaN) are
+    /// ignored.
+    pub fn new(stops: &[(f32, Vec3)], color_space: ColorSpace) -> Gradient {
+        let mut stops = stops
+            .iter()
+            .filter(|(position, _)| !position.is_nan())
+            .map(|&(position, color)| (position.clamp(0.0, 1.0), color))
+            .collect::<Vec<_>>();
+        stops.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+        Gradient { stops, color_space }
+    }
+
+    /// Creates a gradient whose stops are evenly spaced along the range `0..1`.
+    pub fn linear_spacing(colors: &[Vec3], color_space: ColorSpace) -> Gradient {
+        let spacing = 1.0 / (colors.len().max(2) - 1) as f32;
+        Gradient::new(
+            &colors
+                .iter()
+                .enumerate()
+                .map(|(i, &color)| (i as f32 * spacing, color))
+                .collect::<Vec<_>>(),
+            color_space,
+        )
+    }
+
+    /// Returns the color of the gradient at `t`, which is clamped to the range `0..1` (where NaN is
+    /// the same as 0). Before the first stop and after the last stop, the gradient has the color of
+    /// the closest stop, so a gradient with a single stop has the same color everywhere, and a
+    /// gradient without any stops is black everywhere.
+    pub fn color_at(&self, t: f32) -> Vec3 {
+        let t = if t.is_nan() { 0.0 } else { t.clamp(0.0, 1.0) };
+        match self.stops.iter().position(|&(position, _)| position >= t) {
+            Some(0) => self.stops[0].1,
+            Some(i) => {
+                let (start, from) = self.stops[i - 1];
+                let (end, to) = self.stops[i];
+                self.color_space
+                    .lerp(&from, &to, (t - start) / (end - start))
+            }
+            None => self
+                .stops
+                .last()
+                .map(|&(_, color)| color)
+                .unwrap_or(Vec3::zero()),
+        }
+    }
+}
+
+/// Gives each polygon a random color, such that no two neighboring polygons (where `neighbors`
+/// lists the indices of the neighbors of each polygon) share the same color. The same seed always
+/// results in the same colors.
+///
+/// This is a simple greedy graph coloring: the polygons are visited in a random order, and each one
+/// picks one of the colors that none of its neighbors have picked so far. A new color is only
+/// created when every existing color is taken, so the net ends up with a small number of distinct
+/// colors. The hues of these colors are spread out as evenly as possible (starting from a random
+/// hue), so that they are easy to tell apart.
+pub fn random_coloring(neighbors: &[Vec<usize>], seed: u64) -> Vec<Vec3> {
+    // Stepping around the color wheel by the golden ratio keeps consecutive hues far apart
+    const HUE_STEP: f32 = 0.618_034;
+
+    let mut rng = StdRng::seed_from_u64(seed);
+    let first_hue: f32 = rng.gen();
+
+    let mut order = (0..neighbors.len()).collect::<Vec<_>>();
+    order.shuffle(&mut rng);
+
+    let mut colors: Vec<Vec3> = vec![];
+    let mut assigned: Vec<Option<usize>> = vec![None; neighbors.len()];
+    for polygon in order {
+        let available = (0..colors.len())
+            .filter(|&color| {
+                !neighbors[polygon]
+                    .iter()
+                    .any(|&neighbor| assigned[neighbor] == Some(color))
+            })
+            .collect::<Vec<_>>();
+
+        assigned[polygon] = Some(match available.choose(&mut rng) {
+            Some(&color) => color,
+            None => {
+                let hue = (first_hue + colors.len() as f32 * HUE_STEP).fract();
+                colors.push(hsv_to_rgb(
+                    hue,
+                    rng.gen_range(0.4, 0.7),
+                    rng.gen_range(0.75, 0.95),
+                ));
+                colors.len() - 1
+            }
+        });
+    }
+
+    assigned
+        .iter()
+        .map(|color| colors[color.unwrap()])
+        .collect()
+}
+
+/// Recolors the polygons of a net that consists of several separate pieces (where
+/// `polygon_components` lists the connected component that each polygon belongs to), so that the
+/// polygons of each piece share their own range of hues. The components are given evenly spaced
+/// base hues (starting from the hue of the first color), and the hue of each original color
+/// (relative to that first hue) is scaled down to within a quarter of the spacing on either side of
+/// the base hue of its component, so the ranges of neighboring components never overlap. The
+/// saturation and value of every color are kept, so the original colors still show up as
+/// variations within each piece. Grays don't have a hue, so they are left as they are.
+pub fn component_coloring(colors: &[Vec3], polygon_components: &[usize]) -> Vec<Vec3> {
+    debug_assert_eq!(colors.len(), polygon_components.len());
+    const ACHROMATIC: f32 = 1.0e-4;
+
+    let component_count = polygon_components
+        .iter()
+        .max()
+        .map_or(0, |&component| component + 1);
+    let first_hue = colors
+        .iter()
+        .map(rgb_to_hsv)
+        .find(|&(_, saturation, _)| saturation >= ACHROMATIC)
+        .map(|(hue, _, _)| hue);
+    let first_hue = match first_hue {
+        Some(hue) if component_count > 1 => hue,
+        _ => return colors.to_vec(),
+    };
+
+    let spacing = 1.0 / component_count as f32;
+    colors
+        .iter()
+        .zip(polygon_components.iter())
+        .map(|(color, &component)| {
+            let (hue, saturation, value) = rgb_to_hsv(color);
+            if saturation < ACHROMATIC {
+                return *color;
+            }
+            let offset = (hue - first_hue + 0.5).rem_euclid(1.0) - 0.5;
+            let hue = first_hue + component as f32 * spacing + offset * spacing * 0.5;
+            hsv_to_rgb(hue.rem_euclid(1.0), saturation, value)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gradient() {
+        let red = Vec3::new(1.0, 0.0, 0.0);
+        let blue = Vec3::new(0.0, 0.0, 1.0);
+        let gradient = Gradient::linear_spacing(&[red, Vec3::zero(), blue], ColorSpace::Rgb);
+
+        assert_eq!(gradient.color_at(0.0), red);
+        assert_eq!(gradient.color_at(0.25), Vec3::new(0.5, 0.0, 0.0));
+        assert_eq!(gradient.color_at(0.5), Vec3::zero());
+        assert_eq!(gradient.color_at(1.0), blue);
+
+        // Anything outside of the gradient gets the color of the closest stop
+        assert_eq!(gradient.color_at(-1.0), red);
+        assert_eq!(gradient.color_at(2.0), blue);
+
+        // A single stop is used everywhere
+        let gradient = Gradient::linear_spacing(&[red], ColorSpace::Rgb);
+        assert_eq!(gradient.color_at(0.75), red);
+    }
+
+    #[test]
+    fn test_gradient_stops() {
+        let red = Vec3::new(1.0, 0.0, 0.0);
+        let blue = Vec3::new(0.0, 0.0, 1.0);
+
+        // Stops are sorted by their positions, and don't need to cover the whole range
+        let gradient = Gradient::new(&[(0.75, blue), (0.25, red)], ColorSpace::Rgb);
+        assert_eq!(gradient.color_at(0.0), red);
+        assert_eq!(gradient.color_at(0.25), red);
+        assert_eq!(gradient.color_at(0.5), Vec3::new(0.5, 0.0, 0.5));
+        assert_eq!(gradient.color_at(0.75), blue);
+        assert_eq!(gradient.color_at(1.0), blue);
+        assert_eq!(gradient.color_at(f32::INFINITY), blue);
+        assert_eq!(gradient.color_at(f32::NEG_INFINITY), red);
+        assert_eq!(gradient.color_at(f32::NAN), red);
+
+        // Stops outside of the range are clamped to it, and stops without a position are ignored
+        let gradient = Gradient::new(
+            &[(-1.0, red), (f32::NAN, Vec3::one()), (2.0, blue)],
+            ColorSpace::Rgb,
+        );
+        assert_eq!(gradient.color_at(0.0), red);
+        assert_eq!(gradient.color_at(0.5), Vec3::new(0.5, 0.0, 0.5));
+        assert_eq!(gradient.color_at(1.0), blue);
+
+        // A single stop is used everywhere, even if it isn't at either end of the range
+        let gradient = Gradient::new(&[(0.5, blue)], ColorSpace::Hsv);
+        for &t in [-1.0, 0.0, 0.5, 1.0, 2.0].iter() {
+            assert_eq!(gradient.color_at(t), blue);
+        }
+
+        // Without any stops, the gradient is black everywhere
+        for gradient in [
+            Gradient::new(&[], ColorSpace::Rgb),
+            Gradient::linear_spacing(&[], ColorSpace::Lch),
+        ]
+        .iter()
+        {
+            for &t in [-1.0, 0.0, 0.5, 1.0, 2.0, f32::NAN].iter() {
+                assert_eq!(gradient.color_at(t), Vec3::zero());
+            }
+        }
+    }
+
+    #[test]
+    fn test_color_spaces() {
+        let close = |a: Vec3, b: Vec3| (a - b).length() < 1.0e-3;
+        let red = Vec3::new(1.0, 0.0, 0.0);
+        let green = Vec3::new(0.0, 1.0, 0.0);
+        let blue = Vec3::new(0.0, 0.0, 1.0);
+        let gray = Vec3::splat(0.5);
+
+        // Conversions should round-trip
+        for color in [red, green, blue, gray, Vec3::new(0.2, 0.6, 0.9)].iter() {
+            let (hue, saturation, value) = rgb_to_hsv(color);
+            assert!(close(hsv_to_rgb(hue, saturation, value), *color));
+            assert!(close(lch_to_srgb(&srgb_to_lch(color)), *color));
+        }
+        assert!((srgb_to_lch(&Vec3::one()).x() - 100.0).abs() < 1.0e-2);
+        assert!(srgb_to_lch(&gray).y() < 1.0e-2);
+
+        // The endpoints are the same in every color space
+        for color_space in [ColorSpace::Rgb, ColorSpace::Hsv, ColorSpace::Lch].iter() {
+            assert!(close(color_space.lerp(&red, &blue, 0.0), red));
+            assert!(close(color_space.lerp(&red, &blue, 1.0), blue));
+        }
+
+        // Halfway between red and green is a muddy yellow in RGB, but a bright one in HSV
+        assert!(close(
+            ColorSpace::Rgb.lerp(&red, &green, 0.5),
+            Vec3::new(0.5, 0.5, 0.0)
+        ));
+        assert!(close(
+            ColorSpace::Hsv.lerp(&red, &green, 0.5),
+            Vec3::new(1.0, 1.0, 0.0)
+        ));
+
+        // Hues take the shorter way around the color wheel (from red to blue via magenta), and
+        // grays don't change the hue of the other color
+        assert!(close(
+            ColorSpace::Hsv.lerp(&red, &blue, 0.5),
+            Vec3::new(1.0, 0.0, 1.0)
+        ));
+        let (hue, _, _) = rgb_to_hsv(&ColorSpace::Hsv.lerp(&gray, &blue, 0.5));
+        assert!((hue - 2.0 / 3.0).abs() < 1.0e-3);
+        let lch = srgb_to_lch(&ColorSpace::Lch.lerp(&gray, &blue, 0.5));
+        assert!((lch.z() - srgb_to_lch(&blue).z()).abs() < 1.0e-2);
+    }
+
+    #[test]
+    fn test_random_coloring() {
+        // A wheel: polygon 0 is surrounded by a ring of 5 other polygons, each of which neighbors
+        // the ones next to it
+        let mut neighbors = vec![(1..6).collect::<Vec<_>>()];
+        for i in 0..5 {
+            neighbors.push(vec![0, (i + 4) % 5 + 1, (i + 1) % 5 + 1]);
+        }
+
+        let colors = random_coloring(&neighbors, 42);
+        for (polygon, polygon_neighbors) in neighbors.iter().enumerate() {
+            for &neighbor in polygon_neighbors.iter() {
+                assert_ne!(colors[polygon], colors[neighbor]);
+            }
+        }
+
+        // The same seed should always give the same colors
+        assert_eq!(colors, random_coloring(&neighbors, 42));
+        assert_ne!(colors, random_coloring(&neighbors, 43));
+    }
+
+    #[test]
+    fn test_component_coloring() {
+        let red = Vec3::new(1.0, 0.0, 0.0);
+        let yellow = Vec3::new(0.8, 0.8, 0.2);
+        let gray = Vec3::splat(0.5);
+        let colors = vec![red, yellow, red, yellow, gray];
+
+        // A single piece keeps its colors
+        assert_eq!(component_coloring(&colors, &[0; 5]), colors);
+
+        // Each of the two pieces stays within a quarter turn of its own base hue (red, then cyan),
+        // with the same saturation and value as before
+        let components = vec![0, 0, 1, 1, 1];
+        let recolored = component_coloring(&colors, &components);
+        let hue_distance = |a: f32, b: f32| ((a - b + 0.5).rem_euclid(1.0) - 0.5).abs();
+        for ((color, original), &component) in
+            recolored.iter().zip(colors.iter()).zip(components.iter())
+        {
+            let (hue, saturation, value) = rgb_to_hsv(color);
+            let (_, original_saturation, original_value) = rgb_to_hsv(original);
+            assert!((saturation - original_saturation).abs() < 1.0e-4);
+            assert!((value - original_value).abs() < 1.0e-4);
+            if saturation > 0.0 {
+                assert!(hue_distance(hue, component as f32 * 0.5) <= 0.25 + 1.0e-4);
+            }
         }
+        assert_eq!(recolored[0], red);
+        assert_ne!(recolored[0], recolored[1]);
+        assert_ne!(recolored[2], red);
+        assert_eq!(recolored[4], gray);
     }
 }
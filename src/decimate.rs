@@ -0,0 +1,433 @@
+use glam::Vec3;
+
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+/// Collapsing an edge is not allowed to turn the normal of any of the faces around it by more than
+/// this (in radians), which keeps the surface from folding over onto itself.
+const MAX_NORMAL_CHANGE: f32 = 60.0 * std::f32::consts::PI / 180.0;
+
+/// Collapsing an edge is not allowed to leave any face whose area is smaller than this fraction of
+/// the square of its longest side, since slivers like these can't be unfolded (or cut out).
+const SLIVER_TOLERANCE: f32 = 1.0e-3;
+
+/// A triangle mesh that was simplified with `decimate()`.
+pub struct Decimated {
+    // The remaining vertices (some of which were moved) and faces
+    pub vertices: Vec<Vec3>,
+    pub faces: Vec<[usize; 3]>,
+
+    // The index of the vertex of the original mesh that each remaining vertex used to be, and the
+    // index of the face of the original mesh that each remaining face was left over from
+    pub vertex_sources: Vec<usize>,
+    pub face_sources: Vec<usize>,
+}
+
+/// Returns the vertices that share a face with the specified vertex (not including the vertex
+/// itself), given the faces around each vertex.
+fn neighbors(faces: &[[usize; 3]], vertex_faces: &[Vec<usize>], vertex: usize) -> Vec<usize> {
+    let mut neighbors = vertex_faces[vertex]
+        .iter()
+        .flat_map(|&face| faces[face].iter().copied())
+        .filter(|&neighbor| neighbor != vertex)
+        .collect::<Vec<_>>();
+    neighbors.sort_unstable();
+    neighbors.dedup();
+    neighbors
+}
+
+/// Returns `true` if the specified vertex lies on the border of an open surface, i.e. if one of
+/// its edges only belongs to a single face.
+fn is_border_vertex(faces: &[[usize; 3]], vertex_faces: &[Vec<usize>], vertex: usize) -> bool {
+    neighbors(faces, vertex_faces, vertex)
+        .into_iter()
+        .any(|neighbor| {
+            vertex_faces[vertex]
+                .iter()
+                .filter(|&&face| faces[face].contains(&neighbor))
+                .count()
+                == 1
+        })
+}
+
+/// Returns the face with the vertex `from` replaced by `to` (if it is one of the face's vertices).
+fn replace_vertex(mut face: [usize; 3], from: usize, to: usize) -> [usize; 3] {
+    for vertex in face.iter_mut() {
+        if *vertex == from {
+            *vertex = to;
+        }
+    }
+    face
+}
+
+/// Returns the (unnormalized) normal of the triangle with the specified corners, whose length is
+/// twice the area of the triangle.
+fn triangle_normal(a: &Vec3, b: &Vec3, c: &Vec3) -> Vec3 {
+    (*b - *a).cross(*c - *a)
+}
+
+/// Returns `true` if the edge between vertices `u` and `v` can be collapsed (with both vertices
+/// moving to `position`) without making the surface non-manifold, introducing duplicate faces, or
+/// flipping (or squashing) any of the faces around the edge.
+fn can_collapse(
+    positions: &[Vec3],
+    faces: &[[usize; 3]],
+    vertex_faces: &[Vec<usize>],
+    u: usize,
+    v: usize,
+    position: &Vec3,
+) -> bool {
+    // An edge of a closed 2-manifold is shared by exactly two faces
+    let shared = vertex_faces[u]
+        .iter()
+        .copied()
+        .filter(|&face| faces[face].contains(&v))
+        .collect::<Vec<_>>();
+    if shared.len() != 2 {
+        return false;
+    }
+
+    // Edges that touch the border of an open surface are left alone, so that the border keeps its
+    // shape (and so that an edge between two vertices of the border can't pinch the surface)
+    if is_border_vertex(faces, vertex_faces, u) || is_border_vertex(faces, vertex_faces, v) {
+        return false;
+    }
+
+    // The link condition: the only vertices that are connected to both ends of the edge are the
+    // corners of the two faces across from it, otherwise the collapse would pinch the surface
+    let mut opposite = shared
+        .iter()
+        .map(|&face| {
+            *faces[face]
+                .iter()
+                .find(|&&vertex| vertex != u && vertex != v)
+                .unwrap()
+        })
+        .collect::<Vec<_>>();
+    opposite.sort_unstable();
+    let v_neighbors = neighbors(faces, vertex_faces, v);
+    let common = neighbors(faces, vertex_faces, u)
+        .into_iter()
+        .filter(|neighbor| v_neighbors.binary_search(neighbor).is_ok())
+        .collect::<Vec<_>>();
+    if common != opposite {
+        return false;
+    }
+
+    // Collapsing an edge of a tetrahedron (or of a tetrahedral "spike" of a larger mesh) passes the
+    // link condition, but turns two of its faces into copies of one another
+    let sorted = |face: &[usize; 3]| {
+        let mut face = *face;
+        face.sort_unstable();
+        face
+    };
+    for &face in vertex_faces[u].iter().filter(|face| !shared.contains(face)) {
+        let collapsed = sorted(&replace_vertex(faces[face], u, v));
+        if vertex_faces[v]
+            .iter()
+            .filter(|face| !shared.contains(face))
+            .any(|&other| sorted(&faces[other]) == collapsed)
+        {
+            return false;
+        }
+    }
+
+    // Every other face around the edge keeps its orientation (and some of its area)
+    let min_cos = MAX_NORMAL_CHANGE.cos();
+    vertex_faces[u]
+        .iter()
+        .chain(vertex_faces[v].iter())
+        .filter(|face| !shared.contains(face))
+        .all(|&face| {
+            let corners = faces[face]
+                .iter()
+                .map(|&vertex| positions[vertex])
+                .collect::<Vec<_>>();
+            let moved = faces[face]
+                .iter()
+                .map(|&vertex| {
+                    if vertex == u || vertex == v {
+                        *position
+                    } else {
+                        positions[vertex]
+                    }
+                })
+                .collect::<Vec<_>>();
+            let before = triangle_normal(&corners[0], &corners[1], &corners[2]);
+            let after = triangle_normal(&moved[0], &moved[1], &moved[2]);
+            let longest_side = (0..3)
+                .map(|i| (moved[(i + 1) % 3] - moved[i]).length())
+                .fold(0.0, f32::max);
+
+            after.length() * 0.5 > SLIVER_TOLERANCE * longest_side * longest_side
+                && before.normalize().dot(after.normalize()) >= min_cos
+        })
+}
+
+/// Simplifies a triangle mesh until it has at most `target` faces (or until none of its edges can
+/// be collapsed any further) by repeatedly collapsing its shortest edge into the midpoint of the
+/// edge. Every collapse removes the two faces on either side of an edge, and collapses that would
+/// make the mesh non-manifold (see `can_collapse()`) are skipped, so the result is still a
+/// 2-manifold with the same topology as the original mesh. The mesh may be an open surface, in
+/// which case its border is kept as it is.
+///
+/// The winding order of every remaining face is kept.
+pub fn decimate(vertices: &[Vec3], triangles: &[[usize; 3]], target: usize) -> Decimated {
+    let mut positions = vertices.to_vec();
+    let mut faces = triangles.to_vec();
+    let mut alive = vec![true; faces.len()];
+    let mut face_count = faces.len();
+
+    let mut vertex_faces = vec![vec![]; positions.len()];
+    for (face, triangle) in faces.iter().enumerate() {
+        for &vertex in triangle.iter() {
+            vertex_faces[vertex].push(face);
+        }
+    }
+
+    // The edges are kept in a priority queue (shortest first), where each edge is stored along
+    // with its length at the time that it was added: edges whose lengths have changed since then
+    // were added again, so the outdated copies are skipped
+    let mut edges = BinaryHeap::new();
+    let push_edge = |edges: &mut BinaryHeap<_>, positions: &[Vec3], u: usize, v: usize| {
+        let length = (positions[v] - positions[u]).length();
+        edges.push(Reverse((length.to_bits(), u.min(v), u.max(v))));
+    };
+    for triangle in faces.iter() {
+        for i in 0..3 {
+            let (u, v) = (triangle[i], triangle[(i + 1) % 3]);
+            if u < v {
+                push_edge(&mut edges, &positions, u, v);
+            }
+        }
+    }
+
+    while face_count > target {
+        let Reverse((length, u, v)) = match edges.pop() {
+            Some(edge) => edge,
+            None => break,
+        };
+        if vertex_faces[u].is_empty()
+            || vertex_faces[v].is_empty()
+            || (positions[v] - positions[u]).length().to_bits() != length
+        {
+            continue;
+        }
+
+        let position = (positions[u] + positions[v]) * 0.5;
+        if !can_collapse(&positions, &faces, &vertex_faces, u, v, &position) {
+            continue;
+        }
+
+        // Remove the two faces on either side of the edge, then move the rest of the faces around
+        // `u` over to `v`
+        let shared = vertex_faces[u]
+            .iter()
+            .copied()
+            .filter(|&face| faces[face].contains(&v))
+            .collect::<Vec<_>>();
+        for &face in shared.iter() {
+            alive[face] = false;
+            for &vertex in faces[face].iter() {
+                vertex_faces[vertex].retain(|&other| other != face);
+            }
+        }
+        face_count -= shared.len();
+
+        for face in std::mem::take(&mut vertex_faces[u]) {
+            faces[face] = replace_vertex(faces[face], u, v);
+            vertex_faces[v].push(face);
+        }
+        positions[v] = position;
+
+        for neighbor in neighbors(&faces, &vertex_faces, v) {
+            push_edge(&mut edges, &positions, v, neighbor);
+        }
+    }
+
+    // Only keep the vertices that are still used by a face, and renumber them
+    let mut indices = vec![None; positions.len()];
+    let mut decimated = Decimated {
+        vertices: vec![],
+        faces: vec![],
+        vertex_sources: vec![],
+        face_sources: vec![],
+    };
+    for (face, triangle) in faces.iter().enumerate().filter(|&(face, _)| alive[face]) {
+        let mut renumbered = [0; 3];
+        for (corner, &vertex) in renumbered.iter_mut().zip(triangle.iter()) {
+            *corner = *indices[vertex].get_or_insert_with(|| {
+                decimated.vertices.push(positions[vertex]);
+                decimated.vertex_sources.push(vertex);
+                decimated.vertices.len() - 1
+            });
+        }
+        decimated.faces.push(renumbered);
+        decimated.face_sources.push(face);
+    }
+
+    decimated
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::collections::HashMap;
+
+    /// Returns a unit sphere that is made by subdividing the faces of an octahedron the specified
+    /// number of times (where each subdivision splits every triangle into 4).
+    fn octasphere(subdivisions: usize) -> (Vec<Vec3>, Vec<[usize; 3]>) {
+        let mut vertices = vec![
+            Vec3::unit_x(),
+            -Vec3::unit_x(),
+            Vec3::unit_y(),
+            -Vec3::unit_y(),
+            Vec3::unit_z(),
+            -Vec3::unit_z(),
+        ];
+        let mut faces = vec![
+            [0, 2, 4],
+            [2, 1, 4],
+            [1, 3, 4],
+            [3, 0, 4],
+            [2, 0, 5],
+            [1, 2, 5],
+            [3, 1, 5],
+            [0, 3, 5],
+        ];
+
+        for _ in 0..subdivisions {
+            let mut midpoints: HashMap<(usize, usize), usize> = HashMap::new();
+            let mut midpoint = |a: usize, b: usize| {
+                *midpoints.entry((a.min(b), a.max(b))).or_insert_with(|| {
+                    vertices.push(((vertices[a] + vertices[b]) * 0.5).normalize());
+                    vertices.len() - 1
+                })
+            };
+            faces = faces
+                .iter()
+                .flat_map(|&[a, b, c]| {
+                    let (ab, bc, ca) = (midpoint(a, b), midpoint(b, c), midpoint(c, a));
+                    vec![[a, ab, ca], [b, bc, ab], [c, ca, bc], [ab, bc, ca]]
+                })
+                .collect();
+        }
+        (vertices, faces)
+    }
+
+    /// Asserts that the faces form a closed, consistently oriented 2-manifold that is topologically
+    /// a sphere: every (directed) edge appears exactly once, along with its opposite.
+    fn assert_closed_sphere(vertices: &[Vec3], faces: &[[usize; 3]]) {
+        let mut edges = HashMap::new();
+        for face in faces.iter() {
+            for i in 0..3 {
+                *edges.entry((face[i], face[(i + 1) % 3])).or_insert(0) += 1;
+            }
+        }
+        for (&(a, b), &count) in edges.iter() {
+            assert_eq!(count, 1);
+            assert_eq!(edges.get(&(b, a)), Some(&1));
+        }
+        let euler_characteristic =
+            vertices.len() as isize - (edges.len() / 2) as isize + faces.len() as isize;
+        assert_eq!(euler_characteristic, 2);
+    }
+
+    #[test]
+    fn test_decimate() {
+        let (vertices, faces) = octasphere(3);
+        assert_eq!(faces.len(), 512);
+        assert_closed_sphere(&vertices, &faces);
+
+        let decimated = decimate(&vertices, &faces, 100);
+        assert!(decimated.faces.len() <= 100 && decimated.faces.len() > 90);
+        assert_closed_sphere(&decimated.vertices, &decimated.faces);
+
+        // Every remaining face was left over from a different face, and still points outward
+        let mut sources = decimated.face_sources.clone();
+        sources.dedup();
+        assert_eq!(sources.len(), decimated.faces.len());
+        for face in decimated.faces.iter() {
+            let [a, b, c] = [
+                decimated.vertices[face[0]],
+                decimated.vertices[face[1]],
+                decimated.vertices[face[2]],
+            ];
+            assert!(triangle_normal(&a, &b, &c).dot(a + b + c) > 0.0);
+        }
+        for (vertex, &source) in decimated
+            .vertices
+            .iter()
+            .zip(decimated.vertex_sources.iter())
+        {
+            assert!(source < vertices.len());
+            assert!(vertex.length() <= 1.0 + 1.0e-4);
+        }
+
+        // A mesh that already has few enough faces is left alone (although its vertices are
+        // renumbered in the order that they are used)
+        let unchanged = decimate(&vertices, &faces, 512);
+        assert_eq!(unchanged.face_sources, (0..512).collect::<Vec<_>>());
+        for (face, original) in unchanged.faces.iter().zip(faces.iter()) {
+            for (&vertex, &original) in face.iter().zip(original.iter()) {
+                assert_eq!(unchanged.vertices[vertex], vertices[original]);
+                assert_eq!(unchanged.vertex_sources[vertex], original);
+            }
+        }
+    }
+
+    #[test]
+    fn test_decimate_open() {
+        // The upper half of the sphere is a bowl, whose border runs around the equator
+        let (vertices, faces) = octasphere(3);
+        let faces = faces
+            .into_iter()
+            .filter(|face| face.iter().all(|&vertex| vertices[vertex].z() >= 0.0))
+            .collect::<Vec<_>>();
+        assert_eq!(faces.len(), 256);
+        let border = |vertices: &[Vec3], faces: &[[usize; 3]]| {
+            let edges = faces
+                .iter()
+                .flat_map(|face| (0..3).map(move |i| (face[i], face[(i + 1) % 3])))
+                .collect::<Vec<_>>();
+            let mut border = edges
+                .iter()
+                .filter(|&&(a, b)| !edges.contains(&(b, a)))
+                .map(|&(a, b)| format!("{:?} {:?}", vertices[a], vertices[b]))
+                .collect::<Vec<_>>();
+            border.sort();
+            border
+        };
+
+        // The border is left exactly as it was, and the bowl is still a disk
+        let decimated = decimate(&vertices, &faces, 60);
+        assert!(decimated.faces.len() <= 60);
+        assert_eq!(border(&vertices, &faces).len(), 32);
+        assert_eq!(
+            border(&decimated.vertices, &decimated.faces),
+            border(&vertices, &faces)
+        );
+        let edges = (decimated.faces.len() * 3 + 32) / 2;
+        let euler_characteristic =
+            decimated.vertices.len() as isize - edges as isize + decimated.faces.len() as isize;
+        assert_eq!(euler_characteristic, 1);
+    }
+
+    #[test]
+    fn test_decimate_tetrahedron() {
+        // Collapsing any edge of a tetrahedron would flatten it into two copies of the same face
+        let vertices = vec![
+            Vec3::new(1.0, 1.0, 1.0),
+            Vec3::new(1.0, -1.0, -1.0),
+            Vec3::new(-1.0, 1.0, -1.0),
+            Vec3::new(-1.0, -1.0, 1.0),
+        ];
+        let faces = vec![[0, 1, 2], [0, 3, 1], [0, 2, 3], [1, 3, 2]];
+        assert_closed_sphere(&vertices, &faces);
+
+        let decimated = decimate(&vertices, &faces, 2);
+        assert_eq!(decimated.faces, faces);
+        assert_eq!(decimated.vertices, vertices);
+    }
+}
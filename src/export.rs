@@ -0,0 +1,156 @@
+pub mod dot;
+pub mod dxf;
+pub mod instructions;
+pub mod obj;
+pub mod page;
+pub mod pdf;
+pub mod png;
+pub mod svg;
+
+use crate::goal_mesh::NetEdge;
+
+use glam::Vec3;
+
+/// The (SRGB) gray level of the outlines of filled polygons, when no stroke color is provided.
+pub const OUTLINE_COLOR: f32 = 0.1;
+
+/// Describes how the net should be drawn in exported images (i.e. .svg, .png, and .pdf files).
+pub struct NetStyle<'a> {
+    // One (SRGB) color per polygon of the net
+    pub colors: &'a Vec<Vec3>,
+
+    // The (SRGB) color of the canvas
+    pub background: Vec3,
+
+    // Whether to draw the edges of the net (instead of filling each polygon)
+    pub wireframe: bool,
+
+    // Whether to also outline each polygon when it is filled (i.e. when not in wireframe mode), in
+    // the stroke color (or dark gray, see `OUTLINE_COLOR`)
+    pub outline: bool,
+
+    // Whether to also draw the lines between the triangles that each polygon was split into (see
+    // `UnfoldResult::triangulation_edges()`), so that every face is drawn as triangles
+    pub triangulated: bool,
+
+    // Whether to number each pair of matching cut edges
+    pub labels: bool,
+
+    // Whether to label each polygon with the index of the face of the .obj file that it came from
+    pub face_labels: bool,
+
+    // Whether to write each polygon into its own group in .svg files (named after the index of its
+    // face of the .obj file), with its cut and fold edges in nested groups, so that individual
+    // faces can be edited in other tools
+    pub face_groups: bool,
+
+    // Whether to label each fold with the dihedral angle that its faces meet at (see
+    // `UnfoldResult::fold_angle_label()`)
+    pub fold_angles: bool,
+
+    // The (SRGB) color of every stroke (i.e. the edges in wireframe mode and the outlines of the
+    // glue tabs), or `None` to draw each edge in the color of its polygon and each tab in gray
+    pub stroke_color: Option<Vec3>,
+
+    // The width of every stroke, in the same units as the net
+    pub stroke_width: f32,
+
+    // The opacity of each polygon when it is filled (i.e. when not in wireframe mode), from 0
+    // (transparent) to 1 (opaque), so that overlapping polygons show through one another
+    pub fill_opacity: f32,
+
+    // The length of each dash and gap along the folds (in the same units as the net), or `None`
+    // to dash each kind of fold with its default pattern
+    pub fold_dash: Option<(f32, f32)>,
+
+    // The widths of the shallowest and the sharpest folds (in the same units as the net), between
+    // which the width of every other fold is interpolated based on how far it is folded (see
+    // `edge_width()`), or `None` to draw the folds as wide as every other stroke
+    pub fold_widths: Option<(f32, f32)>,
+
+    // Whether to draw a labeled bar of a known (physical) length in a corner of .svg and .pdf
+    // files, so that the printed size can be checked
+    pub scale_bar: bool,
+
+    // Whether to draw a key in a corner of .svg and .pdf files, with a sample of each kind of line
+    // (cuts, valley folds, and mountain folds) and its name
+    pub legend: bool,
+}
+
+impl<'a> NetStyle<'a> {
+    /// Returns a plain style with the specified polygon colors: each polygon is filled (and
+    /// outlined) on a white background, and matching cut edges are numbered, but nothing else is
+    /// drawn. Individual fields can be overridden with struct update syntax.
+    pub fn new(colors: &'a Vec<Vec3>) -> NetStyle<'a> {
+        NetStyle {
+            colors,
+            background: Vec3::one(),
+            wireframe: false,
+            outline: true,
+            triangulated: false,
+            labels: true,
+            face_labels: false,
+            face_groups: false,
+            fold_angles: false,
+            stroke_color: None,
+            stroke_width: 2.0,
+            fill_opacity: 1.0,
+            fold_dash: None,
+            fold_widths: None,
+            scale_bar: false,
+            legend: false,
+        }
+    }
+
+    /// Returns the width that the specified edge of the net should be drawn with: this is the
+    /// stroke width, unless the edge is a fold and `fold_widths` is set, in which case folds between
+    /// (nearly) coplanar faces are drawn with the first width and folds that turn all the way back
+    /// onto themselves (i.e. by 180 degrees) with the second.
+    pub fn edge_width(&self, edge: &NetEdge) -> f32 {
+        match (self.fold_widths, edge.interior_angle) {
+            (Some((shallow, sharp)), Some(angle)) if edge.kind.is_fold() => {
+                let t = (std::f32::consts::PI - angle).abs() / std::f32::consts::PI;
+                shallow + (sharp - shallow) * t.min(1.0)
+            }
+            _ => self.stroke_width,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::goal_mesh::{GoalMesh, LoadOptions, UnfoldStrategy};
+
+    use std::path::Path;
+
+    #[test]
+    fn test_edge_width() {
+        let goal_mesh = GoalMesh::from_obj(
+            Path::new("tests/fixtures/unit_cube.obj"),
+            &LoadOptions {
+                strict: true,
+                ..LoadOptions::default()
+            },
+        )
+        .unwrap();
+        let net = goal_mesh.unfold(0, UnfoldStrategy::BreadthFirst).unwrap();
+        let colors = vec![Vec3::one(); net.polygons.len()];
+        let plain = NetStyle::new(&colors);
+        let style = NetStyle {
+            fold_widths: Some((1.0, 5.0)),
+            ..NetStyle::new(&colors)
+        };
+
+        // Every fold of the cube turns by 90 degrees (halfway between flat and folded all the way
+        // back), and cuts are always as wide as the other strokes
+        for edge in net.edges.iter() {
+            assert_eq!(plain.edge_width(edge), plain.stroke_width);
+            if edge.kind.is_fold() {
+                assert!((style.edge_width(edge) - 3.0).abs() < 1.0e-4);
+            } else {
+                assert_eq!(style.edge_width(edge), style.stroke_width);
+            }
+        }
+    }
+}
@@ -0,0 +1,67 @@
+use crate::goal_mesh::{EdgeKind, UnfoldResult};
+
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+/// Writes the dual graph of the goal mesh to a Graphviz .dot file at the specified path, which
+/// shows how the net was unfolded: each polygon (i.e. each face of the .obj file) is a node, and
+/// each edge of the goal mesh that is shared by two polygons is an arc between them. Folds (the
+/// edges of the spanning tree) are drawn as bold, solid arcs, and labeled `M` or `V` for mountain
+/// and valley folds, while cuts are drawn as dashed, gray arcs, labeled with their cut labels. The
+/// nodes of the polygons that the net was unfolded around (one per piece of the goal mesh) are
+/// drawn with a double outline.
+pub fn write_dot(path: &Path, net: &UnfoldResult) -> std::io::Result<()> {
+    let mut file = BufWriter::new(File::create(path)?);
+
+    writeln!(file, "graph dual {{")?;
+    writeln!(file, "  node [shape=circle];")?;
+
+    for (polygon_index, &depth) in net.polygon_depths.iter().enumerate() {
+        let shape = if depth == 0 {
+            ", shape=doublecircle"
+        } else {
+            ""
+        };
+        writeln!(
+            file,
+            "  p{} [label=\"{}\"{}];",
+            polygon_index, net.obj_faces[polygon_index], shape
+        )?;
+    }
+
+    // Both sides of each edge are part of the net, so only write an arc for one of them (edges
+    // along the border of the goal mesh only have one side, so they don't connect two nodes)
+    let faces = net
+        .edges
+        .iter()
+        .map(|edge| (edge.half_edge, edge.face))
+        .collect::<HashMap<_, _>>();
+    for edge in net.edges.iter().filter(|edge| edge.half_edge < edge.pair) {
+        let other_face = match faces.get(&edge.pair) {
+            Some(&face) => face,
+            None => continue,
+        };
+        let polygon = net.triangle_polygons[usize::from(edge.face)];
+        let other_polygon = net.triangle_polygons[usize::from(other_face)];
+
+        let attributes = match edge.kind {
+            EdgeKind::Cut => format!(
+                "style=dashed, color=gray, label=\"{}\"",
+                net.cut_label(edge).unwrap_or_default()
+            ),
+            EdgeKind::Mountain => String::from("style=bold, label=\"M\""),
+            EdgeKind::Valley => String::from("style=bold, label=\"V\""),
+        };
+        writeln!(
+            file,
+            "  p{} -- p{} [{}];",
+            polygon, other_polygon, attributes
+        )?;
+    }
+
+    writeln!(file, "}}")?;
+
+    Ok(())
+}
@@ -0,0 +1,347 @@
+use crate::goal_mesh::UnfoldResult;
+use crate::utils::dash_segments;
+
+use glam::Vec3;
+
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+/// The layer that holds all of the lines that should be cut all the way through.
+const CUT_LAYER: &str = "CUT";
+
+/// The layer that holds all of the lines that should only be scored (i.e. folded).
+const FOLD_LAYER: &str = "FOLD";
+
+/// The layer that holds the lines between the triangles of each face (see `write_dxf()`), which
+/// are neither cut nor folded.
+const TRIANGULATION_LAYER: &str = "TRIANGULATION";
+
+/// Writes a single pair of DXF group codes and values.
+fn write_group<W: Write, T: std::fmt::Display>(
+    file: &mut W,
+    code: u32,
+    value: T,
+) -> std::io::Result<()> {
+    writeln!(file, "{}", code)?;
+    writeln!(file, "{}", value)
+}
+
+/// Writes a single LINE entity on the specified layer.
+fn write_line<W: Write>(file: &mut W, layer: &str, a: &Vec3, b: &Vec3) -> std::io::Result<()> {
+    write_group(file, 0, "LINE")?;
+    write_group(file, 8, layer)?;
+    write_group(file, 10, a.x())?;
+    write_group(file, 20, a.y())?;
+    write_group(file, 30, 0.0)?;
+    write_group(file, 11, b.x())?;
+    write_group(file, 21, b.y())?;
+    write_group(file, 31, 0.0)
+}
+
+/// Returns the parts of the edge from `a` to `b` that tabs are attached to, as pairs of distances
+/// along the edge (from 0 at `a` to 1 at `b`), sorted by their starts. The first two vertices of
+/// each tab lie on its edge and run in the same direction as the edge (the first two vertices of
+/// slots run in the opposite direction, since slots lie on the inside of their edges).
+fn tab_bases(a: &Vec3, b: &Vec3, tabs: &[Vec<Vec3>]) -> Vec<(f32, f32)> {
+    const RELATIVE_TOLERANCE: f32 = 1.0e-4;
+    let length = (*b - *a).length();
+    let along = (*b - *a) / length;
+    let outward = Vec3::new(along.y(), -along.x(), 0.0);
+
+    let mut bases = tabs
+        .iter()
+        .filter_map(|tab| {
+            let on_edge = |point: &Vec3| {
+                let t = (*point - *a).dot(along) / length;
+                let distance = (*point - *a).dot(outward).abs() / length;
+                // Snap to the ends of the edge, so that tabs along the entire edge don't leave a
+                // sliver of it to be cut
+                if distance > RELATIVE_TOLERANCE {
+                    None
+                } else if t.abs() <= RELATIVE_TOLERANCE {
+                    Some(0.0)
+                } else if (t - 1.0).abs() <= RELATIVE_TOLERANCE {
+                    Some(1.0)
+                } else if t > 0.0 && t < 1.0 {
+                    Some(t)
+                } else {
+                    None
+                }
+            };
+            match (on_edge(&tab[0]), on_edge(&tab[1])) {
+                (Some(start), Some(end)) if start < end => Some((start, end)),
+                _ => None,
+            }
+        })
+        .collect::<Vec<_>>();
+    bases.sort_by(|p, q| p.0.partial_cmp(&q.0).unwrap());
+    bases
+}
+
+/// Writes the unfolded net to an (ASCII) .dxf file at the specified path, for use with laser
+/// cutters and plotters. Cut edges are written to the "CUT" layer and fold edges to the "FOLD"
+/// layer, so that each can be assigned different power settings in the cutter's software. Every
+/// position (including the glue tabs) is multiplied by `scale` to convert it to millimeters.
+///
+/// The outline of each glue tab is cut, while the part of the edge that it is attached to is folded
+/// instead. Slots (see `generate_joints()`) can be passed along with the tabs, since they lie on the
+/// inside of their edges: their outlines are cut, without folding any part of their edges.
+/// Folds are written as continuous lines, unless `fold_dash` (the length of each dash and gap, in
+/// millimeters) is provided: then, every fold is split into separate dashes, since most cutters
+/// ignore the linetypes of a .dxf file.
+///
+/// If `triangulated` is `true`, the lines between the triangles that each face was split into are
+/// also written, to a separate "TRIANGULATION" layer (for CAD software that expects triangles),
+/// which a cutter can simply be told to skip.
+pub fn write_dxf(
+    path: &Path,
+    net: &UnfoldResult,
+    tabs: &[Vec<Vec3>],
+    scale: f32,
+    fold_dash: Option<(f32, f32)>,
+    triangulated: bool,
+) -> std::io::Result<()> {
+    let mut file = BufWriter::new(File::create(path)?);
+
+    // Set the drawing units to millimeters
+    write_group(&mut file, 0, "SECTION")?;
+    write_group(&mut file, 2, "HEADER")?;
+    write_group(&mut file, 9, "$INSUNITS")?;
+    write_group(&mut file, 70, 4)?;
+    write_group(&mut file, 9, "$MEASUREMENT")?;
+    write_group(&mut file, 70, 1)?;
+    write_group(&mut file, 0, "ENDSEC")?;
+
+    // Declare the layers: cuts are drawn in red, folds in blue, and triangulation lines in gray
+    let mut layers = vec![(CUT_LAYER, 1), (FOLD_LAYER, 5)];
+    if triangulated {
+        layers.push((TRIANGULATION_LAYER, 8));
+    }
+    write_group(&mut file, 0, "SECTION")?;
+    write_group(&mut file, 2, "TABLES")?;
+    write_group(&mut file, 0, "TABLE")?;
+    write_group(&mut file, 2, "LAYER")?;
+    write_group(&mut file, 70, layers.len())?;
+    for (layer, color) in layers.iter() {
+        write_group(&mut file, 0, "LAYER")?;
+        write_group(&mut file, 2, layer)?;
+        write_group(&mut file, 70, 0)?;
+        write_group(&mut file, 62, color)?;
+        write_group(&mut file, 6, "CONTINUOUS")?;
+    }
+    write_group(&mut file, 0, "ENDTAB")?;
+    write_group(&mut file, 0, "ENDSEC")?;
+
+    write_group(&mut file, 0, "SECTION")?;
+    write_group(&mut file, 2, "ENTITIES")?;
+
+    let pattern = match fold_dash {
+        Some((dash, gap)) => vec![dash, gap],
+        None => vec![],
+    };
+
+    for edge in net.edges.iter() {
+        // Folds are shared by two polygons, so only write them once
+        if edge.kind.is_fold() && edge.half_edge > edge.pair {
+            continue;
+        }
+
+        let a = net.positions[edge.indices[0]];
+        let b = net.positions[edge.indices[1]];
+
+        if edge.kind.is_fold() {
+            for (start, end) in dash_segments(&(a * scale), &(b * scale), &pattern) {
+                write_line(&mut file, FOLD_LAYER, &start, &end)?;
+            }
+            continue;
+        }
+
+        // The parts of a cut edge that tabs are attached to are folded instead, and the rest of
+        // the edge is cut as usual
+        let mut cut_from = 0.0;
+        for (start, end) in tab_bases(&a, &b, tabs) {
+            if start > cut_from {
+                write_line(
+                    &mut file,
+                    CUT_LAYER,
+                    &(a.lerp(b, cut_from) * scale),
+                    &(a.lerp(b, start) * scale),
+                )?;
+            }
+            let base = (a.lerp(b, start) * scale, a.lerp(b, end) * scale);
+            for (start, end) in dash_segments(&base.0, &base.1, &pattern) {
+                write_line(&mut file, FOLD_LAYER, &start, &end)?;
+            }
+            cut_from = cut_from.max(end);
+        }
+        if cut_from < 1.0 {
+            write_line(
+                &mut file,
+                CUT_LAYER,
+                &(a.lerp(b, cut_from) * scale),
+                &(b * scale),
+            )?;
+        }
+    }
+
+    // Cut around the outside of each tab (the remaining side is the edge itself, which is either
+    // folded or, for slots, already cut)
+    for tab in tabs.iter() {
+        for i in 1..tab.len() {
+            let a = tab[i];
+            let b = tab[(i + 1) % tab.len()];
+            write_line(&mut file, CUT_LAYER, &(a * scale), &(b * scale))?;
+        }
+    }
+
+    if triangulated {
+        for [a, b] in net.triangulation_edges() {
+            write_line(
+                &mut file,
+                TRIANGULATION_LAYER,
+                &(net.positions[a] * scale),
+                &(net.positions[b] * scale),
+            )?;
+        }
+    }
+
+    write_group(&mut file, 0, "ENDSEC")?;
+    write_group(&mut file, 0, "EOF")?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::goal_mesh::{
+        generate_tabs, GoalMesh, LoadOptions, TabOptions, TabStyle, UnfoldStrategy,
+    };
+    use crate::utils::find_bounding_box;
+
+    /// A LINE entity of a .dxf file: its layer and its endpoints.
+    struct Line {
+        layer: String,
+        a: Vec3,
+        b: Vec3,
+    }
+
+    /// Reads back the pairs of group codes and values of a .dxf file, along with its LINE entities.
+    fn read_dxf(path: &Path) -> (Vec<(u32, String)>, Vec<Line>) {
+        let contents = std::fs::read_to_string(path).unwrap();
+        let lines = contents.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len() % 2, 0);
+        let groups = lines
+            .chunks(2)
+            .map(|pair| (pair[0].trim().parse::<u32>().unwrap(), pair[1].to_string()))
+            .collect::<Vec<_>>();
+
+        let mut entities = vec![];
+        for (i, (code, value)) in groups.iter().enumerate() {
+            if *code != 0 || value != "LINE" {
+                continue;
+            }
+            let value = |code: u32| {
+                &groups[i + 1..]
+                    .iter()
+                    .find(|group| group.0 == code)
+                    .unwrap()
+                    .1
+            };
+            let coordinate = |code: u32| value(code).parse::<f32>().unwrap();
+            entities.push(Line {
+                layer: value(8).clone(),
+                a: Vec3::new(coordinate(10), coordinate(20), coordinate(30)),
+                b: Vec3::new(coordinate(11), coordinate(21), coordinate(31)),
+            });
+        }
+        (groups, entities)
+    }
+
+    /// Returns the lines on the specified layer.
+    fn on_layer<'a>(lines: &'a [Line], layer: &str) -> Vec<&'a Line> {
+        lines.iter().filter(|line| line.layer == layer).collect()
+    }
+
+    /// Returns the total length of the specified lines.
+    fn length(lines: &[&Line]) -> f32 {
+        lines.iter().map(|line| (line.b - line.a).length()).sum()
+    }
+
+    #[test]
+    fn test_write_dxf() {
+        let goal_mesh = GoalMesh::from_obj(
+            Path::new("tests/fixtures/unit_cube.obj"),
+            &LoadOptions {
+                strict: true,
+                ..LoadOptions::default()
+            },
+        )
+        .unwrap();
+        let net = goal_mesh.unfold(0, UnfoldStrategy::BreadthFirst).unwrap();
+        let path = std::env::temp_dir().join("durer_write_dxf.dxf");
+        // The drawing is in millimeters, and only declares the cut and fold layers
+        write_dxf(&path, &net, &[], 10.0, None, false).unwrap();
+        let (groups, lines) = read_dxf(&path);
+        let units = groups
+            .iter()
+            .position(|group| group.1 == "$INSUNITS")
+            .unwrap();
+        assert_eq!(groups[units + 1], (70, String::from("4")));
+        let layers = groups
+            .windows(2)
+            .filter(|pair| pair[0] == (0, String::from("LAYER")) && pair[1].0 == 2)
+            .map(|pair| pair[1].1.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(layers, vec![CUT_LAYER, FOLD_LAYER]);
+
+        // The 14 sides of the cross are cut and the 5 edges between its faces are folded (once
+        // each), all scaled to millimeters
+        assert_eq!(lines.len(), 19);
+        assert_eq!(on_layer(&lines, CUT_LAYER).len(), 14);
+        assert_eq!(on_layer(&lines, FOLD_LAYER).len(), 5);
+        assert!((length(&on_layer(&lines, CUT_LAYER)) - 140.0).abs() < 1.0e-3);
+        assert!((length(&on_layer(&lines, FOLD_LAYER)) - 50.0).abs() < 1.0e-3);
+        let (min, max) = find_bounding_box(&net.positions);
+        let points = lines
+            .iter()
+            .flat_map(|line| vec![line.a, line.b])
+            .collect::<Vec<_>>();
+        let (line_min, line_max) = find_bounding_box(&points);
+        assert!((line_min - min * 10.0).length() < 1.0e-3);
+        assert!((line_max - max * 10.0).length() < 1.0e-3);
+
+        // Dashed folds are split into separate lines, which leave gaps along each fold
+        write_dxf(&path, &net, &[], 10.0, Some((2.0, 1.0)), false).unwrap();
+        let (_, lines) = read_dxf(&path);
+        let folds = on_layer(&lines, FOLD_LAYER);
+        assert!(folds.len() > 5);
+        assert!(length(&folds) < 50.0);
+        assert_eq!(on_layer(&lines, CUT_LAYER).len(), 14);
+
+        // Each (rectangular) tab runs along an entire cut edge, which is folded instead, and the
+        // other 3 sides of the tab are cut
+        let tabs = generate_tabs(
+            &net.positions,
+            &net.edges,
+            &TabOptions {
+                width: 0.1,
+                style: TabStyle::Rectangle,
+                ..TabOptions::default()
+            },
+        );
+        assert_eq!(tabs.len(), 7);
+        write_dxf(&path, &net, &tabs, 10.0, None, false).unwrap();
+        let (_, lines) = read_dxf(&path);
+        assert_eq!(on_layer(&lines, FOLD_LAYER).len(), 5 + 7);
+        assert_eq!(on_layer(&lines, CUT_LAYER).len(), 14 - 7 + 7 * 3);
+        assert!((length(&on_layer(&lines, CUT_LAYER)) - (70.0 + 7.0 * 12.0)).abs() < 1.0e-3);
+
+        // The diagonals of the faces are written to their own layer
+        write_dxf(&path, &net, &[], 10.0, None, true).unwrap();
+        let (_, lines) = read_dxf(&path);
+        assert_eq!(on_layer(&lines, TRIANGULATION_LAYER).len(), 6);
+        assert_eq!(lines.len(), 19 + 6);
+    }
+}
@@ -0,0 +1,44 @@
+use crate::goal_mesh::UnfoldResult;
+
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+/// Writes assembly instructions for the net to a text file at the specified path: one numbered
+/// step per pair of matching cut edges, in the order returned by `UnfoldResult::gluing_order()`,
+/// naming the label of the cut (which is the same on both sides) and the two faces that it joins (numbered like the face labels of
+/// the net, i.e. by the faces of the .obj file).
+pub fn write_instructions(path: &Path, net: &UnfoldResult) -> std::io::Result<()> {
+    let mut file = BufWriter::new(File::create(path)?);
+
+    let order = net.gluing_order();
+    writeln!(
+        file,
+        "Assembly instructions: fold along every fold line, then glue the {} pairs of matching cut edges in this order",
+        order.len()
+    )?;
+
+    let mut component = None;
+    for (step, (edge, other)) in order.iter().enumerate() {
+        let polygon = net.triangle_polygons[usize::from(edge.face)];
+        let other_polygon = net.triangle_polygons[usize::from(other.face)];
+
+        // Each piece of the goal mesh is assembled separately (from its own root face)
+        if net.component_count() > 1 && component != Some(net.polygon_components[polygon]) {
+            component = Some(net.polygon_components[polygon]);
+            writeln!(file)?;
+            writeln!(file, "Piece {}:", net.polygon_components[polygon] + 1)?;
+        }
+
+        writeln!(
+            file,
+            "{:>4}. Glue together the edges labeled {}, joining face {} to face {}",
+            step + 1,
+            net.cut_label(edge).unwrap_or_default(),
+            net.obj_faces[polygon],
+            net.obj_faces[other_polygon]
+        )?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,72 @@
+use crate::goal_mesh::UnfoldResult;
+
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+/// Writes the unfolded net to an .obj file at the specified path, so that it can be imported into
+/// Blender or CAD software for further processing. Every vertex is multiplied by `scale` and lies
+/// in the xy-plane (i.e. its z-coordinate is 0).
+///
+/// Each face of the goal mesh is placed in a separate group named `face_<index>`, and is written
+/// as a single polygon, unless `triangulated` is `true`: then, the triangles that it was split into
+/// are written instead (for software that only imports triangles). Vertices are shared between
+/// neighboring faces wherever they remain joined in the net, so the resulting mesh is connected
+/// along folds and split apart along cuts.
+pub fn write_obj(
+    path: &Path,
+    net: &UnfoldResult,
+    scale: f32,
+    triangulated: bool,
+) -> std::io::Result<()> {
+    let mut file = BufWriter::new(File::create(path)?);
+
+    writeln!(file, "# Unfolded net")?;
+    writeln!(file, "o net")?;
+
+    // Welded vertices are numbered in the order that they first appear, so each one is written
+    // the first time that it is encountered
+    let mut vertex_count = 0;
+    for (position, &welded_index) in net.positions.iter().zip(net.welded_indices.iter()) {
+        if welded_index == vertex_count {
+            writeln!(
+                file,
+                "v {} {} 0",
+                position.x() * scale,
+                position.y() * scale
+            )?;
+            vertex_count += 1;
+        }
+    }
+
+    for (polygon_index, polygon) in net.polygons.iter().enumerate() {
+        writeln!(file, "g face_{}", polygon_index)?;
+
+        // Vertex indices in .obj files start at 1
+        let corner_lists = if triangulated {
+            net.triangle_polygons
+                .iter()
+                .enumerate()
+                .filter(|(_, &triangle_polygon)| triangle_polygon == polygon_index)
+                .map(|(triangle_index, _)| {
+                    net.welded_indices[triangle_index * 3..triangle_index * 3 + 3].to_vec()
+                })
+                .collect::<Vec<_>>()
+        } else {
+            vec![polygon
+                .iter()
+                .map(|&index| net.welded_indices[index])
+                .collect::<Vec<_>>()]
+        };
+        for corners in corner_lists.iter() {
+            let corners = corners
+                .iter()
+                .map(|corner| (corner + 1).to_string())
+                .collect::<Vec<_>>()
+                .join(" ");
+            writeln!(file, "f {}", corners)?;
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,375 @@
+use crate::goal_mesh::{EdgeKind, UnfoldResult};
+
+use glam::{Vec2, Vec3};
+
+/// The width (in millimeters) of the strip that is shared by neighboring pages.
+pub const PAGE_OVERLAP: f32 = 10.0;
+
+/// The size (in millimeters) of the registration marks drawn on each page.
+pub const MARK_SIZE: f32 = 6.0;
+
+/// The size of a single (printed) page, in millimeters.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PageSize {
+    pub width: f32,
+    pub height: f32,
+}
+
+impl PageSize {
+    pub const A4: PageSize = PageSize {
+        width: 210.0,
+        height: 297.0,
+    };
+
+    pub const LETTER: PageSize = PageSize {
+        width: 215.9,
+        height: 279.4,
+    };
+
+    /// Returns the printable area of this page (inside of a blank border that is `margin`
+    /// millimeters wide), along with the width of the strip that neighboring pages share, both in
+    /// the units of the net (where `units_per_mm` is the number of units per millimeter on paper).
+    /// The strip is at most a quarter of the printable area, so that small pages can still be
+    /// tiled.
+    pub fn printable_area(&self, units_per_mm: f32, margin: f32) -> (PageSize, f32) {
+        let margin = margin * units_per_mm;
+        let page = PageSize {
+            width: self.width * units_per_mm - margin * 2.0,
+            height: self.height * units_per_mm - margin * 2.0,
+        };
+        let overlap = (PAGE_OVERLAP * units_per_mm).min(page.width.min(page.height) * 0.25);
+        (page, overlap)
+    }
+
+    /// Splits the rectangle between `min` and `max` into a grid of overlapping, page-sized tiles,
+    /// where neighboring tiles share a strip that is `overlap` units wide. All of the arguments
+    /// (and the returned tiles) should be in the same units as this page, `max` should be larger
+    /// than `min` along both axes, and the overlap should be smaller than the page.
+    ///
+    /// Returns the minimum corner of each tile, along with its (0-based) row and column in the
+    /// grid. The grid is centered on the rectangle, and the outer border of the grid is inset by
+    /// half of the overlap, so that every tile can be trimmed in the same way.
+    pub fn tiles(&self, min: Vec2, max: Vec2, overlap: f32) -> Vec<(Vec2, usize, usize)> {
+        debug_assert!(overlap < self.width.min(self.height));
+        let page = Vec2::new(self.width, self.height);
+        let step = page - Vec2::splat(overlap);
+
+        let size = max - min;
+        let columns = (size.x() / step.x()).ceil().max(1.0) as usize;
+        let rows = (size.y() / step.y()).ceil().max(1.0) as usize;
+
+        // Any leftover space is split evenly between both sides of the grid
+        let slack = Vec2::new(
+            columns as f32 * step.x() - size.x(),
+            rows as f32 * step.y() - size.y(),
+        );
+        let origin = min - slack * 0.5 - Vec2::splat(overlap * 0.5);
+
+        let mut tiles = vec![];
+        for row in 0..rows {
+            for column in 0..columns {
+                let corner = origin + Vec2::new(column as f32 * step.x(), row as f32 * step.y());
+                tiles.push((corner, row, column));
+            }
+        }
+
+        tiles
+    }
+}
+
+/// Splits the unfolded net (and any glue tabs) across as many pages as necessary, where `page` and
+/// `overlap` are in the same units as the net. All of the tiles are in SVG space, i.e. the y-axis
+/// points down, so the first row of pages is at the top of the net.
+///
+/// Returns the minimum corner, row, and column of each page (see `PageSize::tiles()`), skipping any
+/// pages that don't contain any part of the net.
+pub fn net_pages(
+    net: &UnfoldResult,
+    tabs: &[Vec<Vec3>],
+    page: &PageSize,
+    overlap: f32,
+) -> Vec<(Vec2, usize, usize)> {
+    let to_svg = |point: &Vec3| Vec2::new(point.x(), -point.y());
+
+    // Find the bounding box of each shape in the net (triangles and tabs), which is later used to
+    // skip empty pages
+    let bounds = net
+        .positions
+        .chunks(3)
+        .map(|triangle| triangle.to_vec())
+        .chain(tabs.iter().cloned())
+        .map(|shape| {
+            shape.iter().map(to_svg).fold(
+                (Vec2::splat(f32::INFINITY), Vec2::splat(f32::NEG_INFINITY)),
+                |(min, max), point| (min.min(point), max.max(point)),
+            )
+        })
+        .collect::<Vec<_>>();
+    let (min, max) = bounds.iter().fold(
+        (Vec2::splat(f32::INFINITY), Vec2::splat(f32::NEG_INFINITY)),
+        |(min, max), (shape_min, shape_max)| (min.min(*shape_min), max.max(*shape_max)),
+    );
+
+    page.tiles(min, max, overlap)
+        .into_iter()
+        .filter(|(corner, _, _)| {
+            let far_corner = *corner + Vec2::new(page.width, page.height);
+            bounds.iter().any(|(shape_min, shape_max)| {
+                shape_min.x() < far_corner.x()
+                    && shape_max.x() > corner.x()
+                    && shape_min.y() < far_corner.y()
+                    && shape_max.y() > corner.y()
+            })
+        })
+        .collect()
+}
+
+/// Returns the number of pages of the specified size that the unfolded net (and any glue tabs) is
+/// split across by `write_svg_pages()` and `write_pdf()`, where `units_per_mm` is the number of
+/// units of the net per millimeter on paper and `margin` is the blank border of each page (in
+/// millimeters), without writing any of them.
+pub fn sheet_count(
+    net: &UnfoldResult,
+    tabs: &[Vec<Vec3>],
+    page_size: &PageSize,
+    units_per_mm: f32,
+    margin: f32,
+) -> usize {
+    let (page, overlap) = page_size.printable_area(units_per_mm, margin);
+    net_pages(net, tabs, &page, overlap).len()
+}
+
+/// Returns the length (in millimeters) of a scale bar that is at most `max_length` millimeters
+/// long, i.e. the longest "round" length (1, 2, or 5 times a power of ten) that fits.
+pub fn scale_bar_length(max_length: f32) -> f32 {
+    debug_assert!(max_length > 0.0);
+    let magnitude = 10.0f32.powf(max_length.log10().floor());
+    [5.0, 2.0, 1.0]
+        .iter()
+        .map(|step| step * magnitude)
+        .find(|&length| length <= max_length * (1.0 + 1e-5))
+        .unwrap_or(magnitude)
+}
+
+/// The kinds of lines that the legend of an exported net explains, along with their names (from
+/// top to bottom).
+pub const LEGEND_ROWS: [(EdgeKind, &str); 3] = [
+    (EdgeKind::Cut, "Cut"),
+    (EdgeKind::Valley, "Valley fold"),
+    (EdgeKind::Mountain, "Mountain fold"),
+];
+
+/// The layout of a legend (see `LEGEND_ROWS`): each row is a sample of a line, followed by its
+/// name. All of the lengths are in the same units as the net.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Legend {
+    // The font size of the names, which the rest of the legend is scaled by
+    pub font_size: f32,
+
+    // The length of each sample line
+    pub sample_length: f32,
+
+    // The size of the whole legend
+    pub size: Vec2,
+}
+
+impl Legend {
+    /// Lays out a legend with the specified font size, where `fold_dash` is the dash pattern of
+    /// the folds (see `EdgeKind::dash_pattern()`): each sample line is long enough to show at
+    /// least two repetitions of its pattern, so that the kinds of folds can be told apart.
+    pub fn new(font_size: f32, fold_dash: Option<(f32, f32)>) -> Legend {
+        let longest_pattern = LEGEND_ROWS
+            .iter()
+            .map(|(kind, _)| kind.dash_pattern(fold_dash).iter().sum::<f32>())
+            .fold(0.0, f32::max);
+        let sample_length = (font_size * 3.0).max(longest_pattern * 2.0);
+
+        // Names are drawn in a monospaced font, where each glyph is 0.6 times as wide as it is tall
+        let longest_name = LEGEND_ROWS
+            .iter()
+            .map(|(_, name)| name.chars().count())
+            .max()
+            .unwrap_or(0);
+        Legend {
+            font_size,
+            sample_length,
+            size: Vec2::new(
+                sample_length + font_size * (0.5 + longest_name as f32 * 0.6),
+                font_size * 1.5 * LEGEND_ROWS.len() as f32,
+            ),
+        }
+    }
+
+    /// Returns the distance from the top of the legend to the middle of the specified row.
+    pub fn row_offset(&self, row: usize) -> f32 {
+        (row as f32 + 0.5) * self.font_size * 1.5
+    }
+}
+
+/// The units that physical lengths can be given in on the command line (lengths are always
+/// converted to millimeters internally).
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Units {
+    #[default]
+    Millimeters,
+    Centimeters,
+    Inches,
+}
+
+impl Units {
+    /// Returns the number of millimeters in one of these units.
+    pub fn millimeters(&self) -> f32 {
+        match self {
+            Units::Millimeters => 1.0,
+            Units::Centimeters => 10.0,
+            Units::Inches => 25.4,
+        }
+    }
+
+    /// Returns the abbreviation of these units, as accepted by `from_str()`.
+    pub fn abbreviation(&self) -> &'static str {
+        match self {
+            Units::Millimeters => "mm",
+            Units::Centimeters => "cm",
+            Units::Inches => "in",
+        }
+    }
+}
+
+impl std::str::FromStr for Units {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "mm" => Ok(Units::Millimeters),
+            "cm" => Ok(Units::Centimeters),
+            "in" => Ok(Units::Inches),
+            _ => Err(format!("Unknown units: {:?}", s)),
+        }
+    }
+}
+
+impl std::str::FromStr for PageSize {
+    type Err = String;
+
+    /// Parses either one of the named page sizes (`A4` or `Letter`) or a custom size of the form
+    /// `WxH`, where both dimensions are in millimeters.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "a4" => Ok(PageSize::A4),
+            "letter" => Ok(PageSize::LETTER),
+            custom => {
+                let dimensions = custom
+                    .split('x')
+                    .map(|dimension| dimension.trim().parse::<f32>())
+                    .collect::<Result<Vec<_>, _>>()
+                    .map_err(|_| format!("Invalid page size: {:?}", s))?;
+
+                match dimensions[..] {
+                    [width, height] if width > 0.0 && height > 0.0 => {
+                        Ok(PageSize { width, height })
+                    }
+                    _ => Err(format!(
+                        "Invalid page size: {:?} (expected A4, Letter, or WxH in millimeters)",
+                        s
+                    )),
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::goal_mesh::{GoalMesh, LoadOptions, UnfoldStrategy};
+
+    use std::path::Path;
+
+    #[test]
+    fn test_from_str() {
+        assert_eq!("A4".parse::<PageSize>(), Ok(PageSize::A4));
+        assert_eq!("letter".parse::<PageSize>(), Ok(PageSize::LETTER));
+        assert_eq!(
+            "100x150".parse::<PageSize>(),
+            Ok(PageSize {
+                width: 100.0,
+                height: 150.0
+            })
+        );
+        assert!("100".parse::<PageSize>().is_err());
+        assert!("0x150".parse::<PageSize>().is_err());
+        assert!("Tabloid".parse::<PageSize>().is_err());
+
+        assert_eq!("in".parse::<Units>(), Ok(Units::Inches));
+        assert_eq!("cm".parse::<Units>().unwrap().millimeters(), 10.0);
+        assert!("ft".parse::<Units>().is_err());
+    }
+
+    #[test]
+    fn test_sheet_count() {
+        let goal_mesh = GoalMesh::from_obj(
+            Path::new("tests/fixtures/unit_cube.obj"),
+            &LoadOptions {
+                strict: true,
+                ..LoadOptions::default()
+            },
+        )
+        .unwrap();
+        let net = goal_mesh.unfold(0, UnfoldStrategy::BreadthFirst).unwrap();
+
+        // At 40 mm per unit, the 4 x 3 cross fits on a single A4 page, but not inside of a wide
+        // margin, or at a larger scale
+        assert_eq!(sheet_count(&net, &[], &PageSize::A4, 1.0 / 40.0, 0.0), 1);
+        assert_eq!(sheet_count(&net, &[], &PageSize::A4, 1.0 / 40.0, 50.0), 2);
+        assert!(sheet_count(&net, &[], &PageSize::A4, 1.0 / 100.0, 0.0) > 2);
+    }
+
+    #[test]
+    fn test_scale_bar_length() {
+        assert_eq!(scale_bar_length(52.5), 50.0);
+        assert_eq!(scale_bar_length(50.0), 50.0);
+        assert_eq!(scale_bar_length(49.0), 20.0);
+        assert_eq!(scale_bar_length(3.0), 2.0);
+        assert_eq!(scale_bar_length(1.5), 1.0);
+        assert!((scale_bar_length(0.7) - 0.5).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_legend() {
+        // The default fold patterns are short enough for the samples to be 3 font sizes long
+        let legend = Legend::new(14.0, None);
+        assert_eq!(legend.sample_length, 42.0);
+        assert!((legend.size.x() - (42.0 + 14.0 * 0.5 + 14.0 * 0.6 * 13.0)).abs() < 1.0e-4);
+        assert_eq!(legend.size.y(), 63.0);
+        assert_eq!(legend.row_offset(0), 10.5);
+        assert_eq!(legend.row_offset(2), 52.5);
+
+        // Longer dashes stretch the samples
+        let legend = Legend::new(3.0, Some((10.0, 5.0)));
+        assert_eq!(legend.sample_length, 30.0);
+    }
+
+    #[test]
+    fn test_tiles() {
+        let page = PageSize {
+            width: 100.0,
+            height: 100.0,
+        };
+
+        // Something smaller than a single page only needs one tile, which is centered on it
+        let tiles = page.tiles(Vec2::new(0.0, 0.0), Vec2::new(50.0, 50.0), 10.0);
+        assert_eq!(tiles.len(), 1);
+        assert_eq!(tiles[0], (Vec2::new(-25.0, -25.0), 0, 0));
+
+        // Each tile advances by the page size minus the overlap, and together, the tiles should
+        // cover the entire rectangle
+        let (min, max) = (Vec2::new(0.0, 0.0), Vec2::new(200.0, 80.0));
+        let tiles = page.tiles(min, max, 10.0);
+        assert_eq!(tiles.len(), 3);
+        assert!((tiles[1].0.x() - tiles[0].0.x() - 90.0).abs() < 1.0e-4);
+        assert!(tiles[0].0.x() <= min.x() && tiles[0].0.y() <= min.y());
+        let (last, _, _) = tiles[tiles.len() - 1];
+        assert!(last.x() + page.width >= max.x() && last.y() + page.height >= max.y());
+    }
+}
@@ -0,0 +1,614 @@
+use crate::export::page::{net_pages, scale_bar_length, Legend, PageSize, LEGEND_ROWS, MARK_SIZE};
+use crate::export::NetStyle;
+use crate::goal_mesh::UnfoldResult;
+use crate::utils::find_bounding_box;
+
+use glam::{Vec2, Vec3};
+use pdf_writer::types::{LineCapStyle, LineJoinStyle};
+use pdf_writer::{Buf, Content, Name, Pdf, Rect, Ref, Str};
+
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+/// The number of points (the units of a .pdf file) per millimeter.
+const POINTS_PER_MM: f32 = 72.0 / 25.4;
+
+/// The (SRGB) color of the edges in filled mode, when no stroke color is provided.
+const EDGE_COLOR: f32 = 0.1;
+
+/// The name of the font (Courier) that every page can use.
+const FONT: Name = Name(b"F1");
+
+/// The name of the graphics state that sets the opacity of fills (see `write_document()`).
+const FILL: Name = Name(b"Fill");
+
+/// Appends a line from `a` to `b` to a content stream, and strokes it.
+fn line_to(content: &mut Content, a: &Vec2, b: &Vec2) {
+    content.move_to(a.x(), a.y()).line_to(b.x(), b.y()).stroke();
+}
+
+/// Appends a closed polygon to a content stream, and either fills or strokes it.
+fn polygon_to(content: &mut Content, points: &[Vec3], fill: bool) {
+    for (i, point) in points.iter().enumerate() {
+        if i == 0 {
+            content.move_to(point.x(), point.y());
+        } else {
+            content.line_to(point.x(), point.y());
+        }
+    }
+    content.close_path();
+    if fill {
+        content.fill_nonzero();
+    } else {
+        content.stroke();
+    }
+}
+
+/// Appends a circle to a content stream, and strokes it.
+fn circle_to(content: &mut Content, center: &Vec2, radius: f32) {
+    // Each quarter of the circle is approximated by a cubic Bézier curve, whose control points are
+    // this fraction of the radius away from its endpoints (along their tangents)
+    const KAPPA: f32 = 0.552_284_8;
+    let directions = [
+        Vec2::new(1.0, 0.0),
+        Vec2::new(0.0, 1.0),
+        Vec2::new(-1.0, 0.0),
+        Vec2::new(0.0, -1.0),
+    ];
+    let tangent = |direction: &Vec2| Vec2::new(-direction.y(), direction.x());
+
+    let start = *center + directions[0] * radius;
+    content.move_to(start.x(), start.y());
+    for i in 0..4 {
+        let (from, to) = (&directions[i], &directions[(i + 1) % 4]);
+        let control_0 = *center + (*from + tangent(from) * KAPPA) * radius;
+        let control_1 = *center + (*to - tangent(to) * KAPPA) * radius;
+        let end = *center + *to * radius;
+        content.cubic_to(
+            control_0.x(),
+            control_0.y(),
+            control_1.x(),
+            control_1.y(),
+            end.x(),
+            end.y(),
+        );
+    }
+    content.stroke();
+}
+
+/// Sets the fill color of a content stream.
+fn fill_color(content: &mut Content, color: &Vec3) {
+    content.set_fill_rgb(color.x(), color.y(), color.z());
+}
+
+/// Sets the stroke color of a content stream.
+fn stroke_color(content: &mut Content, color: &Vec3) {
+    content.set_stroke_rgb(color.x(), color.y(), color.z());
+}
+
+/// Appends `text` to a content stream, with its lower-left corner at the specified point. The font
+/// uses the WinAnsi encoding, so characters in Latin-1 (like the degree sign of fold angles) are
+/// written as their code points, and any other characters are replaced with `?`.
+fn text_to(content: &mut Content, text: &str, x: f32, y: f32, size: f32, color: &Vec3) {
+    let encoded = text
+        .chars()
+        .map(|c| match c as u32 {
+            code @ 0..=0xff => code as u8,
+            _ => b'?',
+        })
+        .collect::<Vec<_>>();
+    content.begin_text().set_font(FONT, size);
+    fill_color(content, color);
+    content.next_line(x, y).show(Str(&encoded)).end_text();
+}
+
+/// Appends a label that is centered on the specified point: Courier is monospaced (each glyph is
+/// 0.6 times as wide as it is tall), so the text can be centered without measuring it.
+fn label_to(content: &mut Content, text: &str, position: &Vec3) {
+    const FONT_SIZE: f32 = 14.0;
+    text_to(
+        content,
+        text,
+        position.x() - text.chars().count() as f32 * FONT_SIZE * 0.3,
+        position.y() - FONT_SIZE * 0.3,
+        FONT_SIZE,
+        &Vec3::splat(EDGE_COLOR),
+    );
+}
+
+/// Appends a scale bar: a horizontal line that starts at `start` and is `length` units long, with a
+/// tick at each end and `label` (its physical length) centered above it. `size` is both the height
+/// of the ticks and the font size of the label.
+fn scale_bar_to(
+    content: &mut Content,
+    start: &Vec2,
+    length: f32,
+    label: &str,
+    size: f32,
+    stroke_width: f32,
+) {
+    let (x, y) = (start.x(), start.y());
+    let edge_color = Vec3::splat(EDGE_COLOR);
+    content.save_state();
+    stroke_color(content, &edge_color);
+    content
+        .set_line_width(stroke_width)
+        .set_dash_pattern(vec![], 0.0);
+    line_to(
+        content,
+        &Vec2::new(x, y - size * 0.5),
+        &Vec2::new(x, y + size * 0.5),
+    );
+    line_to(content, &Vec2::new(x, y), &Vec2::new(x + length, y));
+    line_to(
+        content,
+        &Vec2::new(x + length, y - size * 0.5),
+        &Vec2::new(x + length, y + size * 0.5),
+    );
+    text_to(
+        content,
+        label,
+        x + length * 0.5 - label.len() as f32 * size * 0.3,
+        y + size * 0.75,
+        size,
+        &edge_color,
+    );
+    content.restore_state();
+}
+
+/// Appends a legend (see `Legend`) whose top-left corner is at `corner`, with each sample line
+/// dashed in the same way as the edges of the net.
+fn legend_to(content: &mut Content, corner: &Vec2, legend: &Legend, style: &NetStyle) {
+    let edge_color = Vec3::splat(EDGE_COLOR);
+    content.save_state();
+    stroke_color(content, &edge_color);
+    content
+        .set_line_width(style.stroke_width)
+        .set_line_cap(LineCapStyle::RoundCap);
+    for (row, (kind, name)) in LEGEND_ROWS.iter().enumerate() {
+        let (x, y) = (corner.x(), corner.y() - legend.row_offset(row));
+        content.set_dash_pattern(kind.dash_pattern(style.fold_dash), 0.0);
+        line_to(
+            content,
+            &Vec2::new(x, y),
+            &Vec2::new(x + legend.sample_length, y),
+        );
+        text_to(
+            content,
+            name,
+            x + legend.sample_length + legend.font_size * 0.5,
+            y - legend.font_size * 0.3,
+            legend.font_size,
+            &edge_color,
+        );
+    }
+    content.restore_state();
+}
+
+/// Appends the net itself (i.e. the polygons, tabs, edges, and labels) to a content stream, in the
+/// units of the net. Unlike the .svg exporter, the edges are always drawn, so that the cuts and
+/// folds are visible on the printed page: cuts are solid, and folds are dashed based on their kind.
+fn net_to(content: &mut Content, net: &UnfoldResult, tabs: &[Vec<Vec3>], style: &NetStyle) {
+    debug_assert_eq!(net.polygons.len(), style.colors.len());
+
+    if !style.wireframe {
+        // Translucent polygons are filled with the opacity of the `FILL` graphics state, which is
+        // restored before anything else is drawn
+        content.save_state().set_parameters(FILL);
+        for (polygon, polygon_color) in net.polygons.iter().zip(style.colors.iter()) {
+            let points = polygon
+                .iter()
+                .map(|&index| net.positions[index])
+                .collect::<Vec<_>>();
+            fill_color(content, polygon_color);
+            polygon_to(content, &points, true);
+        }
+        content.restore_state();
+    }
+
+    // Use the same styling as the renderer: strokes with round caps and joins
+    content
+        .set_line_width(style.stroke_width)
+        .set_line_cap(LineCapStyle::RoundCap)
+        .set_line_join(LineJoinStyle::RoundJoin);
+
+    stroke_color(content, &style.stroke_color.unwrap_or(Vec3::splat(0.5)));
+    for tab in tabs.iter() {
+        polygon_to(content, tab, false);
+    }
+
+    for edge in net.edges.iter() {
+        // Folds are shared by two polygons, so only draw them once
+        if edge.kind.is_fold() && edge.half_edge > edge.pair {
+            continue;
+        }
+
+        let edge_color = match style.stroke_color {
+            Some(stroke_color) => stroke_color,
+            None if style.wireframe => style.colors[net.triangle_polygons[usize::from(edge.face)]],
+            None => Vec3::splat(EDGE_COLOR),
+        };
+        stroke_color(content, &edge_color);
+        content
+            .set_line_width(style.edge_width(edge))
+            .set_dash_pattern(edge.kind.dash_pattern(style.fold_dash), 0.0);
+
+        let a = net.positions[edge.indices[0]];
+        let b = net.positions[edge.indices[1]];
+        line_to(content, &a.truncate(), &b.truncate());
+    }
+    content
+        .set_line_width(style.stroke_width)
+        .set_dash_pattern(vec![], 0.0);
+
+    // The diagonals of each polygon are solid, since they are neither cut nor folded
+    if style.triangulated {
+        for [a, b] in net.triangulation_edges() {
+            let edge_color = match style.stroke_color {
+                Some(stroke_color) => stroke_color,
+                None if style.wireframe => style.colors[net.triangle_polygons[a / 3]],
+                None => Vec3::splat(EDGE_COLOR),
+            };
+            stroke_color(content, &edge_color);
+            line_to(
+                content,
+                &net.positions[a].truncate(),
+                &net.positions[b].truncate(),
+            );
+        }
+    }
+
+    // Number each pair of matching cut edges
+    if style.labels {
+        for edge in net.edges.iter() {
+            if let Some(label) = net.cut_label(edge) {
+                label_to(content, &label, &net.label_position(edge, 12.0));
+            }
+        }
+    }
+
+    // Label each fold with its dihedral angle, next to the middle of the fold
+    if style.fold_angles {
+        for edge in net.edges.iter() {
+            if let Some(label) = net.fold_angle_label(edge) {
+                label_to(content, &label, &net.label_position(edge, 12.0));
+            }
+        }
+    }
+
+    // Label each polygon with its face of the .obj file, centered on the polygon
+    if style.face_labels {
+        for (polygon, face) in net.obj_faces.iter().enumerate() {
+            label_to(content, &face.to_string(), &net.polygon_center(polygon));
+        }
+    }
+}
+
+/// Writes a .pdf file with one page per entry of `pages`, where each page is a size (in points)
+/// and a content stream. Every page can use Courier as the font `FONT`, and the graphics state
+/// `FILL`, which sets the opacity of fills to `fill_opacity`.
+fn write_document(path: &Path, pages: &[(Vec2, Buf)], fill_opacity: f32) -> std::io::Result<()> {
+    // The catalog, the page tree, the font, and the graphics state come first: after that, each
+    // page is followed by its content stream
+    let catalog_id = Ref::new(1);
+    let page_tree_id = Ref::new(2);
+    let font_id = Ref::new(3);
+    let fill_id = Ref::new(4);
+    let page_ids = (0..pages.len())
+        .map(|i| Ref::new(5 + i as i32 * 2))
+        .collect::<Vec<_>>();
+
+    let mut pdf = Pdf::new();
+    pdf.catalog(catalog_id).pages(page_tree_id);
+    pdf.pages(page_tree_id)
+        .kids(page_ids.iter().copied())
+        .count(pages.len() as i32);
+    pdf.type1_font(font_id)
+        .base_font(Name(b"Courier"))
+        .encoding_predefined(Name(b"WinAnsiEncoding"));
+    pdf.ext_graphics(fill_id).non_stroking_alpha(fill_opacity);
+
+    for (&page_id, (size, content)) in page_ids.iter().zip(pages.iter()) {
+        let contents_id = page_id.next();
+        {
+            let mut page = pdf.page(page_id);
+            page.media_box(Rect::new(0.0, 0.0, size.x(), size.y()))
+                .parent(page_tree_id)
+                .contents(contents_id);
+            let mut resources = page.resources();
+            resources.fonts().pair(FONT, font_id);
+            resources.ext_g_states().pair(FILL, fill_id);
+        }
+        pdf.stream(contents_id, content);
+    }
+
+    let mut file = BufWriter::new(File::create(path)?);
+    file.write_all(&pdf.finish())
+}
+
+/// Writes the unfolded net to a (vector) .pdf file at the specified path, at its physical size:
+/// `units_per_mm` is the number of units of the net that make up a single millimeter on paper.
+/// The net is drawn in the same way as in exported .svg files, except that the edges of the net
+/// are always stroked (cuts are solid and folds are dashed), even in filled mode.
+///
+/// If a page size is provided, the net is split across as many pages of that size as necessary,
+/// with the same layout and registration marks as paginated .svg files. Otherwise, the file has a
+/// single page that fits the net (plus `padding` units on each side). If the style has a scale
+/// bar, it is drawn along the bottom of every page (or below the net, if there is a single page),
+/// and if it has a legend, it is drawn in the bottom-right corner of every page (or to the right
+/// of the top-right corner of the net, if there is a single page).
+/// Like paginated .svg files, nothing is drawn within `margin` millimeters of the edges of each
+/// page (the margin only applies when a page size is provided). Returns the number of pages.
+#[allow(clippy::too_many_arguments)]
+pub fn write_pdf(
+    path: &Path,
+    net: &UnfoldResult,
+    tabs: &[Vec<Vec3>],
+    style: &NetStyle,
+    page_size: Option<&PageSize>,
+    units_per_mm: f32,
+    padding: f32,
+    margin: f32,
+) -> std::io::Result<usize> {
+    let points_per_unit = POINTS_PER_MM / units_per_mm;
+
+    // Each page maps a rectangle of the net (given by its minimum corner and size, in the units of
+    // the net) onto the page, and fills it with the background color
+    let to_page = |min: Vec2, size: Vec2| {
+        let mut content = Content::new();
+        content.transform([
+            points_per_unit,
+            0.0,
+            0.0,
+            points_per_unit,
+            -min.x() * points_per_unit,
+            -min.y() * points_per_unit,
+        ]);
+        fill_color(&mut content, &style.background);
+        content
+            .rect(min.x(), min.y(), size.x(), size.y())
+            .fill_nonzero();
+        content
+    };
+
+    let mut pages = vec![];
+    match page_size {
+        Some(page_size) => {
+            // `page` is the printable area of each page (inside of the margin)
+            let (page, overlap) = page_size.printable_area(units_per_mm, margin);
+            let margin = margin * units_per_mm;
+            let mark_size = MARK_SIZE * units_per_mm;
+
+            for (corner, row, column) in net_pages(net, tabs, &page, overlap) {
+                // The tiles are in SVG space, where the y-axis points down
+                let min = Vec2::new(corner.x(), -corner.y() - page.height);
+                let size = Vec2::new(page.width, page.height);
+
+                // The contents are clipped to the printable area of the page
+                let mut content =
+                    to_page(min - Vec2::splat(margin), size + Vec2::splat(margin * 2.0));
+                content
+                    .save_state()
+                    .rect(min.x(), min.y(), size.x(), size.y())
+                    .clip_nonzero()
+                    .end_path();
+                net_to(&mut content, net, tabs, style);
+                content.restore_state();
+
+                // Draw a registration mark (a circle with a crosshair) at each corner of the area
+                // that this page owns
+                stroke_color(&mut content, &Vec3::zero());
+                content.set_line_width(0.25 * units_per_mm);
+                let inset = Vec2::splat(overlap * 0.5);
+                let (trim_min, trim_max) = (min + inset, min + size - inset);
+                let trim_corners = [
+                    trim_min,
+                    Vec2::new(trim_max.x(), trim_min.y()),
+                    trim_max,
+                    Vec2::new(trim_min.x(), trim_max.y()),
+                ];
+                for mark in trim_corners.iter() {
+                    let (x, y) = (mark.x(), mark.y());
+                    circle_to(&mut content, mark, mark_size * 0.25);
+                    line_to(
+                        &mut content,
+                        &Vec2::new(x - mark_size * 0.5, y),
+                        &Vec2::new(x + mark_size * 0.5, y),
+                    );
+                    line_to(
+                        &mut content,
+                        &Vec2::new(x, y - mark_size * 0.5),
+                        &Vec2::new(x, y + mark_size * 0.5),
+                    );
+                }
+
+                // Label the page with its position in the grid, so that the pages can be laid out
+                // in order
+                text_to(
+                    &mut content,
+                    &format!("Row {}, column {}", row + 1, column + 1),
+                    trim_min.x() + mark_size,
+                    trim_max.y() - units_per_mm,
+                    3.0 * units_per_mm,
+                    &Vec3::zero(),
+                );
+
+                if style.scale_bar {
+                    let length = scale_bar_length(page_size.width * 0.25);
+                    scale_bar_to(
+                        &mut content,
+                        &Vec2::new(trim_min.x() + mark_size, trim_min.y()),
+                        length * units_per_mm,
+                        &format!("{} mm", length),
+                        3.0 * units_per_mm,
+                        0.25 * units_per_mm,
+                    );
+                }
+
+                // The legend sits just above the bottom of the area that this page owns, clear of
+                // the registration mark in its corner
+                if style.legend {
+                    let legend = Legend::new(3.0 * units_per_mm, style.fold_dash);
+                    legend_to(
+                        &mut content,
+                        &Vec2::new(
+                            trim_max.x() - mark_size - legend.size.x(),
+                            trim_min.y() + legend.size.y(),
+                        ),
+                        &legend,
+                        style,
+                    );
+                }
+
+                pages.push((
+                    Vec2::new(page_size.width, page_size.height) * POINTS_PER_MM,
+                    content.finish(),
+                ));
+            }
+        }
+        None => {
+            let (net_min, net_max) = find_bounding_box(&net.positions);
+            let (net_min, net_max) = (net_min.truncate(), net_max.truncate());
+            let mut min = net_min - Vec2::splat(padding);
+            if style.scale_bar {
+                min -= Vec2::new(0.0, padding);
+            }
+            let mut max = net_max + Vec2::splat(padding);
+
+            // The legend uses the same size as the labels
+            let legend = Legend::new(14.0, style.fold_dash);
+            if style.legend {
+                max += Vec2::new(legend.size.x() + padding, 0.0);
+                min = Vec2::new(
+                    min.x(),
+                    min.y().min(net_max.y() - legend.size.y() - padding),
+                );
+            }
+            let size = max - min;
+
+            let mut content = to_page(min, size);
+            net_to(&mut content, net, tabs, style);
+
+            // The bar is at most a third as wide as the net, and uses the same size as the labels
+            if style.scale_bar {
+                let length = scale_bar_length((net_max.x() - net_min.x()) / units_per_mm / 3.0);
+                scale_bar_to(
+                    &mut content,
+                    &Vec2::new(net_min.x(), net_min.y() - padding),
+                    length * units_per_mm,
+                    &format!("{} mm", length),
+                    14.0,
+                    style.stroke_width,
+                );
+            }
+            if style.legend {
+                legend_to(
+                    &mut content,
+                    &Vec2::new(net_max.x() + padding, net_max.y()),
+                    &legend,
+                    style,
+                );
+            }
+            pages.push((size * points_per_unit, content.finish()));
+        }
+    }
+
+    write_document(path, &pages, style.fill_opacity)?;
+    Ok(pages.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::export::page::sheet_count;
+    use crate::goal_mesh::{GoalMesh, LoadOptions, UnfoldStrategy};
+
+    use lopdf::Document;
+
+    #[test]
+    fn test_write_pdf() {
+        let goal_mesh = GoalMesh::from_obj(
+            Path::new("tests/fixtures/unit_cube.obj"),
+            &LoadOptions {
+                strict: true,
+                ..LoadOptions::default()
+            },
+        )
+        .unwrap();
+        let net = goal_mesh.unfold(0, UnfoldStrategy::BreadthFirst).unwrap();
+        let colors = vec![Vec3::one(); net.polygons.len()];
+        let style = NetStyle::new(&colors);
+        let path = std::env::temp_dir().join("durer_write_pdf.pdf");
+
+        // Without a page size, the single page fits the net (plus the padding on each side) at its
+        // physical size, i.e. 40 mm per unit
+        let pages = write_pdf(&path, &net, &[], &style, None, 1.0 / 40.0, 0.5, 0.0).unwrap();
+        assert_eq!(pages, 1);
+        let document = Document::load(&path).unwrap();
+        let page = document.get_pages()[&1];
+        let media_box = document
+            .get_dictionary(page)
+            .unwrap()
+            .get(b"MediaBox")
+            .unwrap()
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|value| value.as_float().unwrap())
+            .collect::<Vec<_>>();
+        let (min, max) = find_bounding_box(&net.positions);
+        let size = (max - min).truncate() + Vec2::splat(1.0);
+        assert_eq!(&media_box[..2], &[0.0, 0.0]);
+        assert!((media_box[2] - size.x() * 40.0 * POINTS_PER_MM).abs() < 1.0e-2);
+        assert!((media_box[3] - size.y() * 40.0 * POINTS_PER_MM).abs() < 1.0e-2);
+        let fonts = document.get_page_fonts(page).unwrap();
+        assert_eq!(
+            fonts[&b"F1"[..]]
+                .get(b"BaseFont")
+                .unwrap()
+                .as_name()
+                .unwrap(),
+            b"Courier"
+        );
+
+        // The background and each polygon are filled, every cut (and every fold, once) is stroked,
+        // and each cut edge is labeled
+        let content =
+            lopdf::content::Content::decode(&document.get_page_content(page).unwrap()).unwrap();
+        let count = |operator: &str| {
+            content
+                .operations
+                .iter()
+                .filter(|operation| operation.operator == operator)
+                .count()
+        };
+        let folds = net.edges.iter().filter(|edge| edge.kind.is_fold()).count();
+        let labels = net
+            .edges
+            .iter()
+            .filter(|edge| net.cut_label(edge).is_some())
+            .count();
+        assert_eq!(count("f"), net.polygons.len() + 1);
+        assert_eq!(count("S"), net.edges.len() - folds / 2);
+        assert_eq!(count("Tj"), labels);
+
+        // With a page size, the net is split across the same pages as `sheet_count()` predicts
+        let pages = write_pdf(
+            &path,
+            &net,
+            &[],
+            &style,
+            Some(&PageSize::A4),
+            1.0 / 40.0,
+            0.5,
+            50.0,
+        )
+        .unwrap();
+        assert_eq!(
+            pages,
+            sheet_count(&net, &[], &PageSize::A4, 1.0 / 40.0, 50.0)
+        );
+        assert_eq!(Document::load(&path).unwrap().get_pages().len(), pages);
+    }
+}
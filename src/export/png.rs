@@ -0,0 +1,507 @@
+use crate::export::{NetStyle, OUTLINE_COLOR};
+use crate::goal_mesh::UnfoldResult;
+use crate::utils::{
+    dash_segments, find_bounding_box, linear_to_srgb, point_in_triangle_2d, srgb_to_linear,
+};
+
+use glam::{Mat3, Vec2, Vec3};
+
+use std::path::Path;
+
+/// The number of samples taken along each axis of a pixel, which is used for anti-aliasing.
+const SAMPLES_PER_AXIS: usize = 4;
+
+/// The largest width or height (in pixels) of a .png file whose size is based on its DPI, which
+/// keeps a typo in the DPI (or the scale) from exhausting the memory.
+const MAX_DPI_SIZE: u32 = 30_000;
+
+/// The number of millimeters per inch.
+const MM_PER_INCH: f32 = 25.4;
+
+/// The angle (in radians) that the goal mesh is tilted towards the viewer before it starts to
+/// unfold, see `write_png_frame()`.
+const ANIMATION_TILT: f32 = 0.9;
+
+/// An image that shapes can be (software) rasterized into, where colors are blended in linear
+/// space (just like the renderer does) and the y-axis points down.
+struct Canvas {
+    width: usize,
+    height: usize,
+
+    // The (linear) color of each pixel, row by row
+    pixels: Vec<Vec3>,
+}
+
+impl Canvas {
+    fn new(width: usize, height: usize, background: Vec3) -> Canvas {
+        Canvas {
+            width,
+            height,
+            pixels: vec![background; width * height],
+        }
+    }
+
+    /// Blends the specified (linear) color, at the specified opacity, into all of the pixels that
+    /// are covered by a shape, where `contains` returns `true` if a point (in pixel coordinates)
+    /// lies inside of the shape, and `min` and `max` are the corners of the shape's bounding box.
+    fn fill<F: Fn(&Vec2) -> bool>(
+        &mut self,
+        min: Vec2,
+        max: Vec2,
+        color: Vec3,
+        opacity: f32,
+        contains: F,
+    ) {
+        let clamp_to_canvas = |val: f32, size: usize| (val.max(0.0) as usize).min(size);
+        let (x_range, y_range) = (
+            clamp_to_canvas(min.x().floor(), self.width)
+                ..clamp_to_canvas(max.x().ceil() + 1.0, self.width),
+            clamp_to_canvas(min.y().floor(), self.height)
+                ..clamp_to_canvas(max.y().ceil() + 1.0, self.height),
+        );
+
+        for y in y_range {
+            for x in x_range.clone() {
+                // The fraction of samples (on a regular grid) that lie inside of the shape
+                let mut covered = 0;
+                for i in 0..SAMPLES_PER_AXIS {
+                    for j in 0..SAMPLES_PER_AXIS {
+                        let sample = Vec2::new(
+                            x as f32 + (i as f32 + 0.5) / SAMPLES_PER_AXIS as f32,
+                            y as f32 + (j as f32 + 0.5) / SAMPLES_PER_AXIS as f32,
+                        );
+                        if contains(&sample) {
+                            covered += 1;
+                        }
+                    }
+                }
+
+                if covered > 0 {
+                    let coverage = covered as f32 / (SAMPLES_PER_AXIS * SAMPLES_PER_AXIS) as f32;
+                    let pixel = &mut self.pixels[y * self.width + x];
+                    *pixel = pixel.lerp(color, coverage * opacity);
+                }
+            }
+        }
+    }
+
+    /// Blends the specified (linear) color into all of the pixels that are within `width / 2`
+    /// units of any of the given line segments, which results in round caps and joins.
+    fn stroke(&mut self, segments: &[(Vec2, Vec2)], width: f32, color: Vec3) {
+        if segments.is_empty() {
+            return;
+        }
+
+        let radius = width * 0.5;
+        let (min, max) = segments.iter().fold(
+            (Vec2::splat(f32::INFINITY), Vec2::splat(f32::NEG_INFINITY)),
+            |(min, max), (a, b)| (min.min(a.min(*b)), max.max(a.max(*b))),
+        );
+
+        self.fill(
+            min - Vec2::splat(radius),
+            max + Vec2::splat(radius),
+            color,
+            1.0,
+            |point| {
+                segments
+                    .iter()
+                    .any(|(a, b)| distance_to_segment(point, a, b) <= radius)
+            },
+        );
+    }
+
+    /// Returns the pixels of this canvas as (8-bit) SRGB values.
+    fn to_srgb_bytes(&self) -> Vec<u8> {
+        let to_byte = |val: f32| (linear_to_srgb(val).clamp(0.0, 1.0) * 255.0).round() as u8;
+        let mut bytes = Vec::with_capacity(self.pixels.len() * 3);
+        for color in self.pixels.iter() {
+            bytes.extend_from_slice(&[to_byte(color.x()), to_byte(color.y()), to_byte(color.z())]);
+        }
+        bytes
+    }
+}
+
+/// Returns the distance between `point` and the line segment from `a` to `b`.
+fn distance_to_segment(point: &Vec2, a: &Vec2, b: &Vec2) -> f32 {
+    let along = *b - *a;
+    let length_squared = along.dot(along);
+    let t = if length_squared > 0.0 {
+        ((*point - *a).dot(along) / length_squared).clamp(0.0, 1.0)
+    } else {
+        0.0
+    };
+    (*point - (*a + along * t)).length()
+}
+
+/// Converts an SRGB color to linear space.
+fn to_linear(color: &Vec3) -> Vec3 {
+    Vec3::new(
+        srgb_to_linear(color.x()),
+        srgb_to_linear(color.y()),
+        srgb_to_linear(color.z()),
+    )
+}
+
+/// Rasterizes the unfolded net (and any glue tabs) into a `resolution` x `resolution` .png file.
+/// The positions of the net should already be scaled and centered on a canvas that is
+/// `canvas_size` units wide, i.e. the same as the renderer's window: the net is drawn exactly as it
+/// appears on-screen (with the same colors, line widths, and dash patterns), just scaled to fit the
+/// requested resolution. Note that labels are not drawn.
+pub fn write_png(
+    path: &Path,
+    net: &UnfoldResult,
+    tabs: &[Vec<Vec3>],
+    style: &NetStyle,
+    canvas_size: f32,
+    resolution: u32,
+) -> std::io::Result<()> {
+    // Convert from the (centered) coordinate system of the renderer to pixel coordinates, where
+    // the origin is at the top-left corner of the image
+    let scale = resolution as f32 / canvas_size;
+    let canvas = rasterize(
+        net,
+        tabs,
+        style,
+        (resolution as usize, resolution as usize),
+        scale,
+        |point: &Vec3| {
+            Vec2::new(
+                point.x() * scale + resolution as f32 * 0.5,
+                resolution as f32 * 0.5 - point.y() * scale,
+            )
+        },
+    );
+    save_png(path, &canvas, None)
+}
+
+/// Rasterizes the unfolded net (and any glue tabs) into a .png file at its physical size, in the
+/// same way as `write_png()`: `units_per_mm` is the number of units of the net that make up a
+/// single millimeter on paper, and `dpi` is the number of pixels per inch. The image covers the
+/// bounding box of the net and its tabs, grown by `padding` units on each side, and the DPI is
+/// stored in the file (in its `pHYs` chunk), so that it prints at the right size.
+///
+/// Returns the width and height of the image (in pixels), or an error if either of them would be
+/// larger than `MAX_DPI_SIZE`.
+pub fn write_png_at_dpi(
+    path: &Path,
+    net: &UnfoldResult,
+    tabs: &[Vec<Vec3>],
+    style: &NetStyle,
+    units_per_mm: f32,
+    dpi: f32,
+    padding: f32,
+) -> std::io::Result<(u32, u32)> {
+    // The glue tabs stick out past the edges of the net, so they are part of the image as well
+    let (min, max) = tabs
+        .iter()
+        .flatten()
+        .fold(find_bounding_box(&net.positions), |(min, max), point| {
+            (min.min(*point), max.max(*point))
+        });
+    let (min, max) = (
+        min.truncate() - Vec2::splat(padding),
+        max.truncate() + Vec2::splat(padding),
+    );
+
+    let scale = dpi / MM_PER_INCH / units_per_mm;
+    let (width, height) = (
+        ((max.x() - min.x()) * scale).ceil().max(1.0),
+        ((max.y() - min.y()) * scale).ceil().max(1.0),
+    );
+    if width > MAX_DPI_SIZE as f32 || height > MAX_DPI_SIZE as f32 {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            format!(
+                "The .png file would be {}x{} pixels at {} DPI, which is larger than the limit of {} pixels along each side",
+                width, height, dpi, MAX_DPI_SIZE
+            ),
+        ));
+    }
+    let (width, height) = (width as u32, height as u32);
+
+    let canvas = rasterize(
+        net,
+        tabs,
+        style,
+        (width as usize, height as usize),
+        scale,
+        |point: &Vec3| Vec2::new(point.x() - min.x(), max.y() - point.y()) * scale,
+    );
+    save_png(path, &canvas, Some(dpi))?;
+    Ok((width, height))
+}
+
+/// Rasterizes a single frame of the net unfolding from the goal mesh into a `resolution` x
+/// `resolution` .png file, in the same way as `write_png()`: `positions` are the (3D) positions of
+/// the triangles of the net partway through unfolding (see `GoalMesh::fold_positions()`), and
+/// `progress` is how far along they are, from 0 (folded) to 1 (flat). The goal mesh is tilted
+/// towards the viewer while it is folded (by up to `ANIMATION_TILT`), so that its sides can be
+/// seen, and it levels out as it unfolds. Polygons are drawn from back to front and shaded by
+/// how much they face the viewer. Glue tabs and labels are not drawn.
+pub fn write_png_frame(
+    path: &Path,
+    net: &UnfoldResult,
+    positions: &[Vec3],
+    progress: f32,
+    style: &NetStyle,
+    canvas_size: f32,
+    resolution: u32,
+) -> std::io::Result<()> {
+    debug_assert_eq!(net.polygons.len(), style.colors.len());
+    let scale = resolution as f32 / canvas_size;
+    let line_width = style.stroke_width * scale;
+
+    // Tilting the goal mesh about the x-axis brings the parts below the page (i.e. behind the root
+    // face) down the image, and the parts above the page closer to the viewer
+    let tilt = Mat3::from_rotation_x(-ANIMATION_TILT * (1.0 - progress));
+    let positions = positions
+        .iter()
+        .map(|point| tilt.mul_vec3(*point))
+        .collect::<Vec<_>>();
+    let to_pixels = |point: &Vec3| {
+        Vec2::new(
+            point.x() * scale + resolution as f32 * 0.5,
+            resolution as f32 * 0.5 - point.y() * scale,
+        )
+    };
+
+    let mut polygon_triangles = vec![vec![]; net.polygons.len()];
+    for (triangle, &polygon) in positions.chunks(3).zip(net.triangle_polygons.iter()) {
+        polygon_triangles[polygon].push(triangle);
+    }
+    let depth = |polygon: usize| {
+        let outline = &net.polygons[polygon];
+        outline
+            .iter()
+            .map(|&index| positions[index].z())
+            .sum::<f32>()
+            / outline.len() as f32
+    };
+    let mut order = (0..net.polygons.len()).collect::<Vec<_>>();
+    order.sort_by(|&a, &b| depth(a).partial_cmp(&depth(b)).unwrap());
+
+    let mut canvas = Canvas::new(
+        resolution as usize,
+        resolution as usize,
+        to_linear(&style.background),
+    );
+    let outline_color = to_linear(&style.stroke_color.unwrap_or(Vec3::splat(OUTLINE_COLOR)));
+    let mut polygon_diagonals = vec![vec![]; net.polygons.len()];
+    if style.triangulated {
+        for [a, b] in net.triangulation_edges() {
+            polygon_diagonals[net.triangle_polygons[a / 3]]
+                .push((to_pixels(&positions[a]), to_pixels(&positions[b])));
+        }
+    }
+    for &polygon in order.iter() {
+        let color = to_linear(&style.colors[polygon]);
+        let outline = &net.polygons[polygon];
+        let segments = outline
+            .iter()
+            .zip(outline.iter().cycle().skip(1))
+            .map(|(&a, &b)| (to_pixels(&positions[a]), to_pixels(&positions[b])))
+            .collect::<Vec<_>>();
+
+        if style.wireframe {
+            let stroke_color = style.stroke_color.map_or(color, |color| to_linear(&color));
+            canvas.stroke(&segments, line_width, stroke_color);
+            canvas.stroke(&polygon_diagonals[polygon], line_width, stroke_color);
+            continue;
+        }
+
+        let triangles = polygon_triangles[polygon]
+            .iter()
+            .map(|triangle| {
+                [
+                    to_pixels(&triangle[0]),
+                    to_pixels(&triangle[1]),
+                    to_pixels(&triangle[2]),
+                ]
+            })
+            .collect::<Vec<_>>();
+        let (min, max) = triangles.iter().flatten().fold(
+            (Vec2::splat(f32::INFINITY), Vec2::splat(f32::NEG_INFINITY)),
+            |(min, max), point| (min.min(*point), max.max(*point)),
+        );
+        let triangle = polygon_triangles[polygon][0];
+        let normal = (triangle[1] - triangle[0])
+            .cross(triangle[2] - triangle[0])
+            .normalize();
+        let shade = 0.55 + 0.45 * normal.z().abs();
+        canvas.fill(min, max, color * shade, style.fill_opacity, |point| {
+            triangles
+                .iter()
+                .any(|[a, b, c]| point_in_triangle_2d(point, a, b, c))
+        });
+
+        // Outlines are drawn along with each polygon, so that the polygons in front cover them
+        if style.outline {
+            canvas.stroke(&segments, line_width, outline_color);
+        }
+        canvas.stroke(&polygon_diagonals[polygon], line_width, outline_color);
+    }
+
+    save_png(path, &canvas, None)
+}
+
+/// Rasterizes the unfolded net (and any glue tabs) into a canvas of the specified size (in
+/// pixels), where `to_pixels` converts the positions of the net to pixel coordinates and `scale`
+/// is the number of pixels per unit of the net.
+fn rasterize<F: Fn(&Vec3) -> Vec2>(
+    net: &UnfoldResult,
+    tabs: &[Vec<Vec3>],
+    style: &NetStyle,
+    size: (usize, usize),
+    scale: f32,
+    to_pixels: F,
+) -> Canvas {
+    debug_assert_eq!(net.polygons.len(), style.colors.len());
+    let line_width = style.stroke_width * scale;
+
+    let mut canvas = Canvas::new(size.0, size.1, to_linear(&style.background));
+
+    if style.wireframe {
+        // Folds are shared by two polygons, so only draw them once
+        for edge in net.edges.iter() {
+            if edge.kind.is_fold() && edge.half_edge > edge.pair {
+                continue;
+            }
+
+            let a = net.positions[edge.indices[0]];
+            let b = net.positions[edge.indices[1]];
+            let segments = dash_segments(&a, &b, &edge.kind.dash_pattern(style.fold_dash))
+                .iter()
+                .map(|(start, end)| (to_pixels(start), to_pixels(end)))
+                .collect::<Vec<_>>();
+
+            let color = style
+                .stroke_color
+                .unwrap_or(style.colors[net.triangle_polygons[usize::from(edge.face)]]);
+            canvas.stroke(&segments, style.edge_width(edge) * scale, to_linear(&color));
+        }
+    } else {
+        // Each polygon is drawn as a single shape, so that no seams appear between its triangles
+        for (polygon_index, color) in style.colors.iter().enumerate() {
+            let triangles = net
+                .positions
+                .chunks(3)
+                .zip(net.triangle_polygons.iter())
+                .filter(|(_, &triangle_polygon)| triangle_polygon == polygon_index)
+                .map(|(triangle, _)| {
+                    [
+                        to_pixels(&triangle[0]),
+                        to_pixels(&triangle[1]),
+                        to_pixels(&triangle[2]),
+                    ]
+                })
+                .collect::<Vec<_>>();
+
+            let (min, max) = triangles.iter().flatten().fold(
+                (Vec2::splat(f32::INFINITY), Vec2::splat(f32::NEG_INFINITY)),
+                |(min, max), point| (min.min(*point), max.max(*point)),
+            );
+
+            canvas.fill(min, max, to_linear(color), style.fill_opacity, |point| {
+                triangles
+                    .iter()
+                    .any(|[a, b, c]| point_in_triangle_2d(point, a, b, c))
+            });
+        }
+
+        // Outlines are drawn after all of the polygons, so that none of them are covered up
+        if style.outline {
+            let outline_color =
+                to_linear(&style.stroke_color.unwrap_or(Vec3::splat(OUTLINE_COLOR)));
+            for polygon in net.polygons.iter() {
+                let segments = polygon
+                    .iter()
+                    .zip(polygon.iter().cycle().skip(1))
+                    .map(|(&a, &b)| (to_pixels(&net.positions[a]), to_pixels(&net.positions[b])))
+                    .collect::<Vec<_>>();
+                canvas.stroke(&segments, line_width, outline_color);
+            }
+        }
+    }
+
+    // The diagonals of each polygon are drawn in the color of its edges (or of the outlines in
+    // filled mode)
+    if style.triangulated {
+        for [a, b] in net.triangulation_edges() {
+            let color = match style.stroke_color {
+                Some(stroke_color) => stroke_color,
+                None if style.wireframe => style.colors[net.triangle_polygons[a / 3]],
+                None => Vec3::splat(OUTLINE_COLOR),
+            };
+            canvas.stroke(
+                &[(to_pixels(&net.positions[a]), to_pixels(&net.positions[b]))],
+                line_width,
+                to_linear(&color),
+            );
+        }
+    }
+
+    // Draw the glue tabs as (closed) outlines
+    let tab_color = to_linear(&style.stroke_color.unwrap_or(Vec3::splat(0.5)));
+    for tab in tabs.iter() {
+        let segments = tab
+            .iter()
+            .zip(tab.iter().cycle().skip(1))
+            .map(|(a, b)| (to_pixels(a), to_pixels(b)))
+            .collect::<Vec<_>>();
+        canvas.stroke(&segments, line_width, tab_color);
+    }
+
+    canvas
+}
+
+/// Returns the CRC-32 of the specified bytes, which is the checksum of every chunk of a .png file.
+fn crc32(bytes: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for &byte in bytes {
+        crc ^= byte as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 != 0 {
+                (crc >> 1) ^ 0xedb8_8320
+            } else {
+                crc >> 1
+            };
+        }
+    }
+    !crc
+}
+
+/// Encodes the canvas as a .png file at the specified path, which optionally stores the number of
+/// pixels per inch in a `pHYs` chunk (that the encoder doesn't support writing itself).
+fn save_png(path: &Path, canvas: &Canvas, dpi: Option<f32>) -> std::io::Result<()> {
+    let mut bytes = vec![];
+    image::png::PngEncoder::new(&mut bytes)
+        .encode(
+            &canvas.to_srgb_bytes(),
+            canvas.width as u32,
+            canvas.height as u32,
+            image::ColorType::Rgb8,
+        )
+        .map_err(|e| match e {
+            image::ImageError::IoError(e) => e,
+            e => std::io::Error::other(e.to_string()),
+        })?;
+
+    if let Some(dpi) = dpi {
+        // The chunk stores the number of pixels per meter along each axis, and it has to come
+        // before the image data: the file starts with an 8-byte signature and the 25-byte header
+        let pixels_per_meter = (dpi / MM_PER_INCH * 1000.0).round() as u32;
+        let mut chunk = b"pHYs".to_vec();
+        chunk.extend_from_slice(&pixels_per_meter.to_be_bytes());
+        chunk.extend_from_slice(&pixels_per_meter.to_be_bytes());
+        chunk.push(1);
+        let crc = crc32(&chunk);
+
+        let mut physical = 9u32.to_be_bytes().to_vec();
+        physical.extend_from_slice(&chunk);
+        physical.extend_from_slice(&crc.to_be_bytes());
+        bytes.splice(33..33, physical);
+    }
+
+    std::fs::write(path, bytes)
+}
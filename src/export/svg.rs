@@ -0,0 +1,933 @@
+use crate::export::page::{net_pages, scale_bar_length, Legend, PageSize, LEGEND_ROWS, MARK_SIZE};
+use crate::export::{NetStyle, OUTLINE_COLOR};
+use crate::goal_mesh::{NetEdge, UnfoldResult};
+use crate::utils::{find_bounding_box, rgb_to_hex};
+
+use glam::{Vec2, Vec3};
+
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+/// Returns the `stroke-dasharray` attribute (with a leading space) of a line with the specified dash
+/// pattern, or nothing for solid lines.
+fn dash_array(pattern: &[f32]) -> String {
+    if pattern.is_empty() {
+        String::new()
+    } else {
+        format!(
+            " stroke-dasharray=\"{}\"",
+            pattern
+                .iter()
+                .map(|length| length.to_string())
+                .collect::<Vec<_>>()
+                .join(" ")
+        )
+    }
+}
+
+/// A helper function that writes a single (optionally dashed) line from `a` to `b`, with round
+/// caps, where `indent` is the whitespace in front of the element.
+fn write_line<W: Write>(
+    file: &mut W,
+    indent: &str,
+    (a, b): (&Vec3, &Vec3),
+    color: &Vec3,
+    width: f32,
+    dash_array: &str,
+) -> std::io::Result<()> {
+    writeln!(
+        file,
+        "{}<line x1=\"{}\" y1=\"{}\" x2=\"{}\" y2=\"{}\" stroke=\"{}\" stroke-width=\"{}\" stroke-linecap=\"round\"{}/>",
+        indent,
+        a.x(),
+        -a.y(),
+        b.x(),
+        -b.y(),
+        rgb_to_hex(color),
+        width,
+        dash_array
+    )
+}
+
+/// A helper function that writes a label (i.e. the number of a cut, the angle of a fold, or the
+/// index of a face), centered on `position`, where `indent` is the whitespace in front of the
+/// element.
+fn write_label<W: Write>(
+    file: &mut W,
+    indent: &str,
+    position: &Vec3,
+    text: &str,
+) -> std::io::Result<()> {
+    writeln!(
+        file,
+        "{}<text x=\"{}\" y=\"{}\" font-family=\"DejaVu Sans Mono, monospace\" font-size=\"14\" fill=\"#1a1a1a\" text-anchor=\"middle\" dominant-baseline=\"central\">{}</text>",
+        indent,
+        position.x(),
+        -position.y(),
+        text
+    )
+}
+
+/// A helper function that writes the filled shape of the specified polygon.
+fn write_polygon<W: Write>(
+    file: &mut W,
+    indent: &str,
+    net: &UnfoldResult,
+    polygon: &[usize],
+    color: &Vec3,
+    style: &NetStyle,
+) -> std::io::Result<()> {
+    let points = polygon
+        .iter()
+        .map(|&index| net.positions[index])
+        .map(|point| format!("{},{}", point.x(), -point.y()))
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    // Opaque polygons don't need the attribute
+    let opacity = if style.fill_opacity < 1.0 {
+        format!(" fill-opacity=\"{}\"", style.fill_opacity)
+    } else {
+        String::new()
+    };
+    writeln!(
+        file,
+        "{}<polygon points=\"{}\" fill=\"{}\"{}/>",
+        indent,
+        points,
+        rgb_to_hex(color),
+        opacity
+    )
+}
+
+/// A helper function that writes every polygon of the net into its own group (see
+/// `NetStyle::face_groups`), whose id is based on the index of the face of the .obj file that it
+/// came from, e.g. `face-12`. The sides of the polygon are written into nested groups of cuts and
+/// folds (`face-12-cuts` and `face-12-folds`), followed by its diagonals (if the net is
+/// triangulated) and its labels. Folds are shared by two polygons, but each one is only written
+/// once, into the group of whichever polygon is written last, so that neither polygon covers it.
+fn write_face_groups<W: Write>(
+    file: &mut W,
+    net: &UnfoldResult,
+    style: &NetStyle,
+) -> std::io::Result<()> {
+    let edge_polygon = |edge: &NetEdge| net.triangle_polygons[usize::from(edge.face)];
+    let half_edge_polygons = net
+        .edges
+        .iter()
+        .map(|edge| (edge.half_edge, edge_polygon(edge)))
+        .collect::<HashMap<_, _>>();
+
+    let mut polygon_edges = vec![vec![]; net.polygons.len()];
+    let mut polygon_sides = vec![vec![]; net.polygons.len()];
+    for edge in net.edges.iter() {
+        polygon_sides[edge_polygon(edge)].push(edge);
+        if !edge.kind.is_fold() {
+            polygon_edges[edge_polygon(edge)].push(edge);
+        } else if edge.half_edge < edge.pair {
+            let polygon = edge_polygon(edge).max(half_edge_polygons[&edge.pair]);
+            polygon_edges[polygon].push(edge);
+        }
+    }
+    let mut polygon_diagonals = vec![vec![]; net.polygons.len()];
+    if style.triangulated {
+        for [a, b] in net.triangulation_edges() {
+            polygon_diagonals[net.triangle_polygons[a / 3]].push([a, b]);
+        }
+    }
+
+    let outline_color = style.stroke_color.unwrap_or(Vec3::splat(OUTLINE_COLOR));
+    for (polygon, color) in style.colors.iter().enumerate() {
+        let face = net.obj_faces[polygon];
+        writeln!(file, "  <g id=\"face-{}\">", face)?;
+
+        // In filled mode, the sides are only drawn if the polygon is outlined (and then, like the
+        // outline, they are solid)
+        if !style.wireframe {
+            write_polygon(file, "    ", net, &net.polygons[polygon], color, style)?;
+        }
+        if style.wireframe || style.outline {
+            for &(name, folds) in [("cuts", false), ("folds", true)].iter() {
+                writeln!(file, "    <g id=\"face-{}-{}\">", face, name)?;
+                for edge in polygon_edges[polygon]
+                    .iter()
+                    .filter(|edge| edge.kind.is_fold() == folds)
+                {
+                    let a = &net.positions[edge.indices[0]];
+                    let b = &net.positions[edge.indices[1]];
+                    if style.wireframe {
+                        write_line(
+                            file,
+                            "      ",
+                            (a, b),
+                            &style.stroke_color.unwrap_or(*color),
+                            style.edge_width(edge),
+                            &dash_array(&edge.kind.dash_pattern(style.fold_dash)),
+                        )?;
+                    } else {
+                        write_line(
+                            file,
+                            "      ",
+                            (a, b),
+                            &outline_color,
+                            style.stroke_width,
+                            "",
+                        )?;
+                    }
+                }
+                writeln!(file, "    </g>")?;
+            }
+        }
+
+        let diagonal_color = match style.stroke_color {
+            Some(stroke_color) => stroke_color,
+            None if style.wireframe => *color,
+            None => outline_color,
+        };
+        for &[a, b] in polygon_diagonals[polygon].iter() {
+            let (a, b) = (&net.positions[a], &net.positions[b]);
+            write_line(
+                file,
+                "    ",
+                (a, b),
+                &diagonal_color,
+                style.stroke_width,
+                "",
+            )?;
+        }
+
+        for edge in polygon_sides[polygon].iter() {
+            if style.labels {
+                if let Some(label) = net.cut_label(edge) {
+                    write_label(file, "    ", &net.label_position(edge, 12.0), &label)?;
+                }
+            }
+            if style.fold_angles {
+                if let Some(label) = net.fold_angle_label(edge) {
+                    write_label(file, "    ", &net.label_position(edge, 12.0), &label)?;
+                }
+            }
+        }
+        if style.face_labels {
+            write_label(
+                file,
+                "    ",
+                &net.polygon_center(polygon),
+                &face.to_string(),
+            )?;
+        }
+        writeln!(file, "  </g>")?;
+    }
+
+    Ok(())
+}
+
+/// A helper function that writes the net itself (i.e. the polygons or edges, tabs, and labels),
+/// without the surrounding `<svg>` element.
+fn write_contents<W: Write>(
+    file: &mut W,
+    net: &UnfoldResult,
+    tabs: &[Vec<Vec3>],
+    style: &NetStyle,
+) -> std::io::Result<()> {
+    debug_assert_eq!(net.polygons.len(), style.colors.len());
+
+    if style.face_groups {
+        write_face_groups(file, net, style)?;
+    } else if style.wireframe {
+        // Use the same styling as the renderer: strokes with round caps, where folds are
+        // dashed based on their kind (and shared folds are only written once)
+        for edge in net.edges.iter() {
+            if edge.kind.is_fold() && edge.half_edge > edge.pair {
+                continue;
+            }
+
+            let a = &net.positions[edge.indices[0]];
+            let b = &net.positions[edge.indices[1]];
+            let dash_array = dash_array(&edge.kind.dash_pattern(style.fold_dash));
+
+            let color = style
+                .stroke_color
+                .unwrap_or(style.colors[net.triangle_polygons[usize::from(edge.face)]]);
+            write_line(
+                file,
+                "  ",
+                (a, b),
+                &color,
+                style.edge_width(edge),
+                &dash_array,
+            )?;
+        }
+    } else {
+        for (polygon, color) in net.polygons.iter().zip(style.colors.iter()) {
+            write_polygon(file, "  ", net, polygon, color, style)?;
+        }
+
+        // Outlines are drawn after all of the polygons, so that none of them are covered up (and
+        // like in wireframe mode, shared folds are only written once)
+        if style.outline {
+            let outline_color = style.stroke_color.unwrap_or(Vec3::splat(OUTLINE_COLOR));
+            for edge in net.edges.iter() {
+                if edge.kind.is_fold() && edge.half_edge > edge.pair {
+                    continue;
+                }
+
+                let a = &net.positions[edge.indices[0]];
+                let b = &net.positions[edge.indices[1]];
+                write_line(file, "  ", (a, b), &outline_color, style.stroke_width, "")?;
+            }
+        }
+    }
+
+    // The diagonals of each polygon are solid, since they are neither cut nor folded, and are
+    // drawn in the color of its edges (or of the outlines in filled mode)
+    if style.triangulated && !style.face_groups {
+        for [a, b] in net.triangulation_edges() {
+            let color = match style.stroke_color {
+                Some(stroke_color) => stroke_color,
+                None if style.wireframe => style.colors[net.triangle_polygons[a / 3]],
+                None => Vec3::splat(OUTLINE_COLOR),
+            };
+            let (a, b) = (&net.positions[a], &net.positions[b]);
+            write_line(file, "  ", (a, b), &color, style.stroke_width, "")?;
+        }
+    }
+
+    // Glue tabs don't belong to a single face, so they are grouped on their own
+    if style.face_groups {
+        writeln!(file, "  <g id=\"tabs\">")?;
+    }
+    let tab_color = style.stroke_color.unwrap_or(Vec3::splat(0.5));
+    for tab in tabs.iter() {
+        let points = tab
+            .iter()
+            .map(|point| format!("{},{}", point.x(), -point.y()))
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        writeln!(
+            file,
+            "  <polygon points=\"{}\" fill=\"none\" stroke=\"{}\" stroke-width=\"{}\" stroke-linejoin=\"round\"/>",
+            points,
+            rgb_to_hex(&tab_color),
+            style.stroke_width
+        )?;
+    }
+    if style.face_groups {
+        writeln!(file, "  </g>")?;
+        return Ok(());
+    }
+
+    // Number each pair of matching cut edges, using the same size and placement as the renderer
+    if style.labels {
+        for edge in net.edges.iter() {
+            if let Some(label) = net.cut_label(edge) {
+                write_label(file, "  ", &net.label_position(edge, 12.0), &label)?;
+            }
+        }
+    }
+
+    // Label each fold with its dihedral angle, next to the middle of the fold
+    if style.fold_angles {
+        for edge in net.edges.iter() {
+            if let Some(label) = net.fold_angle_label(edge) {
+                write_label(file, "  ", &net.label_position(edge, 12.0), &label)?;
+            }
+        }
+    }
+
+    // Label each polygon with its face of the .obj file, centered on the polygon
+    if style.face_labels {
+        for (polygon, face) in net.obj_faces.iter().enumerate() {
+            write_label(file, "  ", &net.polygon_center(polygon), &face.to_string())?;
+        }
+    }
+
+    Ok(())
+}
+
+/// A helper function that writes a scale bar: a horizontal line that starts at `start` and is
+/// `length` units long, with a tick at each end and `label` (its physical length) written above it.
+/// `size` is both the height of the ticks and the font size of the label.
+fn write_scale_bar<W: Write>(
+    file: &mut W,
+    start: Vec2,
+    length: f32,
+    label: &str,
+    size: f32,
+    stroke_width: f32,
+) -> std::io::Result<()> {
+    let (x, y) = (start.x(), start.y());
+    writeln!(
+        file,
+        "  <path d=\"M {} {} V {} M {} {} H {} M {} {} V {}\" fill=\"none\" stroke=\"#1a1a1a\" stroke-width=\"{}\"/>",
+        x,
+        y - size * 0.5,
+        y + size * 0.5,
+        x,
+        y,
+        x + length,
+        x + length,
+        y - size * 0.5,
+        y + size * 0.5,
+        stroke_width
+    )?;
+    writeln!(
+        file,
+        "  <text x=\"{}\" y=\"{}\" font-family=\"DejaVu Sans Mono, monospace\" font-size=\"{}\" fill=\"#1a1a1a\" text-anchor=\"middle\">{}</text>",
+        x + length * 0.5,
+        y - size * 0.75,
+        size,
+        label
+    )
+}
+
+/// A helper function that writes a legend (see `Legend`) whose top-left corner is at `corner`, with
+/// each sample line dashed in the same way as the edges of the net.
+fn write_legend<W: Write>(
+    file: &mut W,
+    corner: Vec2,
+    legend: &Legend,
+    style: &NetStyle,
+) -> std::io::Result<()> {
+    for (row, (kind, name)) in LEGEND_ROWS.iter().enumerate() {
+        let (x, y) = (corner.x(), corner.y() + legend.row_offset(row));
+        let dash_array = dash_array(&kind.dash_pattern(style.fold_dash));
+        writeln!(
+            file,
+            "  <line x1=\"{}\" y1=\"{}\" x2=\"{}\" y2=\"{}\" stroke=\"#1a1a1a\" stroke-width=\"{}\" stroke-linecap=\"round\"{}/>",
+            x,
+            y,
+            x + legend.sample_length,
+            y,
+            style.stroke_width,
+            dash_array
+        )?;
+        writeln!(
+            file,
+            "  <text x=\"{}\" y=\"{}\" font-family=\"DejaVu Sans Mono, monospace\" font-size=\"{}\" fill=\"#1a1a1a\" dominant-baseline=\"central\">{}</text>",
+            x + legend.sample_length + legend.font_size * 0.5,
+            y,
+            legend.font_size,
+            name
+        )?;
+    }
+
+    Ok(())
+}
+
+/// The default padding (in the units of the net) between the net and the edges of the canvas, see
+/// `SvgOptions`.
+pub const DEFAULT_PADDING: f32 = 20.0;
+
+/// Controls the canvas of `write_svg()`. Missing fields can be filled in with their defaults, i.e.
+/// `SvgOptions { padding: 0.0, ..SvgOptions::default() }`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SvgOptions {
+    // The number of units of the net that make up a single millimeter on paper, which sets the
+    // physical size of the file (and the length of the scale bar, if the style has one)
+    pub units_per_mm: f32,
+
+    // The canvas is the bounding box of the net, grown by this many units on each side
+    pub padding: f32,
+}
+
+impl Default for SvgOptions {
+    fn default() -> Self {
+        SvgOptions {
+            units_per_mm: 1.0,
+            padding: DEFAULT_PADDING,
+        }
+    }
+}
+
+/// Writes the unfolded net to an .svg file at the specified path. The positions of the net
+/// should already be scaled and centered. Any glue tabs are drawn as outlines, and if
+/// labels are enabled, each pair of matching cut edges is numbered (and each polygon is labeled
+/// with its face of the .obj file, if face labels are enabled). In wireframe mode, each edge
+/// is written as a separate line, with mountain and valley folds dashed so they can be told apart
+/// (otherwise, each polygon is filled, and optionally outlined).
+///
+/// The viewBox of the resulting file is the bounding box of the net, grown by `options.padding`
+/// units on each side, and the file is sized to print at the physical size of the net, where
+/// `options.units_per_mm` is the number of units of the net that make up a single millimeter on
+/// paper. Since the y-axis points down in SVG space (but up in Bevy), all y-coordinates are negated
+/// so that the file matches what is drawn on-screen.
+///
+/// If the style has a scale bar, it is drawn below the bottom-left corner of the net (and the
+/// canvas is grown to fit it), so that it measures its label once printed. If the style has a legend, it is drawn to the right of the
+/// top-right corner of the net (and the canvas is grown to fit it as well).
+pub fn write_svg(
+    path: &Path,
+    net: &UnfoldResult,
+    tabs: &[Vec<Vec3>],
+    style: &NetStyle,
+    options: &SvgOptions,
+) -> std::io::Result<()> {
+    let SvgOptions {
+        units_per_mm,
+        padding,
+    } = *options;
+    let (min, max) = find_bounding_box(&net.positions);
+    let (min_x, min_y) = (min.x() - padding, -max.y() - padding);
+    let (mut width, mut height) = (
+        max.x() - min.x() + padding * 2.0,
+        max.y() - min.y() + padding * 2.0,
+    );
+    if style.scale_bar {
+        height += padding;
+    }
+
+    // The legend uses the same size as the other labels
+    let legend = Legend::new(14.0, style.fold_dash);
+    if style.legend {
+        width += legend.size.x() + padding;
+        height = height.max(legend.size.y() + padding * 2.0);
+    }
+
+    let mut file = BufWriter::new(File::create(path)?);
+
+    writeln!(
+        file,
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"{} {} {} {}\" width=\"{}mm\" height=\"{}mm\">",
+        min_x,
+        min_y,
+        width,
+        height,
+        width / units_per_mm,
+        height / units_per_mm
+    )?;
+
+    // Fill the entire canvas with the background color
+    writeln!(
+        file,
+        "  <rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"{}\"/>",
+        min_x,
+        min_y,
+        width,
+        height,
+        rgb_to_hex(&style.background)
+    )?;
+
+    write_contents(&mut file, net, tabs, style)?;
+
+    // The bar is at most a third as wide as the net, and uses the same size as the other labels
+    if style.scale_bar {
+        let length = scale_bar_length((max.x() - min.x()) / units_per_mm / 3.0);
+        write_scale_bar(
+            &mut file,
+            Vec2::new(min.x(), -min.y() + padding),
+            length * units_per_mm,
+            &format!("{} mm", length),
+            14.0,
+            style.stroke_width,
+        )?;
+    }
+
+    if style.legend {
+        write_legend(
+            &mut file,
+            Vec2::new(max.x() + padding, -max.y()),
+            &legend,
+            style,
+        )?;
+    }
+
+    writeln!(file, "</svg>")?;
+
+    Ok(())
+}
+
+/// Writes only the outline of the unfolded net (see `UnfoldResult::outline()`) to an .svg file at
+/// the specified path, for cutters that should only cut the net out: the whole outline (including
+/// any tabs and slots) is a single `<path>` with one closed subpath per loop, without any folds,
+/// labels, or fills. The file is sized to print at the physical size of the net, where
+/// `units_per_mm` is the number of units of the net that make up a single millimeter on paper, and
+/// its viewBox is the bounding box of the outline, grown by `padding` units on each side (with
+/// the y-axis flipped, like `write_svg()`).
+pub fn write_outline_svg(
+    path: &Path,
+    net: &UnfoldResult,
+    tabs: &[Vec<Vec3>],
+    units_per_mm: f32,
+    padding: f32,
+) -> std::io::Result<()> {
+    let outline = net
+        .outline(tabs)
+        .map_err(|e| std::io::Error::other(e.to_string()))?;
+    let points = outline.iter().flatten().cloned().collect::<Vec<_>>();
+    let (min, max) = find_bounding_box(&points);
+    let (min_x, min_y) = (min.x() - padding, -max.y() - padding);
+    let (width, height) = (
+        max.x() - min.x() + padding * 2.0,
+        max.y() - min.y() + padding * 2.0,
+    );
+
+    let mut file = BufWriter::new(File::create(path)?);
+
+    writeln!(
+        file,
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"{} {} {} {}\" width=\"{}mm\" height=\"{}mm\">",
+        min_x,
+        min_y,
+        width,
+        height,
+        width / units_per_mm,
+        height / units_per_mm
+    )?;
+
+    let commands = outline
+        .iter()
+        .map(|points| {
+            let mut subpath = String::new();
+            for (i, point) in points.iter().enumerate() {
+                subpath.push_str(&format!(
+                    "{}{} {} ",
+                    if i == 0 { "M" } else { "L" },
+                    point.x(),
+                    -point.y()
+                ));
+            }
+            subpath.push('Z');
+            subpath
+        })
+        .collect::<Vec<_>>();
+    writeln!(
+        file,
+        "  <path d=\"{}\" fill=\"none\" stroke=\"#000000\" stroke-width=\"{}\" stroke-linejoin=\"round\"/>",
+        commands.join(" "),
+        0.25 * units_per_mm
+    )?;
+
+    writeln!(file, "</svg>")?;
+
+    Ok(())
+}
+
+/// Splits the unfolded net across as many pages of the specified size as necessary, and writes
+/// each page to a separate .svg file next to `path`: for example, the page in the first row and
+/// second column of `net.svg` is written to `net-1-2.svg`. `units_per_mm` is the number of units
+/// of the net that make up a single millimeter on paper. Pages that don't contain any part of the
+/// net are skipped, and the paths of all of the files that were written are returned.
+///
+/// Neighboring pages share a strip along their common border: registration marks are drawn at the
+/// middle of each strip (i.e. at the corners of the area that each page "owns"), so that pages can be
+/// lined up or trimmed along the marks. Faces that straddle the border of a page are drawn on every
+/// page that they touch (and clipped to each page). If the style has a scale bar, one is drawn
+/// along the bottom of every page, and if it has a legend, one is drawn in the bottom-right corner
+/// of every page (also at the same physical size on every page).
+///
+/// Nothing is drawn within `margin` millimeters of the edges of each page (e.g. where a printer
+/// can't print), so only the rest of each page is used to tile the net.
+pub fn write_svg_pages(
+    path: &Path,
+    net: &UnfoldResult,
+    tabs: &[Vec<Vec3>],
+    style: &NetStyle,
+    page_size: &PageSize,
+    units_per_mm: f32,
+    margin: f32,
+) -> std::io::Result<Vec<PathBuf>> {
+    // Work in SVG space (where the y-axis points down) and in the units of the net, where `page`
+    // is the printable area of each page (inside of the margin)
+    let (page, overlap) = page_size.printable_area(units_per_mm, margin);
+    let margin = margin * units_per_mm;
+    let mark_size = MARK_SIZE * units_per_mm;
+    let stroke_width = 0.25 * units_per_mm;
+
+    let stem = path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or(String::from("net"));
+    let extension = path
+        .extension()
+        .map(|extension| extension.to_string_lossy().into_owned())
+        .unwrap_or(String::from("svg"));
+
+    let mut paths = vec![];
+    for (corner, row, column) in net_pages(net, tabs, &page, overlap) {
+        let far_corner = corner + Vec2::new(page.width, page.height);
+        let page_path =
+            path.with_file_name(format!("{}-{}-{}.{}", stem, row + 1, column + 1, extension));
+        let mut file = BufWriter::new(File::create(&page_path)?);
+
+        // The viewBox covers the whole page (including the margin), and the contents are clipped
+        // to its printable area
+        writeln!(
+            file,
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"{} {} {} {}\" width=\"{}mm\" height=\"{}mm\">",
+            corner.x() - margin,
+            corner.y() - margin,
+            page.width + margin * 2.0,
+            page.height + margin * 2.0,
+            page_size.width,
+            page_size.height
+        )?;
+        writeln!(
+            file,
+            "  <rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"{}\"/>",
+            corner.x() - margin,
+            corner.y() - margin,
+            page.width + margin * 2.0,
+            page.height + margin * 2.0,
+            rgb_to_hex(&style.background)
+        )?;
+
+        writeln!(
+            file,
+            "  <clipPath id=\"printable\"><rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\"/></clipPath>",
+            corner.x(),
+            corner.y(),
+            page.width,
+            page.height
+        )?;
+        writeln!(file, "  <g clip-path=\"url(#printable)\">")?;
+        write_contents(&mut file, net, tabs, style)?;
+        writeln!(file, "  </g>")?;
+
+        // Draw a registration mark (a circle with a crosshair) at each corner of the area that this
+        // page owns, which lies in the middle of the strip that it shares with its neighbors
+        let inset = Vec2::splat(overlap * 0.5);
+        let (trim_min, trim_max) = (corner + inset, far_corner - inset);
+        let trim_corners = [
+            trim_min,
+            Vec2::new(trim_max.x(), trim_min.y()),
+            trim_max,
+            Vec2::new(trim_min.x(), trim_max.y()),
+        ];
+        for mark in trim_corners.iter() {
+            writeln!(
+                file,
+                "  <circle cx=\"{}\" cy=\"{}\" r=\"{}\" fill=\"none\" stroke=\"#000000\" stroke-width=\"{}\"/>",
+                mark.x(),
+                mark.y(),
+                mark_size * 0.25,
+                stroke_width
+            )?;
+            writeln!(
+                file,
+                "  <path d=\"M {} {} H {} M {} {} V {}\" stroke=\"#000000\" stroke-width=\"{}\"/>",
+                mark.x() - mark_size * 0.5,
+                mark.y(),
+                mark.x() + mark_size * 0.5,
+                mark.x(),
+                mark.y() - mark_size * 0.5,
+                mark.y() + mark_size * 0.5,
+                stroke_width
+            )?;
+        }
+
+        // Label the page with its position in the grid, so that the pages can be laid out in order
+        writeln!(
+            file,
+            "  <text x=\"{}\" y=\"{}\" font-family=\"DejaVu Sans Mono, monospace\" font-size=\"{}\" fill=\"#000000\" dominant-baseline=\"central\">Row {}, column {}</text>",
+            trim_min.x() + mark_size,
+            trim_min.y(),
+            3.0 * units_per_mm,
+            row + 1,
+            column + 1
+        )?;
+
+        // Draw the scale bar along the bottom of the area that this page owns, at the same
+        // (physical) size on every page
+        if style.scale_bar {
+            let length = scale_bar_length(page_size.width * 0.25);
+            write_scale_bar(
+                &mut file,
+                Vec2::new(trim_min.x() + mark_size, trim_max.y()),
+                length * units_per_mm,
+                &format!("{} mm", length),
+                3.0 * units_per_mm,
+                stroke_width,
+            )?;
+        }
+
+        // The legend sits just above the bottom of the area that this page owns, clear of the
+        // registration mark in its corner
+        if style.legend {
+            let legend = Legend::new(3.0 * units_per_mm, style.fold_dash);
+            write_legend(
+                &mut file,
+                Vec2::new(
+                    trim_max.x() - mark_size - legend.size.x(),
+                    trim_max.y() - legend.size.y(),
+                ),
+                &legend,
+                style,
+            )?;
+        }
+
+        writeln!(file, "</svg>")?;
+        paths.push(page_path);
+    }
+
+    Ok(paths)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::goal_mesh::{GoalMesh, LoadOptions, UnfoldStrategy};
+
+    /// Writes the net of the unit cube fixture to an .svg file with the specified style (which is
+    /// given the colors of the net), and returns the net along with the contents of the file.
+    fn write_unit_cube(
+        name: &str,
+        style: impl Fn(&Vec<Vec3>) -> NetStyle,
+    ) -> (UnfoldResult, String) {
+        let goal_mesh = GoalMesh::from_obj(
+            Path::new("tests/fixtures/unit_cube.obj"),
+            &LoadOptions {
+                strict: true,
+                ..LoadOptions::default()
+            },
+        )
+        .unwrap();
+        let net = goal_mesh.unfold(0, UnfoldStrategy::BreadthFirst).unwrap();
+        let colors = vec![Vec3::new(1.0, 0.0, 0.0); net.polygons.len()];
+        let path = std::env::temp_dir().join(name);
+        let options = SvgOptions {
+            units_per_mm: 2.0,
+            padding: 0.5,
+        };
+        write_svg(&path, &net, &[], &style(&colors), &options).unwrap();
+        let contents = std::fs::read_to_string(&path).unwrap();
+        (net, contents)
+    }
+
+    #[test]
+    fn test_write_svg() {
+        let (net, contents) =
+            write_unit_cube("durer_write_svg.svg", |colors| NetStyle::new(colors));
+        let document = roxmltree::Document::parse(&contents).unwrap();
+        let elements = |tag: &str| {
+            document
+                .descendants()
+                .filter(|node| node.has_tag_name(tag))
+                .collect::<Vec<_>>()
+        };
+
+        // The viewBox is the bounding box of the net (with the y-axis flipped), grown by the
+        // padding on each side
+        let (min, max) = find_bounding_box(&net.positions);
+        let view_box = document
+            .root_element()
+            .attribute("viewBox")
+            .unwrap()
+            .split(' ')
+            .map(|value| value.parse::<f32>().unwrap())
+            .collect::<Vec<_>>();
+        let expected = [
+            min.x() - 0.5,
+            -max.y() - 0.5,
+            max.x() - min.x() + 1.0,
+            max.y() - min.y() + 1.0,
+        ];
+        for (value, expected) in view_box.iter().zip(expected.iter()) {
+            assert!((value - expected).abs() < 1.0e-4);
+        }
+
+        // The file prints at the physical size of the net, i.e. 2 units per millimeter
+        for (attribute, size) in [("width", view_box[2]), ("height", view_box[3])].iter() {
+            let value = document.root_element().attribute(*attribute).unwrap();
+            assert!(value.ends_with("mm"));
+            let value = value.trim_end_matches("mm").parse::<f32>().unwrap();
+            assert!((value - size / 2.0).abs() < 1.0e-4);
+        }
+
+        // In filled mode, each polygon is filled in its own color (the background is the only
+        // rectangle), and every cut edge is labeled
+        let polygons = elements("polygon");
+        assert_eq!(elements("rect").len(), 1);
+        assert_eq!(polygons.len(), net.polygons.len());
+        assert!(polygons
+            .iter()
+            .all(|polygon| polygon.attribute("fill") == Some("#ff0000")));
+
+        // The outlines are drawn afterwards as separate lines, where each fold (which is shared by
+        // two faces) is only stroked once
+        let lines = elements("line");
+        assert!(lines
+            .iter()
+            .all(|line| line.attribute("stroke") == Some("#1a1a1a")));
+        let endpoints = lines
+            .iter()
+            .map(|line| {
+                let coordinate = |name: &str| line.attribute(name).unwrap().parse::<f32>().unwrap();
+                (
+                    Vec2::new(coordinate("x1"), -coordinate("y1")),
+                    Vec2::new(coordinate("x2"), -coordinate("y2")),
+                )
+            })
+            .collect::<Vec<_>>();
+        let strokes = |edge: &NetEdge| {
+            let a = net.positions[edge.indices[0]];
+            let b = net.positions[edge.indices[1]];
+            let (a, b) = (Vec2::new(a.x(), a.y()), Vec2::new(b.x(), b.y()));
+            endpoints
+                .iter()
+                .filter(|&&(p, q)| {
+                    ((p - a).length() < 1.0e-4 && (q - b).length() < 1.0e-4)
+                        || ((p - b).length() < 1.0e-4 && (q - a).length() < 1.0e-4)
+                })
+                .count()
+        };
+        let folds = net
+            .edges
+            .iter()
+            .filter(|edge| edge.kind.is_fold() && edge.half_edge < edge.pair)
+            .collect::<Vec<_>>();
+        assert_eq!(folds.len(), 5);
+        assert!(folds.iter().all(|edge| strokes(edge) == 1));
+        assert_eq!(lines.len(), net.edges.len() - folds.len());
+        let labels = net
+            .edges
+            .iter()
+            .filter(|edge| net.cut_label(edge).is_some())
+            .count();
+        assert_eq!(labels, 14);
+        assert_eq!(elements("text").len(), labels);
+    }
+
+    #[test]
+    fn test_write_svg_wireframe() {
+        let (net, contents) = write_unit_cube("durer_write_svg_wireframe.svg", |colors| NetStyle {
+            wireframe: true,
+            labels: false,
+            ..NetStyle::new(colors)
+        });
+        let document = roxmltree::Document::parse(&contents).unwrap();
+
+        // In wireframe mode, each edge is a separate line (where the folds, which are shared by
+        // two faces, are only written once): cuts are solid, and folds are dashed
+        let lines = document
+            .descendants()
+            .filter(|node| node.has_tag_name("line"))
+            .collect::<Vec<_>>();
+        let (folds, cuts): (Vec<&roxmltree::Node>, Vec<_>) = lines
+            .iter()
+            .partition(|line| line.has_attribute("stroke-dasharray"));
+        let fold_edges = net.edges.iter().filter(|edge| edge.kind.is_fold()).count() / 2;
+        assert_eq!(fold_edges, 5);
+        assert_eq!(folds.len(), fold_edges);
+        assert_eq!(cuts.len(), net.edges.len() - fold_edges * 2);
+        assert!(lines
+            .iter()
+            .all(|line| line.attribute("stroke") == Some("#ff0000")));
+        assert!(!document
+            .descendants()
+            .any(|node| node.has_tag_name("polygon") || node.has_tag_name("text")));
+
+        // Every line lies within the net (with the y-axis flipped)
+        let (min, max) = find_bounding_box(&net.positions);
+        for line in lines.iter() {
+            let coordinate = |name: &str| line.attribute(name).unwrap().parse::<f32>().unwrap();
+            for &(x, y) in [("x1", "y1"), ("x2", "y2")].iter() {
+                assert!(coordinate(x) >= min.x() - 1.0e-4 && coordinate(x) <= max.x() + 1.0e-4);
+                assert!(coordinate(y) >= -max.y() - 1.0e-4 && coordinate(y) <= -min.y() + 1.0e-4);
+            }
+        }
+    }
+}
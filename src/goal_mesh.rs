@@ -1,471 +1,5416 @@
+use crate::decimate::decimate;
 use crate::half_edge::ids::*;
-use crate::half_edge::mesh::HalfEdgeMesh;
-use crate::utils::angle_with_e1;
+use crate::half_edge::mesh::{HalfEdgeMesh, TopologyError};
+use crate::stl::{read_stl, weld_vertices, StlError};
+use crate::utils::{
+    angle_with_e1, convex_hull_2d, find_bounding_box, find_centroid,
+    find_intersecting_triangles_3d, find_oriented_bounding_box, find_overlapping_triangles,
+    linear_to_srgb, overlap_tolerance, pack_shelves, point_in_triangle_2d, polygon_area,
+    srgb_to_linear, triangles_overlap_2d,
+};
 
-use glam::{Mat3, Vec3};
-use log::{info, warn};
+use glam::{Mat3, Vec2, Vec3};
+use log::{debug, info, log_enabled, warn, Level};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
 use tobj;
 
-use std::collections::HashMap;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+use std::fmt;
 use std::fs::File;
-use std::io::BufReader;
+use std::io::{BufRead, BufReader};
 use std::path::Path;
+use std::time::Instant;
 
-pub struct GoalMesh {
-    // The internal HEM data structure, used for adjacency queries
-    half_edge_mesh: HalfEdgeMesh,
+/// Describes how an edge of the net should be treated when building the physical model.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum EdgeKind {
+    // The edge lies along the cut boundary (i.e. it is *not* crossed by the spanning tree)
+    Cut,
 
-    // The index of the face from which the unfolding map will be calculated
-    reference_face: FaceIndex,
+    // The edge is crossed by the spanning tree and the faces on either side of it fold away from
+    // the viewer (i.e. the edge is convex)
+    Mountain,
 
-    // A map that holds information about where each face came from in the spanning tree
-    came_from: HashMap<FaceIndex, (FaceIndex, HalfEdgeIndex)>,
+    // The edge is crossed by the spanning tree and the faces on either side of it fold towards
+    // the viewer (i.e. the edge is reflex or flat)
+    Valley,
+}
 
-    // The IDs of the edges crossed by the spanning tree (the "cut boundary" is the set
-    // of edges *not* included in this list)
-    crossed_edges: Vec<HalfEdgeIndex>,
+impl EdgeKind {
+    /// Returns `true` if the two faces on either side of this edge remain joined in the net.
+    pub fn is_fold(&self) -> bool {
+        *self != EdgeKind::Cut
+    }
 
-    // The IDs of the faces that lie "in the middle" of the spanning tree
-    branch_faces: Vec<FaceIndex>,
+    /// Returns the dash pattern (alternating lengths of dashes and gaps, in the same units as
+    /// the net) that should be used when drawing this edge, following the usual papercraft
+    /// convention: cuts are solid, valley folds are dashed, and mountain folds are dash-dotted.
+    /// An empty pattern denotes a solid line. If `fold_dash` (the length of a single dash and
+    /// gap) is provided, it replaces the pattern of both kinds of folds, e.g. to match the score
+    /// lines that a cutter expects.
+    pub fn dash_pattern(&self, fold_dash: Option<(f32, f32)>) -> Vec<f32> {
+        match (self, fold_dash) {
+            (EdgeKind::Cut, _) => vec![],
+            (_, Some((dash, gap))) => vec![dash, gap],
+            (EdgeKind::Mountain, None) => vec![8.0, 3.0, 1.0, 3.0],
+            (EdgeKind::Valley, None) => vec![8.0, 4.0],
+        }
+    }
 
-    // The IDs of the faces that are the "leaves" of the spanning tree
-    leaf_faces: Vec<FaceIndex>,
+    /// Returns the kind of this edge when the net is viewed from the other side of the paper, i.e.
+    /// mountain folds become valley folds (and vice versa), while cuts remain cuts.
+    pub fn flipped(&self) -> EdgeKind {
+        match self {
+            EdgeKind::Cut => EdgeKind::Cut,
+            EdgeKind::Mountain => EdgeKind::Valley,
+            EdgeKind::Valley => EdgeKind::Mountain,
+        }
+    }
 }
 
-impl GoalMesh {
-    pub fn from_obj(path_to_file: &Path, reference_face: FaceIndex) -> GoalMesh {
-        // Make sure to triangulate the model
-        let (models, materials) = tobj::load_obj(&path_to_file, true).expect("Failed to load file");
+/// The default tolerance for degenerate faces (e.g. slivers, or faces with repeated vertices), see
+/// `Tolerances::degenerate`.
+pub const DEFAULT_DEGENERATE_TOLERANCE: f32 = 1.0e-6;
 
-        if models.len() > 1 {
-            warn!("The specified .obj file has more than one model - only the first will be used");
-        }
+/// The default tolerance for welding together the vertices of an .stl file, see
+/// `Tolerances::stl_weld`.
+pub const DEFAULT_STL_WELD_TOLERANCE: f32 = 1.0e-6;
 
-        // Containers for storing vertices and faces
-        let mut base_vertices = vec![];
-        let mut base_faces = vec![];
-        let mesh = &models[0].mesh;
+/// The default tolerance for testing the faces of a net for overlaps, see `Tolerances::overlap`.
+pub const DEFAULT_OVERLAP_TOLERANCE: f32 = 1.0e-4;
 
-        // Parse faces
-        info!(
-            "Number of triangular faces: {}",
-            mesh.num_face_indices.len()
-        );
-        let mut next_face = 0;
-        for face_index in 0..mesh.num_face_indices.len() {
-            let end = next_face + mesh.num_face_indices[face_index] as usize;
-            let face_indices: Vec<_> = mesh.indices[next_face..end].iter().collect();
-            debug_assert_eq!(face_indices.len(), 3);
+/// The default tolerance (in radians) for edges that are considered to be flat, see
+/// `Tolerances::flat`.
+pub const DEFAULT_FLAT_TOLERANCE: f32 = 1.0e-4;
 
-            base_faces.push([
-                *face_indices[0] as usize,
-                *face_indices[1] as usize,
-                *face_indices[2] as usize,
-            ]);
+/// The default tolerance (in radians) for merging neighboring faces of the goal mesh that are
+/// coplanar, i.e. faces that meet at a dihedral angle of less than 0.1 degrees are merged.
+pub const DEFAULT_COPLANAR_TOLERANCE: f32 = 0.1 * std::f32::consts::PI / 180.0;
 
-            next_face = end;
-        }
+/// The default tolerance (in the units of the goal mesh) for snapping together positions of the
+/// net, see `UnfoldResult::weld_positions()`.
+pub const DEFAULT_WELD_TOLERANCE: f32 = 1.0e-5;
 
-        // Parse vertices
-        info!("Number of vertices: {}", mesh.positions.len() / 3);
-        debug_assert_eq!(mesh.positions.len() % 3, 0);
+/// The tolerances of the geometric predicates that are used while loading and unfolding a goal mesh
+/// (besides `coplanar_tolerance` and `weld_tolerance`, which have options of their own, see
+/// `LoadOptions` and `UnfoldOptions`). Most of them are normalized, i.e. relative to the size of
+/// whatever is being tested, so that they don't depend on the units of the goal mesh.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Tolerances {
+    // Faces whose area is at most this fraction of the square of their longest side are considered
+    // to be degenerate, and are skipped while loading the goal mesh (or rejected, in strict mode)
+    pub degenerate: f32,
 
-        for vertex_index in 0..mesh.positions.len() / 3 {
-            base_vertices.push(Vec3::new(
-                mesh.positions[3 * vertex_index + 0],
-                mesh.positions[3 * vertex_index + 1],
-                mesh.positions[3 * vertex_index + 2],
-            ));
-        }
+    // The vertices of an .stl file that lie within this fraction of the length of the diagonal of
+    // the bounding box of the goal mesh are welded together
+    pub stl_weld: f32,
 
-        let mut goal_mesh = GoalMesh {
-            half_edge_mesh: HalfEdgeMesh::from_faces(&base_faces, &base_vertices)
-                .expect("Failed to create half-edge data structure"),
-            reference_face,
-            came_from: HashMap::new(),
-            crossed_edges: vec![],
-            branch_faces: vec![],
-            leaf_faces: vec![],
-        };
+    // Faces of the net only overlap if they do so by more than this fraction of the larger side of
+    // the bounding box of the net, so that neighboring faces never overlap due to floating point
+    // error
+    pub overlap: f32,
 
-        // Make sure that the provided reference face is valid
-        debug_assert!(
-            reference_face >= 0.into()
-                && reference_face < goal_mesh.half_edge_mesh.faces().len().into()
-        );
+    // Edges whose dihedral angle is within this many radians of zero are considered to be flat,
+    // i.e. they are neither reflex (when testing whether the goal mesh is convex) nor mountain
+    // folds (in the net)
+    pub flat: f32,
+}
 
-        goal_mesh.compute_spanning_tree();
-        goal_mesh
+impl Tolerances {
+    /// Returns the default tolerances, except that all of the normalized ones (i.e. `degenerate`,
+    /// `stl_weld` and `overlap`) are set to `epsilon`, or `None` if `epsilon` isn't positive (and
+    /// finite): with a tolerance of zero, floating point error alone makes faces that only share an
+    /// edge overlap, and keeps the vertices of .stl files from being welded.
+    pub fn from_epsilon(epsilon: f32) -> Option<Tolerances> {
+        if !(epsilon > 0.0 && epsilon.is_finite()) {
+            return None;
+        }
+        Some(Tolerances {
+            degenerate: epsilon,
+            stl_weld: epsilon,
+            overlap: epsilon,
+            ..Tolerances::default()
+        })
     }
+}
 
-    fn compute_spanning_tree(&mut self) {
-        info!("Starting spanning tree computation");
-
-        // Cache all of the face normals
-        let face_normals = self
-            .half_edge_mesh
-            .face_id_iter()
-            .map(|fid| self.half_edge_mesh.face_normal(fid))
-            .collect::<Vec<_>>();
-
-        // Calculate which faces are neighbors to each other
-        let mut face_neighbors = vec![];
+impl Default for Tolerances {
+    fn default() -> Self {
+        Tolerances {
+            degenerate: DEFAULT_DEGENERATE_TOLERANCE,
+            stl_weld: DEFAULT_STL_WELD_TOLERANCE,
+            overlap: DEFAULT_OVERLAP_TOLERANCE,
+            flat: DEFAULT_FLAT_TOLERANCE,
+        }
+    }
+}
 
-        for fid in self.half_edge_mesh.face_id_iter() {
-            // Find all neighbor faces of the face at index `fid`, along with the index of the
-            // half-edge that is between them
-            let neighbors = self
-                .half_edge_mesh
-                .adjacent_half_edges_to_face(fid)
-                .map(|shared_edge| {
-                    // Get edge -> pair -> face, which may be `None`
-                    let neighbor = self
-                        .half_edge_mesh
-                        .half_edge(self.half_edge_mesh.half_edge(shared_edge).pair())
-                        .face();
-                    (neighbor, shared_edge)
-                })
-                // Ignore "infinite" face outside of the mesh
-                .filter(|(neighbor, _)| neighbor.is_some())
-                .map(|(neighbor, shared_edge)| (neighbor.unwrap(), shared_edge))
-                .collect::<Vec<_>>();
+/// Controls how a goal mesh is loaded, see `GoalMesh::from_obj()`. Missing fields can be filled in
+/// with their defaults, i.e. `LoadOptions { strict: true, ..LoadOptions::default() }`.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LoadOptions {
+    // Whether degenerate faces are an error (instead of being skipped)
+    pub strict: bool,
 
-            face_neighbors.push(neighbors);
-        }
+    // Neighboring faces that meet at a dihedral angle of less than this (in radians) are merged
+    // into a single polygon (0 disables merging)
+    pub coplanar_tolerance: f32,
 
-        // Now, construct the spanning tree
-        let mut queue = vec![self.reference_face];
-        let mut not_seen_faces = self.half_edge_mesh.face_id_iter().collect::<Vec<_>>();
-        info!(
-            "Starting spanning tree calculation at face with ID: {:?}",
-            self.reference_face
-        );
+    // Whether to flip faces whose winding orders are inconsistent with their neighbors' (instead of
+    // rejecting the goal mesh)
+    pub fix_winding: bool,
 
-        // A dictionary that maps each face to the face it "comes from" in the spanning tree, along with
-        // the edge that is shared between the pair of faces
-        self.came_from
-            .insert(self.reference_face, (NO_FACE, NO_HALF_EDGE));
+    // Whether open surfaces (i.e. goal meshes with a border, such as a bowl) are loaded as well
+    // (instead of being rejected), where the edges along the border are always cut
+    pub allow_open: bool,
 
-        while !queue.is_empty() && !not_seen_faces.is_empty() {
-            let curr_face = queue.remove(0);
+    // The tolerances of the rest of the geometric predicates, which are kept by the goal mesh (so
+    // they apply to unfolding it as well)
+    pub tolerances: Tolerances,
+}
 
-            for (neighbor, shared_edge) in face_neighbors[usize::from(curr_face)].iter() {
-                if not_seen_faces.contains(neighbor) && !queue.contains(neighbor) {
-                    // Update the spanning tree
-                    self.came_from.insert(*neighbor, (curr_face, *shared_edge));
-                    queue.push(*neighbor);
-                }
-            }
-            // Remove `curr_face` from the list of not-seen faces, since we just examined its neighbors
-            not_seen_faces.retain(|&fid| fid != curr_face);
+impl Default for LoadOptions {
+    fn default() -> Self {
+        LoadOptions {
+            strict: false,
+            coplanar_tolerance: DEFAULT_COPLANAR_TOLERANCE,
+            fix_winding: false,
+            allow_open: false,
+            tolerances: Tolerances::default(),
         }
+    }
+}
 
-        // Edges that are crossed by the spanning tree
-        self.crossed_edges = self
-            .came_from
-            .values()
-            .filter(|(fid, _)| *fid != NO_FACE)
-            .map(|(_, eid)| *eid)
-            .collect::<Vec<_>>();
-
-        // Faces that are in the "middle" of a path along the spanning tree
-        self.branch_faces = self
-            .came_from
-            .values()
-            .filter(|(fid, _)| *fid != NO_FACE)
-            .map(|(fid, _)| *fid)
-            .collect::<Vec<_>>();
-        self.branch_faces.sort();
-        self.branch_faces.dedup();
+/// The default width of glue tabs (in the units of the net), which suits goal meshes that are
+/// about as large as a unit cube, see `TabOptions`.
+pub const DEFAULT_TAB_WIDTH: f32 = 0.1;
 
-        // Faces that are at the "end" of a path along the spanning tree
-        self.leaf_faces = self
-            .half_edge_mesh
-            .face_id_iter()
-            .filter(|fid| !self.branch_faces.contains(fid))
-            .collect::<Vec<_>>();
+/// The default angle (in radians) between the slanted sides of trapezoidal glue tabs and the edges
+/// that they are attached to, see `generate_tabs()`.
+pub const DEFAULT_TAB_ANGLE: f32 = 45.0 * std::f32::consts::PI / 180.0;
 
-        debug_assert_eq!(
-            self.branch_faces.len() + self.leaf_faces.len(),
-            self.half_edge_mesh.faces().len()
-        );
-    }
+/// The default miter limit of neighboring glue tabs that are trimmed to meet at the corner between
+/// their edges, see `generate_tabs()`.
+pub const DEFAULT_TAB_MITER_LIMIT: f32 = 4.0;
 
-    /// A helper function for finding the index of a vertex in the "global" array (i.e. m1, m2, or m3), given
-    /// its face ID and vertex ID (from the half-edge data structure). This is necessary because the half-edge
-    /// mesh essentially becomes "unwelded" during the unfolding process. In other words, we often end up with
-    /// more vertices in the unfolded mesh than we started with. So, while we are calculating m1, m2, and m3, we
-    /// need some way to find where a particular vertex belonging to a particular face lies in one or more of
-    /// those arrays.
-    ///
-    /// We assume that m1, m2, and m3 are constructed in such a way that faces are stored sequentially in the
-    /// same order that they appear in the half-edge data structure (i.e. as if we called `.face_id_iter()`).
-    /// Additionally, vertices are stored in the same order as the "vertex loop" iterator returned by
-    /// `.adjacent_vertices_to_face()`.
-    fn get_global_vertex_index(&self, fid: FaceIndex, vid: VertexIndex) -> usize {
-        let index_in_face = self
-            .half_edge_mesh
-            .adjacent_vertices_to_face(fid)
-            .position(|adjacent| adjacent == vid)
-            .expect("The specified vertex ID is not part of this face: this should never happen");
+/// The number of line segments used to approximate each of the rounded corners of a glue tab.
+const TAB_CORNER_SEGMENTS: usize = 8;
 
-        // Assume triangular faces
-        usize::from(fid) * 3 + index_in_face
-    }
+/// The number of root faces between each progress update while unfolding the goal mesh from every
+/// root face, see `count_overlaps_by_reference_face()`.
+const ROOT_SCAN_PROGRESS_INTERVAL: usize = 100;
 
-    /// A helper function for finding the properly oriented "version" of a half-edge belonging to a particular face.
-    /// The specified half-edge might technically be adjacent to the specified face, but with the wrong orientation
-    /// (i.e. we really want edge -> pair, so that the winding order is correct). This function assumes that the
-    /// specified half-edge or its pair is, in fact, adjacent to the specified face. Otherwise, an error will be thrown.
-    fn get_aligned_half_edge(&self, fid: FaceIndex, eid: HalfEdgeIndex) -> HalfEdgeIndex {
-        let mut oriented_edge = eid;
+/// The gap between the nets of separate pieces of the goal mesh, as a fraction of the size of the
+/// largest net.
+const COMPONENT_SPACING: f32 = 0.1;
 
-        if !self.half_edge_mesh.face_contains_half_edge(fid, eid) {
-            oriented_edge = self.half_edge_mesh.half_edge(oriented_edge).pair();
-            debug_assert!(self
-                .half_edge_mesh
-                .face_contains_half_edge(fid, oriented_edge));
+/// Returns the (SRGB) colors that some exporters (e.g. MeshLab) append to the vertices of an .obj
+/// file, i.e. `v x y z r g b`, which `tobj` ignores. Each color is keyed by the bits of the
+/// coordinates of its vertex, since that is how the vertices are welded together once they are
+/// loaded (see `GoalMesh::from_models()`). Vertices without a color are left out.
+fn parse_vertex_colors(text: &str) -> HashMap<[u32; 3], Vec3> {
+    let mut vertex_colors = HashMap::new();
+    for line in text.lines() {
+        let mut tokens = line.split_whitespace();
+        if tokens.next() != Some("v") {
+            continue;
+        }
+        let values = tokens
+            .map(|token| token.parse::<f32>())
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap_or_default();
+        if let [x, y, z, r, g, b, ..] = values[..] {
+            vertex_colors
+                .entry([x.to_bits(), y.to_bits(), z.to_bits()])
+                .or_insert_with(|| Vec3::new(r, g, b));
         }
-
-        oriented_edge
     }
+    vertex_colors
+}
 
-    /// Determines the incoming and outgoing edges along the unfolding path connecting
-    /// the target face to the reference face. Additionally, we must specify the
-    /// index of the face that comes *after* the target face along the desired
-    /// unfolding path (this is `towards_face`).
-    ///
-    /// This is necessary because the target face may be part of multiple unfolding
-    /// paths for different leaf faces. In this scenario, the outgoing edge depends
-    /// on which of these paths we are considering.
-    pub fn get_incoming_outgoing_edges(
-        &self,
-        target_face: FaceIndex,
-        towards_face: FaceIndex,
-    ) -> (HalfEdgeIndex, Option<HalfEdgeIndex>) {
-        // Not every face has an outgoing edge (for example, all of the leaf faces in the spanning tree)
-        let mut outgoing = None;
-
-        // Every face has an incoming edge - for the reference face, we can just choose one of its
-        // edges arbitrarily
-        let mut incoming = if target_face == self.reference_face {
-            let reference_edge = self
-                .half_edge_mesh
-                .adjacent_half_edges_to_face(self.reference_face)
-                .collect::<Vec<_>>()[0];
-            reference_edge
-        } else {
-            // The target face's incoming edge is simply the shared edge between the target face and the
-            // face that it came from in the spanning tree
-            self.came_from[&target_face].1
+/// Checks the vertices and faces of the contents of an .obj file, i.e. that every vertex has 3
+/// finite coordinates, and that every face has at least 3 vertices, which refer to vertices that
+/// were defined earlier in the file (with positive indices starting at 1, or negative indices
+/// counting backwards from the most recent vertex).
+fn check_obj_syntax(text: &str) -> Result<(), GoalMeshError> {
+    let mut vertices = 0;
+    for (line_index, line) in text.lines().enumerate() {
+        let error = |reason: String| GoalMeshError::Syntax {
+            line: line_index + 1,
+            reason,
         };
-
-        // Find the outgoing edge: "leaf" faces are at the end of the branches of the spanning tree and thus,
-        // don't have an outgoing edge
-        if !self.leaf_faces.contains(&target_face) {
-            // Search for the face that came from this one and grab the corresponding shared edge -
-            // this is the outgoing edge for the target face
-            for (node_to, (node_from, shared_edge)) in self.came_from.iter() {
-                if *node_from == target_face && *node_to == towards_face {
-                    outgoing = Some(*shared_edge);
+        let mut tokens = line.split_whitespace();
+        match tokens.next() {
+            Some("v") => {
+                let coordinates = tokens
+                    .take(3)
+                    .map(|token| token.parse::<f32>())
+                    .collect::<Result<Vec<_>, _>>()
+                    .map_err(|_| error(String::from("expected 3 numbers")))?;
+                if coordinates.len() < 3 {
+                    return Err(error(format!(
+                        "expected 3 coordinates, found {}",
+                        coordinates.len()
+                    )));
+                }
+                if !coordinates.iter().all(|coordinate| coordinate.is_finite()) {
+                    return Err(error(String::from(
+                        "coordinates must be finite numbers (not NaN or infinity)",
+                    )));
+                }
+                vertices += 1;
+            }
+            Some("f") => {
+                let mut count = 0;
+                for token in tokens {
+                    // Only the vertex index matters (i.e. not the texture coordinates or normal)
+                    let index = token
+                        .split('/')
+                        .next()
+                        .and_then(|index| index.parse::<isize>().ok())
+                        .ok_or_else(|| error(format!("invalid vertex index: {:?}", token)))?;
+                    let valid = if index > 0 {
+                        index <= vertices
+                    } else {
+                        index < 0 && -index <= vertices
+                    };
+                    if !valid {
+                        return Err(error(format!(
+                            "vertex index {} is out of range ({} vertices are defined before this line)",
+                            index, vertices
+                        )));
+                    }
+                    count += 1;
+                }
+                if count < 3 {
+                    return Err(error(format!(
+                        "expected at least 3 vertex indices, found {}",
+                        count
+                    )));
                 }
             }
+            _ => {}
         }
+    }
+    Ok(())
+}
 
-        // If the incoming edge isn't part of this face, that means that it isn't oriented correctly
-        // (i.e. it is CW instead of CCW) - flip it here by grabbing its pair half-edge instead
-        incoming = self.get_aligned_half_edge(target_face, incoming);
-        debug_assert!(self
-            .half_edge_mesh
-            .face_contains_half_edge(target_face, incoming));
+/// Finds the polygons (i.e. loops of vertex indices) whose winding orders have to be reversed so
+/// that every pair of neighboring polygons runs along their shared edge in opposite directions,
+/// which is what the half-edge mesh requires. Starting from the first polygon of each connected
+/// piece, the orientation is propagated (breadth-first) across the edges that are shared by
+/// exactly two polygons: any other edges are left for the validation of the half-edge mesh to
+/// report. Afterwards, every piece whose signed volume is negative is turned inside out, so that
+/// its faces are CCW when viewed from the outside. Open pieces (i.e. pieces with a border) don't
+/// have an inside or an outside, so they keep the winding order of their first polygon.
+///
+/// Returns whether each polygon should be flipped, along with the number of pieces that can't be
+/// oriented consistently (e.g. a Möbius strip): none of the polygons of these pieces are flipped.
+fn orient_polygons(polygons: &[Vec<VertexIndex>], vertices: &[Vec3]) -> (Vec<bool>, usize) {
+    let directed_edges = |polygon: &Vec<VertexIndex>| {
+        polygon
+            .iter()
+            .zip(polygon.iter().cycle().skip(1))
+            .map(|(&a, &b)| (usize::from(a), usize::from(b)))
+            .collect::<Vec<_>>()
+    };
 
-        // Same as above but for the outgoing edge
-        if let Some(maybe_outgoing) = outgoing {
-            outgoing = Some(self.get_aligned_half_edge(target_face, maybe_outgoing));
-            debug_assert!(self
-                .half_edge_mesh
-                .face_contains_half_edge(target_face, outgoing.unwrap()));
+    // Each edge lists the polygons that it belongs to, and whether each of them runs along it from
+    // its lower vertex index to its higher one
+    let mut edges: HashMap<(usize, usize), Vec<(usize, bool)>> = HashMap::new();
+    for (polygon_index, polygon) in polygons.iter().enumerate() {
+        for (a, b) in directed_edges(polygon) {
+            edges
+                .entry((a.min(b), a.max(b)))
+                .or_default()
+                .push((polygon_index, a < b));
         }
-
-        (incoming, outgoing)
     }
 
-    /// Returns the indices of the faces along the path from the reference face
-    /// to the target face in the spanning tree. Note that the indices will be
-    /// ordered in such a way that the target face is the first entry and the
-    /// reference face is the last entry.
-    pub fn get_unfolding_path_to(
-        &self,
-        target_face: FaceIndex,
-    ) -> (Vec<FaceIndex>, Vec<HalfEdgeIndex>) {
-        let mut faces_along_path = vec![target_face];
-        let mut edges_along_path = vec![];
+    let mut flips = vec![false; polygons.len()];
+    let mut visited: Vec<Option<bool>> = vec![None; polygons.len()];
+    let mut non_orientable = 0;
+    for seed in 0..polygons.len() {
+        if visited[seed].is_some() {
+            continue;
+        }
 
-        let mut curr = target_face;
+        // The seed keeps its winding order, and every other polygon of its piece is flipped
+        // relative to it
+        visited[seed] = Some(false);
+        let mut piece = vec![seed];
+        let mut queue = VecDeque::from(vec![seed]);
+        let mut orientable = true;
+        let mut open = false;
+        while let Some(polygon_index) = queue.pop_front() {
+            let flip = visited[polygon_index].unwrap();
+            for (a, b) in directed_edges(&polygons[polygon_index]) {
+                let incident = &edges[&(a.min(b), a.max(b))];
+                open |= incident.len() == 1;
+                if incident.len() != 2 {
+                    continue;
+                }
+                for &(neighbor, forward) in incident.iter() {
+                    if neighbor == polygon_index {
+                        continue;
+                    }
+                    let neighbor_flip = flip ^ (forward == (a < b));
+                    match visited[neighbor] {
+                        None => {
+                            visited[neighbor] = Some(neighbor_flip);
+                            piece.push(neighbor);
+                            queue.push_back(neighbor);
+                        }
+                        Some(existing) => orientable &= existing == neighbor_flip,
+                    }
+                }
+            }
+        }
+        if !orientable {
+            non_orientable += 1;
+            continue;
+        }
 
-        while curr != self.reference_face {
-            let (prev, shared_edge) = self.came_from[&curr];
-            faces_along_path.push(prev);
-            edges_along_path.push(shared_edge);
-            curr = prev;
+        // The signed volume of the (fan triangulated) piece is positive when its faces point outwards
+        let volume = piece
+            .iter()
+            .map(|&polygon_index| {
+                let points = polygons[polygon_index]
+                    .iter()
+                    .map(|&vertex| vertices[usize::from(vertex)])
+                    .collect::<Vec<_>>();
+                let volume = (1..points.len() - 1)
+                    .map(|i| points[0].dot(points[i].cross(points[i + 1])))
+                    .sum::<f32>();
+                if visited[polygon_index].unwrap() {
+                    -volume
+                } else {
+                    volume
+                }
+            })
+            .sum::<f32>();
+        for &polygon_index in piece.iter() {
+            flips[polygon_index] = visited[polygon_index].unwrap() ^ (!open && volume < 0.0);
         }
+    }
 
-        // The path will be ordered such that the target face is first and the reference face is last -
-        // we want the opposite, so we reverse it below
-        faces_along_path.reverse();
-        edges_along_path.reverse();
+    (flips, non_orientable)
+}
 
-        (faces_along_path, edges_along_path)
-    }
+/// The reasons why a goal mesh might fail to load.
+#[derive(Debug)]
+pub enum GoalMeshError {
+    // The input file couldn't be opened or read
+    Io(std::io::Error),
 
-    pub fn unfold(&mut self) -> Vec<Vec3> {
-        // Basis vectors in R3
-        let _e1 = Vec3::unit_x();
-        let _e2 = Vec3::unit_y();
-        let e3 = Vec3::unit_z();
-        let r1_pi = Mat3::from_rotation_x(std::f32::consts::PI);
+    // The .obj file couldn't be parsed
+    Load(tobj::LoadError),
 
-        // Grab the first edge of the reference face to serve as the "reference edge," i.e. the
-        // incoming edge for the reference face
-        let reference_edge = self
-            .half_edge_mesh
-            .adjacent_half_edges_to_face(self.reference_face)
-            .collect::<Vec<_>>()[0];
+    // This line of the .obj file (starting at 1) is malformed, for the specified reason
+    Syntax {
+        line: usize,
+        reason: String,
+    },
 
-        // (1) Rotating each mesh face to align its unit normal vector with e3
-        info!("Starting M1");
-        let mut m1 = vec![];
+    // The .stl file couldn't be read or parsed
+    Stl(StlError),
 
-        for fid in self.half_edge_mesh.face_id_iter() {
-            // Precompute items that involve this face's normal vector
-            let normal = self.half_edge_mesh.face_normal(fid);
-            let normal_cross_e3 = normal.cross(e3);
-            let normal_dot_e3 = normal.dot(e3);
+    // The .obj file doesn't contain any faces
+    Empty,
 
-            for vid in self.half_edge_mesh.adjacent_vertices_to_face(fid) {
-                let coords = *self.half_edge_mesh.vertex(vid).coordinates();
+    // The faces at these indices of the .obj file (starting at 0) have (almost) no area, and
+    // degenerate faces are not allowed
+    DegenerateFaces(Vec<usize>),
 
-                // If the normal vector is aligned with the negative z-axis, simply flip
-                // all of the vertices 180-degrees about the x-axis
-                if normal.cmpeq(-e3).all() {
-                    let transformed = r1_pi.mul_vec3(coords);
-                    m1.push(transformed);
-                } else {
-                    let transformed = normal_dot_e3 * coords
-                        + normal_cross_e3.cross(coords)
-                        + (normal_cross_e3 / (1.0 + normal_dot_e3)) * normal_cross_e3.dot(coords);
-                    m1.push(transformed);
-                }
-            }
-        }
+    // Every face of the goal mesh (i.e. this many) has (almost) no area, so all of them were
+    // skipped and nothing is left to unfold
+    AllFacesDegenerate(usize),
 
-        // (2) Translate and rotate each mesh face to place one of its nodes at the origin and one of its edges along e1
-        info!("Starting M2");
-        let mut m2 = vec![];
+    // The half-edge data structure couldn't be built from the faces of the .obj file
+    HalfEdgeMesh(&'static str),
 
-        for fid in self.half_edge_mesh.face_id_iter() {
-            // Since we only need the incoming edge at the moment, we can just pass an arbitrary face ID
-            // as the `towards_face` in the function below
-            let (incoming, _) = self.get_incoming_outgoing_edges(fid, NO_FACE);
+    // The goal mesh is not a closed 2-manifold (any face indices refer to the faces of the .obj
+    // file, before triangulation, and all indices start at 0)
+    Topology(TopologyError),
 
-            // The IDs of the 2 vertices that form the incoming edge of this face
-            let incoming_vids = self.half_edge_mesh.adjacent_vertices_to_half_edge(incoming);
-            let src = m1[self.get_global_vertex_index(fid, incoming_vids[0])];
-            let dst = m1[self.get_global_vertex_index(fid, incoming_vids[1])];
-            let along_incoming_edge = dst - src;
-            let r3 = Mat3::from_rotation_z(-angle_with_e1(&along_incoming_edge));
+    // The requested reference (i.e. root) face doesn't exist
+    InvalidReferenceFace {
+        face: usize,
+        faces: usize,
+    },
 
-            for vid in self.half_edge_mesh.adjacent_vertices_to_face(fid) {
-                let coords = m1[self.get_global_vertex_index(fid, vid)];
+    // The face at this index (starting at 0) refers to a vertex that doesn't exist, see
+    // `GoalMesh::from_vertices_faces()`
+    InvalidVertexIndex {
+        face: usize,
+        index: usize,
+        vertices: usize,
+    },
 
-                // Translate the face so that the "source" (first) vertex of its incoming edge is
-                // coincident with the origin
-                let translated = coords - src;
+    // The goal mesh has more (triangular) faces than the limit, see `UnfoldOptions::max_faces`
+    TooManyFaces {
+        faces: usize,
+        max_faces: usize,
+    },
 
-                // Perform a rotation around the z-axis that causes the incoming edge of this face
-                // (w.r.t. the spanning tree) to be aligned with the positive x-axis
-                let rotated = r3.mul_vec3(translated);
-                m2.push(rotated);
+    // There is no edge between these vertices of the goal mesh (starting at 0), see
+    // `GoalMesh::find_edge()`
+    InvalidEdge {
+        a: usize,
+        b: usize,
+    },
+}
+
+impl fmt::Display for GoalMeshError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GoalMeshError::Io(e) => write!(f, "Failed to read input file: {}", e),
+            GoalMeshError::Load(e) => write!(f, "Failed to load .obj file: {}", e),
+            GoalMeshError::Syntax { line, reason } => {
+                write!(f, "Could not parse line {} of the .obj file: {}", line, reason)
             }
+            GoalMeshError::Stl(e) => write!(f, "Failed to load .stl file: {}", e),
+            GoalMeshError::Empty => write!(f, "The .obj file doesn't contain any faces"),
+            GoalMeshError::DegenerateFaces(faces) => write!(
+                f,
+                "The .obj file contains {} degenerate (zero-area) faces: {:?}",
+                faces.len(),
+                faces
+            ),
+            GoalMeshError::AllFacesDegenerate(faces) => write!(
+                f,
+                "All {} faces of the goal mesh were skipped as degenerate (zero-area): fix the goal mesh, or lower the degenerate tolerance",
+                faces
+            ),
+            GoalMeshError::HalfEdgeMesh(e) => {
+                write!(f, "Failed to create half-edge data structure: {}", e)
+            }
+            GoalMeshError::Topology(e) => write!(f, "Invalid goal mesh: {}", e),
+            GoalMeshError::InvalidReferenceFace { face, faces } => write!(
+                f,
+                "Invalid root face: {} (the goal mesh has {} faces, so the index must be less than {})",
+                face, faces, faces
+            ),
+            GoalMeshError::InvalidVertexIndex {
+                face,
+                index,
+                vertices,
+            } => write!(
+                f,
+                "Face {} refers to vertex {}, but there are only {} vertices",
+                face, index, vertices
+            ),
+            GoalMeshError::TooManyFaces { faces, max_faces } => write!(
+                f,
+                "The goal mesh has {} (triangular) faces, which is more than the limit of {} - try decimating it first (e.g. with the Decimate modifier in Blender), or raise the limit",
+                faces, max_faces
+            ),
+            GoalMeshError::InvalidEdge { a, b } => write!(
+                f,
+                "Invalid edge: there is no edge between vertices {} and {} of the goal mesh (see --list-faces)",
+                a, b
+            ),
         }
+    }
+}
 
-        // (3) Translate and rotate each mesh face in the e1/e2 plane to its position in the net
-        info!("Starting M3");
-        let mut m3 = vec![];
+impl From<tobj::LoadError> for GoalMeshError {
+    fn from(e: tobj::LoadError) -> Self {
+        GoalMeshError::Load(e)
+    }
+}
 
-        for fid in self.half_edge_mesh.face_id_iter() {
-            // The reference face is already in the correct position
-            if fid == self.reference_face {
-                for vid in self
-                    .half_edge_mesh
-                    .adjacent_vertices_to_face(self.reference_face)
-                {
-                    m3.push(m2[self.get_global_vertex_index(self.reference_face, vid)]);
-                }
-                continue;
-            }
+impl From<std::io::Error> for GoalMeshError {
+    fn from(e: std::io::Error) -> Self {
+        GoalMeshError::Io(e)
+    }
+}
 
-            // Get the unfolding path from the reference face to the target face
-            let (path, _) = self.get_unfolding_path_to(fid);
-            debug_assert!(path.len() >= 2);
+impl From<StlError> for GoalMeshError {
+    fn from(e: StlError) -> Self {
+        GoalMeshError::Stl(e)
+    }
+}
 
-            let mut cumulative_translation = Vec3::zero();
-            let mut mu_history = vec![];
+impl From<TopologyError> for GoalMeshError {
+    fn from(e: TopologyError) -> Self {
+        GoalMeshError::Topology(e)
+    }
+}
 
-            // Go through all of the other faces along this path including the reference face and ignoring the last
-            // face, which is the target face itself
-            for path_index in 0..(path.len() - 1) {
-                // The IDs of the current and next faces along the section of the unfolding path that connects
-                // the face `fid` to the reference face
-                let fid_curr = path[path_index];
-                let fid_next = path[path_index + 1];
+/// The traversal used to build the spanning tree of the goal mesh's faces, which determines
+/// which edges are folded (and which are cut) in the net.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum UnfoldStrategy {
+    // Faces are added to the spanning tree in breadth-first order, starting at the reference face
+    #[default]
+    BreadthFirst,
 
-                // We should be able to safely unwrap `outgoing` here - if it is `None` something is seriously wrong
-                let (incoming, mut maybe_outgoing) =
-                    self.get_incoming_outgoing_edges(fid_curr, fid_next);
-                let outgoing = maybe_outgoing.expect(
-                    "Encountered branch face with no outgoing edge - this should never happen",
-                );
+    // Faces are added to the spanning tree in depth-first order, starting at the reference face,
+    // which tends to produce long "strips" of faces
+    DepthFirst,
 
-                // A pair of vertex IDs for the incoming / outgoing edges
-                let incoming_vids = self.half_edge_mesh.adjacent_vertices_to_half_edge(incoming);
-                let outgoing_vids = self.half_edge_mesh.adjacent_vertices_to_half_edge(outgoing);
+    // Every vertex cuts the edge that points "most upwards" (w.r.t. a fixed direction), and
+    // the faces are then joined across all remaining edges: this heuristic avoids overlaps for
+    // most convex polyhedra
+    SteepestEdge,
 
-                // Vectors along the incoming and outgoing edges of this face
-                let along_incoming = m1[self.get_global_vertex_index(fid_curr, incoming_vids[1])]
-                    - m1[self.get_global_vertex_index(fid_curr, incoming_vids[0])];
-                let along_outgoing = m1[self.get_global_vertex_index(fid_curr, outgoing_vids[1])]
+    // Faces are joined across the longest edges first (i.e. the spanning tree maximizes the total
+    // length of the folds, measured on the goal mesh), so that only short edges are cut, which
+    // tends to produce more compact nets
+    MinWeightSpanningTree,
+
+    // The sides of a prism-like goal mesh (e.g. a prism, column, or tube), i.e. a loop of faces
+    // around a common axis, are unrolled into a single straight band, and the rest of the faces
+    // (e.g. the caps) are attached to the band afterwards, see `find_strip()` - goal meshes
+    // without such a loop are unfolded breadth-first instead
+    Strip,
+}
+
+impl std::str::FromStr for UnfoldStrategy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "breadth-first" => Ok(UnfoldStrategy::BreadthFirst),
+            "depth-first" => Ok(UnfoldStrategy::DepthFirst),
+            "steepest-edge" => Ok(UnfoldStrategy::SteepestEdge),
+            "min-weight-spanning-tree" => Ok(UnfoldStrategy::MinWeightSpanningTree),
+            "strip" => Ok(UnfoldStrategy::Strip),
+            _ => Err(format!("Unknown unfolding strategy: {:?}", s)),
+        }
+    }
+}
+
+/// The shape of the glue tabs along the cut edges of the net, see `generate_tabs()`.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum TabStyle {
+    // The sides of each tab are slanted inward (at a configurable angle), so that the tabs of
+    // neighboring edges don't run into each other
+    #[default]
+    Trapezoid,
+
+    // The sides of each tab are perpendicular to its edge
+    Rectangle,
+
+    // Like `Rectangle`, but the outer corners of each tab are rounded off, which makes the tabs
+    // easier to tuck under their neighbors
+    Rounded,
+}
+
+impl std::str::FromStr for TabStyle {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "trapezoid" => Ok(TabStyle::Trapezoid),
+            "rectangle" => Ok(TabStyle::Rectangle),
+            "rounded" => Ok(TabStyle::Rounded),
+            _ => Err(format!("Unknown tab style: {:?}", s)),
+        }
+    }
+}
+
+/// Controls the shape of the glue tabs along the cut edges of the net, see `generate_tabs()`.
+/// Missing fields can be filled in with their defaults, i.e.
+/// `TabOptions { style: TabStyle::Rounded, ..TabOptions::default() }`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TabOptions {
+    // How far each tab extends outward from its edge (in the units of the net)
+    pub width: f32,
+
+    pub style: TabStyle,
+
+    // The angle (in radians) between the slanted sides of trapezoidal tabs and their edge
+    pub angle: f32,
+
+    // Neighboring tabs that are trimmed to meet at a miter are cut back square from their corner
+    // instead if the miter would be longer than this times `width`
+    pub miter_limit: f32,
+}
+
+impl Default for TabOptions {
+    fn default() -> Self {
+        TabOptions {
+            width: DEFAULT_TAB_WIDTH,
+            style: TabStyle::default(),
+            angle: DEFAULT_TAB_ANGLE,
+            miter_limit: DEFAULT_TAB_MITER_LIMIT,
+        }
+    }
+}
+
+/// How the matching cut edges of the net are joined together when it is assembled.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Joinery {
+    // One side of each cut edge receives a glue tab, see `generate_tabs()`
+    #[default]
+    Glue,
+
+    // One side of each cut edge receives a tab, which fits into a slot in the face on the other
+    // side, see `generate_joints()`
+    Slot,
+}
+
+impl std::str::FromStr for Joinery {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "glue" => Ok(Joinery::Glue),
+            "slot" => Ok(Joinery::Slot),
+            _ => Err(format!("Unknown joinery: {:?}", s)),
+        }
+    }
+}
+
+/// A single side of one of the polygons in the net.
+#[derive(Clone, Copy, Debug)]
+pub struct NetEdge {
+    // The ID of the corresponding half-edge in the goal mesh (oriented CCW w.r.t. `face`)
+    pub half_edge: HalfEdgeIndex,
+
+    // The ID of the half-edge's pair, which is part of the face on the other side of this edge
+    pub pair: HalfEdgeIndex,
+
+    // The ID of the face that this edge belongs to
+    pub face: FaceIndex,
+
+    // The indices of the edge's endpoints in the net's list of positions
+    pub indices: [usize; 2],
+
+    // Whether this edge should be cut or folded
+    pub kind: EdgeKind,
+
+    // The (interior) dihedral angle between the faces on either side of this edge in the goal
+    // mesh, in radians (see `HalfEdgeMesh::interior_angle()`), or `None` along its border
+    pub interior_angle: Option<f32>,
+
+    // Whether this edge lies along the border of the goal mesh (i.e. the goal mesh is an open
+    // surface, and there is no face on the other side of the edge): such edges are always cut, but
+    // unlike the cuts made by the unfolding, they don't have a matching side to be glued to
+    pub border: bool,
+}
+
+/// The result of unfolding a goal mesh.
+pub struct UnfoldResult {
+    // The vertices of the net in the xy-plane, stored sequentially (3 per triangle) in the
+    // same order as the faces of the goal mesh: this order never depends on the spanning tree
+    // (or on how it was found), so nets unfolded from the same goal mesh can be compared triangle
+    // by triangle (see `triangle_obj_face()`)
+    pub positions: Vec<Vec3>,
+
+    // The outline of each polygon (i.e. each face of the .obj file) in the net, as a list of
+    // indices into `positions` in CCW order
+    pub polygons: Vec<Vec<usize>>,
+
+    // The index of the polygon that each triangle belongs to
+    pub triangle_polygons: Vec<usize>,
+
+    // The index of the face of the .obj file that each polygon was loaded from, which is the same
+    // as the index of the polygon unless some of the faces of the .obj file were skipped
+    pub obj_faces: Vec<usize>,
+
+    // The (SRGB) diffuse color of the material that each polygon was assigned in the .obj file (via
+    // `usemtl`), if any
+    pub material_colors: Vec<Option<Vec3>>,
+
+    // The index of the connected component (i.e. the separate piece of the goal mesh) that each
+    // polygon belongs to: each piece is unfolded into its own net, and the nets are packed together
+    // (see `pack_shelves()`)
+    pub polygon_components: Vec<usize>,
+
+    // The sides of all of the polygons in the net, in the order of the faces that they belong to
+    // (note that the edges between triangles that belong to the same polygon are not included)
+    pub edges: Vec<NetEdge>,
+
+    // Pairs of (indices of) triangles whose interiors overlap in the net - if this is non-empty,
+    // the net is not valid
+    pub overlaps: Vec<(usize, usize)>,
+
+    // A map from the ID of each cut half-edge to a label (starting at 1) that it shares with its
+    // pair, i.e. the other side of the same edge of the goal mesh - edges along the border of the
+    // goal mesh don't have a matching side, so they aren't labeled (and the labels of each piece of
+    // the goal mesh start at 1, see `cut_label()`)
+    pub cut_labels: HashMap<HalfEdgeIndex, usize>,
+
+    // The index of the island (i.e. the separate net) that each polygon belongs to: every piece of
+    // the goal mesh is unfolded into a single island, unless it is split into several smaller ones
+    // (see `UnfoldOptions::max_island_size`), which are packed like separate pieces but keep the
+    // numbering of their piece, so that the labels of the cuts between them still match
+    pub polygon_islands: Vec<usize>,
+
+    // The number of folds between each polygon and the polygon of the reference face, i.e. the
+    // depth of each polygon in the spanning tree
+    pub polygon_depths: Vec<usize>,
+
+    // The index of the vertex of the net that each entry of `positions` belongs to: the corners of
+    // faces that remain joined in the net share a vertex, while the corners on either side of a
+    // cut don't (unless the faces are joined some other way, e.g. at the end of the cut)
+    pub welded_indices: Vec<usize>,
+
+    // The indices (into `positions`) of the endpoints of the incoming edge of each triangle, i.e.
+    // the edge that it was unfolded across (or an arbitrary edge for the reference face, and the
+    // root of every other piece): each triangle is unfolded with this edge along the x-axis, so it
+    // shows how the orientation of the reference face was propagated through the net
+    pub incoming_edges: Vec<[usize; 2]>,
+
+    // Whether the net was mirrored by `flip()`, which changes the order of the corners of each
+    // triangle in `positions`
+    pub flipped: bool,
+}
+
+impl UnfoldResult {
+    /// Returns `true` if no pair of faces overlap in the net and `false` otherwise.
+    pub fn is_valid(&self) -> bool {
+        self.overlaps.is_empty()
+    }
+
+    /// Returns the (sorted) indices of the polygons that overlap at least one other polygon.
+    pub fn overlapping_polygons(&self) -> Vec<usize> {
+        let mut polygons = self
+            .overlaps
+            .iter()
+            .flat_map(|&(i, j)| vec![i, j])
+            .map(|triangle_index| self.triangle_polygons[triangle_index])
+            .collect::<Vec<_>>();
+        polygons.sort();
+        polygons.dedup();
+        polygons
+    }
+
+    /// Returns the edges between triangles that belong to the same polygon (i.e. the diagonals that
+    /// the faces of the .obj file were split along when they were triangulated), as pairs of
+    /// indices into `positions`. Each edge is only returned once, even though it is shared by two
+    /// triangles, and polygons that are already triangles don't have any.
+    pub fn triangulation_edges(&self) -> Vec<[usize; 2]> {
+        let welded_pair = |a: usize, b: usize| {
+            let (a, b) = (self.welded_indices[a], self.welded_indices[b]);
+            (a.min(b), a.max(b))
+        };
+
+        // The sides of each polygon, which are the only edges of its triangles that aren't diagonals
+        let mut sides = HashSet::new();
+        for (polygon_index, polygon) in self.polygons.iter().enumerate() {
+            for (&a, &b) in polygon.iter().zip(polygon.iter().cycle().skip(1)) {
+                sides.insert((polygon_index, welded_pair(a, b)));
+            }
+        }
+
+        let mut seen = HashSet::new();
+        let mut edges = vec![];
+        for (triangle_index, &polygon_index) in self.triangle_polygons.iter().enumerate() {
+            for i in 0..3 {
+                let a = triangle_index * 3 + i;
+                let b = triangle_index * 3 + (i + 1) % 3;
+                let key = (polygon_index, welded_pair(a, b));
+                if !sides.contains(&key) && seen.insert(key) {
+                    edges.push([a, b]);
+                }
+            }
+        }
+        edges
+    }
+
+    /// Returns the index of the face of the .obj file that the specified triangle of the net (i.e.
+    /// the triangle whose corners start at `positions[triangle * 3]`) was loaded from. Triangles are
+    /// always stored in the order of the faces of the goal mesh, so this is non-decreasing and
+    /// doesn't depend on the unfolding strategy.
+    pub fn triangle_obj_face(&self, triangle: usize) -> usize {
+        self.obj_faces[self.triangle_polygons[triangle]]
+    }
+
+    /// Returns the number of separate pieces of the goal mesh (and therefore nets) that make up
+    /// this net.
+    pub fn component_count(&self) -> usize {
+        self.polygon_components
+            .iter()
+            .max()
+            .map_or(0, |&component| component + 1)
+    }
+
+    /// Returns the number of islands (i.e. separate nets) that make up this net, which is larger
+    /// than `component_count()` if any of the pieces of the goal mesh were split up.
+    pub fn island_count(&self) -> usize {
+        self.polygon_islands
+            .iter()
+            .max()
+            .map_or(0, |&island| island + 1)
+    }
+
+    /// Returns the text of the label of the specified cut edge (if it has one), which is drawn on
+    /// both sides of the cut. If the net is made up of several pieces, the numbers of each piece are
+    /// prefixed with a letter (`A`, `B`, and so on), so that the labels of separate pieces can't be
+    /// mixed up.
+    pub fn cut_label(&self, edge: &NetEdge) -> Option<String> {
+        let label = self.cut_labels.get(&edge.half_edge)?;
+        if self.component_count() <= 1 {
+            return Some(label.to_string());
+        }
+
+        // Components after `Z` continue with `AA`, `AB`, and so on (like the columns of a
+        // spreadsheet)
+        let mut component = self.polygon_components[self.triangle_polygons[usize::from(edge.face)]];
+        let mut prefix = vec![];
+        loop {
+            prefix.push((b'A' + (component % 26) as u8) as char);
+            if component < 26 {
+                break;
+            }
+            component = component / 26 - 1;
+        }
+        Some(format!(
+            "{}{}",
+            prefix.iter().rev().collect::<String>(),
+            label
+        ))
+    }
+
+    /// Returns the length of the specified edge of the net.
+    pub fn edge_length(&self, edge: &NetEdge) -> f32 {
+        (self.positions[edge.indices[1]] - self.positions[edge.indices[0]]).length()
+    }
+
+    /// Returns the total length of the cut edges of the net, i.e. the length of its outline. Both
+    /// sides of each edge that was cut apart are counted, since they end up in different places.
+    pub fn cut_length(&self) -> f32 {
+        self.edges
+            .iter()
+            .filter(|edge| edge.kind == EdgeKind::Cut)
+            .map(|edge| self.edge_length(edge))
+            .sum()
+    }
+
+    /// Returns the total length of the fold edges of the net. Each fold is shared by two
+    /// polygons, but it is only counted once.
+    pub fn fold_length(&self) -> f32 {
+        self.edges
+            .iter()
+            .filter(|edge| edge.kind.is_fold() && edge.half_edge < edge.pair)
+            .map(|edge| self.edge_length(edge))
+            .sum()
+    }
+
+    /// Returns the indices of the polygons that each polygon is joined to along a fold, i.e. its
+    /// neighbors in the net (polygons that share a cut edge end up apart, so they aren't included).
+    pub fn polygon_neighbors(&self) -> Vec<Vec<usize>> {
+        let faces = self
+            .edges
+            .iter()
+            .map(|edge| (edge.half_edge, edge.face))
+            .collect::<HashMap<_, _>>();
+
+        let mut neighbors = vec![vec![]; self.polygons.len()];
+        for edge in self.edges.iter().filter(|edge| edge.kind.is_fold()) {
+            let polygon = self.triangle_polygons[usize::from(edge.face)];
+            let other = self.triangle_polygons[usize::from(faces[&edge.pair])];
+            if !neighbors[polygon].contains(&other) {
+                neighbors[polygon].push(other);
+            }
+        }
+
+        neighbors
+    }
+
+    /// Returns the outline of the net, i.e. the path that a cutter has to follow to cut it out: one
+    /// closed loop of positions per border of the net (a single piece without holes has exactly
+    /// one), in order and without repeating the first position at the end. The loops are found
+    /// by welding the corners of the net into a mesh of their own (see `welded_indices`) and
+    /// walking along its border, so that no fold is part of the outline.
+    ///
+    /// The outlines of the specified tabs (and slots, see `generate_joints()`) take a detour from
+    /// the edge that they are drawn along, so they are cut out along with the net without being cut
+    /// off of it. Tabs that don't lie along the outline are added as loops of their own.
+    pub fn outline(&self, tabs: &[Vec<Vec3>]) -> Result<Vec<Vec<Vec3>>, GoalMeshError> {
+        let vertex_count = self.welded_indices.iter().max().map_or(0, |max| max + 1);
+        let mut vertices = vec![Vec3::zero(); vertex_count];
+        for (position, &welded_index) in self.positions.iter().zip(self.welded_indices.iter()) {
+            vertices[welded_index] = *position;
+        }
+        let faces = self
+            .welded_indices
+            .chunks(3)
+            .map(|triangle| [triangle[0], triangle[1], triangle[2]])
+            .collect::<Vec<_>>();
+        let net_mesh =
+            HalfEdgeMesh::from_faces(&faces, &vertices).map_err(GoalMeshError::HalfEdgeMesh)?;
+
+        // A tab belongs to the side of the outline that its first two points lie on
+        let epsilon = overlap_tolerance(&self.positions, DEFAULT_OVERLAP_TOLERANCE);
+        let on_segment = |point: &Vec3, a: &Vec3, b: &Vec3| {
+            let along = *b - *a;
+            let t = ((*point - *a).dot(along) / along.dot(along)).clamp(0.0, 1.0);
+            (*a + along * t - *point).length() <= epsilon
+        };
+        let mut unused = vec![true; tabs.len()];
+
+        let mut loops = vec![];
+        for boundary_loop in net_mesh.boundary_loops() {
+            let mut points = vec![];
+            for &eid in boundary_loop.iter() {
+                let [u, v] = net_mesh.adjacent_vertices_to_half_edge(eid);
+                let (a, b) = (vertices[usize::from(u)], vertices[usize::from(v)]);
+                points.push(a);
+
+                let tab = (0..tabs.len()).find(|&tab| {
+                    unused[tab]
+                        && tabs[tab].len() > 2
+                        && on_segment(&tabs[tab][0], &a, &b)
+                        && on_segment(&tabs[tab][1], &a, &b)
+                });
+                if let Some(tab) = tab {
+                    unused[tab] = false;
+
+                    // Walk around the tab from the end of its edge that is closest to `a`, without
+                    // walking along the edge itself
+                    let tab = &tabs[tab];
+                    let mut detour = tab[1..].iter().chain(tab[..1].iter()).collect::<Vec<_>>();
+                    if (tab[1] - a).length() > (tab[0] - a).length() {
+                        detour.reverse();
+                    }
+                    points.extend(
+                        detour
+                            .into_iter()
+                            .filter(|point| (**point - a).length() > epsilon)
+                            .filter(|point| (**point - b).length() > epsilon),
+                    );
+                }
+            }
+            loops.push(points);
+        }
+
+        for (tab, _) in tabs.iter().zip(unused.iter()).filter(|(_, &unused)| unused) {
+            loops.push(tab.clone());
+        }
+
+        Ok(loops)
+    }
+
+    /// Returns the pairs of matching cut edges of the net (i.e. both sides of each edge of the goal
+    /// mesh that was cut) in the order that they should be glued together, so that the model can
+    /// be assembled one step at a time: each piece of the net is glued together separately, and
+    /// the cuts closest to its root face (in the spanning tree) come first, so that the model
+    /// closes up around the root face and the last seams are the ones furthest away from it. Each
+    /// pair is returned once, with the side that gets the lower half-edge ID first.
+    pub fn gluing_order(&self) -> Vec<(&NetEdge, &NetEdge)> {
+        let edges = self
+            .edges
+            .iter()
+            .map(|edge| (edge.half_edge, edge))
+            .collect::<HashMap<_, _>>();
+
+        let mut pairs = self
+            .edges
+            .iter()
+            .filter(|edge| edge.kind == EdgeKind::Cut && edge.half_edge < edge.pair)
+            .filter_map(|edge| Some((edge, *edges.get(&edge.pair)?)))
+            .collect::<Vec<_>>();
+
+        pairs.sort_by_key(|(edge, other)| {
+            let depth = |edge: &NetEdge| {
+                self.polygon_depths[self.triangle_polygons[usize::from(edge.face)]]
+            };
+            let (near, far) = (depth(edge).min(depth(other)), depth(edge).max(depth(other)));
+            let component = self.polygon_components[self.triangle_polygons[usize::from(edge.face)]];
+            (
+                component,
+                far,
+                near,
+                self.cut_labels.get(&edge.half_edge).copied(),
+            )
+        });
+
+        pairs
+    }
+
+    /// Mirrors the net across the y-axis (i.e. negates the x-coordinate of every position), so that
+    /// it can be printed on the other side of the paper. Since the net is then viewed from the
+    /// inside of the goal mesh, mountain folds become valley folds and vice versa.
+    ///
+    /// Mirroring reverses the winding order of every triangle, so the last two corners of each
+    /// triangle are swapped (and every edge and polygon is reversed) to keep them in CCW order.
+    pub fn flip(&mut self) {
+        let remap = |index: usize| match index % 3 {
+            1 => index + 1,
+            2 => index - 1,
+            _ => index,
+        };
+
+        let mut positions = self.positions.clone();
+        let mut welded_indices = self.welded_indices.clone();
+        for (index, position) in self.positions.iter().enumerate() {
+            positions[remap(index)] = Vec3::new(-position.x(), position.y(), position.z());
+            welded_indices[remap(index)] = self.welded_indices[index];
+        }
+        self.positions = positions;
+
+        // Welded vertices are numbered in the order that they first appear, which has changed
+        let mut numbering = HashMap::new();
+        self.welded_indices = welded_indices
+            .iter()
+            .map(|welded_index| {
+                let next = numbering.len();
+                *numbering.entry(welded_index).or_insert(next)
+            })
+            .collect();
+
+        for polygon in self.polygons.iter_mut() {
+            *polygon = polygon.iter().rev().map(|&index| remap(index)).collect();
+        }
+
+        for edge in self.edges.iter_mut() {
+            edge.indices = [remap(edge.indices[1]), remap(edge.indices[0])];
+            edge.kind = edge.kind.flipped();
+        }
+        self.flipped = !self.flipped;
+    }
+
+    /// Snaps the positions of the net that lie within `tolerance` units of each other to their
+    /// average, so that the corners of neighboring faces coincide exactly (floating point error
+    /// builds up along each path of the spanning tree, which otherwise leaves hairline gaps between
+    /// faces that cutters treat as broken paths). Only positions are changed, so the net keeps its
+    /// connectivity, and two corners of the same triangle are never snapped together. Returns the
+    /// number of positions that were moved.
+    pub fn weld_positions(&mut self, tolerance: f32) -> usize {
+        if tolerance <= 0.0 {
+            return 0;
+        }
+
+        // Each cluster of nearby positions is found through the grid cell of its first position,
+        // and every position within `tolerance` of it lies in one of the neighboring cells
+        let cell_of = |position: &Vec3| {
+            (
+                (position.x() / tolerance).floor() as i64,
+                (position.y() / tolerance).floor() as i64,
+            )
+        };
+        let mut grid: HashMap<(i64, i64), Vec<usize>> = HashMap::new();
+        let mut clusters: Vec<Vec<usize>> = vec![];
+        for (index, position) in self.positions.iter().enumerate() {
+            let (x, y) = cell_of(position);
+            let found = (x - 1..=x + 1)
+                .flat_map(|x| (y - 1..=y + 1).map(move |y| (x, y)))
+                .filter_map(|cell| grid.get(&cell))
+                .flatten()
+                .copied()
+                .find(|&cluster| {
+                    let members = &clusters[cluster];
+                    (self.positions[members[0]] - *position).length() <= tolerance
+                        && members.iter().all(|&member| member / 3 != index / 3)
+                });
+
+            match found {
+                Some(cluster) => clusters[cluster].push(index),
+                None => {
+                    grid.entry((x, y)).or_default().push(clusters.len());
+                    clusters.push(vec![index]);
+                }
+            }
+        }
+
+        let mut moved = 0;
+        for members in clusters.iter().filter(|members| members.len() > 1) {
+            let average = members
+                .iter()
+                .fold(Vec3::zero(), |sum, &member| sum + self.positions[member])
+                / members.len() as f32;
+            for &member in members.iter() {
+                if self.positions[member] != average {
+                    self.positions[member] = average;
+                    moved += 1;
+                }
+            }
+        }
+        moved
+    }
+
+    /// Returns the area of each polygon of the net, where `scale` is the number of millimeters per
+    /// unit of the net: the areas are in square millimeters, i.e. the size of each face once the net
+    /// is printed.
+    pub fn polygon_areas(&self, scale: f32) -> Vec<f32> {
+        self.polygons
+            .iter()
+            .map(|polygon| {
+                let points = polygon
+                    .iter()
+                    .map(|&index| self.positions[index])
+                    .collect::<Vec<_>>();
+                polygon_area(&points) * scale * scale
+            })
+            .collect()
+    }
+
+    /// Returns the centroid of the specified polygon of the net (i.e. the average of the centroids of
+    /// its triangles, weighted by their areas), which is where its face label is drawn.
+    pub fn polygon_center(&self, polygon: usize) -> Vec3 {
+        let (weighted_sum, total_area) = self
+            .positions
+            .chunks(3)
+            .zip(self.triangle_polygons.iter())
+            .filter(|(_, &triangle_polygon)| triangle_polygon == polygon)
+            .fold((Vec3::zero(), 0.0), |(sum, total), (triangle, _)| {
+                let area = (triangle[1] - triangle[0])
+                    .cross(triangle[2] - triangle[0])
+                    .length()
+                    * 0.5;
+                let centroid = (triangle[0] + triangle[1] + triangle[2]) / 3.0;
+                (sum + centroid * area, total + area)
+            });
+        weighted_sum / total_area
+    }
+
+    /// Returns the text of the angle label of the specified fold (if it is one), i.e. the dihedral
+    /// angle that the faces on either side of it meet at in the assembled goal mesh, in whole
+    /// degrees and prefixed with `M` or `V` for mountain and valley folds (e.g. `M 90°` for each
+    /// fold of a cube). Convex edges are folded to less than 180 degrees and reflex edges to more,
+    /// but either way, the angle is measured on the side of the paper that closes up, so folds are
+    /// never labeled with more than 180 degrees. Since each fold is shared by two polygons, only
+    /// one of its sides is labeled, and folds between coplanar faces aren't labeled at all.
+    pub fn fold_angle_label(&self, edge: &NetEdge) -> Option<String> {
+        if !edge.kind.is_fold() || edge.half_edge < edge.pair {
+            return None;
+        }
+        let angle = edge.interior_angle?.to_degrees();
+        let degrees = angle.min(360.0 - angle).round();
+        if degrees >= 180.0 {
+            return None;
+        }
+
+        let prefix = match edge.kind {
+            EdgeKind::Mountain => "M",
+            _ => "V",
+        };
+        Some(format!("{} {}°", prefix, degrees))
+    }
+
+    /// Returns the point at which the label of the specified edge should be drawn, which is
+    /// `inset` units away from the edge's midpoint, towards the inside of its face.
+    pub fn label_position(&self, edge: &NetEdge, inset: f32) -> Vec3 {
+        let a = self.positions[edge.indices[0]];
+        let b = self.positions[edge.indices[1]];
+
+        // Since the faces of the net are in CCW order, "inward" is always to the left of the edge
+        let along = (b - a).normalize();
+        let inward = Vec3::new(-along.y(), along.x(), 0.0);
+
+        (a + b) * 0.5 + inward * inset
+    }
+}
+
+pub struct GoalMesh {
+    // The internal HEM data structure, used for adjacency queries
+    half_edge_mesh: HalfEdgeMesh,
+
+    // The vertex IDs of each polygon (i.e. each face of the .obj file, before triangulation, or
+    // several coplanar faces that were merged together), in CCW order
+    polygons: Vec<Vec<VertexIndex>>,
+
+    // The index of the polygon that each (triangular) face of the half-edge mesh belongs to
+    face_polygons: Vec<usize>,
+
+    // The index of the face of the .obj file that each polygon was loaded from (the first one, if
+    // several coplanar faces were merged)
+    obj_faces: Vec<usize>,
+
+    // The (SRGB) diffuse color of the material of each polygon, if the .obj file assigns one
+    polygon_colors: Vec<Option<Vec3>>,
+
+    // The (SRGB) color of each vertex, if the .obj file provides one (see `parse_vertex_colors()`)
+    vertex_colors: Vec<Option<Vec3>>,
+
+    // The index of the connected component (i.e. the separate solid) that each (triangular) face of
+    // the half-edge mesh belongs to, and the number of components
+    face_components: Vec<usize>,
+    component_count: usize,
+
+    // The neighbors of each (triangular) face, along with the half-edge (of the face) that is
+    // shared with each neighbor - these don't depend on the spanning tree, so they are only
+    // computed once
+    face_neighbors: Vec<Vec<(FaceIndex, HalfEdgeIndex)>>,
+
+    // The cut boundary used by `UnfoldStrategy::SteepestEdge`, which doesn't depend on the
+    // reference face either (both half-edges of every cut edge are included)
+    steepest_edges: HashSet<HalfEdgeIndex>,
+
+    // The polygons that make up the band unrolled by `UnfoldStrategy::Strip`, if the goal mesh
+    // has one (which doesn't depend on the reference face either)
+    strip: Option<HashSet<usize>>,
+
+    // The tolerances that the goal mesh was loaded with, which are also used while unfolding it
+    tolerances: Tolerances,
+}
+
+// `count_overlaps_by_reference_face()` unfolds the goal mesh on several threads at once, so it has
+// to stay `Sync` (i.e. it can't hold any `Rc`s or `RefCell`s)
+const _: fn() = || {
+    fn assert_sync<T: Sync>() {}
+    assert_sync::<GoalMesh>();
+};
+
+/// The spanning tree of the faces of the goal mesh, which determines the layout of the net: each
+/// face is unfolded into the plane of the face that it "comes from," so only the edges that are
+/// crossed by the tree are folded (and all of the others are cut).
+struct SpanningTree {
+    // The face at the root of the tree, which stays in place when the goal mesh is unfolded
+    reference_face: FaceIndex,
+
+    // The face at the root of each connected component of the goal mesh, in the order of the
+    // components: if the goal mesh consists of several pieces, the spanning tree is really a forest
+    // (where the reference face is the root of its own piece, and the first face of each of the other
+    // pieces is the root of that piece), followed by the roots of any islands that pieces were split
+    // into (see `GoalMesh::split_into_islands()`)
+    roots: Vec<FaceIndex>,
+
+    // A map that holds information about where each face came from in the spanning tree
+    came_from: HashMap<FaceIndex, (FaceIndex, HalfEdgeIndex)>,
+
+    // The IDs of the edges crossed by the spanning tree (the "cut boundary" is the set
+    // of edges *not* included in this set)
+    crossed_edges: HashSet<HalfEdgeIndex>,
+}
+
+impl SpanningTree {
+    fn new(
+        reference_face: FaceIndex,
+        roots: Vec<FaceIndex>,
+        came_from: HashMap<FaceIndex, (FaceIndex, HalfEdgeIndex)>,
+    ) -> SpanningTree {
+        // Edges that are crossed by the spanning tree
+        let crossed_edges = came_from
+            .values()
+            .filter(|(fid, _)| *fid != NO_FACE)
+            .map(|(_, eid)| *eid)
+            .collect::<HashSet<_>>();
+
+        SpanningTree {
+            reference_face,
+            roots,
+            came_from,
+            crossed_edges,
+        }
+    }
+}
+
+impl GoalMesh {
+    /// Loads the goal mesh from the specified .obj file. The goal mesh itself never changes once it
+    /// has been loaded: the spanning tree of its faces is computed whenever the mesh is unfolded, so
+    /// the same goal mesh can be unfolded any number of times (e.g. from different reference faces
+    /// or with different strategies) without having to load the .obj file again.
+    ///
+    /// Faces with more than 3 vertices (quads, pentagons, etc.) are fan triangulated around their first
+    /// vertex, which preserves their winding order. The resulting triangles are kept together as a single
+    /// polygon during the unfolding, so no fold lines are drawn between them. Note that if a polygon is
+    /// not planar, its triangles will not be coplanar: they are still unfolded (and drawn) as a single
+    /// polygon, but the net will not fold back into exactly the same shape. Such faces should be
+    /// triangulated before exporting the .obj file.
+    ///
+    /// If the .obj file assigns materials to its faces (i.e. with `usemtl` and a corresponding .mtl
+    /// file), the diffuse color (`Kd`) of each face's material is kept, so that the net can be
+    /// colored to match the goal mesh. Likewise, if its vertices have colors (i.e. `v x y z r g b`,
+    /// as written by MeshLab and some 3D scanners), they are kept, see `face_vertex_color()`.
+    ///
+    /// If the .obj file provides vertex normals (i.e. faces of the form `f v//vn` or `f v/vt/vn`),
+    /// they are stored on the vertices of the half-edge mesh and used to decide which way each edge
+    /// folds. Otherwise, the orientation of the surface is based on the winding order of its faces.
+    ///
+    /// Neighboring faces that are (nearly) coplanar, i.e. whose dihedral angle is less than
+    /// `options.coplanar_tolerance` radians, are merged into a single polygon as well, so that no
+    /// folds are drawn across flat regions of the goal mesh (such as a cube whose square faces were
+    /// each split into two triangles). Faces are only merged if the resulting polygon doesn't have
+    /// any holes (and if all of them have the same material), and a tolerance of zero disables
+    /// merging altogether. Merging renumbers the faces that come after the merged ones, just like
+    /// skipping degenerate faces (see below).
+    ///
+    /// Faces with (almost) no area, such as slivers or faces with repeated vertices, can't be
+    /// unfolded (see `Tolerances::degenerate`). If `options.strict` is `true`, an error listing the
+    /// indices of these faces is returned. Otherwise, they are skipped (which renumbers the faces
+    /// after them, although the index of the face of the .obj file that each polygon was loaded
+    /// from is kept) and a warning is logged.
+    /// Note that skipping a face leaves a gap in the surface unless its vertices coincide, so the
+    /// goal mesh may still fail to load.
+    ///
+    /// The goal mesh keeps `options.tolerances`, which are also used while it is unfolded, see
+    /// `tolerances()`.
+    ///
+    /// The .obj file may contain several separate solids (e.g. one per `o` or `g` statement): each
+    /// connected piece of the goal mesh is unfolded into its own net, and the nets are packed
+    /// together (see `unfold()`).
+    ///
+    /// Some .obj files mix faces that are wound clockwise with faces that are wound
+    /// counterclockwise, which the half-edge mesh can't represent. If `options.fix_winding` is
+    /// `true`, the winding order of the first face of each piece of the goal mesh is propagated to
+    /// the rest of its faces (flipping them as needed), and pieces that end up inside out are
+    /// flipped as a whole, so that every face is CCW when viewed from the outside. The number of
+    /// flipped faces is logged, along with a warning for any pieces that can't be oriented
+    /// consistently.
+    ///
+    /// The goal mesh has to be a 2-manifold, and unless `options.allow_open` is `true`, it has to
+    /// be closed as well. Open surfaces (such as a bowl, or a patch of terrain) can be unfolded just
+    /// like closed ones: the edges along their border are always cut, but unlike the other cuts, they
+    /// don't have a matching side to be glued to.
+    ///
+    /// Returns an error if the file can't be loaded, or if the goal mesh is not a (closed)
+    /// 2-manifold (which is required for the unfolding to make sense).
+    pub fn from_obj(path_to_file: &Path, options: &LoadOptions) -> Result<GoalMesh, GoalMeshError> {
+        let file = File::open(path_to_file)?;
+
+        // Like `tobj::load_obj()`, any .mtl files are found relative to the .obj file itself
+        let material_directory = path_to_file.parent().unwrap_or_else(|| Path::new(""));
+        GoalMesh::from_obj_reader(&mut BufReader::new(file), material_directory, options)
+    }
+
+    /// Loads the goal mesh from the contents of an .obj file that are read from `reader` (e.g. an
+    /// open file, or stdin), in the same way as `from_obj()`. Any .mtl files that it references
+    /// are loaded relative to `material_directory`.
+    ///
+    /// Returns `GoalMeshError::Empty` if there is nothing to read (or nothing but comments), and
+    /// `GoalMeshError::Syntax` (with the number of the offending line) if a vertex doesn't have 3
+    /// finite coordinates, or a face doesn't have at least 3 vertices that were defined before it.
+    pub fn from_obj_reader<R: BufRead>(
+        reader: &mut R,
+        material_directory: &Path,
+        options: &LoadOptions,
+    ) -> Result<GoalMesh, GoalMeshError> {
+        // Large goal meshes take a while to load, so the time spent on each step is logged
+        let start = Instant::now();
+
+        // The errors of `tobj` don't say where the problem is, so check the most common mistakes
+        // first (which also catches NaNs, since `tobj` parses them just like any other number)
+        let mut text = String::new();
+        reader.read_to_string(&mut text)?;
+        check_obj_syntax(&text)?;
+
+        // Don't triangulate the model here, since we need to know which triangles belong to which polygon
+        let (models, materials) = tobj::load_obj_buf(&mut text.as_bytes(), false, |path| {
+            tobj::load_mtl(material_directory.join(path))
+        })?;
+        if models
+            .iter()
+            .all(|model| model.mesh.num_face_indices.is_empty())
+        {
+            return Err(GoalMeshError::Empty);
+        }
+
+        // The diffuse colors of .mtl files are linear (as written by Blender), but the net is
+        // colored in SRGB
+        let material_colors = materials
+            .iter()
+            .map(|material| {
+                let [r, g, b] = material.diffuse;
+                Vec3::new(linear_to_srgb(r), linear_to_srgb(g), linear_to_srgb(b))
+            })
+            .collect::<Vec<_>>();
+
+        GoalMesh::from_models(
+            &models,
+            &material_colors,
+            &parse_vertex_colors(&text),
+            options,
+            start,
+        )
+    }
+
+    /// Loads the goal mesh from an .stl file (in either the binary or the ASCII format), in the
+    /// same way as `from_obj()`. Every triangle of an .stl file has its own copy of each of its
+    /// vertices, so the vertices that lie within a small distance of each other (relative to the
+    /// size of the goal mesh) are welded together first, which connects the triangles into a
+    /// closed surface. Each triangle is a separate face of the goal mesh, but coplanar triangles
+    /// are merged back into polygons (following `options.coplanar_tolerance`).
+    pub fn from_stl(path_to_file: &Path, options: &LoadOptions) -> Result<GoalMesh, GoalMeshError> {
+        let start = Instant::now();
+        let triangles = read_stl(path_to_file)?;
+
+        let (min, max) = triangles.iter().flatten().fold(
+            (Vec3::splat(f32::INFINITY), Vec3::splat(f32::NEG_INFINITY)),
+            |(min, max), point| (min.min(*point), max.max(*point)),
+        );
+        let tolerance = (max - min).length().max(f32::MIN_POSITIVE) * options.tolerances.stl_weld;
+        let (vertices, faces) = weld_vertices(&triangles, tolerance);
+        info!(
+            "Welded the {} vertices of the .stl file into {} vertices (within {:?})",
+            triangles.len() * 3,
+            vertices.len(),
+            tolerance
+        );
+
+        let mesh = tobj::Mesh {
+            positions: vertices
+                .iter()
+                .flat_map(|vertex| vec![vertex.x(), vertex.y(), vertex.z()])
+                .collect(),
+            normals: vec![],
+            texcoords: vec![],
+            indices: faces.iter().flatten().map(|&index| index as u32).collect(),
+            num_face_indices: vec![3; faces.len()],
+            material_id: None,
+        };
+        let name = path_to_file
+            .file_stem()
+            .map_or(String::new(), |stem| stem.to_string_lossy().into_owned());
+
+        GoalMesh::from_models(
+            &[tobj::Model::new(mesh, name)],
+            &[],
+            &HashMap::new(),
+            options,
+            start,
+        )
+    }
+
+    /// Builds the goal mesh from triangles that are already in memory (e.g. generated
+    /// procedurally), in the same way as `from_obj()`: each triangle indexes (starting at 0) into
+    /// `vertices`, and is a separate face of the goal mesh, but coplanar triangles are merged back
+    /// into polygons (following `options.coplanar_tolerance`). Vertices are only shared by
+    /// triangles that refer to them by the same index (or that have exactly the same position).
+    ///
+    /// Returns `GoalMeshError::InvalidVertexIndex` if a triangle refers to a vertex that doesn't
+    /// exist, and the same errors as `from_obj()` otherwise (e.g. if the goal mesh is not a closed
+    /// 2-manifold).
+    pub fn from_vertices_faces(
+        vertices: &[Vec3],
+        faces: &[[usize; 3]],
+        options: &LoadOptions,
+    ) -> Result<GoalMesh, GoalMeshError> {
+        let start = Instant::now();
+        for (face, triangle) in faces.iter().enumerate() {
+            if let Some(&index) = triangle.iter().find(|&&index| index >= vertices.len()) {
+                return Err(GoalMeshError::InvalidVertexIndex {
+                    face,
+                    index,
+                    vertices: vertices.len(),
+                });
+            }
+        }
+
+        let mesh = tobj::Mesh {
+            positions: vertices
+                .iter()
+                .flat_map(|vertex| vec![vertex.x(), vertex.y(), vertex.z()])
+                .collect(),
+            normals: vec![],
+            texcoords: vec![],
+            indices: faces.iter().flatten().map(|&index| index as u32).collect(),
+            num_face_indices: vec![3; faces.len()],
+            material_id: None,
+        };
+
+        GoalMesh::from_models(
+            &[tobj::Model::new(mesh, String::new())],
+            &[],
+            &HashMap::new(),
+            options,
+            start,
+        )
+    }
+
+    /// Simplifies the goal mesh until it has at most `target_faces` (triangular) faces by collapsing
+    /// its shortest edges (see `decimate()`), and returns the simplified goal mesh: each of its
+    /// pieces keeps its topology, so it is still a closed 2-manifold. The remaining triangles keep
+    /// the colors of the faces that they were left over from (as well as their vertex normals and
+    /// colors, if the .obj file provided any), and coplanar triangles are merged back into polygons (following
+    /// `coplanar_tolerance`). The simplified goal mesh keeps the tolerances of this one. Since the
+    /// faces of the .obj file no longer exist, the faces of the simplified goal mesh are numbered
+    /// anew (starting at 0, grouped by their colors).
+    pub fn decimate(
+        &self,
+        target_faces: usize,
+        coplanar_tolerance: f32,
+    ) -> Result<GoalMesh, GoalMeshError> {
+        let start = Instant::now();
+        let triangles = self
+            .half_edge_mesh
+            .face_id_iter()
+            .map(|fid| {
+                let mut corners = self
+                    .half_edge_mesh
+                    .adjacent_vertices_to_face(fid)
+                    .map(usize::from);
+                [
+                    corners.next().unwrap(),
+                    corners.next().unwrap(),
+                    corners.next().unwrap(),
+                ]
+            })
+            .collect::<Vec<_>>();
+        let decimated = decimate(&self.vertex_coordinates(), &triangles, target_faces);
+        info!(
+            "Decimated the goal mesh from {} to {} triangular faces in {:?}",
+            triangles.len(),
+            decimated.faces.len(),
+            start.elapsed()
+        );
+        if decimated.faces.len() > target_faces {
+            warn!(
+                "Could not decimate the goal mesh to {} faces without changing its topology",
+                target_faces
+            );
+        }
+
+        // Each model of an .obj file has a single material, so the triangles are split into one
+        // model per color (from the same list of vertices, which are welded back together when the
+        // models are loaded)
+        let normals = decimated
+            .vertex_sources
+            .iter()
+            .map(|&vid| self.half_edge_mesh.vertex(VertexIndex(vid)).normal())
+            .collect::<Option<Vec<_>>>()
+            .unwrap_or_default();
+        let vertex_colors = decimated
+            .vertices
+            .iter()
+            .zip(decimated.vertex_sources.iter())
+            .filter_map(|(vertex, &vid)| {
+                let key = [
+                    vertex.x().to_bits(),
+                    vertex.y().to_bits(),
+                    vertex.z().to_bits(),
+                ];
+                self.vertex_colors[vid].map(|color| (key, color))
+            })
+            .collect::<HashMap<_, _>>();
+        let mut colors: Vec<Vec3> = vec![];
+        let mut groups: Vec<(Option<usize>, Vec<[usize; 3]>)> = vec![];
+        for (face, &source) in decimated.faces.iter().zip(decimated.face_sources.iter()) {
+            let material = self.polygon_colors[self.face_polygons[source]].map(|color| {
+                colors
+                    .iter()
+                    .position(|&other| other == color)
+                    .unwrap_or_else(|| {
+                        colors.push(color);
+                        colors.len() - 1
+                    })
+            });
+            match groups.iter_mut().find(|(other, _)| *other == material) {
+                Some((_, faces)) => faces.push(*face),
+                None => groups.push((material, vec![*face])),
+            }
+        }
+
+        let models = groups
+            .iter()
+            .map(|(material, faces)| {
+                let mesh = tobj::Mesh {
+                    positions: decimated
+                        .vertices
+                        .iter()
+                        .flat_map(|vertex| vec![vertex.x(), vertex.y(), vertex.z()])
+                        .collect(),
+                    normals: normals
+                        .iter()
+                        .flat_map(|normal| vec![normal.x(), normal.y(), normal.z()])
+                        .collect(),
+                    texcoords: vec![],
+                    indices: faces.iter().flatten().map(|&index| index as u32).collect(),
+                    num_face_indices: vec![3; faces.len()],
+                    material_id: *material,
+                };
+                tobj::Model::new(mesh, String::new())
+            })
+            .collect::<Vec<_>>();
+
+        let options = LoadOptions {
+            coplanar_tolerance,
+            allow_open: !self.half_edge_mesh.boundary_edges().is_empty(),
+            tolerances: self.tolerances,
+            ..LoadOptions::default()
+        };
+        GoalMesh::from_models(&models, &colors, &vertex_colors, &options, start)
+    }
+
+    /// Builds the goal mesh from the models of an .obj file (or the single model of an .stl file),
+    /// given the (SRGB) colors of the materials of the file and of its vertices (keyed by their
+    /// positions, see `parse_vertex_colors()`), see `from_obj()`.
+    fn from_models(
+        models: &[tobj::Model],
+        material_colors: &[Vec3],
+        vertex_colors: &HashMap<[u32; 3], Vec3>,
+        options: &LoadOptions,
+        start: Instant,
+    ) -> Result<GoalMesh, GoalMeshError> {
+        let LoadOptions {
+            strict,
+            coplanar_tolerance,
+            fix_winding,
+            allow_open,
+            tolerances,
+        } = *options;
+
+        // Containers for storing vertices and faces
+        let mut base_vertices = vec![];
+        let mut base_faces = vec![];
+        let mut polygons = vec![];
+        let mut face_polygons = vec![];
+        let mut obj_faces = vec![];
+        let mut polygon_colors = vec![];
+
+        // Each object or group (`o` or `g`) of the .obj file is loaded as a separate model, with its
+        // own list of vertices: the faces of all of the models are gathered into a single goal mesh
+        // (and numbered in the order that they appear in the file), and any separate solids are
+        // unfolded into separate nets later on
+        let mut has_normals = false;
+        let mut vertex_normals = vec![];
+        let mut base_colors = vec![];
+        let mut index_map = HashMap::new();
+        let mut degenerate_faces = vec![];
+        let mut first_face = 0;
+
+        for model in models.iter() {
+            let mesh = &model.mesh;
+            let color = mesh
+                .material_id
+                .and_then(|material| material_colors.get(material).copied());
+
+            // Parse vertices: tobj creates a separate vertex for each unique combination of
+            // position, normal, and texture coordinate indices, so vertices that share a position
+            // are welded back together (otherwise, faces with different normals would not be
+            // connected to one another)
+            debug_assert_eq!(mesh.positions.len() % 3, 0);
+            let model_has_normals =
+                !mesh.normals.is_empty() && mesh.normals.len() == mesh.positions.len();
+            has_normals |= model_has_normals;
+            let mut welded_indices = vec![];
+
+            for vertex_index in 0..mesh.positions.len() / 3 {
+                let position = Vec3::new(
+                    mesh.positions[3 * vertex_index + 0],
+                    mesh.positions[3 * vertex_index + 1],
+                    mesh.positions[3 * vertex_index + 2],
+                );
+                let key = [
+                    position.x().to_bits(),
+                    position.y().to_bits(),
+                    position.z().to_bits(),
+                ];
+                let welded_index = *index_map.entry(key).or_insert_with(|| {
+                    base_vertices.push(position);
+                    vertex_normals.push(Vec3::zero());
+                    base_colors.push(vertex_colors.get(&key).copied());
+                    base_vertices.len() - 1
+                });
+                welded_indices.push(welded_index);
+
+                // The normals of all of the vertices that were welded together are averaged
+                if model_has_normals {
+                    vertex_normals[welded_index] += Vec3::new(
+                        mesh.normals[3 * vertex_index],
+                        mesh.normals[3 * vertex_index + 1],
+                        mesh.normals[3 * vertex_index + 2],
+                    );
+                }
+            }
+
+            // Parse faces
+            let mut next_face = 0;
+            for model_face_index in 0..mesh.num_face_indices.len() {
+                let face_index = first_face + model_face_index;
+                let end = next_face + mesh.num_face_indices[model_face_index] as usize;
+                let face_indices: Vec<_> = mesh.indices[next_face..end]
+                    .iter()
+                    .map(|&index| welded_indices[index as usize])
+                    .collect();
+                next_face = end;
+
+                if face_indices.len() < 3 {
+                    warn!(
+                        "Skipping face #{} with fewer than 3 vertices (lines are not supported)",
+                        face_index
+                    );
+                    continue;
+                }
+
+                let points = face_indices
+                    .iter()
+                    .map(|&index| base_vertices[index])
+                    .collect::<Vec<_>>();
+                let longest_side = points
+                    .iter()
+                    .zip(points.iter().cycle().skip(1))
+                    .map(|(a, b)| (*b - *a).length())
+                    .fold(0.0, f32::max);
+                if polygon_area(&points) <= tolerances.degenerate * longest_side * longest_side {
+                    degenerate_faces.push(face_index);
+                    continue;
+                }
+
+                // Fan triangulation around the first vertex of the polygon: the triangles are
+                // stored sequentially, and each one is tagged with the polygon that it belongs to
+                for i in 1..face_indices.len() - 1 {
+                    base_faces.push([face_indices[0], face_indices[i], face_indices[i + 1]]);
+                    face_polygons.push(polygons.len());
+                }
+                obj_faces.push(face_index);
+                polygon_colors.push(color);
+                polygons.push(
+                    face_indices
+                        .iter()
+                        .map(|&index| VertexIndex(index))
+                        .collect::<Vec<_>>(),
+                );
+            }
+            first_face += mesh.num_face_indices.len();
+        }
+        info!(
+            "Number of models: {}, vertices: {} (normals provided: {})",
+            models.len(),
+            base_vertices.len(),
+            has_normals
+        );
+        info!("Number of polygonal faces: {}", first_face);
+        info!("Number of triangular faces: {}", base_faces.len());
+        info!("Parsed goal mesh file in {:?}", start.elapsed());
+
+        if !degenerate_faces.is_empty() {
+            if strict {
+                return Err(GoalMeshError::DegenerateFaces(degenerate_faces));
+            }
+            warn!(
+                "Skipping {} degenerate (zero-area) faces of the .obj file: {:?}",
+                degenerate_faces.len(),
+                degenerate_faces
+            );
+        }
+
+        if base_faces.is_empty() {
+            return Err(if degenerate_faces.is_empty() {
+                GoalMeshError::Empty
+            } else {
+                GoalMeshError::AllFacesDegenerate(degenerate_faces.len())
+            });
+        }
+
+        // Flip any faces that are wound the opposite way from their neighbors (before building the
+        // half-edge mesh, which would reject them)
+        if fix_winding {
+            let (flips, non_orientable) = orient_polygons(&polygons, &base_vertices);
+            if non_orientable > 0 {
+                warn!(
+                    "{} pieces of the goal mesh are not orientable (e.g. a Möbius strip), so the winding orders of their faces can't be made consistent",
+                    non_orientable
+                );
+            }
+
+            // The triangles of each polygon are fanned around its first vertex, which stays first
+            for (face, &polygon) in base_faces.iter_mut().zip(face_polygons.iter()) {
+                if flips[polygon] {
+                    face.swap(1, 2);
+                }
+            }
+            for (polygon, &flip) in polygons.iter_mut().zip(flips.iter()) {
+                if flip {
+                    polygon[1..].reverse();
+                }
+            }
+            info!(
+                "Flipped {} faces of the .obj file to make their winding orders consistent",
+                flips.iter().filter(|&&flip| flip).count()
+            );
+        }
+
+        // Report the faces of the .obj file in topology errors, rather than the triangles they were
+        // split into
+        let to_obj_faces = |e: TopologyError| {
+            let to_polygon = |fid: &FaceIndex| FaceIndex(face_polygons[usize::from(*fid)]);
+            let e = match e {
+                TopologyError::BoundaryEdge { vertices, face } => TopologyError::BoundaryEdge {
+                    vertices,
+                    face: to_polygon(&face),
+                },
+                TopologyError::NonManifoldEdge { vertices, faces } => {
+                    let mut faces = faces.iter().map(to_polygon).collect::<Vec<_>>();
+                    faces.dedup();
+                    TopologyError::NonManifoldEdge { vertices, faces }
+                }
+                TopologyError::Disconnected { face } => TopologyError::Disconnected {
+                    face: to_polygon(&face),
+                },
+            };
+            GoalMeshError::Topology(e)
+        };
+
+        // Build the half-edge data structure (once the faces are known to fit into one) and make
+        // sure that the goal mesh can actually be unfolded
+        let half_edge_start = Instant::now();
+        HalfEdgeMesh::validate_faces(&base_faces).map_err(to_obj_faces)?;
+        let mut half_edge_mesh = HalfEdgeMesh::from_faces(&base_faces, &base_vertices)
+            .map_err(GoalMeshError::HalfEdgeMesh)?;
+        if has_normals {
+            for (vid, normal) in vertex_normals.iter().enumerate() {
+                // Normals that cancel out (or are missing) don't say anything about the orientation
+                // of the surface, so they are ignored
+                if normal.length() > 0.0 {
+                    half_edge_mesh
+                        .vertex_mut(VertexIndex(vid))
+                        .set_normal(normal.normalize());
+                }
+            }
+        }
+        // Separate solids are unfolded into separate nets, so the goal mesh doesn't have to be
+        // connected (but each of its pieces does have to be closed, unless open surfaces are
+        // allowed)
+        let validated = if allow_open {
+            half_edge_mesh.validate_open()
+        } else {
+            half_edge_mesh.validate()
+        };
+        let validated = match validated {
+            Err(TopologyError::Disconnected { .. }) => Ok(()),
+            result => result,
+        };
+        validated.map_err(to_obj_faces)?;
+        info!(
+            "Built half-edge mesh with {} faces in {:?}",
+            base_faces.len(),
+            half_edge_start.elapsed()
+        );
+
+        // Merge neighboring polygons that are coplanar: merged polygons are outlined by walking
+        // along the border of their triangles, while all of the other polygons keep their original
+        // outlines (and starting vertices)
+        let mut merged_polygons =
+            half_edge_mesh.merge_coplanar_faces(&face_polygons, coplanar_tolerance);
+
+        // Faces with different materials are never merged, so that every polygon keeps its color
+        let mut merged_colors = HashMap::new();
+        let mut mixed_colors = HashSet::new();
+        for (&polygon, &merged_polygon) in face_polygons.iter().zip(merged_polygons.iter()) {
+            let color = merged_colors
+                .entry(merged_polygon)
+                .or_insert(polygon_colors[polygon]);
+            if *color != polygon_colors[polygon] {
+                mixed_colors.insert(merged_polygon);
+            }
+        }
+        if !mixed_colors.is_empty() {
+            let mut numbers = HashMap::new();
+            merged_polygons = face_polygons
+                .iter()
+                .zip(merged_polygons.iter())
+                .map(|(&polygon, &merged_polygon)| {
+                    let key = if mixed_colors.contains(&merged_polygon) {
+                        (polygon, true)
+                    } else {
+                        (merged_polygon, false)
+                    };
+                    let next = numbers.len();
+                    *numbers.entry(key).or_insert(next)
+                })
+                .collect();
+        }
+        let merged_count = merged_polygons
+            .iter()
+            .max()
+            .map_or(0, |&polygon| polygon + 1);
+        if merged_count < polygons.len() {
+            let mut sources = vec![vec![]; merged_count];
+            for (&polygon, &merged_polygon) in face_polygons.iter().zip(merged_polygons.iter()) {
+                if !sources[merged_polygon].contains(&polygon) {
+                    sources[merged_polygon].push(polygon);
+                }
+            }
+            let mut merged_faces = vec![vec![]; merged_count];
+            for (fid, &merged_polygon) in merged_polygons.iter().enumerate() {
+                merged_faces[merged_polygon].push(FaceIndex(fid));
+            }
+
+            let merged = sources.iter().filter(|sources| sources.len() > 1);
+            info!(
+                "Merged {} coplanar faces into {} polygons",
+                merged.clone().map(|sources| sources.len()).sum::<usize>(),
+                merged.count()
+            );
+            polygons = sources
+                .iter()
+                .zip(merged_faces.iter())
+                .map(|(sources, faces)| match sources[..] {
+                    [polygon] => polygons[polygon].clone(),
+                    _ => half_edge_mesh
+                        .region_outline(faces)
+                        .expect("Merged polygon is not a disk - this should never happen"),
+                })
+                .collect();
+            obj_faces = sources
+                .iter()
+                .map(|sources| obj_faces[sources[0]])
+                .collect();
+            polygon_colors = sources
+                .iter()
+                .map(|sources| polygon_colors[sources[0]])
+                .collect();
+            face_polygons = merged_polygons;
+        }
+
+        let face_components = half_edge_mesh.connected_components();
+        let component_count = face_components
+            .iter()
+            .max()
+            .map_or(0, |&component| component + 1);
+        if component_count > 1 {
+            info!(
+                "The goal mesh consists of {} separate pieces, which are unfolded into separate nets",
+                component_count
+            );
+        }
+
+        let mut goal_mesh = GoalMesh {
+            half_edge_mesh,
+            polygons,
+            face_polygons,
+            obj_faces,
+            polygon_colors,
+            vertex_colors: base_colors,
+            face_components,
+            component_count,
+            face_neighbors: vec![],
+            steepest_edges: HashSet::new(),
+            strip: None,
+            tolerances,
+        };
+        goal_mesh.face_neighbors = goal_mesh.find_face_neighbors();
+        goal_mesh.steepest_edges = goal_mesh.find_steepest_edges().into_iter().collect();
+        goal_mesh.strip = goal_mesh.find_strip();
+        info!(
+            "Loaded goal mesh with {} faces in {:?}",
+            goal_mesh.face_count(),
+            start.elapsed()
+        );
+
+        Ok(goal_mesh)
+    }
+
+    /// Returns the number of faces of the goal mesh (i.e. the number of faces in the .obj file,
+    /// before triangulation, minus any that were skipped or merged).
+    pub fn face_count(&self) -> usize {
+        self.polygons.len()
+    }
+
+    /// Returns the index of the face of the .obj file that the specified face of the goal mesh was
+    /// loaded from (see `face_count()`).
+    pub fn obj_face(&self, face: usize) -> usize {
+        self.obj_faces[face]
+    }
+
+    /// Returns the IDs of the vertices of the specified face of the goal mesh, in CCW order.
+    pub fn face_vertices(&self, face: usize) -> &[VertexIndex] {
+        &self.polygons[face]
+    }
+
+    /// Returns the unit normal of the specified face of the goal mesh (i.e. the normal of its first
+    /// triangle, since all of its triangles are coplanar).
+    pub fn face_normal(&self, face: usize) -> Vec3 {
+        let triangle = self
+            .face_polygons
+            .iter()
+            .position(|&polygon| polygon == face)
+            .unwrap();
+        self.half_edge_mesh.face_normal(FaceIndex(triangle))
+    }
+
+    /// Returns the (SRGB) color of the specified face of the goal mesh based on the colors of its
+    /// vertices (see `from_obj()`), i.e. their average, which is taken in linear space so that
+    /// faces between very different colors don't come out too dark. Returns `None` if any of its
+    /// vertices doesn't have a color.
+    pub fn face_vertex_color(&self, face: usize) -> Option<Vec3> {
+        let colors = self.polygons[face]
+            .iter()
+            .map(|&vid| {
+                self.vertex_colors[usize::from(vid)].map(|color| {
+                    Vec3::new(
+                        srgb_to_linear(color.x()),
+                        srgb_to_linear(color.y()),
+                        srgb_to_linear(color.z()),
+                    )
+                })
+            })
+            .collect::<Option<Vec<_>>>()?;
+        let average =
+            colors.iter().fold(Vec3::zero(), |sum, color| sum + *color) / colors.len() as f32;
+        Some(Vec3::new(
+            linear_to_srgb(average.x()),
+            linear_to_srgb(average.y()),
+            linear_to_srgb(average.z()),
+        ))
+    }
+
+    /// Returns the face on the other side of each side of the specified face of the goal mesh, in
+    /// the same order as `face_vertices()` (i.e. the first entry lies across the side between the
+    /// first two vertices), or `None` for sides along the border of the goal mesh.
+    pub fn adjacent_faces(&self, face: usize) -> Vec<Option<usize>> {
+        let vertices = &self.polygons[face];
+        vertices
+            .iter()
+            .zip(vertices.iter().cycle().skip(1))
+            .map(|(&a, &b)| {
+                let eid = self.half_edge_mesh.find_half_edge_between_vertices(a, b)?;
+                let pair = self.half_edge_mesh.half_edge(eid).pair();
+                self.half_edge_mesh
+                    .half_edge(pair)
+                    .face()
+                    .map(|fid| self.face_polygons[usize::from(fid)])
+            })
+            .collect()
+    }
+
+    /// Returns the half-edge that runs from vertex `a` to vertex `b` of the goal mesh (numbered like
+    /// `face_vertices()`), or an error if they aren't joined by an edge. The diagonals that split
+    /// faces into triangles don't count, since they aren't part of the net.
+    pub fn find_edge(&self, a: usize, b: usize) -> Result<HalfEdgeIndex, GoalMeshError> {
+        let vertices = self.half_edge_mesh.vertices().len();
+        if a >= vertices || b >= vertices {
+            return Err(GoalMeshError::InvalidEdge { a, b });
+        }
+        match self
+            .half_edge_mesh
+            .find_half_edge_between_vertices(VertexIndex(a), VertexIndex(b))
+        {
+            Some(eid) if !self.is_internal_edge(eid) => Ok(eid),
+            _ => Err(GoalMeshError::InvalidEdge { a, b }),
+        }
+    }
+
+    /// Returns the corners of the (axis-aligned) bounding box of the goal mesh, i.e. the minimum and
+    /// maximum coordinates of its vertices, in the units of the .obj file.
+    pub fn bounding_box(&self) -> (Vec3, Vec3) {
+        find_bounding_box(&self.vertex_coordinates())
+    }
+
+    /// Returns the dimensions of the (axis-aligned) bounding box of the goal mesh, in the units of
+    /// the .obj file.
+    pub fn size(&self) -> Vec3 {
+        let (min, max) = self.bounding_box();
+        max - min
+    }
+
+    /// Returns the centroid (i.e. the average) of the vertices of the goal mesh, in the units of the
+    /// .obj file. This isn't the center of its bounding box, unless the vertices are spread evenly.
+    pub fn centroid(&self) -> Vec3 {
+        find_centroid(&self.vertex_coordinates())
+    }
+
+    /// Finds all pairs of faces of the goal mesh that intersect each other in 3D, which usually
+    /// means that the input is broken (even if it is a closed manifold): the net of a
+    /// self-intersecting goal mesh can't be folded back up. Each pair `(i, j)` holds the indices of
+    /// the two faces (numbered like `face_count()`), where `i < j`, and the pairs are sorted.
+    ///
+    /// Unlike the overlaps of a net, this works on the original geometry of the goal mesh, and
+    /// since it tests every pair of (nearby) triangles, it can take a long time for large meshes.
+    pub fn find_self_intersections(&self) -> Vec<(usize, usize)> {
+        let triangles = self
+            .half_edge_mesh
+            .face_refs()
+            .map(|face| {
+                let mut vertices = face
+                    .vertices()
+                    .map(|vid| *self.half_edge_mesh.vertex(vid).coordinates());
+                [
+                    vertices.next().unwrap(),
+                    vertices.next().unwrap(),
+                    vertices.next().unwrap(),
+                ]
+            })
+            .collect::<Vec<_>>();
+
+        // The triangles of a single face never intersect each other, and faces that are split into
+        // several triangles can intersect another face more than once
+        let mut intersections = find_intersecting_triangles_3d(&triangles)
+            .into_iter()
+            .map(|(a, b)| (self.face_polygons[a], self.face_polygons[b]))
+            .filter(|(a, b)| a != b)
+            .map(|(a, b)| (a.min(b), a.max(b)))
+            .collect::<Vec<_>>();
+        intersections.sort();
+        intersections.dedup();
+        intersections
+    }
+
+    /// Returns the coordinates of every vertex of the half-edge mesh.
+    fn vertex_coordinates(&self) -> Vec<Vec3> {
+        self.half_edge_mesh
+            .vertices()
+            .iter()
+            .map(|vertex| *vertex.coordinates())
+            .collect()
+    }
+
+    /// Returns the (triangular) face of the half-edge mesh that the net should be unfolded from, given
+    /// the index of one of the faces in the .obj file. If the face is split into several triangles,
+    /// the first one is used. Returns an error if the face doesn't exist.
+    fn reference_triangle(&self, face: usize) -> Result<FaceIndex, GoalMeshError> {
+        if face >= self.polygons.len() {
+            return Err(GoalMeshError::InvalidReferenceFace {
+                face,
+                faces: self.polygons.len(),
+            });
+        }
+
+        // Every polygon has at least one triangle, so this should never fail
+        let triangle = self
+            .face_polygons
+            .iter()
+            .position(|&polygon| polygon == face)
+            .unwrap();
+
+        Ok(triangle.into())
+    }
+
+    /// Unfolds the goal mesh starting at each one of its faces in turn, and returns the number of
+    /// pairs of overlapping triangles in each of the resulting nets (indexed by reference face).
+    /// Each unfolding only reads the goal mesh, so the root faces are spread across rayon's thread
+    /// pool (the result doesn't depend on the number of threads). Since this can still take a long
+    /// time for large goal meshes, progress is logged every `ROOT_SCAN_PROGRESS_INTERVAL` root
+    /// faces.
+    pub fn count_overlaps_by_reference_face(&self, strategy: UnfoldStrategy) -> Vec<usize> {
+        let start = Instant::now();
+        let face_count = self.face_count();
+
+        // The root faces are scanned in batches, so that the progress can be logged in between
+        let mut overlaps = Vec::with_capacity(face_count);
+        while overlaps.len() < face_count {
+            let batch =
+                overlaps.len()..(overlaps.len() + ROOT_SCAN_PROGRESS_INTERVAL).min(face_count);
+            let counts = batch
+                .into_par_iter()
+                .map(|face| self.unfold(face, strategy).unwrap().overlaps.len())
+                .collect::<Vec<_>>();
+            overlaps.extend(counts);
+            info!(
+                "Unfolded {} of {} root faces in {:?}",
+                overlaps.len(),
+                face_count,
+                start.elapsed()
+            );
+        }
+        debug!(
+            "Scanned {} root faces on {} threads",
+            face_count,
+            rayon::current_num_threads()
+        );
+        overlaps
+    }
+
+    /// Unfolds the goal mesh starting at each one of its faces in turn, and returns the indices of
+    /// the faces that result in nets without any overlaps. Different reference faces can produce
+    /// very different nets, so this can be used to find a valid net when the default one overlaps.
+    pub fn find_overlap_free_reference_faces(&self, strategy: UnfoldStrategy) -> Vec<usize> {
+        self.count_overlaps_by_reference_face(strategy)
+            .iter()
+            .enumerate()
+            .filter(|(_, &overlaps)| overlaps == 0)
+            .map(|(face, _)| face)
+            .collect()
+    }
+
+    /// Returns an immutable reference to the half-edge data structure of the goal mesh.
+    pub fn half_edge_mesh(&self) -> &HalfEdgeMesh {
+        &self.half_edge_mesh
+    }
+
+    /// Returns the tolerances that the goal mesh was loaded with, which are used to test its nets
+    /// for overlaps and to classify their folds, as well as to find its reflex edges (see
+    /// `HalfEdgeMesh::reflex_edges()`).
+    pub fn tolerances(&self) -> &Tolerances {
+        &self.tolerances
+    }
+
+    /// A helper function that finds the neighbors of every (triangular) face of the half-edge mesh,
+    /// along with the half-edge that is shared with each neighbor. Edges along the border of the
+    /// mesh don't have a face on the other side, so they are skipped.
+    fn find_face_neighbors(&self) -> Vec<Vec<(FaceIndex, HalfEdgeIndex)>> {
+        self.half_edge_mesh
+            .face_id_iter()
+            .map(|fid| {
+                self.half_edge_mesh
+                    .adjacent_half_edges_to_face(fid)
+                    .filter_map(|shared_edge| {
+                        // Get edge -> pair -> face, which may be `None` (i.e. the "infinite" face
+                        // outside of the mesh)
+                        self.half_edge_mesh
+                            .half_edge(self.half_edge_mesh.half_edge(shared_edge).pair())
+                            .face()
+                            .map(|neighbor| (neighbor, shared_edge))
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect()
+    }
+
+    fn compute_spanning_tree(
+        &self,
+        reference_face: FaceIndex,
+        strategy: UnfoldStrategy,
+    ) -> SpanningTree {
+        info!(
+            "Starting spanning tree computation with strategy: {:?}",
+            strategy
+        );
+
+        // With the steepest edge heuristic, the cut boundary is chosen up front, so faces may
+        // only be joined across the remaining edges
+        let face_neighbors = if strategy == UnfoldStrategy::SteepestEdge {
+            self.face_neighbors
+                .iter()
+                .map(|neighbors| {
+                    neighbors
+                        .iter()
+                        .filter(|(_, shared_edge)| !self.steepest_edges.contains(shared_edge))
+                        .cloned()
+                        .collect::<Vec<_>>()
+                })
+                .collect::<Vec<_>>()
+        } else {
+            self.face_neighbors.clone()
+        };
+
+        let strategy = match (strategy, &self.strip) {
+            (UnfoldStrategy::Strip, None) => {
+                warn!("The goal mesh doesn't have a loop of faces around an axis that can be unrolled into a strip, so it is unfolded breadth-first instead");
+                UnfoldStrategy::BreadthFirst
+            }
+            _ => strategy,
+        };
+
+        // The band of the strip strategy is only entered once (from whichever face reaches it
+        // first), so that it is only cut open across a single edge
+        let mut strip_entered = false;
+
+        // Now, construct the spanning tree
+        info!(
+            "Starting spanning tree calculation at face with ID: {:?}",
+            reference_face
+        );
+
+        // A dictionary that maps each face to the face it "comes from" in the spanning tree, along with
+        // the edge that is shared between the pair of faces
+        let mut came_from = HashMap::new();
+
+        // Faces in different pieces of the goal mesh can't reach one another, so a separate tree is
+        // grown from the first face of each piece that the reference face doesn't belong to
+        let mut roots = vec![NO_FACE; self.component_count];
+        for root in std::iter::once(reference_face).chain(self.half_edge_mesh.face_id_iter()) {
+            if came_from.contains_key(&root) {
+                continue;
+            }
+            roots[self.face_components[usize::from(root)]] = root;
+            came_from.insert(root, (NO_FACE, NO_HALF_EDGE));
+
+            match strategy {
+                UnfoldStrategy::BreadthFirst | UnfoldStrategy::SteepestEdge => {
+                    let mut queue = VecDeque::new();
+                    queue.push_back(root);
+                    self.visit_polygon(root, &face_neighbors, &mut came_from, &mut queue);
+
+                    while let Some(curr_face) = queue.pop_front() {
+                        for (neighbor, shared_edge) in face_neighbors[usize::from(curr_face)].iter()
+                        {
+                            // Every face that has been queued is already part of the spanning tree
+                            if !came_from.contains_key(neighbor) {
+                                // Update the spanning tree
+                                came_from.insert(*neighbor, (curr_face, *shared_edge));
+                                queue.push_back(*neighbor);
+                                self.visit_polygon(
+                                    *neighbor,
+                                    &face_neighbors,
+                                    &mut came_from,
+                                    &mut queue,
+                                );
+                            }
+                        }
+                    }
+                }
+                UnfoldStrategy::DepthFirst => {
+                    let mut stack = VecDeque::new();
+                    stack.push_back(root);
+                    self.visit_polygon(root, &face_neighbors, &mut came_from, &mut stack);
+
+                    // Keep walking away from the face on top of the stack until we hit a dead end,
+                    // then backtrack
+                    while let Some(&curr_face) = stack.back() {
+                        let next = face_neighbors[usize::from(curr_face)]
+                            .iter()
+                            .find(|(neighbor, _)| !came_from.contains_key(neighbor))
+                            .cloned();
+
+                        match next {
+                            Some((neighbor, shared_edge)) => {
+                                // Update the spanning tree
+                                came_from.insert(neighbor, (curr_face, shared_edge));
+                                stack.push_back(neighbor);
+                                self.visit_polygon(
+                                    neighbor,
+                                    &face_neighbors,
+                                    &mut came_from,
+                                    &mut stack,
+                                );
+                            }
+                            None => {
+                                stack.pop_back();
+                            }
+                        }
+                    }
+                }
+                UnfoldStrategy::MinWeightSpanningTree => {
+                    // Prim's algorithm, where the weight of each edge is its negated length: the
+                    // longest edge between the tree and the rest of the goal mesh is always crossed
+                    // next. Lengths are never negative, so their bits sort like the lengths
+                    // themselves, and ties are broken in the order that the edges were found (which
+                    // keeps the tree deterministic)
+                    let mut heap = BinaryHeap::new();
+                    let mut found = 0;
+                    let mut added = VecDeque::new();
+                    added.push_back(root);
+                    self.visit_polygon(root, &face_neighbors, &mut came_from, &mut added);
+                    loop {
+                        for curr_face in added.drain(..) {
+                            for &(neighbor, shared_edge) in
+                                face_neighbors[usize::from(curr_face)].iter()
+                            {
+                                if !came_from.contains_key(&neighbor) {
+                                    let length =
+                                        self.half_edge_mesh.edge_vector(shared_edge).length();
+                                    heap.push((
+                                        length.to_bits(),
+                                        Reverse(found),
+                                        curr_face,
+                                        neighbor,
+                                        shared_edge,
+                                    ));
+                                    found += 1;
+                                }
+                            }
+                        }
+
+                        // Faces can be reached across several edges, so some of the edges in the
+                        // heap may be stale by the time that they are popped
+                        let next = loop {
+                            match heap.pop() {
+                                Some((_, _, curr_face, neighbor, shared_edge))
+                                    if !came_from.contains_key(&neighbor) =>
+                                {
+                                    break Some((curr_face, neighbor, shared_edge))
+                                }
+                                Some(_) => continue,
+                                None => break None,
+                            }
+                        };
+                        match next {
+                            Some((curr_face, neighbor, shared_edge)) => {
+                                came_from.insert(neighbor, (curr_face, shared_edge));
+                                added.push_back(neighbor);
+                                self.visit_polygon(
+                                    neighbor,
+                                    &face_neighbors,
+                                    &mut came_from,
+                                    &mut added,
+                                );
+                            }
+                            None => break,
+                        }
+                    }
+                }
+                UnfoldStrategy::Strip => {
+                    // A breadth-first traversal, except that the band is grown across the edges
+                    // between its sides before anything else, which unrolls it into a straight
+                    // band on both sides of the face that it was entered from. The other faces
+                    // are attached afterwards, so the caps end up next to that face
+                    let strip = self.strip.as_ref().unwrap();
+                    let in_strip =
+                        |fid: FaceIndex| strip.contains(&self.face_polygons[usize::from(fid)]);
+                    let mut band = VecDeque::new();
+                    let mut rest = VecDeque::new();
+                    if in_strip(root) {
+                        strip_entered = true;
+                        band.push_back(root);
+                        self.visit_polygon(root, &face_neighbors, &mut came_from, &mut band);
+                    } else {
+                        rest.push_back(root);
+                        self.visit_polygon(root, &face_neighbors, &mut came_from, &mut rest);
+                    }
+
+                    while let Some(curr_face) = band.pop_front().or_else(|| rest.pop_front()) {
+                        for &(neighbor, shared_edge) in
+                            face_neighbors[usize::from(curr_face)].iter()
+                        {
+                            if came_from.contains_key(&neighbor) {
+                                continue;
+                            }
+
+                            let queue = if in_strip(neighbor) {
+                                if strip_entered && !in_strip(curr_face) {
+                                    continue;
+                                }
+                                strip_entered = true;
+                                &mut band
+                            } else {
+                                &mut rest
+                            };
+                            came_from.insert(neighbor, (curr_face, shared_edge));
+                            queue.push_back(neighbor);
+                            self.visit_polygon(neighbor, &face_neighbors, &mut came_from, queue);
+                        }
+                    }
+                }
+            }
+        }
+
+        debug_assert_eq!(came_from.len(), self.half_edge_mesh.faces().len());
+
+        // Log which face each face of the goal mesh is unfolded from (ignoring the triangles that
+        // belong to the same polygon), sorted by face
+        if log_enabled!(Level::Debug) {
+            for &root in roots.iter() {
+                debug!(
+                    "Face {} is the root of its piece of the goal mesh",
+                    self.face_polygons[usize::from(root)]
+                );
+            }
+            let mut joins = came_from
+                .iter()
+                .filter(|(_, (parent, _))| *parent != NO_FACE)
+                .map(|(&fid, (parent, _))| {
+                    (
+                        self.face_polygons[usize::from(fid)],
+                        self.face_polygons[usize::from(*parent)],
+                    )
+                })
+                .filter(|(polygon, parent)| polygon != parent)
+                .collect::<Vec<_>>();
+            joins.sort_unstable();
+            for (polygon, parent) in joins {
+                debug!("Face {} is unfolded from face {}", polygon, parent);
+            }
+        }
+
+        SpanningTree::new(reference_face, roots, came_from)
+    }
+
+    /// Builds a random spanning tree (or forest, see `compute_spanning_tree()`) of the faces of the
+    /// goal mesh, starting at the specified reference face: the tree is grown by repeatedly
+    /// crossing a random edge between a face that is part of the tree and one that isn't, so
+    /// every call produces a different net.
+    fn compute_random_spanning_tree(
+        &self,
+        reference_face: FaceIndex,
+        rng: &mut StdRng,
+    ) -> SpanningTree {
+        let mut came_from = HashMap::new();
+        let mut roots = vec![NO_FACE; self.component_count];
+        for root in std::iter::once(reference_face).chain(self.half_edge_mesh.face_id_iter()) {
+            if came_from.contains_key(&root) {
+                continue;
+            }
+            roots[self.face_components[usize::from(root)]] = root;
+            came_from.insert(root, (NO_FACE, NO_HALF_EDGE));
+
+            // The edges between the faces that were just added to the tree (i.e. all of the
+            // triangles of a polygon) and their neighbors are candidates for the next edge
+            let mut added = VecDeque::new();
+            added.push_back(root);
+            self.visit_polygon(root, &self.face_neighbors, &mut came_from, &mut added);
+            let mut candidates = vec![];
+            loop {
+                for curr_face in added.drain(..) {
+                    for &(neighbor, shared_edge) in
+                        self.face_neighbors[usize::from(curr_face)].iter()
+                    {
+                        if !came_from.contains_key(&neighbor) {
+                            candidates.push((curr_face, neighbor, shared_edge));
+                        }
+                    }
+                }
+
+                // Faces can be reached across several edges, so some of the candidates may be
+                // stale by the time that they are picked
+                let next = loop {
+                    if candidates.is_empty() {
+                        break None;
+                    }
+                    let candidate = candidates.swap_remove(rng.gen_range(0, candidates.len()));
+                    if !came_from.contains_key(&candidate.1) {
+                        break Some(candidate);
+                    }
+                };
+                match next {
+                    Some((curr_face, neighbor, shared_edge)) => {
+                        came_from.insert(neighbor, (curr_face, shared_edge));
+                        added.push_back(neighbor);
+                        self.visit_polygon(
+                            neighbor,
+                            &self.face_neighbors,
+                            &mut came_from,
+                            &mut added,
+                        );
+                    }
+                    None => break,
+                }
+            }
+        }
+
+        debug_assert_eq!(came_from.len(), self.half_edge_mesh.faces().len());
+
+        SpanningTree::new(reference_face, roots, came_from)
+    }
+
+    /// A helper function that finds the cut boundary used by the steepest edge heuristic: for a fixed
+    /// "cut direction," every vertex cuts the edge that leaves it at the steepest upward angle. Vertices
+    /// without any upward edges (such as the top-most vertex) don't cut anything. Since every cut edge
+    /// goes "up," the cuts can never form a loop, so the rest of the mesh always stays connected.
+    /// On open surfaces, vertices along the border don't cut anything either: the border is already
+    /// cut, so every chain of cuts that runs into it ends there (rather than joining up with another
+    /// chain of cuts, which would cut off the faces between them).
+    ///
+    /// Edges that are internal to a polygon are never cut. The returned list contains both half-edges
+    /// of every cut edge.
+    fn find_steepest_edges(&self) -> Vec<HalfEdgeIndex> {
+        // A slightly skewed direction, so that axis-aligned meshes don't result in ties
+        let cut_direction = Vec3::new(0.1, 1.0, 0.2).normalize();
+        let border = self
+            .half_edge_mesh
+            .boundary_vertices()
+            .into_iter()
+            .collect::<HashSet<_>>();
+
+        let mut cut_edges = vec![];
+        for vid in self.half_edge_mesh.vertex_id_iter() {
+            if border.contains(&vid) {
+                continue;
+            }
+            let steepest = self
+                .half_edge_mesh
+                .adjacent_half_edges_to_vertex(vid)
+                .filter(
+                    |&eid| match self.half_edge_mesh.adjacent_faces_to_half_edge(eid) {
+                        [Some(_), Some(_)] => !self.is_internal_edge(eid),
+                        _ => false,
+                    },
+                )
+                .map(|eid| {
+                    let slope = self
+                        .half_edge_mesh
+                        .edge_vector(eid)
+                        .normalize()
+                        .dot(cut_direction);
+                    (eid, slope)
+                })
+                .filter(|&(_, slope)| slope > 0.0)
+                .max_by(|(_, a), (_, b)| a.partial_cmp(b).unwrap());
+
+            if let Some((eid, _)) = steepest {
+                cut_edges.push(eid);
+                cut_edges.push(self.half_edge_mesh.half_edge(eid).pair());
+            }
+        }
+
+        cut_edges
+    }
+
+    /// A helper function that finds the band of polygons unrolled by the strip strategy: the sides of
+    /// a prism-like goal mesh, whose normals are all perpendicular to a common axis, and each of
+    /// which is joined to exactly two of the others, so that together they form a single loop
+    /// around the axis. Since the edges between the sides of a prism are parallel to its axis, the
+    /// axes that are tried are the most common directions of the edges between polygons. Returns
+    /// `None` if none of them results in such a loop.
+    fn find_strip(&self) -> Option<HashSet<usize>> {
+        // The number of axes that are tried, and the precision that edge directions are rounded to
+        // (so that nearly parallel edges are counted together)
+        const AXIS_CANDIDATES: usize = 3;
+        const DIRECTION_PRECISION: f32 = 1.0e3;
+
+        let mut directions = HashMap::new();
+        for eid in self.half_edge_mesh.half_edge_id_iter() {
+            let pair = self.half_edge_mesh.half_edge(eid).pair();
+            let between_polygons = match self.half_edge_mesh.adjacent_faces_to_half_edge(eid) {
+                [Some(_), Some(_)] => !self.is_internal_edge(eid),
+                _ => false,
+            };
+            let direction = self.half_edge_mesh.edge_vector(eid);
+            if eid > pair || !between_polygons || direction.length() == 0.0 {
+                continue;
+            }
+
+            // Edges that point in opposite directions are parallel, too
+            let mut direction = direction.normalize();
+            let mut key = [direction.x(), direction.y(), direction.z()]
+                .map(|coordinate| (coordinate * DIRECTION_PRECISION).round() as i32);
+            let first = key.iter().find(|&&coordinate| coordinate != 0);
+            if first.copied().unwrap_or(0) < 0 {
+                direction = -direction;
+                key = key.map(|coordinate| -coordinate);
+            }
+            directions.entry(key).or_insert((0, direction)).0 += 1;
+        }
+
+        // Ties are broken by the rounded directions themselves, so that the result is deterministic
+        let mut candidates = directions.into_iter().collect::<Vec<_>>();
+        candidates
+            .sort_by(|(a, (a_count, _)), (b, (b_count, _))| b_count.cmp(a_count).then(a.cmp(b)));
+        let strip = candidates
+            .iter()
+            .take(AXIS_CANDIDATES)
+            .find_map(|&(_, (_, axis))| self.find_strip_around(axis))?;
+        info!(
+            "Found a loop of {} faces that can be unrolled into a strip",
+            strip.len()
+        );
+
+        Some(strip)
+    }
+
+    /// A helper function for `find_strip()` that returns the polygons whose normals are
+    /// perpendicular to the specified axis, if they form a single loop (and `None` otherwise).
+    fn find_strip_around(&self, axis: Vec3) -> Option<HashSet<usize>> {
+        const SIDE_TOLERANCE: f32 = 1.0e-3;
+
+        // Every triangle of a side has to be parallel to the axis (which degenerate triangles,
+        // whose normals are NaN, never are)
+        let mut sides = vec![true; self.polygons.len()];
+        for fid in self.half_edge_mesh.face_id_iter() {
+            let parallel = self.half_edge_mesh.face_normal(fid).dot(axis).abs() <= SIDE_TOLERANCE;
+            if !parallel {
+                sides[self.face_polygons[usize::from(fid)]] = false;
+            }
+        }
+
+        let mut neighbors = vec![HashSet::new(); self.polygons.len()];
+        for fid in self.half_edge_mesh.face_id_iter() {
+            let polygon = self.face_polygons[usize::from(fid)];
+            for &(neighbor, _) in self.face_neighbors[usize::from(fid)].iter() {
+                let other = self.face_polygons[usize::from(neighbor)];
+                if sides[polygon] && sides[other] && other != polygon {
+                    neighbors[polygon].insert(other);
+                }
+            }
+        }
+
+        let band = (0..self.polygons.len())
+            .filter(|&polygon| sides[polygon])
+            .collect::<HashSet<_>>();
+        if band.len() < 3 || band.iter().any(|&polygon| neighbors[polygon].len() != 2) {
+            return None;
+        }
+
+        // Walk around the loop (which is a cycle, since every side has exactly two neighbors) to
+        // make sure that it passes through every side, rather than there being several loops
+        let start = *band.iter().min().unwrap();
+        let (mut previous, mut current, mut length) = (start, start, 0);
+        loop {
+            let next = *neighbors[current]
+                .iter()
+                .filter(|&&next| next != previous)
+                .min()
+                .unwrap();
+            previous = current;
+            current = next;
+            length += 1;
+            if current == start {
+                break;
+            }
+        }
+
+        if length == band.len() {
+            Some(band)
+        } else {
+            None
+        }
+    }
+
+    /// A helper function that adds all of the other triangles that belong to the same polygon as
+    /// `fid` (i.e. that were triangulated from the same face of the .obj file) to the spanning tree,
+    /// by walking across the polygon's internal edges. This guarantees that internal edges are always
+    /// crossed by the spanning tree, so polygons are never cut apart in the net.
+    fn visit_polygon(
+        &self,
+        fid: FaceIndex,
+        face_neighbors: &[Vec<(FaceIndex, HalfEdgeIndex)>],
+        came_from: &mut HashMap<FaceIndex, (FaceIndex, HalfEdgeIndex)>,
+        queue: &mut VecDeque<FaceIndex>,
+    ) {
+        let mut stack = vec![fid];
+
+        while let Some(curr_face) = stack.pop() {
+            for (neighbor, shared_edge) in face_neighbors[usize::from(curr_face)].iter() {
+                if self.face_polygons[usize::from(*neighbor)]
+                    == self.face_polygons[usize::from(curr_face)]
+                    && !came_from.contains_key(neighbor)
+                {
+                    came_from.insert(*neighbor, (curr_face, *shared_edge));
+                    queue.push_back(*neighbor);
+                    stack.push(*neighbor);
+                }
+            }
+        }
+    }
+
+    /// Returns `true` if the specified half-edge lies between two triangles that belong to the same
+    /// polygon and `false` otherwise.
+    fn is_internal_edge(&self, eid: HalfEdgeIndex) -> bool {
+        match self.half_edge_mesh.adjacent_faces_to_half_edge(eid) {
+            [Some(a), Some(b)] => {
+                self.face_polygons[usize::from(a)] == self.face_polygons[usize::from(b)]
+            }
+            _ => false,
+        }
+    }
+
+    /// A helper function for finding the index of a vertex in the "global" array (i.e. m1, m2, or m3), given
+    /// its face ID and vertex ID (from the half-edge data structure). This is necessary because the half-edge
+    /// mesh essentially becomes "unwelded" during the unfolding process. In other words, we often end up with
+    /// more vertices in the unfolded mesh than we started with. So, while we are calculating m1, m2, and m3, we
+    /// need some way to find where a particular vertex belonging to a particular face lies in one or more of
+    /// those arrays.
+    ///
+    /// We assume that m1, m2, and m3 are constructed in such a way that faces are stored sequentially in the
+    /// same order that they appear in the half-edge data structure (i.e. as if we called `.face_id_iter()`).
+    /// Additionally, vertices are stored in the same order as the "vertex loop" iterator returned by
+    /// `.adjacent_vertices_to_face()`.
+    fn get_global_vertex_index(&self, fid: FaceIndex, vid: VertexIndex) -> usize {
+        let index_in_face = self
+            .half_edge_mesh
+            .adjacent_vertices_to_face(fid)
+            .position(|adjacent| adjacent == vid)
+            .expect("The specified vertex ID is not part of this face: this should never happen");
+
+        // Assume triangular faces
+        usize::from(fid) * 3 + index_in_face
+    }
+
+    /// A helper function for finding the properly oriented "version" of a half-edge belonging to a particular face.
+    /// The specified half-edge might technically be adjacent to the specified face, but with the wrong orientation
+    /// (i.e. we really want edge -> pair, so that the winding order is correct). This function assumes that the
+    /// specified half-edge or its pair is, in fact, adjacent to the specified face. Otherwise, an error will be thrown.
+    fn get_aligned_half_edge(&self, fid: FaceIndex, eid: HalfEdgeIndex) -> HalfEdgeIndex {
+        let mut oriented_edge = eid;
+
+        if !self.half_edge_mesh.face_contains_half_edge(fid, eid) {
+            oriented_edge = self.half_edge_mesh.half_edge(oriented_edge).pair();
+            debug_assert!(self
+                .half_edge_mesh
+                .face_contains_half_edge(fid, oriented_edge));
+        }
+
+        oriented_edge
+    }
+
+    /// Determines the incoming and outgoing edges along the unfolding path connecting
+    /// the target face to the reference face. Additionally, we must specify the
+    /// index of the face that comes *after* the target face along the desired
+    /// unfolding path (this is `towards_face`).
+    ///
+    /// This is necessary because the target face may be part of multiple unfolding
+    /// paths for different leaf faces. In this scenario, the outgoing edge depends
+    /// on which of these paths we are considering.
+    fn get_incoming_outgoing_edges(
+        &self,
+        tree: &SpanningTree,
+        target_face: FaceIndex,
+        towards_face: FaceIndex,
+    ) -> (HalfEdgeIndex, Option<HalfEdgeIndex>) {
+        // Every face has an incoming edge - for the reference face (or the root of any other piece
+        // of the goal mesh), we can just choose one of its edges arbitrarily
+        let mut incoming = if tree.roots.contains(&target_face) {
+            let reference_edge = self
+                .half_edge_mesh
+                .adjacent_half_edges_to_face(target_face)
+                .collect::<Vec<_>>()[0];
+            reference_edge
+        } else {
+            // The target face's incoming edge is simply the shared edge between the target face and the
+            // face that it came from in the spanning tree
+            tree.came_from[&target_face].1
+        };
+
+        // Find the outgoing edge, i.e. the shared edge between the target face and `towards_face`,
+        // if the latter came from the target face in the spanning tree (so the "leaf" faces at the
+        // end of the branches of the spanning tree never have an outgoing edge)
+        let mut outgoing = match tree.came_from.get(&towards_face) {
+            Some(&(node_from, shared_edge)) if node_from == target_face => Some(shared_edge),
+            _ => None,
+        };
+
+        // If the incoming edge isn't part of this face, that means that it isn't oriented correctly
+        // (i.e. it is CW instead of CCW) - flip it here by grabbing its pair half-edge instead
+        incoming = self.get_aligned_half_edge(target_face, incoming);
+        debug_assert!(self
+            .half_edge_mesh
+            .face_contains_half_edge(target_face, incoming));
+
+        // Same as above but for the outgoing edge
+        if let Some(maybe_outgoing) = outgoing {
+            outgoing = Some(self.get_aligned_half_edge(target_face, maybe_outgoing));
+            debug_assert!(self
+                .half_edge_mesh
+                .face_contains_half_edge(target_face, outgoing.unwrap()));
+        }
+
+        (incoming, outgoing)
+    }
+
+    /// Returns the indices of the faces along the path from the reference face (or the root of the
+    /// target face's piece of the goal mesh) to the target face in the spanning tree. Note that the indices will be
+    /// ordered in such a way that the target face is the first entry and the
+    /// reference face is the last entry.
+    fn get_unfolding_path_to(
+        &self,
+        tree: &SpanningTree,
+        target_face: FaceIndex,
+    ) -> (Vec<FaceIndex>, Vec<HalfEdgeIndex>) {
+        let mut faces_along_path = vec![target_face];
+        let mut edges_along_path = vec![];
+
+        let mut curr = target_face;
+
+        while !tree.roots.contains(&curr) {
+            let (prev, shared_edge) = tree.came_from[&curr];
+            faces_along_path.push(prev);
+            edges_along_path.push(shared_edge);
+            curr = prev;
+        }
+
+        // The path will be ordered such that the target face is first and the reference face is last -
+        // we want the opposite, so we reverse it below
+        faces_along_path.reverse();
+        edges_along_path.reverse();
+
+        (faces_along_path, edges_along_path)
+    }
+
+    /// Unfolds the goal mesh into a net around the specified reference face (the index of one of the
+    /// faces in the .obj file, starting at 0), using the specified strategy to build the spanning
+    /// tree of its faces. The goal mesh isn't modified, so it can be unfolded again (e.g. from a
+    /// different reference face) without loading it from scratch. Returns an error if the reference
+    /// face doesn't exist.
+    pub fn unfold(
+        &self,
+        reference_face: usize,
+        strategy: UnfoldStrategy,
+    ) -> Result<UnfoldResult, GoalMeshError> {
+        let reference_triangle = self.reference_triangle(reference_face)?;
+        let start = Instant::now();
+        let tree = self.compute_spanning_tree(reference_triangle, strategy);
+        Ok(self.unfold_along(&tree, start))
+    }
+
+    /// Searches for a net without overlaps: the goal mesh is unfolded around the specified
+    /// reference face with the specified strategy first, and then with up to `attempts` random
+    /// spanning trees (based on `seed`), until a net without overlaps is found. Otherwise, the net
+    /// with the fewest pairs of overlapping faces is returned, where ties are broken by the area of
+    /// its (minimum-area) bounding box, since smaller nets are easier to print. If `max_island_size`
+    /// is larger than 0, every spanning tree is split into islands that are at most that large (see
+    /// `split_into_islands()`) before it is unfolded. Returns an error if the reference face doesn't
+    /// exist.
+    pub fn unfold_best(
+        &self,
+        reference_face: usize,
+        strategy: UnfoldStrategy,
+        attempts: usize,
+        seed: u64,
+        max_island_size: f32,
+    ) -> Result<UnfoldResult, GoalMeshError> {
+        let score = |net: &UnfoldResult| {
+            let (_, width, height) = find_oriented_bounding_box(&net.positions);
+            (net.overlaps.len(), width * height)
+        };
+
+        let reference_triangle = self.reference_triangle(reference_face)?;
+        let start = Instant::now();
+        let tree = self.compute_spanning_tree(reference_triangle, strategy);
+        let mut best = self.unfold_along(&self.split_into_islands(tree, max_island_size), start);
+        if attempts == 0 {
+            return Ok(best);
+        }
+        let mut best_score = score(&best);
+        let mut rng = StdRng::seed_from_u64(seed);
+        for attempt in 0..attempts {
+            if best_score.0 == 0 {
+                break;
+            }
+
+            let start = Instant::now();
+            let tree = self.compute_random_spanning_tree(reference_triangle, &mut rng);
+            let net = self.unfold_along(&self.split_into_islands(tree, max_island_size), start);
+            let net_score = score(&net);
+            if net_score.0 < best_score.0
+                || (net_score.0 == best_score.0 && net_score.1 < best_score.1)
+            {
+                info!(
+                    "Found a net with {} pairs of overlapping faces after {} random attempts",
+                    net_score.0,
+                    attempt + 1
+                );
+                best = net;
+                best_score = net_score;
+            }
+        }
+
+        if best_score.0 > 0 {
+            warn!(
+                "Every net that was tried overlaps (the best one has {} pairs of overlapping faces)",
+                best_score.0
+            );
+        }
+        Ok(best)
+    }
+
+    /// Returns the positions of the triangles of the specified net (which was unfolded from this
+    /// goal mesh) partway through unfolding, in the same order as `net.positions`: `progress` runs
+    /// from 0 (folded up into the goal mesh) to 1 (flat, i.e. the net itself). Every fold is opened
+    /// by the same fraction of its angle, so the faces stay joined along the folds while each piece
+    /// blooms open around its root face. The goal mesh moves along with the net (which may have
+    /// been rotated, scaled, mirrored, or packed since it was unfolded), so the positions match the
+    /// net exactly once `progress` reaches 1.
+    pub fn fold_positions(&self, net: &UnfoldResult, progress: f32) -> Vec<Vec3> {
+        let coordinates = |vid: VertexIndex| *self.half_edge_mesh.vertex(vid).coordinates();
+
+        // Flipping the net swaps the last two corners of every triangle, see `UnfoldResult::flip()`
+        let position_index = |fid: FaceIndex, corner: usize| {
+            let corner = if net.flipped && corner > 0 {
+                3 - corner
+            } else {
+                corner
+            };
+            usize::from(fid) * 3 + corner
+        };
+
+        // Each polygon is rotated about the fold that joins it to its parent in the spanning tree
+        // (which is found by walking outwards from the roots across the folds), on top of its
+        // parent's own transform, where the transforms map the coordinates of the goal mesh to
+        // their (partially unfolded) positions
+        let mut polygon_folds = vec![vec![]; net.polygons.len()];
+        for edge in net.edges.iter().filter(|edge| edge.kind.is_fold()) {
+            polygon_folds[net.triangle_polygons[usize::from(edge.face)]].push(edge);
+        }
+        let mut transforms = vec![None; net.polygons.len()];
+        let mut queue = VecDeque::new();
+        for polygon in (0..net.polygons.len()).filter(|&polygon| net.polygon_depths[polygon] == 0) {
+            transforms[polygon] = Some((Mat3::identity(), Vec3::zero()));
+            queue.push_back(polygon);
+        }
+        while let Some(polygon) = queue.pop_front() {
+            let (parent_rotation, parent_offset) = transforms[polygon].unwrap();
+            for edge in polygon_folds[polygon].iter() {
+                let fid = self.half_edge_mesh.half_edge(edge.pair).face().unwrap();
+                let child = self.face_polygons[usize::from(fid)];
+                if transforms[child].is_some() {
+                    continue;
+                }
+
+                // The rotation (about the fold) that brings the normal of the child into line with
+                // the normal of its parent flattens the fold
+                let vids = self
+                    .half_edge_mesh
+                    .adjacent_vertices_to_half_edge(edge.half_edge);
+                let (a, b) = (coordinates(vids[0]), coordinates(vids[1]));
+                let axis = (b - a).normalize();
+                let parent_normal = self.half_edge_mesh.face_normal(edge.face);
+                let child_normal = self.half_edge_mesh.face_normal(fid);
+                let angle = axis
+                    .dot(child_normal.cross(parent_normal))
+                    .atan2(child_normal.dot(parent_normal));
+                let rotation = Mat3::from_axis_angle(axis, angle * progress);
+
+                transforms[child] = Some((
+                    parent_rotation * rotation,
+                    parent_rotation.mul_vec3(a - rotation.mul_vec3(a)) + parent_offset,
+                ));
+                queue.push_back(child);
+            }
+        }
+
+        // Once it is flat, every island of the net is moved into place, which is found by matching
+        // up the corners of its root triangle (along with its normal, which points out of the page
+        // in the net)
+        let mut placements = vec![None; net.island_count()];
+        for fid in self.half_edge_mesh.face_id_iter() {
+            let polygon = self.face_polygons[usize::from(fid)];
+            let island = net.polygon_islands[polygon];
+            if placements[island].is_some() || net.polygon_depths[polygon] != 0 {
+                continue;
+            }
+
+            let vids = self
+                .half_edge_mesh
+                .adjacent_vertices_to_face(fid)
+                .collect::<Vec<_>>();
+            let p = vids.iter().map(|&vid| coordinates(vid)).collect::<Vec<_>>();
+            let q = (0..3)
+                .map(|corner| net.positions[position_index(fid, corner)])
+                .collect::<Vec<_>>();
+            let normal = self.half_edge_mesh.face_normal(fid);
+            let from = Mat3::from_cols(p[1] - p[0], p[2] - p[0], normal * (p[1] - p[0]).length());
+            let to = Mat3::from_cols(
+                q[1] - q[0],
+                q[2] - q[0],
+                Vec3::unit_z() * (q[1] - q[0]).length(),
+            );
+            let placement = to * from.inverse();
+            placements[island] = Some((placement, q[0] - placement.mul_vec3(p[0])));
+        }
+
+        let mut positions = vec![Vec3::zero(); net.positions.len()];
+        for fid in self.half_edge_mesh.face_id_iter() {
+            let (rotation, offset) = transforms[self.face_polygons[usize::from(fid)]].unwrap();
+            let (placement, placement_offset) =
+                placements[net.polygon_islands[self.face_polygons[usize::from(fid)]]].unwrap();
+            for (corner, vid) in self
+                .half_edge_mesh
+                .adjacent_vertices_to_face(fid)
+                .enumerate()
+            {
+                let unfolded = rotation.mul_vec3(coordinates(vid)) + offset;
+                positions[position_index(fid, corner)] =
+                    placement.mul_vec3(unfolded) + placement_offset;
+            }
+        }
+        positions
+    }
+
+    /// Splits the spanning tree into islands (i.e. subtrees that are unfolded into separate nets),
+    /// so that no island is larger than `max_size` across (i.e. the longer side of its minimum-area
+    /// bounding box), in the units of the goal mesh. The tree is split from its leaves upwards:
+    /// each face is joined with the islands hanging off of it (smallest first) for as long as they
+    /// fit together, and the ones that don't fit are cut off and become islands of their own. The
+    /// triangles of a polygon are never cut apart, so a polygon that is larger than `max_size`
+    /// on its own is left as an island that is too large. Returns the tree as is if `max_size` is 0.
+    fn split_into_islands(&self, tree: SpanningTree, max_size: f32) -> SpanningTree {
+        if max_size <= 0.0 {
+            return tree;
+        }
+
+        // Cutting an edge of the spanning tree only moves the faces beyond it as a whole, so the
+        // sizes of the islands can be measured in the net of the entire tree
+        let (positions, _) = self.place_faces(&tree);
+        let size = |points: &Vec<Vec3>| {
+            let (_, width, height) = find_oriented_bounding_box(points);
+            width.max(height)
+        };
+        let hull = |points: &Vec<Vec3>| {
+            convex_hull_2d(points)
+                .iter()
+                .map(|point| point.extend(0.0))
+                .collect::<Vec<_>>()
+        };
+
+        let face_count = self.half_edge_mesh.face_id_iter().count();
+        let mut children = vec![vec![]; face_count];
+        for (&fid, &(prev, shared_edge)) in tree.came_from.iter() {
+            if prev != NO_FACE {
+                children[usize::from(prev)].push((fid, shared_edge));
+            }
+        }
+        for face_children in children.iter_mut() {
+            face_children.sort_by_key(|&(fid, _)| fid);
+        }
+
+        // Every face is visited after all of its children
+        let mut order = vec![];
+        let mut stack = tree.roots.clone();
+        while let Some(fid) = stack.pop() {
+            order.push(fid);
+            stack.extend(children[usize::from(fid)].iter().map(|&(child, _)| child));
+        }
+
+        // The convex hull of the island that is still attached to each face
+        let mut hulls = vec![vec![]; face_count];
+        let mut came_from = tree.came_from.clone();
+        let mut roots = tree.roots.clone();
+        for &fid in order.iter().rev() {
+            let index = usize::from(fid);
+            let mut island = positions[index * 3..index * 3 + 3].to_vec();
+
+            // Triangles of the same polygon always stay together, so they are joined first
+            let mut pending = children[index]
+                .iter()
+                .map(|&(child, shared_edge)| {
+                    let internal = self.is_internal_edge(shared_edge);
+                    (child, internal, size(&hulls[usize::from(child)]))
+                })
+                .collect::<Vec<_>>();
+            pending.sort_by(|a, b| b.1.cmp(&a.1).then(a.2.partial_cmp(&b.2).unwrap()));
+
+            for (child, internal, _) in pending {
+                let mut joined = island.clone();
+                joined.extend_from_slice(&hulls[usize::from(child)]);
+                let joined = hull(&joined);
+                if internal || size(&joined) <= max_size {
+                    island = joined;
+                } else {
+                    came_from.insert(child, (NO_FACE, NO_HALF_EDGE));
+                    roots.push(child);
+                }
+            }
+            hulls[index] = island;
+        }
+
+        let too_large = roots
+            .iter()
+            .filter(|&&root| size(&hulls[usize::from(root)]) > max_size * (1.0 + 1.0e-4))
+            .count();
+        info!(
+            "Split the net into {} islands that are at most {:?} units across",
+            roots.len(),
+            max_size
+        );
+        if too_large > 0 {
+            warn!(
+                "{} islands are larger than {:?} units across, since some of the faces of the goal mesh don't fit on their own",
+                too_large, max_size
+            );
+        }
+
+        SpanningTree::new(tree.reference_face, roots, came_from)
+    }
+
+    /// Unfolds the goal mesh along the specified spanning tree, which was computed starting at
+    /// `start` (this is only used for logging).
+    fn unfold_along(&self, tree: &SpanningTree, start: Instant) -> UnfoldResult {
+        let face_count = self.half_edge_mesh.face_id_iter().count();
+        info!(
+            "Built spanning tree of {} faces in {:?}",
+            face_count,
+            start.elapsed()
+        );
+        let (mut m3, incoming_edges) = self.place_faces(tree);
+        let layout_start = Instant::now();
+
+        // Each island belongs to the root that its faces were unfolded from: unless the goal mesh
+        // was split into islands, there is one per piece of the goal mesh
+        let face_islands = self
+            .half_edge_mesh
+            .face_id_iter()
+            .map(|fid| {
+                let (path, _) = self.get_unfolding_path_to(tree, fid);
+                tree.roots.iter().position(|&root| root == path[0]).unwrap()
+            })
+            .collect::<Vec<_>>();
+
+        // Every island was unfolded around its own root (i.e. on top of each other), so rotate each
+        // net to fit its own (minimum-area) bounding box, and pack the boxes onto shelves so that the
+        // nets waste as little paper as possible (each net is only rotated and translated, so its
+        // shape doesn't change)
+        if tree.roots.len() > 1 {
+            let mut island_points = vec![vec![]; tree.roots.len()];
+            for (triangle, &island) in m3.chunks(3).zip(face_islands.iter()) {
+                island_points[island].extend_from_slice(triangle);
+            }
+            let transforms = island_points
+                .iter()
+                .map(|points| {
+                    let rotation = Mat3::from_rotation_z(find_oriented_bounding_box(points).0);
+                    let rotated = points
+                        .iter()
+                        .map(|point| rotation.mul_vec3(*point))
+                        .collect::<Vec<_>>();
+                    (rotation, find_bounding_box(&rotated))
+                })
+                .collect::<Vec<_>>();
+
+            let sizes = transforms
+                .iter()
+                .map(|(_, (min, max))| (*max - *min).truncate())
+                .collect::<Vec<_>>();
+            let largest_extent = sizes
+                .iter()
+                .map(|size| size.max_element())
+                .fold(0.0, f32::max);
+            let corners = pack_shelves(&sizes, largest_extent * COMPONENT_SPACING);
+
+            for (triangle, &island) in m3.chunks_mut(3).zip(face_islands.iter()) {
+                let (rotation, (min, _)) = transforms[island];
+                let offset = corners[island].extend(0.0) - min;
+                for point in triangle.iter_mut() {
+                    *point = rotation.mul_vec3(*point) + offset;
+                }
+            }
+        }
+
+        // Finally, check whether any of the faces overlap in the net
+        let overlaps = find_overlapping_triangles(&m3, self.tolerances.overlap);
+        if overlaps.is_empty() {
+            info!("No overlapping faces found");
+        } else {
+            warn!(
+                "Found {} pairs of overlapping faces: the resulting net is not valid",
+                overlaps.len()
+            );
+        }
+
+        // Gather the outline of each polygon: since the triangles of a polygon are never cut apart,
+        // we can look up each of its vertices in any one of the triangles that contain it
+        let mut polygon_faces = vec![vec![]; self.polygons.len()];
+        for fid in self.half_edge_mesh.face_id_iter() {
+            polygon_faces[self.face_polygons[usize::from(fid)]].push(fid);
+        }
+        let polygons = self
+            .polygons
+            .iter()
+            .zip(polygon_faces.iter())
+            .map(|(vids, fids)| {
+                vids.iter()
+                    .map(|&vid| {
+                        let fid = *fids
+                            .iter()
+                            .find(|&&fid| self.half_edge_mesh.face_contains_vertex(fid, vid))
+                            .expect("Polygon vertex is not part of any of its triangles - this should never happen");
+                        self.get_global_vertex_index(fid, vid)
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+
+        // Classify each side of each polygon as either a cut or a fold
+        let mut edges = vec![];
+        for fid in self.half_edge_mesh.face_id_iter() {
+            for eid in self.half_edge_mesh.adjacent_half_edges_to_face(fid) {
+                if self.is_internal_edge(eid) {
+                    continue;
+                }
+
+                let pair = self.half_edge_mesh.half_edge(eid).pair();
+                let kind =
+                    if tree.crossed_edges.contains(&eid) || tree.crossed_edges.contains(&pair) {
+                        // Since the net is viewed from the outside of the goal mesh, convex edges
+                        // become mountain folds (edges between coplanar faces are never convex,
+                        // regardless of any floating point noise)
+                        match self.half_edge_mesh.dihedral_angle(eid) {
+                            Some(angle) if angle > self.tolerances.flat => EdgeKind::Mountain,
+                            _ => EdgeKind::Valley,
+                        }
+                    } else {
+                        EdgeKind::Cut
+                    };
+
+                let vids = self.half_edge_mesh.adjacent_vertices_to_half_edge(eid);
+                edges.push(NetEdge {
+                    half_edge: eid,
+                    pair,
+                    face: fid,
+                    indices: [
+                        self.get_global_vertex_index(fid, vids[0]),
+                        self.get_global_vertex_index(fid, vids[1]),
+                    ],
+                    kind,
+                    interior_angle: self.half_edge_mesh.interior_angle(eid),
+                    border: self.half_edge_mesh.is_border_half_edge(pair),
+                });
+            }
+        }
+
+        // Give both sides of each edge that was cut apart the same label, so that they can be
+        // matched up when assembling the net: each piece of the goal mesh is assembled separately,
+        // so each one is numbered separately as well (see `UnfoldResult::cut_label()`)
+        let mut cut_labels = HashMap::new();
+        let mut next_labels = vec![1; self.component_count];
+        for edge in edges.iter().filter(|edge| edge.kind == EdgeKind::Cut) {
+            if cut_labels.contains_key(&edge.half_edge) || edge.border {
+                continue;
+            }
+            let next_label = &mut next_labels[self.face_components[usize::from(edge.face)]];
+            cut_labels.insert(edge.half_edge, *next_label);
+            cut_labels.insert(edge.pair, *next_label);
+            *next_label += 1;
+        }
+        info!("Number of labeled cut edges: {}", cut_labels.len() / 2);
+
+        // Every triangle of a polygon belongs to the same piece of the goal mesh (and island)
+        let mut polygon_components = vec![0; self.polygons.len()];
+        let mut polygon_islands = vec![0; self.polygons.len()];
+        for (fid, &polygon) in self.face_polygons.iter().enumerate() {
+            polygon_components[polygon] = self.face_components[fid];
+            polygon_islands[polygon] = face_islands[fid];
+        }
+
+        // Count the folds along the path from the reference face to each polygon (the edges between
+        // the triangles of a polygon aren't folds, so every triangle of a polygon has the same depth)
+        let mut polygon_depths = vec![0; self.polygons.len()];
+        for fid in self.half_edge_mesh.face_id_iter() {
+            let (_, edges_along_path) = self.get_unfolding_path_to(tree, fid);
+            polygon_depths[self.face_polygons[usize::from(fid)]] = edges_along_path
+                .iter()
+                .filter(|&&eid| !self.is_internal_edge(eid))
+                .count();
+        }
+
+        // Weld together the corners of each pair of faces that share an edge of the spanning tree,
+        // keeping track of which corners have been welded with a disjoint-set forest
+        fn find_root(parents: &mut [usize], mut index: usize) -> usize {
+            while parents[index] != index {
+                parents[index] = parents[parents[index]];
+                index = parents[index];
+            }
+            index
+        }
+        let mut parents = (0..m3.len()).collect::<Vec<_>>();
+        let mut weld = |fid: FaceIndex, prev: FaceIndex, shared_edge: HalfEdgeIndex| {
+            for &vid in self
+                .half_edge_mesh
+                .adjacent_vertices_to_half_edge(shared_edge)
+                .iter()
+            {
+                let a = find_root(&mut parents, self.get_global_vertex_index(fid, vid));
+                let b = find_root(&mut parents, self.get_global_vertex_index(prev, vid));
+                parents[a.max(b)] = a.min(b);
+            }
+        };
+        for (&fid, &(prev, shared_edge)) in tree.came_from.iter() {
+            if prev != NO_FACE {
+                weld(fid, prev, shared_edge);
+            }
+        }
+
+        // The spanning tree doesn't necessarily cross every edge inside of a polygon (e.g. if its
+        // triangles surround an interior vertex), but the triangles on either side of such an edge
+        // are still joined, since they are coplanar
+        for eid in self.half_edge_mesh.half_edge_id_iter() {
+            if let [Some(fid), Some(prev)] = self.half_edge_mesh.adjacent_faces_to_half_edge(eid) {
+                if self.is_internal_edge(eid) {
+                    weld(fid, prev, eid);
+                }
+            }
+        }
+
+        // Number the welded vertices in the order that they first appear
+        let mut roots = HashMap::new();
+        let welded_indices = (0..m3.len())
+            .map(|index| {
+                let root = find_root(&mut parents, index);
+                let next = roots.len();
+                *roots.entry(root).or_insert(next)
+            })
+            .collect::<Vec<_>>();
+        info!("Number of (welded) vertices in the net: {}", roots.len());
+        info!(
+            "Laid out net of {} polygons in {:?} (unfolded in {:?} in total)",
+            self.polygons.len(),
+            layout_start.elapsed(),
+            start.elapsed()
+        );
+
+        UnfoldResult {
+            positions: m3,
+            polygons,
+            triangle_polygons: self.face_polygons.clone(),
+            obj_faces: self.obj_faces.clone(),
+            material_colors: self.polygon_colors.clone(),
+            polygon_components,
+            polygon_islands,
+            edges,
+            overlaps,
+            cut_labels,
+            polygon_depths,
+            welded_indices,
+            incoming_edges,
+            flipped: false,
+        }
+    }
+
+    /// Places every face of the goal mesh in the xy-plane by unfolding it along the specified
+    /// spanning tree, before the pieces of the net are laid out (so every piece is unfolded around
+    /// its own root, on top of the others). Returns the positions of the triangles (3 per face, see
+    /// `UnfoldResult::positions`), along with the incoming edge of each one.
+    fn place_faces(&self, tree: &SpanningTree) -> (Vec<Vec3>, Vec<[usize; 2]>) {
+        let face_count = self.half_edge_mesh.face_id_iter().count();
+        let traversal_start = Instant::now();
+
+        // Basis vectors in R3
+        let _e1 = Vec3::unit_x();
+        let _e2 = Vec3::unit_y();
+        let e3 = Vec3::unit_z();
+        let r1_pi = Mat3::from_rotation_x(std::f32::consts::PI);
+
+        // Grab the first edge of the reference face to serve as the "reference edge," i.e. the
+        // incoming edge for the reference face
+        let reference_edge = self
+            .half_edge_mesh
+            .adjacent_half_edges_to_face(tree.reference_face)
+            .collect::<Vec<_>>()[0];
+
+        // (1) Rotating each mesh face to align its unit normal vector with e3
+        debug!("Starting M1");
+        let mut m1 = vec![];
+
+        for fid in self.half_edge_mesh.face_id_iter() {
+            // The rotation below divides by (1 + n.e3), which loses all precision as the normal
+            // vector approaches the negative z-axis, so faces that point "down" are flipped
+            // 180-degrees about the x-axis first (any rotation that aligns the normal with e3
+            // works, since the face is rotated about e3 in the next step anyway)
+            let mut normal = self.half_edge_mesh.face_normal(fid);
+            let flip = normal.dot(e3) < 0.0;
+            if flip {
+                normal = r1_pi.mul_vec3(normal);
+            }
+
+            // Precompute items that involve this face's normal vector
+            let normal_cross_e3 = normal.cross(e3);
+            let normal_dot_e3 = normal.dot(e3);
+
+            for vid in self.half_edge_mesh.adjacent_vertices_to_face(fid) {
+                let mut coords = *self.half_edge_mesh.vertex(vid).coordinates();
+                if flip {
+                    coords = r1_pi.mul_vec3(coords);
+                }
+
+                let transformed = normal_dot_e3 * coords
+                    + normal_cross_e3.cross(coords)
+                    + (normal_cross_e3 / (1.0 + normal_dot_e3)) * normal_cross_e3.dot(coords);
+                m1.push(transformed);
+            }
+        }
+
+        // (2) Translate and rotate each mesh face to place one of its nodes at the origin and one of its edges along e1
+        debug!("Starting M2");
+        let mut m2 = vec![];
+        let mut incoming_edges = vec![];
+
+        for fid in self.half_edge_mesh.face_id_iter() {
+            // Since we only need the incoming edge at the moment, we can just pass an arbitrary face ID
+            // as the `towards_face` in the function below
+            let (incoming, _) = self.get_incoming_outgoing_edges(tree, fid, NO_FACE);
+
+            // The IDs of the 2 vertices that form the incoming edge of this face
+            let incoming_vids = self.half_edge_mesh.adjacent_vertices_to_half_edge(incoming);
+            let incoming_indices = [
+                self.get_global_vertex_index(fid, incoming_vids[0]),
+                self.get_global_vertex_index(fid, incoming_vids[1]),
+            ];
+            incoming_edges.push(incoming_indices);
+            let src = m1[incoming_indices[0]];
+            let dst = m1[incoming_indices[1]];
+            let along_incoming_edge = dst - src;
+            let r3 = Mat3::from_rotation_z(-angle_with_e1(&along_incoming_edge));
+
+            for vid in self.half_edge_mesh.adjacent_vertices_to_face(fid) {
+                let coords = m1[self.get_global_vertex_index(fid, vid)];
+
+                // Translate the face so that the "source" (first) vertex of its incoming edge is
+                // coincident with the origin
+                let translated = coords - src;
+
+                // Perform a rotation around the z-axis that causes the incoming edge of this face
+                // (w.r.t. the spanning tree) to be aligned with the positive x-axis
+                let rotated = r3.mul_vec3(translated);
+                m2.push(rotated);
+            }
+        }
+
+        // (3) Translate and rotate each mesh face in the e1/e2 plane to its position in the net
+        debug!("Starting M3");
+        let mut m3 = vec![];
+
+        for fid in self.half_edge_mesh.face_id_iter() {
+            // The reference face (and the root of every other piece) is already in the correct
+            // position
+            if tree.roots.contains(&fid) {
+                for vid in self.half_edge_mesh.adjacent_vertices_to_face(fid) {
+                    m3.push(m2[self.get_global_vertex_index(fid, vid)]);
+                }
+                continue;
+            }
+
+            // Get the unfolding path from the reference face to the target face
+            let (path, _) = self.get_unfolding_path_to(tree, fid);
+            debug_assert!(path.len() >= 2);
+
+            let mut cumulative_translation = Vec3::zero();
+            let mut mu_history = vec![];
+
+            // Go through all of the other faces along this path including the reference face and ignoring the last
+            // face, which is the target face itself
+            for path_index in 0..(path.len() - 1) {
+                // The IDs of the current and next faces along the section of the unfolding path that connects
+                // the face `fid` to the reference face
+                let fid_curr = path[path_index];
+                let fid_next = path[path_index + 1];
+
+                // We should be able to safely unwrap `outgoing` here - if it is `None` something is seriously wrong
+                let (incoming, mut maybe_outgoing) =
+                    self.get_incoming_outgoing_edges(tree, fid_curr, fid_next);
+                let outgoing = maybe_outgoing.expect(
+                    "Encountered branch face with no outgoing edge - this should never happen",
+                );
+
+                // A pair of vertex IDs for the incoming / outgoing edges
+                let incoming_vids = self.half_edge_mesh.adjacent_vertices_to_half_edge(incoming);
+                let outgoing_vids = self.half_edge_mesh.adjacent_vertices_to_half_edge(outgoing);
+
+                // Vectors along the incoming and outgoing edges of this face
+                let along_incoming = m1[self.get_global_vertex_index(fid_curr, incoming_vids[1])]
+                    - m1[self.get_global_vertex_index(fid_curr, incoming_vids[0])];
+                let along_outgoing = m1[self.get_global_vertex_index(fid_curr, outgoing_vids[1])]
                     - m1[self.get_global_vertex_index(fid_curr, outgoing_vids[0])];
 
-                // The angle formed between the incoming / outgoing edges
-                let mu = angle_with_e1(&-along_outgoing) - angle_with_e1(&along_incoming);
+                // The angle formed between the incoming / outgoing edges
+                let mu = angle_with_e1(&-along_outgoing) - angle_with_e1(&along_incoming);
+
+                // Calculate a translation vector
+                let rhs = along_outgoing
+                    + m1[self.get_global_vertex_index(fid_curr, outgoing_vids[0])]
+                    - m1[self.get_global_vertex_index(fid_curr, incoming_vids[0])];
+                let offset = Mat3::from_rotation_z(
+                    mu_history.iter().sum::<f32>() - angle_with_e1(&along_incoming),
+                )
+                .mul_vec3(rhs);
+
+                // Accumulate all such translation vectors
+                cumulative_translation += offset;
+
+                // Subsequent faces along this fold path will need to take all prior rotations into account
+                mu_history.push(mu);
+            }
+
+            // Transform all of the points in this face to their final positions in the xy-plane
+            let cumulative_rotation = Mat3::from_rotation_z(mu_history.iter().sum());
+            for vid in self.half_edge_mesh.adjacent_vertices_to_face(fid) {
+                let coords = m2[self.get_global_vertex_index(fid, vid)];
+                let rotated = cumulative_rotation.mul_vec3(coords);
+                let translated = rotated + cumulative_translation;
+                m3.push(translated);
+            }
+        }
+
+        info!(
+            "Unfolded {} faces in {:?}",
+            face_count,
+            traversal_start.elapsed()
+        );
+
+        (m3, incoming_edges)
+    }
+}
+
+/// Builds a single tab of the specified style along the edge from `a` to `b`, see
+/// `generate_tabs()`: since the faces of the net are in CCW order, "outward" is always to the
+/// right of the edge.
+fn build_tab(a: Vec3, b: Vec3, width: f32, style: TabStyle, angle: f32, epsilon: f32) -> Vec<Vec3> {
+    let length = (b - a).length();
+    let along = (b - a) / length;
+    let outward = Vec3::new(along.y(), -along.x(), 0.0);
+
+    match style {
+        TabStyle::Trapezoid | TabStyle::Rectangle => {
+            // Make sure that the slanted sides of the tab never cross
+            let inset = if style == TabStyle::Trapezoid {
+                (width / angle.tan()).min(length * 0.25)
+            } else {
+                0.0
+            };
+
+            vec![
+                a,
+                b,
+                b + outward * width - along * inset,
+                a + outward * width + along * inset,
+            ]
+        }
+        TabStyle::Rounded => {
+            // Each outer corner is a quarter circle: the one next to `b` turns from `along` to
+            // `outward`, and the one next to `a` turns from `outward` to `-along`
+            let radius = width.min(length * 0.5);
+            let corners = [
+                (b - along * radius, 0.0),
+                (a + along * radius, std::f32::consts::FRAC_PI_2),
+            ];
+
+            let mut tab = vec![a, b];
+            for &(corner, start) in corners.iter() {
+                let center = corner + outward * (width - radius);
+                for segment in 0..=TAB_CORNER_SEGMENTS {
+                    let theta = start
+                        + std::f32::consts::FRAC_PI_2 * segment as f32 / TAB_CORNER_SEGMENTS as f32;
+                    tab.push(center + (along * theta.cos() + outward * theta.sin()) * radius);
+                }
+            }
+
+            // The corners meet the edge (or each other) when the tab is as narrow (or its edge
+            // is as short) as possible, so drop any points that would be doubled up
+            tab.dedup_by(|p, q| (*p - *q).length() <= epsilon);
+            if tab.len() > 2 && (tab[tab.len() - 1] - tab[0]).length() <= epsilon {
+                tab.pop();
+            }
+            tab
+        }
+    }
+}
+
+/// Returns the triangles of the net (which lie in the xy-plane), given its positions.
+fn net_triangles(positions: &[Vec3]) -> Vec<[Vec2; 3]> {
+    positions
+        .chunks(3)
+        .map(|t| {
+            [
+                Vec2::new(t[0].x(), t[0].y()),
+                Vec2::new(t[1].x(), t[1].y()),
+                Vec2::new(t[2].x(), t[2].y()),
+            ]
+        })
+        .collect()
+}
+
+/// Clips the convex polygon to the half-plane of points `p` where `(p - origin).dot(normal)` is at
+/// least 0 (i.e. on the side of the line through `origin` that `normal` points to), keeping the
+/// order of its vertices: the result starts at the first vertex that is kept, or at the point where
+/// the polygon enters the half-plane if the first vertex is cut off. Points within `epsilon` of the
+/// line are kept as they are, and the result is empty if (almost) nothing is left.
+fn clip_polygon(polygon: &[Vec3], origin: Vec3, normal: Vec3, epsilon: f32) -> Vec<Vec3> {
+    let distance = |point: &Vec3| (*point - origin).dot(normal);
+
+    let mut clipped = vec![];
+    for (p, q) in polygon.iter().zip(polygon.iter().cycle().skip(1)) {
+        let (p_distance, q_distance) = (distance(p), distance(q));
+        if p_distance >= -epsilon {
+            clipped.push(*p);
+        }
+        if (p_distance < -epsilon && q_distance > epsilon)
+            || (p_distance > epsilon && q_distance < -epsilon)
+        {
+            clipped.push(p.lerp(*q, p_distance / (p_distance - q_distance)));
+        }
+    }
+
+    clipped.dedup_by(|p, q| (*p - *q).length() <= epsilon);
+    if clipped.len() > 1 && (clipped[clipped.len() - 1] - clipped[0]).length() <= epsilon {
+        clipped.pop();
+    }
+    if clipped.len() < 3 {
+        clipped.clear();
+    }
+    clipped
+}
+
+/// Trims a pair of glue tabs that overlap at the corner between their edges along the border of
+/// the net, where `corner` holds the start of the edge of `first`, the shared corner, and the end
+/// of the edge of `second` (i.e. the edge of `first` runs into the corner, and the edge of `second`
+/// runs out of it). Both tabs are cut along the line that bisects the free space at the corner
+/// (outside of the net), so that they meet at a miter without covering each other.
+///
+/// At a narrow notch (i.e. a reflex corner of the net, where the tabs are squeezed into less than a
+/// half-turn), the miter would run far out from the corner and leave each tab with a long, thin
+/// point. If it would be longer than `miter_limit` times the width of the tabs, both tabs are
+/// instead cut back square from the corner, just far enough that they no longer reach the
+/// bisector. Either tab may be trimmed away entirely, in which case it is returned empty.
+fn miter_tabs(
+    first: &[Vec3],
+    second: &[Vec3],
+    corner: [Vec3; 3],
+    width: f32,
+    miter_limit: f32,
+    epsilon: f32,
+) -> (Vec<Vec3>, Vec<Vec3>) {
+    let [a, b, c] = corner;
+    let towards_a = (a - b).normalize();
+    let towards_c = (c - b).normalize();
+
+    // Since the faces of the net are in CCW order, the border of the net turns right (i.e.
+    // clockwise) at reflex corners, where the space between the edges is less than a half-turn
+    let turn = (b - a).cross(c - b).z();
+    let between = towards_a
+        .cross(towards_c)
+        .z()
+        .abs()
+        .atan2(towards_a.dot(towards_c));
+    let (free_angle, bisector) = if (towards_a + towards_c).length() <= epsilon {
+        (
+            std::f32::consts::PI,
+            Vec3::new((b - a).y(), -(b - a).x(), 0.0).normalize(),
+        )
+    } else if turn < 0.0 {
+        (between, (towards_a + towards_c).normalize())
+    } else {
+        (
+            std::f32::consts::PI * 2.0 - between,
+            -(towards_a + towards_c).normalize(),
+        )
+    };
+
+    let half_angle = free_angle * 0.5;
+    if free_angle < std::f32::consts::PI && 1.0 / half_angle.sin() > miter_limit {
+        // The outer corner of a (rectangular) tab that starts this far from the corner lies on the
+        // bisector
+        let inset = width / half_angle.tan();
+        (
+            clip_polygon(first, b + towards_a * inset, towards_a, epsilon),
+            clip_polygon(second, b + towards_c * inset, towards_c, epsilon),
+        )
+    } else {
+        let mut across = Vec3::new(-bisector.y(), bisector.x(), 0.0);
+        if across.dot(towards_a) < 0.0 {
+            across = -across;
+        }
+        (
+            clip_polygon(first, b, across, epsilon),
+            clip_polygon(second, b, -across, epsilon),
+        )
+    }
+}
+
+/// Returns `true` if the specified convex polygon overlaps any of the triangles (since the polygon
+/// is convex, it can be split into a fan of triangles around its first vertex).
+fn overlaps_triangles<'a, I>(polygon: &[Vec3], triangles: I, epsilon: f32) -> bool
+where
+    I: Iterator<Item = &'a [Vec2; 3]> + Clone,
+{
+    let polygon = polygon
+        .iter()
+        .map(|point| Vec2::new(point.x(), point.y()))
+        .collect::<Vec<_>>();
+
+    (1..polygon.len() - 1).any(|i| {
+        let part = [polygon[0], polygon[i], polygon[i + 1]];
+        triangles
+            .clone()
+            .any(|triangle| triangles_overlap_2d(&part, triangle, epsilon))
+    })
+}
+
+/// Returns `true` if the specified convex polygons overlap each other.
+fn overlaps_polygon(polygon: &[Vec3], other: &[Vec3], epsilon: f32) -> bool {
+    let other = (1..other.len() - 1)
+        .map(|i| {
+            [
+                Vec2::new(other[0].x(), other[0].y()),
+                Vec2::new(other[i].x(), other[i].y()),
+                Vec2::new(other[i + 1].x(), other[i + 1].y()),
+            ]
+        })
+        .collect::<Vec<_>>();
+
+    overlaps_triangles(polygon, other.iter(), epsilon)
+}
+
+/// Generates glue tabs of the style in `options` for the cut edges of the net, each of which
+/// extends `options.width` units outward from its edge (in the same units as `positions`). The
+/// slanted sides of trapezoidal tabs meet their edge at `options.angle` (in radians, where a right
+/// angle is the same as a rectangular tab), but the taper is clamped so that the outer side of a
+/// tab is never shorter than half of its edge, and the corners of rounded tabs are never wider than
+/// half of their edge (so that no tab is wider than the edge that it is attached to). Every pair of
+/// matching cut edges (i.e. the two sides of an edge that was cut apart) receives only a single
+/// tab: if a tab would overlap one of the faces of the net, the other side of the pair is used
+/// instead, and if both sides overlap, the edge is skipped altogether. Edges along the border of
+/// the goal mesh (see `NetEdge::border`) don't have a matching side to be glued to, so they never
+/// receive a tab.
+///
+/// Tabs along neighboring edges of the border of the net can also overlap each other at the corner
+/// between them (mostly at reflex corners, where the border of the net turns inward): these are
+/// trimmed to meet at a miter, or cut back square from the corner if the miter would be longer than
+/// `options.miter_limit` times `options.width` (see `miter_tabs()`). Tabs that are trimmed away
+/// entirely are skipped.
+///
+/// Each tab is returned as a closed polyline around a convex polygon, whose first two vertices
+/// lie on the cut edge that it is attached to (and are its endpoints, unless the tab was cut back
+/// from one of its corners).
+pub fn generate_tabs(
+    positions: &[Vec3],
+    edges: &[NetEdge],
+    options: &TabOptions,
+) -> Vec<Vec<Vec3>> {
+    let TabOptions {
+        width,
+        style,
+        angle,
+        miter_limit,
+    } = *options;
+    let epsilon = overlap_tolerance(positions, DEFAULT_OVERLAP_TOLERANCE);
+    let triangles = net_triangles(positions);
+
+    let mut visited = vec![];
+    let mut tabs = vec![];
+
+    for edge in edges
+        .iter()
+        .filter(|edge| edge.kind == EdgeKind::Cut && !edge.border)
+    {
+        if visited.contains(&edge.half_edge) {
+            continue;
+        }
+        visited.push(edge.half_edge);
+        visited.push(edge.pair);
+
+        // Try this side of the cut first, then the other side
+        let mut candidates = vec![edge];
+        if let Some(other) = edges.iter().find(|other| other.half_edge == edge.pair) {
+            candidates.push(other);
+        }
+
+        match candidates
+            .iter()
+            .map(|candidate| {
+                let a = positions[candidate.indices[0]];
+                let b = positions[candidate.indices[1]];
+                build_tab(a, b, width, style, angle, epsilon)
+            })
+            .find(|tab| !overlaps_triangles(tab, triangles.iter(), epsilon))
+        {
+            Some(tab) => tabs.push(tab),
+            None => warn!(
+                "Skipping tab for cut edge {:?}, since it would overlap the net",
+                edge.half_edge
+            ),
+        }
+    }
+
+    // Find the pairs of tabs where the edge of one runs into the corner that the edge of the other
+    // runs out of (before any of them are trimmed, which can move the ends of their edges)
+    let corners = (0..tabs.len())
+        .flat_map(|i| (0..tabs.len()).map(move |j| (i, j)))
+        .filter(|&(i, j)| i != j && (tabs[i][1] - tabs[j][0]).length() <= epsilon)
+        .map(|(i, j)| (i, j, [tabs[i][0], tabs[i][1], tabs[j][1]]))
+        .collect::<Vec<_>>();
+
+    let mut trimmed = 0;
+    for (i, j, corner) in corners {
+        if tabs[i].is_empty() || tabs[j].is_empty() {
+            continue;
+        }
+        if overlaps_polygon(&tabs[i], &tabs[j], epsilon) {
+            let (first, second) =
+                miter_tabs(&tabs[i], &tabs[j], corner, width, miter_limit, epsilon);
+            tabs[i] = first;
+            tabs[j] = second;
+            trimmed += 1;
+        }
+    }
+
+    let count = tabs.len();
+    tabs.retain(|tab| !tab.is_empty());
+    if tabs.len() < count {
+        warn!(
+            "Skipping {} tabs, since they were trimmed away by their neighbors",
+            count - tabs.len()
+        );
+    }
 
-                // Calculate a translation vector
-                let rhs = along_outgoing
-                    + m1[self.get_global_vertex_index(fid_curr, outgoing_vids[0])]
-                    - m1[self.get_global_vertex_index(fid_curr, incoming_vids[0])];
-                let offset = Mat3::from_rotation_z(
-                    mu_history.iter().sum::<f32>() - angle_with_e1(&along_incoming),
-                )
-                .mul_vec3(rhs);
+    info!(
+        "Generated {} tabs ({} corners between neighboring tabs were trimmed)",
+        tabs.len(),
+        trimmed
+    );
+    tabs
+}
 
-                // Accumulate all such translation vectors
-                cumulative_translation += offset;
+/// Generates interlocking tabs and slots for the cut edges of the net, so that it can be
+/// assembled without glue. For every pair of matching cut edges, one side receives a tab of the
+/// specified style (see `generate_tabs()`) along the middle half of the edge, and the face on the
+/// other side receives a slot: a cut-out with exactly the shape that the tab covers once the net
+/// is folded, so that the tab fills the slot flush with the surface. The sides are swapped if the
+/// tab would overlap the net or the slot would not fit inside of its face, and if neither works,
+/// the edge is skipped. Edges along the border of the goal mesh don't have a matching side, so they
+/// never receive a tab.
+///
+/// Returns the tabs and the slots, each as a closed polyline around a convex polygon whose first two
+/// vertices lie on its edge.
+pub fn generate_joints(
+    net: &UnfoldResult,
+    width: f32,
+    style: TabStyle,
+    angle: f32,
+) -> (Vec<Vec<Vec3>>, Vec<Vec<Vec3>>) {
+    let epsilon = overlap_tolerance(&net.positions, DEFAULT_OVERLAP_TOLERANCE);
+    let triangles = net_triangles(&net.positions);
 
-                // Subsequent faces along this fold path will need to take all prior rotations into account
-                mu_history.push(mu);
+    // Since both edges have the same length, a point at some distance along (and beyond) the edge
+    // that the tab is attached to is folded onto the point at the same distance along (and inside
+    // of) the other edge, starting from the opposite end
+    let fold_onto = |point: Vec3, edge: &NetEdge, other: &NetEdge| {
+        let (a, b) = (
+            net.positions[edge.indices[0]],
+            net.positions[edge.indices[1]],
+        );
+        let (c, d) = (
+            net.positions[other.indices[1]],
+            net.positions[other.indices[0]],
+        );
+        let along = (b - a).normalize();
+        let outward = Vec3::new(along.y(), -along.x(), 0.0);
+        let other_along = (d - c).normalize();
+        let other_inward = Vec3::new(other_along.y(), -other_along.x(), 0.0);
+
+        c + other_along * (point - a).dot(along) + other_inward * (point - a).dot(outward)
+    };
+
+    // Returns `true` if every point of the slot lies inside of the polygon of the specified face
+    // (the points are nudged towards the middle of the slot, since some of them lie on its edge)
+    let fits_face = |slot: &Vec<Vec3>, face: FaceIndex| {
+        let polygon = net.triangle_polygons[usize::from(face)];
+        let center = find_centroid(slot);
+        let parts = triangles
+            .iter()
+            .enumerate()
+            .filter(|&(triangle, _)| net.triangle_polygons[triangle] == polygon)
+            .map(|(_, triangle)| triangle)
+            .collect::<Vec<_>>();
+
+        slot.iter().all(|&point| {
+            let point = point + (center - point).normalize() * epsilon;
+            let point = Vec2::new(point.x(), point.y());
+            parts
+                .iter()
+                .any(|t| point_in_triangle_2d(&point, &t[0], &t[1], &t[2]))
+        })
+    };
+
+    let mut visited = vec![];
+    let mut tabs = vec![];
+    let mut slots = vec![];
+
+    for edge in net.edges.iter().filter(|edge| edge.kind == EdgeKind::Cut) {
+        if visited.contains(&edge.half_edge) {
+            continue;
+        }
+        visited.push(edge.half_edge);
+        visited.push(edge.pair);
+
+        let other = match net.edges.iter().find(|other| other.half_edge == edge.pair) {
+            Some(other) => other,
+            None => continue,
+        };
+
+        // Try attaching the tab to this side of the cut first, then to the other side
+        let joint = [(edge, other), (other, edge)]
+            .iter()
+            .map(|&(tab_edge, slot_edge)| {
+                let a = net.positions[tab_edge.indices[0]];
+                let b = net.positions[tab_edge.indices[1]];
+                let tab = build_tab(
+                    a.lerp(b, 0.25),
+                    a.lerp(b, 0.75),
+                    width,
+                    style,
+                    angle,
+                    epsilon,
+                );
+                let slot = tab
+                    .iter()
+                    .map(|&point| fold_onto(point, tab_edge, slot_edge))
+                    .collect::<Vec<_>>();
+                (tab, slot, slot_edge.face)
+            })
+            .find(|(tab, slot, face)| {
+                !overlaps_triangles(tab, triangles.iter(), epsilon) && fits_face(slot, *face)
+            });
+
+        match joint {
+            Some((tab, slot, _)) => {
+                tabs.push(tab);
+                slots.push(slot);
             }
+            None => warn!(
+                "Skipping tab and slot for cut edge {:?}, since they would not fit the net",
+                edge.half_edge
+            ),
+        }
+    }
 
-            // Transform all of the points in this face to their final positions in the xy-plane
-            let cumulative_rotation = Mat3::from_rotation_z(mu_history.iter().sum());
-            for vid in self.half_edge_mesh.adjacent_vertices_to_face(fid) {
-                let coords = m2[self.get_global_vertex_index(fid, vid)];
-                let rotated = cumulative_rotation.mul_vec3(coords);
-                let translated = rotated + cumulative_translation;
-                m3.push(translated);
+    info!("Generated {} tabs and slots", tabs.len());
+    (tabs, slots)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::{find_oriented_bounding_box, find_triangle_containing};
+
+    #[test]
+    fn test_unfold_unit_cube() {
+        let goal_mesh = GoalMesh::from_obj(
+            Path::new("tests/fixtures/unit_cube.obj"),
+            &LoadOptions {
+                strict: true,
+                ..LoadOptions::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(goal_mesh.face_count(), 6);
+        assert_eq!(goal_mesh.size(), Vec3::one());
+        assert_eq!(goal_mesh.bounding_box(), (Vec3::zero(), Vec3::one()));
+        assert_eq!(goal_mesh.centroid(), Vec3::splat(0.5));
+
+        // By symmetry, a breadth-first traversal unfolds the cube into the same net from every face
+        for root in 0..goal_mesh.face_count() {
+            let net = goal_mesh
+                .unfold(root, UnfoldStrategy::BreadthFirst)
+                .unwrap();
+            assert_eq!(net.polygons.len(), 6);
+            assert!(net.polygons.iter().all(|polygon| polygon.len() == 4));
+            assert_eq!(net.positions.len(), 12 * 3);
+            assert!(net.overlaps.is_empty());
+
+            // Unfolding preserves the area of every face, so the net is as large as the surface
+            let area = net.polygon_areas(1.0).iter().sum::<f32>();
+            assert!((area - 6.0).abs() < 1.0e-4);
+
+            // The net is a cross: the root face is surrounded by its 4 neighbors, and the opposite
+            // face hangs off of one of them, so the net fits a 4 x 3 box
+            let mut depths = net.polygon_depths.clone();
+            depths.sort();
+            assert_eq!(depths, vec![0, 1, 1, 1, 1, 2]);
+            let (_, width, height) = find_oriented_bounding_box(&net.positions);
+            let (long, short) = (width.max(height), width.min(height));
+            assert!((long - 4.0).abs() < 1.0e-4 && (short - 3.0).abs() < 1.0e-4);
+
+            // Its outline is a single loop along the 7 cut edges (each of which is cut on both
+            // sides), around the 14 vertices of the net
+            let vertex_count = net.welded_indices.iter().max().unwrap() + 1;
+            assert_eq!(vertex_count, 14);
+            let mut vertices = vec![Vec3::zero(); vertex_count];
+            for (position, &welded_index) in net.positions.iter().zip(net.welded_indices.iter()) {
+                vertices[welded_index] = *position;
+            }
+            let faces = net
+                .welded_indices
+                .chunks(3)
+                .map(|triangle| [triangle[0], triangle[1], triangle[2]])
+                .collect::<Vec<_>>();
+            let net_mesh = HalfEdgeMesh::from_faces(&faces, &vertices).unwrap();
+            let loops = net_mesh.boundary_loops();
+            assert_eq!(loops.len(), 1);
+            assert_eq!(loops[0].len(), 14);
+            assert_eq!(
+                net.edges
+                    .iter()
+                    .filter(|edge| edge.kind == EdgeKind::Cut)
+                    .count(),
+                14
+            );
+
+            // The other 5 edges are (convex) mountain folds, each of which is labeled once
+            let angle_labels = net
+                .edges
+                .iter()
+                .filter_map(|edge| net.fold_angle_label(edge))
+                .collect::<Vec<_>>();
+            assert_eq!(angle_labels, vec![String::from("M 90°"); 5]);
+
+            // Every triangle is unfolded across one of its own sides, which is never a cut (except
+            // for the arbitrary incoming edge of the reference face)
+            assert_eq!(net.incoming_edges.len(), 12);
+            for (triangle, &[a, b]) in net.incoming_edges.iter().enumerate() {
+                assert!(a != b && a / 3 == triangle && b / 3 == triangle);
+                let is_cut = net
+                    .edges
+                    .iter()
+                    .any(|edge| edge.indices == [a, b] && edge.kind == EdgeKind::Cut);
+                assert!(!is_cut || net.polygon_depths[net.triangle_polygons[triangle]] == 0);
+            }
+        }
+    }
+
+    #[test]
+    fn test_generate_tabs() {
+        let goal_mesh = GoalMesh::from_obj(
+            Path::new("tests/fixtures/unit_cube.obj"),
+            &LoadOptions {
+                strict: true,
+                ..LoadOptions::default()
+            },
+        )
+        .unwrap();
+        let net = goal_mesh.unfold(0, UnfoldStrategy::BreadthFirst).unwrap();
+
+        for &(style, vertex_count) in [
+            (TabStyle::Trapezoid, 4),
+            (TabStyle::Rectangle, 4),
+            // The tabs are narrower than their edges, so their corners start at the ends of the edges
+            (TabStyle::Rounded, 2 + 2 * TAB_CORNER_SEGMENTS),
+        ]
+        .iter()
+        {
+            // Each of the 7 cut edges of the cube receives a single tab on one of its sides
+            let tabs = generate_tabs(
+                &net.positions,
+                &net.edges,
+                &TabOptions {
+                    width: 0.2,
+                    style,
+                    ..TabOptions::default()
+                },
+            );
+            assert_eq!(tabs.len(), 7);
+
+            for tab in tabs.iter() {
+                assert_eq!(tab.len(), vertex_count);
+                let (a, b) = (tab[0], tab[1]);
+                assert!(net.edges.iter().any(|edge| edge.kind == EdgeKind::Cut
+                    && net.positions[edge.indices[0]] == a
+                    && net.positions[edge.indices[1]] == b));
+
+                // Every tab is 0.2 units wide (outward) and never extends past the ends of its edge
+                let along = (b - a).normalize();
+                let outward = Vec3::new(along.y(), -along.x(), 0.0);
+                let width = tab
+                    .iter()
+                    .map(|point| (*point - a).dot(outward))
+                    .fold(0.0, f32::max);
+                assert!((width - 0.2).abs() < 1.0e-4);
+                assert!(tab.iter().all(|point| {
+                    let t = (*point - a).dot(along);
+                    t > -1.0e-4 && t < 1.0 + 1.0e-4
+                }));
+            }
+        }
+
+        // A trapezoid whose sides are perpendicular to its edge is a rectangle, and its taper is
+        // clamped when its sides are very slanted (so that they don't cross)
+        let rectangles = generate_tabs(
+            &net.positions,
+            &net.edges,
+            &TabOptions {
+                width: 0.2,
+                style: TabStyle::Rectangle,
+                ..TabOptions::default()
+            },
+        );
+        let trapezoids = generate_tabs(
+            &net.positions,
+            &net.edges,
+            &TabOptions {
+                width: 0.2,
+                style: TabStyle::Trapezoid,
+                angle: std::f32::consts::FRAC_PI_2,
+                ..TabOptions::default()
+            },
+        );
+        for (rectangle, trapezoid) in rectangles.iter().zip(trapezoids.iter()) {
+            for (p, q) in rectangle.iter().zip(trapezoid.iter()) {
+                assert!((*p - *q).length() < 1.0e-4);
+            }
+        }
+        let tapered = generate_tabs(
+            &net.positions,
+            &net.edges,
+            &TabOptions {
+                width: 0.2,
+                style: TabStyle::Trapezoid,
+                angle: 0.1,
+                ..TabOptions::default()
+            },
+        );
+        assert_eq!(tapered.len(), 7);
+        for tab in tapered.iter() {
+            assert!((tab[3] - tab[2]).length() > 0.5 - 1.0e-4);
+        }
+    }
+
+    #[test]
+    fn test_miter_tabs() {
+        let epsilon = 1.0e-5;
+        let width = 0.1;
+
+        // A reflex corner of the net, where the border turns right by 90 degrees: the two tabs
+        // overlap in the square at the corner, and are mitered along its diagonal
+        let (a, b, c) = (
+            Vec3::new(-1.0, 0.0, 0.0),
+            Vec3::zero(),
+            Vec3::new(0.0, -1.0, 0.0),
+        );
+        let first = vec![a, b, b - Vec3::unit_y() * width, a - Vec3::unit_y() * width];
+        let second = vec![b, c, c - Vec3::unit_x() * width, b - Vec3::unit_x() * width];
+        assert!(overlaps_polygon(&first, &second, epsilon));
+
+        let (mitered_first, mitered_second) = miter_tabs(
+            &first,
+            &second,
+            [a, b, c],
+            width,
+            DEFAULT_TAB_MITER_LIMIT,
+            epsilon,
+        );
+        assert!(!overlaps_polygon(&mitered_first, &mitered_second, epsilon));
+        assert_eq!(mitered_first.len(), 4);
+        assert_eq!(&mitered_first[..2], &first[..2]);
+        assert!((mitered_first[2] - Vec3::new(-width, -width, 0.0)).length() < epsilon);
+        assert_eq!(&mitered_second[..1], &second[..1]);
+        assert!(mitered_second
+            .iter()
+            .any(|point| (*point - Vec3::new(-width, -width, 0.0)).length() < epsilon));
+
+        // A narrow notch, where the border turns back by 160 degrees: the miter would run far out
+        // from the corner, so both tabs are cut back square until they just touch
+        let angle = 20.0f32.to_radians();
+        let towards_c = Vec3::new(-angle.cos(), -angle.sin(), 0.0);
+        let c = b + towards_c;
+        let outward = Vec3::new(towards_c.y(), -towards_c.x(), 0.0);
+        let second = vec![b, c, c + outward * width, b + outward * width];
+        assert!(overlaps_polygon(&first, &second, epsilon));
+
+        let (cut_first, cut_second) = miter_tabs(
+            &first,
+            &second,
+            [a, b, c],
+            width,
+            DEFAULT_TAB_MITER_LIMIT,
+            epsilon,
+        );
+        assert!(!overlaps_polygon(&cut_first, &cut_second, epsilon));
+        let inset = width / (angle * 0.5).tan();
+        assert_eq!(cut_first[0], a);
+        assert!((cut_first[1] - Vec3::new(-inset, 0.0, 0.0)).length() < epsilon);
+        assert!((cut_second[0] - (b + towards_c * inset)).length() < epsilon);
+        assert!((cut_second[1] - c).length() < epsilon);
+
+        // With a higher limit, the same tabs are mitered instead (and keep their inner corners)
+        let (mitered_first, _) = miter_tabs(&first, &second, [a, b, c], width, 10.0, epsilon);
+        assert_eq!(&mitered_first[..2], &first[..2]);
+    }
+
+    #[test]
+    fn test_outline() {
+        let goal_mesh = GoalMesh::from_obj(
+            Path::new("tests/fixtures/unit_cube.obj"),
+            &LoadOptions {
+                strict: true,
+                ..LoadOptions::default()
+            },
+        )
+        .unwrap();
+        let net = goal_mesh.unfold(0, UnfoldStrategy::BreadthFirst).unwrap();
+        let area = |points: &Vec<Vec3>| {
+            points
+                .iter()
+                .zip(points.iter().cycle().skip(1))
+                .map(|(a, b)| a.x() * b.y() - b.x() * a.y())
+                .sum::<f32>()
+                .abs()
+                * 0.5
+        };
+
+        // The outline of the cross runs around its 14 corners, along the cut edges only
+        let outline = net.outline(&[]).unwrap();
+        assert_eq!(outline.len(), 1);
+        assert_eq!(outline[0].len(), 14);
+        assert!((area(&outline[0]) - 6.0).abs() < 1.0e-4);
+
+        // Each (rectangular) tab adds its 2 outer corners to the outline, along with its area
+        let tabs = generate_tabs(
+            &net.positions,
+            &net.edges,
+            &TabOptions {
+                width: 0.1,
+                style: TabStyle::Rectangle,
+                ..TabOptions::default()
+            },
+        );
+        let outline = net.outline(&tabs).unwrap();
+        assert_eq!(outline.len(), 1);
+        assert_eq!(outline[0].len(), 14 + 7 * 2);
+        assert!((area(&outline[0]) - 6.7).abs() < 1.0e-4);
+
+        // With joinery, every tab is balanced out by a slot of the same shape, both of which lie
+        // along the middle of their edges
+        let (mut joints, slots) =
+            generate_joints(&net, 0.2, TabStyle::Rectangle, DEFAULT_TAB_ANGLE);
+        joints.extend(slots);
+        let outline = net.outline(&joints).unwrap();
+        assert_eq!(outline.len(), 1);
+        assert_eq!(outline[0].len(), 14 + 14 * 4);
+        assert!((area(&outline[0]) - 6.0).abs() < 1.0e-4);
+        assert!(joints.iter().flatten().all(|point| outline[0]
+            .iter()
+            .any(|other| (*other - *point).length() < 1.0e-4)));
+    }
+
+    #[test]
+    fn test_triangulation_edges() {
+        let goal_mesh = GoalMesh::from_obj(
+            Path::new("tests/fixtures/unit_cube.obj"),
+            &LoadOptions {
+                strict: true,
+                ..LoadOptions::default()
+            },
+        )
+        .unwrap();
+        let net = goal_mesh.unfold(0, UnfoldStrategy::BreadthFirst).unwrap();
+
+        // Each square face is split into 2 triangles along one of its diagonals (rather than one
+        // of its sides, which are all 1 unit long), and each diagonal is only returned once
+        let edges = net.triangulation_edges();
+        assert_eq!(edges.len(), 6);
+        let mut polygons = edges
+            .iter()
+            .map(|&[a, _]| net.triangle_polygons[a / 3])
+            .collect::<Vec<_>>();
+        polygons.sort();
+        assert_eq!(polygons, (0..6).collect::<Vec<_>>());
+        for &[a, b] in edges.iter() {
+            let length = (net.positions[a] - net.positions[b]).length();
+            assert!((length - 2.0f32.sqrt()).abs() < 1.0e-4);
+        }
+    }
+
+    #[test]
+    fn test_generate_joints() {
+        let goal_mesh = GoalMesh::from_obj(
+            Path::new("tests/fixtures/unit_cube.obj"),
+            &LoadOptions {
+                strict: true,
+                ..LoadOptions::default()
+            },
+        )
+        .unwrap();
+        let net = goal_mesh.unfold(0, UnfoldStrategy::BreadthFirst).unwrap();
+
+        // Each of the 7 cut edges of the cube receives a tab on one of its sides and a slot on the
+        // other, both along the middle half of the edge
+        let (tabs, slots) = generate_joints(&net, 0.2, TabStyle::Rectangle, DEFAULT_TAB_ANGLE);
+        assert_eq!(tabs.len(), 7);
+        assert_eq!(slots.len(), 7);
+
+        for (tab, slot) in tabs.iter().zip(slots.iter()) {
+            assert!(((tab[1] - tab[0]).length() - 0.5).abs() < 1.0e-4);
+            assert!(net.edges.iter().any(|edge| {
+                let a = net.positions[edge.indices[0]];
+                let b = net.positions[edge.indices[1]];
+                edge.kind == EdgeKind::Cut
+                    && (a.lerp(b, 0.25) - tab[0]).length() < 1.0e-4
+                    && (a.lerp(b, 0.75) - tab[1]).length() < 1.0e-4
+            }));
+
+            // The slot has the same shape as its tab, and lies inside of the net
+            assert_eq!(tab.len(), slot.len());
+            for i in 0..tab.len() {
+                let j = (i + 1) % tab.len();
+                assert!(((tab[j] - tab[i]).length() - (slot[j] - slot[i]).length()).abs() < 1.0e-4);
+            }
+            let center = find_centroid(slot);
+            assert!(
+                find_triangle_containing(&net.positions, &Vec2::new(center.x(), center.y()))
+                    .is_some()
+            );
+        }
+
+        // Slots that are deeper than the faces of the cube don't fit anywhere
+        let (tabs, slots) = generate_joints(&net, 1.5, TabStyle::Rectangle, DEFAULT_TAB_ANGLE);
+        assert!(tabs.is_empty() && slots.is_empty());
+    }
+
+    #[test]
+    fn test_min_weight_spanning_tree() {
+        // A 1 x 1 x 4 box, starting with one of its square ends
+        let obj = "v 0 0 0\nv 1 0 0\nv 1 1 0\nv 0 1 0\nv 0 0 4\nv 1 0 4\nv 1 1 4\nv 0 1 4\n\
+                   f 1 4 3 2\nf 5 6 7 8\nf 1 2 6 5\nf 2 3 7 6\nf 3 4 8 7\nf 4 1 5 8\n";
+        let goal_mesh = GoalMesh::from_obj_reader(
+            &mut obj.as_bytes(),
+            Path::new(""),
+            &LoadOptions {
+                strict: true,
+                ..LoadOptions::default()
+            },
+        )
+        .unwrap();
+
+        // Walking outwards from the end of the box folds the 4 short edges around it (and one more
+        // to reach the other end), while the longest folds join the long sides of the box along 3
+        // of their long edges (and attach each end along one of its short edges), no matter which
+        // face the net is unfolded from
+        let breadth_first = goal_mesh.unfold(0, UnfoldStrategy::BreadthFirst).unwrap();
+        assert!((breadth_first.fold_length() - 5.0).abs() < 1.0e-4);
+        for root in 0..goal_mesh.face_count() {
+            let net = goal_mesh
+                .unfold(root, UnfoldStrategy::MinWeightSpanningTree)
+                .unwrap();
+            assert!((net.fold_length() - 14.0).abs() < 1.0e-4);
+            assert!(net.overlaps.is_empty());
+        }
+    }
+
+    #[test]
+    fn test_strip() {
+        // A hexagonal prism with sides of length 1 and a height of 3, starting with its two caps
+        let mut obj = String::new();
+        for &z in [0.0, 3.0].iter() {
+            for i in 0..6 {
+                let angle = (i as f32 * 60.0).to_radians();
+                obj.push_str(&format!("v {} {} {}\n", angle.cos(), angle.sin(), z));
+            }
+        }
+        obj.push_str("f 6 5 4 3 2 1\nf 7 8 9 10 11 12\n");
+        for i in 1..=6 {
+            let j = i % 6 + 1;
+            obj.push_str(&format!("f {} {} {} {}\n", i, j, j + 6, i + 6));
+        }
+        let goal_mesh = GoalMesh::from_obj_reader(
+            &mut obj.as_bytes(),
+            Path::new(""),
+            &LoadOptions {
+                strict: true,
+                ..LoadOptions::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(goal_mesh.strip, Some((2..8).collect()));
+
+        // The sides are joined along 5 of their long edges into a 6 x 3 band, and both caps are
+        // attached to the same side (across from each other), no matter which face the net is
+        // unfolded from
+        for root in 0..goal_mesh.face_count() {
+            let net = goal_mesh.unfold(root, UnfoldStrategy::Strip).unwrap();
+            assert!((net.fold_length() - 17.0).abs() < 1.0e-4);
+            assert!(net.overlaps.is_empty());
+            let (_, width, height) = find_oriented_bounding_box(&net.positions);
+            let (long, short) = (width.max(height), width.min(height));
+            assert!((long - (3.0 + 2.0 * 3.0f32.sqrt())).abs() < 1.0e-4);
+            assert!((short - 6.0).abs() < 1.0e-4);
+        }
+
+        // A tetrahedron doesn't have a loop of sides, so it is unfolded breadth-first instead
+        let goal_mesh = GoalMesh::from_obj(
+            Path::new("goal_meshes/tetrahedron.obj"),
+            &LoadOptions {
+                strict: true,
+                ..LoadOptions::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(goal_mesh.strip, None);
+        let strip = goal_mesh.unfold(0, UnfoldStrategy::Strip).unwrap();
+        let breadth_first = goal_mesh.unfold(0, UnfoldStrategy::BreadthFirst).unwrap();
+        assert_eq!(strip.polygon_depths, breadth_first.polygon_depths);
+    }
+
+    #[test]
+    fn test_gluing_order() {
+        let goal_mesh = GoalMesh::from_obj(
+            Path::new("tests/fixtures/unit_cube.obj"),
+            &LoadOptions {
+                strict: true,
+                ..LoadOptions::default()
+            },
+        )
+        .unwrap();
+        let net = goal_mesh.unfold(0, UnfoldStrategy::BreadthFirst).unwrap();
+
+        // Each of the 7 cut edges of the cube is glued exactly once: first the 4 cuts between the
+        // neighbors of the root face, and then the 3 cuts around the opposite face
+        let order = net.gluing_order();
+        assert_eq!(order.len(), 7);
+        let depths = order
+            .iter()
+            .map(|(edge, other)| {
+                let depth = |edge: &NetEdge| {
+                    net.polygon_depths[net.triangle_polygons[usize::from(edge.face)]]
+                };
+                depth(edge).max(depth(other))
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(depths, vec![1, 1, 1, 1, 2, 2, 2]);
+        for (edge, other) in order.iter() {
+            assert_eq!(edge.pair, other.half_edge);
+            assert_eq!(net.cut_label(edge), net.cut_label(other));
+        }
+    }
+
+    #[test]
+    fn test_from_obj_reader() {
+        // The cube can be read from memory just as well as from the fixture
+        let obj = std::fs::read_to_string("tests/fixtures/unit_cube.obj").unwrap();
+        let goal_mesh = GoalMesh::from_obj_reader(
+            &mut obj.as_bytes(),
+            Path::new("tests/fixtures"),
+            &LoadOptions {
+                strict: true,
+                ..LoadOptions::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(goal_mesh.face_count(), 6);
+
+        // Empty input (e.g. an empty pipe) doesn't contain a goal mesh
+        assert!(matches!(
+            GoalMesh::from_obj_reader(
+                &mut "".as_bytes(),
+                Path::new(""),
+                &LoadOptions {
+                    strict: true,
+                    coplanar_tolerance: 0.0,
+                    ..LoadOptions::default()
+                }
+            ),
+            Err(GoalMeshError::Empty)
+        ));
+        assert!(matches!(
+            GoalMesh::from_obj_reader(
+                &mut "# nothing here\n".as_bytes(),
+                Path::new(""),
+                &LoadOptions {
+                    strict: true,
+                    coplanar_tolerance: 0.0,
+                    ..LoadOptions::default()
+                }
+            ),
+            Err(GoalMeshError::Empty)
+        ));
+    }
+
+    #[test]
+    fn test_vertex_colors() {
+        // A unit cube whose bottom vertices are red and whose top vertices are blue, except for one
+        // top vertex without a color
+        let obj = "v 0 0 0 1 0 0\nv 1 0 0 1 0 0\nv 1 1 0 1 0 0\nv 0 1 0 1 0 0\n\
+                   v 0 0 1 0 0 1\nv 1 0 1 0 0 1\nv 1 1 1 0 0 1\nv 0 1 1\n\
+                   f 1 4 3 2\nf 5 6 7 8\nf 1 2 6 5\nf 4 8 7 3\nf 1 5 8 4\nf 2 3 7 6\n";
+        let goal_mesh = GoalMesh::from_obj_reader(
+            &mut obj.as_bytes(),
+            Path::new(""),
+            &LoadOptions {
+                strict: true,
+                ..LoadOptions::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(goal_mesh.face_count(), 6);
+
+        // The sides are halfway between red and blue in linear space, which is brighter in SRGB
+        let is_color = |face: usize, expected: Vec3| matches!(goal_mesh.face_vertex_color(face), Some(color) if (color - expected).length() < 1.0e-4);
+        let halfway = linear_to_srgb(0.5);
+        assert!(is_color(0, Vec3::new(1.0, 0.0, 0.0)));
+        assert!(is_color(2, Vec3::new(halfway, 0.0, halfway)));
+        assert_eq!(goal_mesh.face_vertex_color(1), None);
+        assert_eq!(goal_mesh.face_vertex_color(3), None);
+
+        // Without any colors, the extra numbers are simply missing
+        let goal_mesh = GoalMesh::from_obj(
+            Path::new("tests/fixtures/unit_cube.obj"),
+            &LoadOptions {
+                strict: true,
+                ..LoadOptions::default()
+            },
+        )
+        .unwrap();
+        assert!((0..6).all(|face| goal_mesh.face_vertex_color(face).is_none()));
+    }
+
+    #[test]
+    fn test_malformed_obj() {
+        let load = |obj: &str| {
+            GoalMesh::from_obj_reader(
+                &mut obj.as_bytes(),
+                Path::new(""),
+                &LoadOptions {
+                    strict: true,
+                    coplanar_tolerance: 0.0,
+                    ..LoadOptions::default()
+                },
+            )
+        };
+        let syntax_error = |obj: &str| match load(obj) {
+            Err(GoalMeshError::Syntax { line, .. }) => Some(line),
+            _ => None,
+        };
+        let triangle = "v 0 0 0\nv 1 0 0\nv 0 1 0\n";
+
+        assert!(matches!(
+            GoalMesh::from_obj(
+                Path::new("tests/fixtures/missing.obj"),
+                &LoadOptions {
+                    strict: true,
+                    coplanar_tolerance: 0.0,
+                    ..LoadOptions::default()
+                }
+            ),
+            Err(GoalMeshError::Io(_))
+        ));
+        assert!(matches!(load(triangle), Err(GoalMeshError::Empty)));
+        assert_eq!(syntax_error(&format!("{}f 1 2 4\n", triangle)), Some(4));
+        assert_eq!(syntax_error(&format!("{}f 1 2 0\n", triangle)), Some(4));
+        assert_eq!(syntax_error(&format!("{}f -1 -2 -4\n", triangle)), Some(4));
+        assert_eq!(syntax_error(&format!("{}f 1 2\n", triangle)), Some(4));
+        assert_eq!(syntax_error(&format!("{}f 1 2 x\n", triangle)), Some(4));
+        assert_eq!(
+            syntax_error("v 0 0 0\nv 1 nan 0\nv 0 1 0\nf 1 2 3\n"),
+            Some(2)
+        );
+        assert_eq!(syntax_error("v 0 0\n"), Some(1));
+
+        // Texture coordinates, normals, and relative indices are fine (even though the single
+        // triangle isn't a closed mesh)
+        assert!(matches!(
+            load(&format!("{}vn 0 0 1\nf 1//1 2//1 -1//1\n", triangle)),
+            Err(GoalMeshError::Topology(_))
+        ));
+    }
+
+    #[test]
+    fn test_fix_winding() {
+        let obj = std::fs::read_to_string("tests/fixtures/unit_cube.obj").unwrap();
+        let load = |obj: &str, fix_winding: bool| {
+            GoalMesh::from_obj_reader(
+                &mut obj.as_bytes(),
+                Path::new(""),
+                &LoadOptions {
+                    strict: true,
+                    fix_winding,
+                    ..LoadOptions::default()
+                },
+            )
+        };
+        let is_convex = |goal_mesh: &GoalMesh| {
+            let net = goal_mesh.unfold(0, UnfoldStrategy::BreadthFirst).unwrap();
+            net.edges
+                .iter()
+                .all(|edge| edge.kind == EdgeKind::Cut || edge.kind == EdgeKind::Mountain)
+        };
+
+        // Two of the faces are wound the wrong way, which only loads once they are flipped back
+        let mixed = obj
+            .replace("f 1 2 6 5", "f 5 6 2 1")
+            .replace("f 2 3 7 6", "f 6 7 3 2");
+        assert!(matches!(
+            load(&mixed, false),
+            Err(GoalMeshError::Topology(_))
+        ));
+        let goal_mesh = load(&mixed, true).unwrap();
+        assert_eq!(goal_mesh.face_count(), 6);
+        assert!(is_convex(&goal_mesh));
+
+        // A cube that is inside out loads either way, but is only convex once it is flipped
+        let inverted = obj
+            .lines()
+            .map(|line| match line.strip_prefix("f ") {
+                Some(indices) => {
+                    format!("f {}", indices.rsplit(' ').collect::<Vec<_>>().join(" "))
+                }
+                None => line.to_string(),
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        assert!(!is_convex(&load(&inverted, false).unwrap()));
+        assert!(is_convex(&load(&inverted, true).unwrap()));
+
+        // A Möbius strip made of 3 quads can't be oriented consistently, due to its half twist
+        let polygons = vec![vec![0, 3, 4, 1], vec![1, 4, 5, 2], vec![2, 5, 0, 3]]
+            .into_iter()
+            .map(|polygon| polygon.into_iter().map(VertexIndex).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        let vertices = vec![Vec3::zero(); 6];
+        assert_eq!(orient_polygons(&polygons, &vertices), (vec![false; 3], 1));
+    }
+
+    #[test]
+    fn test_non_manifold_edge() {
+        // Two tetrahedra that share a single edge (between the first two vertices), which is
+        // reported along with the 4 faces of the .obj file around it, rather than failing to build
+        // the half-edge mesh
+        let text = "v -1 0 0\nv 0 -1 0\nv 1 0 0\nv 0 0 1\nv 0 -2 -1\nv -1 -1 -1\n\
+                    f 1 3 2\nf 1 2 4\nf 2 3 4\nf 3 1 4\n\
+                    f 1 5 2\nf 1 2 6\nf 2 5 6\nf 5 1 6\n";
+        match GoalMesh::from_obj_reader(
+            &mut text.as_bytes(),
+            Path::new(""),
+            &LoadOptions::default(),
+        ) {
+            Err(GoalMeshError::Topology(TopologyError::NonManifoldEdge { faces, .. })) => {
+                assert_eq!(
+                    faces,
+                    vec![FaceIndex(0), FaceIndex(1), FaceIndex(4), FaceIndex(5)]
+                );
+            }
+            _ => panic!("expected a non-manifold edge"),
+        }
+    }
+
+    #[test]
+    fn test_open_surface() {
+        let load = |allow_open: bool| {
+            GoalMesh::from_obj(
+                Path::new("tests/fixtures/open_box.obj"),
+                &LoadOptions {
+                    strict: true,
+                    allow_open,
+                    ..LoadOptions::default()
+                },
+            )
+        };
+
+        // A box without a lid is rejected, unless open surfaces are allowed
+        assert!(matches!(
+            load(false),
+            Err(GoalMeshError::Topology(TopologyError::BoundaryEdge { .. }))
+        ));
+        let goal_mesh = load(true).unwrap();
+        assert_eq!(goal_mesh.face_count(), 5);
+        assert_eq!(goal_mesh.half_edge_mesh().topology().border_edges, 4);
+
+        for &strategy in [
+            UnfoldStrategy::BreadthFirst,
+            UnfoldStrategy::DepthFirst,
+            UnfoldStrategy::SteepestEdge,
+            UnfoldStrategy::MinWeightSpanningTree,
+        ]
+        .iter()
+        {
+            // The 4 edges around the rim are cut, but they aren't labeled, since they aren't glued
+            // to anything (unlike the 4 edges between the walls that the unfolding has to cut, in
+            // order to lay the 5 faces out flat)
+            let net = goal_mesh.unfold(0, strategy).unwrap();
+            assert!(net.overlaps.is_empty());
+            let border = net.edges.iter().filter(|edge| edge.border);
+            assert_eq!(border.clone().count(), 4);
+            assert!(border
+                .clone()
+                .all(|edge| edge.kind == EdgeKind::Cut && net.cut_label(edge).is_none()));
+            let cuts = net
+                .edges
+                .iter()
+                .filter(|edge| edge.kind == EdgeKind::Cut && !edge.border)
+                .collect::<Vec<_>>();
+            assert_eq!(cuts.len(), 4 * 2);
+            assert!(cuts.iter().all(|edge| net.cut_label(edge).is_some()));
+
+            // Only the matching cuts receive tabs
+            let tabs = generate_tabs(
+                &net.positions,
+                &net.edges,
+                &TabOptions {
+                    width: 0.1,
+                    style: TabStyle::Rectangle,
+                    ..TabOptions::default()
+                },
+            );
+            assert_eq!(tabs.len(), 4);
+            assert!(tabs.iter().all(|tab| border.clone().all(|edge| {
+                let (a, b) = (
+                    net.positions[edge.indices[0]],
+                    net.positions[edge.indices[1]],
+                );
+                (tab[0] - a).length() + (tab[1] - b).length() > 1.0e-4
+            })));
+        }
+
+        // An open surface has no inside, so fixing its winding order keeps the orientation of the
+        // first face
+        let obj = std::fs::read_to_string("tests/fixtures/open_box.obj").unwrap();
+        let mixed = obj.replace("f 1 2 6 5", "f 5 6 2 1");
+        let goal_mesh = GoalMesh::from_obj_reader(
+            &mut mixed.as_bytes(),
+            Path::new(""),
+            &LoadOptions {
+                strict: true,
+                fix_winding: true,
+                allow_open: true,
+                ..LoadOptions::default()
+            },
+        )
+        .unwrap();
+        let net = goal_mesh.unfold(0, UnfoldStrategy::BreadthFirst).unwrap();
+        assert!(net
+            .edges
+            .iter()
+            .all(|edge| edge.kind == EdgeKind::Cut || edge.kind == EdgeKind::Mountain));
+    }
+
+    #[test]
+    fn test_fold_positions() {
+        let goal_mesh = GoalMesh::from_obj(
+            Path::new("goal_meshes/dodecahedron.obj"),
+            &LoadOptions {
+                strict: true,
+                ..LoadOptions::default()
+            },
+        )
+        .unwrap();
+        let close = |a: &Vec3, b: &Vec3| (*a - *b).length() < 1.0e-4;
+
+        for &flip in [false, true].iter() {
+            // The net is moved around the page afterwards (just like the viewer does), which the
+            // fold has to follow
+            let mut net = goal_mesh.unfold(0, UnfoldStrategy::BreadthFirst).unwrap();
+            if flip {
+                net.flip();
+            }
+            let rotation = Mat3::from_rotation_z(0.5);
+            for point in net.positions.iter_mut() {
+                *point = rotation.mul_vec3(*point) * 2.0 + Vec3::new(3.0, -1.0, 0.0);
+            }
+
+            // Fully unfolded, the positions are the net itself
+            let flat = goal_mesh.fold_positions(&net, 1.0);
+            assert!(flat
+                .iter()
+                .zip(net.positions.iter())
+                .all(|(a, b)| close(a, b)));
+
+            // Halfway there, the faces on either side of each fold are still joined
+            let halfway = goal_mesh.fold_positions(&net, 0.5);
+            for edge in net.edges.iter().filter(|edge| edge.kind.is_fold()) {
+                let other = net
+                    .edges
+                    .iter()
+                    .find(|other| other.half_edge == edge.pair)
+                    .unwrap();
+                assert!(close(&halfway[edge.indices[0]], &halfway[other.indices[1]]));
+                assert!(close(&halfway[edge.indices[1]], &halfway[other.indices[0]]));
+            }
+
+            // Folded up, the corners of every vertex of the goal mesh come back together, and the
+            // goal mesh is scaled just like the net
+            let folded = goal_mesh.fold_positions(&net, 0.0);
+            let mut corners: HashMap<VertexIndex, Vec3> = HashMap::new();
+            for fid in goal_mesh.half_edge_mesh.face_id_iter() {
+                for (corner, vid) in goal_mesh
+                    .half_edge_mesh
+                    .adjacent_vertices_to_face(fid)
+                    .enumerate()
+                {
+                    let corner = if flip && corner > 0 {
+                        3 - corner
+                    } else {
+                        corner
+                    };
+                    let position = folded[usize::from(fid) * 3 + corner];
+                    assert!(close(corners.entry(vid).or_insert(position), &position));
+                }
+            }
+            assert_eq!(corners.len(), 20);
+            let diameter = |points: &[Vec3]| {
+                points
+                    .iter()
+                    .flat_map(|a| points.iter().map(move |b| (*a - *b).length()))
+                    .fold(0.0, f32::max)
+            };
+            let corners = corners.values().cloned().collect::<Vec<_>>();
+            assert!(
+                (diameter(&corners) - diameter(&goal_mesh.vertex_coordinates()) * 2.0).abs()
+                    < 1.0e-3
+            );
+        }
+    }
+
+    #[test]
+    fn test_find_self_intersections() {
+        let obj = std::fs::read_to_string("tests/fixtures/unit_cube.obj").unwrap();
+        let load = |obj: &str| {
+            GoalMesh::from_obj_reader(
+                &mut obj.as_bytes(),
+                Path::new(""),
+                &LoadOptions {
+                    strict: true,
+                    ..LoadOptions::default()
+                },
+            )
+            .unwrap()
+        };
+        assert!(load(&obj).find_self_intersections().is_empty());
+
+        // A second cube that is moved halfway along the diagonal of the first one, so that the 3
+        // far sides of the first cube cross the 3 near sides of the second cube
+        let mut overlapping = obj.clone();
+        for line in obj.lines() {
+            if let Some(coordinates) = line.strip_prefix("v ") {
+                let moved = coordinates
+                    .split(' ')
+                    .map(|coordinate| (coordinate.parse::<f32>().unwrap() + 0.5).to_string())
+                    .collect::<Vec<_>>();
+                overlapping.push_str(&format!("v {}\n", moved.join(" ")));
+            }
+        }
+        for line in obj.lines() {
+            if let Some(indices) = line.strip_prefix("f ") {
+                let moved = indices
+                    .split(' ')
+                    .map(|index| (index.parse::<usize>().unwrap() + 8).to_string())
+                    .collect::<Vec<_>>();
+                overlapping.push_str(&format!("f {}\n", moved.join(" ")));
+            }
+        }
+        let goal_mesh = load(&overlapping);
+        assert_eq!(goal_mesh.component_count, 2);
+        assert_eq!(
+            goal_mesh.find_self_intersections(),
+            vec![(1, 8), (1, 10), (3, 6), (3, 10), (5, 6), (5, 8)]
+        );
+    }
+
+    #[test]
+    fn test_from_stl() {
+        // The .stl fixture is the same cube as the .obj fixture, but with every face split into two
+        // triangles (each with its own copy of its vertices), which are welded and merged back into
+        // the same 6 squares
+        let path = Path::new("tests/fixtures/unit_cube.stl");
+        let goal_mesh = GoalMesh::from_stl(
+            path,
+            &LoadOptions {
+                strict: true,
+                ..LoadOptions::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(goal_mesh.half_edge_mesh().topology().vertices, 8);
+        assert_eq!(goal_mesh.face_count(), 6);
+        let net = goal_mesh.unfold(0, UnfoldStrategy::BreadthFirst).unwrap();
+        assert!(net.polygons.iter().all(|polygon| polygon.len() == 4));
+        assert!(net.overlaps.is_empty());
+
+        // Without merging, every triangle is a separate face
+        let goal_mesh = GoalMesh::from_stl(
+            path,
+            &LoadOptions {
+                strict: true,
+                coplanar_tolerance: 0.0,
+                ..LoadOptions::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(goal_mesh.face_count(), 12);
+
+        // An empty .stl file is reported as such (rather than as an .obj file without any faces)
+        let empty_path = std::env::temp_dir().join("durer_test_from_stl_empty.stl");
+        std::fs::write(&empty_path, "").unwrap();
+        match GoalMesh::from_stl(&empty_path, &LoadOptions::default()) {
+            Err(error @ GoalMeshError::Stl(StlError::Empty)) => {
+                assert!(error.to_string().contains(".stl file"))
+            }
+            _ => panic!("expected an empty .stl file to be rejected"),
+        }
+    }
+
+    #[test]
+    fn test_from_vertices_faces() {
+        // The same cube as the .obj fixture, with every face split into two triangles
+        let vertices = (0..8)
+            .map(|i| Vec3::new((i & 1) as f32, ((i >> 1) & 1) as f32, ((i >> 2) & 1) as f32))
+            .collect::<Vec<_>>();
+        let faces = vec![
+            [0, 2, 3],
+            [0, 3, 1],
+            [4, 5, 7],
+            [4, 7, 6],
+            [0, 1, 5],
+            [0, 5, 4],
+            [2, 6, 7],
+            [2, 7, 3],
+            [0, 4, 6],
+            [0, 6, 2],
+            [1, 3, 7],
+            [1, 7, 5],
+        ];
+        let goal_mesh = GoalMesh::from_vertices_faces(
+            &vertices,
+            &faces,
+            &LoadOptions {
+                strict: true,
+                ..LoadOptions::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(goal_mesh.half_edge_mesh().topology().vertices, 8);
+        assert_eq!(goal_mesh.face_count(), 6);
+        let net = goal_mesh.unfold(0, UnfoldStrategy::BreadthFirst).unwrap();
+        assert!(net.polygons.iter().all(|polygon| polygon.len() == 4));
+        assert!(net.overlaps.is_empty());
+
+        // Missing vertices, missing faces, and open meshes are rejected
+        assert!(matches!(
+            GoalMesh::from_vertices_faces(
+                &vertices,
+                &[[0, 2, 8]],
+                &LoadOptions {
+                    strict: true,
+                    coplanar_tolerance: 0.0,
+                    ..LoadOptions::default()
+                }
+            ),
+            Err(GoalMeshError::InvalidVertexIndex {
+                face: 0,
+                index: 8,
+                vertices: 8
+            })
+        ));
+        assert!(matches!(
+            GoalMesh::from_vertices_faces(
+                &vertices,
+                &[],
+                &LoadOptions {
+                    strict: true,
+                    coplanar_tolerance: 0.0,
+                    ..LoadOptions::default()
+                }
+            ),
+            Err(GoalMeshError::Empty)
+        ));
+        assert!(matches!(
+            GoalMesh::from_vertices_faces(
+                &vertices,
+                &faces[1..],
+                &LoadOptions {
+                    strict: true,
+                    coplanar_tolerance: 0.0,
+                    ..LoadOptions::default()
+                }
+            ),
+            Err(GoalMeshError::Topology(_))
+        ));
+    }
+
+    #[test]
+    fn test_adjacent_faces() {
+        let goal_mesh = GoalMesh::from_obj(
+            Path::new("tests/fixtures/unit_cube.obj"),
+            &LoadOptions {
+                strict: true,
+                ..LoadOptions::default()
+            },
+        )
+        .unwrap();
+
+        // Every square of the cube borders 4 of the other squares (i.e. all of them except the
+        // opposite one, whose normal points the other way), and the neighbors agree with each other
+        for face in 0..goal_mesh.face_count() {
+            let normal = goal_mesh.face_normal(face);
+            assert!((normal.length() - 1.0).abs() < 1.0e-6);
+
+            let neighbors = goal_mesh.adjacent_faces(face);
+            assert_eq!(neighbors.len(), goal_mesh.face_vertices(face).len());
+            assert_eq!(neighbors.iter().collect::<HashSet<_>>().len(), 4);
+            for neighbor in neighbors.iter() {
+                let neighbor = neighbor.unwrap();
+                assert!(goal_mesh.face_normal(neighbor).dot(normal).abs() < 1.0e-6);
+                assert!(goal_mesh.adjacent_faces(neighbor).contains(&Some(face)));
+            }
+        }
+    }
+
+    #[test]
+    fn test_split_into_islands() {
+        let goal_mesh = GoalMesh::from_obj(
+            Path::new("tests/fixtures/unit_cube.obj"),
+            &LoadOptions {
+                strict: true,
+                ..LoadOptions::default()
+            },
+        )
+        .unwrap();
+
+        // Without a maximum size, the cube unfolds into a single cross (which is 3 x 4 units)
+        let net = goal_mesh
+            .unfold_best(0, UnfoldStrategy::BreadthFirst, 0, 0, 0.0)
+            .unwrap();
+        assert_eq!(net.island_count(), 1);
+
+        let net = goal_mesh
+            .unfold_best(0, UnfoldStrategy::BreadthFirst, 0, 0, 2.5)
+            .unwrap();
+        assert!(net.island_count() > 1);
+        assert!(net.is_valid());
+        for island in 0..net.island_count() {
+            let points = net
+                .positions
+                .chunks(3)
+                .zip(net.triangle_polygons.iter())
+                .filter(|(_, &polygon)| net.polygon_islands[polygon] == island)
+                .flat_map(|(triangle, _)| triangle.iter().cloned())
+                .collect::<Vec<_>>();
+            let (_, width, height) = find_oriented_bounding_box(&points);
+            assert!(width.max(height) <= 2.5 + 1.0e-4);
+        }
+
+        // The islands are still a single piece of the goal mesh, whose faces are only joined by the
+        // folds within each island: every other edge is cut, and both sides share a label
+        assert_eq!(net.component_count(), 1);
+        let folds = net.edges.iter().filter(|edge| edge.kind.is_fold()).count() / 2;
+        assert_eq!(folds, 6 - net.island_count());
+        assert_eq!(net.cut_labels.len() / 2, 12 - folds);
+        for edge in net.edges.iter().filter(|edge| edge.kind == EdgeKind::Cut) {
+            assert_eq!(
+                net.cut_label(edge),
+                net.edges
+                    .iter()
+                    .find(|other| other.half_edge == edge.pair)
+                    .and_then(|other| net.cut_label(other))
+            );
+        }
+
+        // Each island is placed separately once it is unfolded
+        for (folded, position) in goal_mesh
+            .fold_positions(&net, 1.0)
+            .iter()
+            .zip(net.positions.iter())
+        {
+            assert!((*folded - *position).length() < 1.0e-4);
+        }
+    }
+
+    #[test]
+    fn test_random_spanning_trees() {
+        let goal_mesh = GoalMesh::from_obj(
+            Path::new("tests/fixtures/unit_cube.obj"),
+            &LoadOptions {
+                strict: true,
+                ..LoadOptions::default()
+            },
+        )
+        .unwrap();
+
+        // Every spanning tree of the cube's faces crosses 5 of its 12 edges, and (unlike most
+        // polyhedra) the cube never unfolds into a net that overlaps
+        let mut rng = StdRng::seed_from_u64(0);
+        let mut trees = HashSet::new();
+        for _ in 0..20 {
+            let tree = goal_mesh.compute_random_spanning_tree(FaceIndex(0), &mut rng);
+            let net = goal_mesh.unfold_along(&tree, Instant::now());
+            let folds = net.edges.iter().filter(|edge| edge.kind.is_fold()).count();
+            assert_eq!(folds, 5 * 2);
+            assert!(net.overlaps.is_empty());
+            assert!((net.polygon_areas(1.0).iter().sum::<f32>() - 6.0).abs() < 1.0e-4);
+
+            let mut crossed_edges = tree
+                .crossed_edges
+                .iter()
+                .map(|&eid| usize::from(eid.min(goal_mesh.half_edge_mesh.half_edge(eid).pair())))
+                .collect::<Vec<_>>();
+            crossed_edges.sort();
+            trees.insert(crossed_edges);
+        }
+        assert!(trees.len() > 1);
+
+        // The search stops as soon as it finds a net without overlaps, which is the first one here
+        let net = goal_mesh
+            .unfold_best(0, UnfoldStrategy::BreadthFirst, 10, 0, 0.0)
+            .unwrap();
+        assert!(net.overlaps.is_empty());
+    }
+
+    #[test]
+    fn test_face_order() {
+        let goal_mesh = GoalMesh::from_obj(
+            Path::new("tests/fixtures/unit_cube.obj"),
+            &LoadOptions {
+                strict: true,
+                ..LoadOptions::default()
+            },
+        )
+        .unwrap();
+        let face_order = |net: &UnfoldResult| {
+            let triangles = (0..net.triangle_polygons.len())
+                .map(|triangle| net.triangle_obj_face(triangle))
+                .collect::<Vec<_>>();
+            let edges = net
+                .edges
+                .iter()
+                .map(|edge| (edge.face, edge.half_edge))
+                .collect::<Vec<_>>();
+            (triangles, edges)
+        };
+
+        let strategies = [
+            UnfoldStrategy::BreadthFirst,
+            UnfoldStrategy::DepthFirst,
+            UnfoldStrategy::SteepestEdge,
+            UnfoldStrategy::MinWeightSpanningTree,
+            UnfoldStrategy::Strip,
+        ];
+
+        // The triangles (and edges) of the net are stored in the order of the faces of the goal
+        // mesh, no matter which spanning tree the net was unfolded along, or whether it was split
+        // into islands (which are packed separately)
+        let expected = face_order(&goal_mesh.unfold(0, UnfoldStrategy::BreadthFirst).unwrap());
+        assert!(expected.0.windows(2).all(|pair| pair[0] <= pair[1]));
+        assert_eq!(expected.0.len(), 12);
+        for &strategy in strategies.iter() {
+            for reference_face in [0, 5].iter() {
+                let net = goal_mesh.unfold(*reference_face, strategy).unwrap();
+                assert_eq!(face_order(&net), expected);
+
+                let net = goal_mesh
+                    .unfold_best(*reference_face, strategy, 0, 0, 2.5)
+                    .unwrap();
+                assert!(net.island_count() > 1);
+                assert_eq!(face_order(&net), expected);
+            }
+        }
+        let mut rng = StdRng::seed_from_u64(0);
+        for _ in 0..5 {
+            let tree = goal_mesh.compute_random_spanning_tree(FaceIndex(0), &mut rng);
+            let net = goal_mesh.unfold_along(&tree, Instant::now());
+            assert_eq!(face_order(&net), expected);
+        }
+
+        // Unfolding the same goal mesh twice gives exactly the same net
+        let first = goal_mesh.unfold(3, UnfoldStrategy::DepthFirst).unwrap();
+        let second = goal_mesh.unfold(3, UnfoldStrategy::DepthFirst).unwrap();
+        assert_eq!(first.positions, second.positions);
+        assert_eq!(first.polygons, second.polygons);
+
+        // The nets of separate solids are packed side by side, but their triangles are still in
+        // the order of the faces of the goal mesh (i.e. the first cube comes first)
+        let vertices = (0..16)
+            .map(|i| {
+                let offset = (i >> 3) as f32 * 3.0;
+                Vec3::new(
+                    (i & 1) as f32 + offset,
+                    ((i >> 1) & 1) as f32,
+                    ((i >> 2) & 1) as f32,
+                )
+            })
+            .collect::<Vec<_>>();
+        let cube = [
+            [0, 2, 3],
+            [0, 3, 1],
+            [4, 5, 7],
+            [4, 7, 6],
+            [0, 1, 5],
+            [0, 5, 4],
+            [2, 6, 7],
+            [2, 7, 3],
+            [0, 4, 6],
+            [0, 6, 2],
+            [1, 3, 7],
+            [1, 7, 5],
+        ];
+        let faces = cube
+            .iter()
+            .cloned()
+            .chain(cube.iter().map(|&[a, b, c]| [a + 8, b + 8, c + 8]))
+            .collect::<Vec<_>>();
+        let goal_mesh = GoalMesh::from_vertices_faces(
+            &vertices,
+            &faces,
+            &LoadOptions {
+                strict: true,
+                ..LoadOptions::default()
+            },
+        )
+        .unwrap();
+        let expected = face_order(&goal_mesh.unfold(0, UnfoldStrategy::BreadthFirst).unwrap());
+        assert!(expected.0.windows(2).all(|pair| pair[0] <= pair[1]));
+        assert_eq!(expected.0.len(), 24);
+        for &strategy in strategies.iter() {
+            for reference_face in [0, 9].iter() {
+                let net = goal_mesh.unfold(*reference_face, strategy).unwrap();
+                assert_eq!(net.component_count(), 2);
+                assert_eq!(face_order(&net), expected);
+
+                let net = goal_mesh
+                    .unfold_best(*reference_face, strategy, 0, 0, 2.5)
+                    .unwrap();
+                assert_eq!(face_order(&net), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn test_count_overlaps_on_threads() {
+        let goal_mesh = GoalMesh::from_obj(
+            Path::new("goal_meshes/icosahedron.obj"),
+            &LoadOptions {
+                strict: true,
+                ..LoadOptions::default()
+            },
+        )
+        .unwrap();
+
+        // The root faces are unfolded in any order, but every root face is unfolded exactly once
+        // and its count ends up in its own slot
+        let expected = (0..goal_mesh.face_count())
+            .map(|face| {
+                goal_mesh
+                    .unfold(face, UnfoldStrategy::DepthFirst)
+                    .unwrap()
+                    .overlaps
+                    .len()
+            })
+            .collect::<Vec<_>>();
+        for &thread_count in [1, 4, 64].iter() {
+            let pool = rayon::ThreadPoolBuilder::new()
+                .num_threads(thread_count)
+                .build()
+                .unwrap();
+            assert_eq!(
+                pool.install(
+                    || goal_mesh.count_overlaps_by_reference_face(UnfoldStrategy::DepthFirst)
+                ),
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn test_weld_positions() {
+        let goal_mesh = GoalMesh::from_obj(
+            Path::new("tests/fixtures/unit_cube.obj"),
+            &LoadOptions {
+                strict: true,
+                ..LoadOptions::default()
+            },
+        )
+        .unwrap();
+        let mut net = goal_mesh.unfold(0, UnfoldStrategy::BreadthFirst).unwrap();
+
+        // Nudge every position by less than the tolerance: welding snaps the corners of each vertex
+        // of the net back together, without collapsing any of the edges
+        for (index, position) in net.positions.iter_mut().enumerate() {
+            *position += Vec3::new(1.0e-4, -1.0e-4, 0.0) * (index % 5) as f32;
+        }
+        let welded_indices = net.welded_indices.clone();
+        assert!(net.weld_positions(1.0e-3) > 0);
+        assert_eq!(net.welded_indices, welded_indices);
+        for (a, b) in net.positions.iter().zip(net.welded_indices.iter()) {
+            for (c, d) in net.positions.iter().zip(net.welded_indices.iter()) {
+                assert_eq!(b == d, a == c);
             }
         }
 
-        m3
+        // A tolerance of zero disables welding
+        assert_eq!(net.weld_positions(0.0), 0);
+    }
+
+    #[test]
+    fn test_tolerances() {
+        let tolerances = Tolerances::from_epsilon(1.0e-3).unwrap();
+        assert_eq!(tolerances.degenerate, 1.0e-3);
+        assert_eq!(tolerances.stl_weld, 1.0e-3);
+        assert_eq!(tolerances.overlap, 1.0e-3);
+        assert_eq!(tolerances.flat, DEFAULT_FLAT_TOLERANCE);
+        for &epsilon in [0.0, -1.0e-3, f32::NAN, f32::INFINITY].iter() {
+            assert_eq!(Tolerances::from_epsilon(epsilon), None);
+        }
+
+        let load = |strict: bool, tolerances: &Tolerances| {
+            GoalMesh::from_obj(
+                Path::new("tests/fixtures/unit_cube.obj"),
+                &LoadOptions {
+                    strict,
+                    tolerances: *tolerances,
+                    ..LoadOptions::default()
+                },
+            )
+        };
+
+        // Every fold of the cube is convex, unless its right angles are within the flat tolerance
+        let goal_mesh = load(true, &Tolerances::default()).unwrap();
+        let net = goal_mesh.unfold(0, UnfoldStrategy::BreadthFirst).unwrap();
+        assert!(net
+            .edges
+            .iter()
+            .all(|edge| edge.kind == EdgeKind::Cut || edge.kind == EdgeKind::Mountain));
+        let flat = Tolerances {
+            flat: std::f32::consts::PI,
+            ..Tolerances::default()
+        };
+        let goal_mesh = load(true, &flat).unwrap();
+        assert_eq!(goal_mesh.tolerances(), &flat);
+        let net = goal_mesh.unfold(0, UnfoldStrategy::BreadthFirst).unwrap();
+        assert!(net
+            .edges
+            .iter()
+            .all(|edge| edge.kind == EdgeKind::Cut || edge.kind == EdgeKind::Valley));
+
+        // The area of each square face of the cube is exactly the square of its (longest) side
+        let degenerate = Tolerances {
+            degenerate: 1.0,
+            ..Tolerances::default()
+        };
+        assert!(matches!(
+            load(true, &degenerate),
+            Err(GoalMeshError::DegenerateFaces(faces)) if faces == vec![0, 1, 2, 3, 4, 5]
+        ));
+
+        // Outside of strict mode, skipping every face says so (rather than that there were none)
+        assert!(matches!(
+            load(false, &degenerate),
+            Err(GoalMeshError::AllFacesDegenerate(6))
+        ));
     }
 }
@@ -1,5 +1,7 @@
-use crate::half_edge::ids::HalfEdgeIndex;
-use crate::half_edge::mesh::HalfEdgeMesh;
+use crate::half_edge::ids::{FaceIndex, HalfEdgeIndex, VertexIndex};
+use crate::half_edge::mesh::{Face, HalfEdgeMesh, Vertex};
+
+use glam::Vec3;
 
 /// An iterator for looping over all of the half-edges that bound a particular face.
 pub struct FaceEdgeLoop<'l> {
@@ -103,3 +105,82 @@ impl<'l> VertexEdgeLoop<'l> {
         }
     }
 }
+
+/// A face of a half-edge mesh, along with the mesh that it belongs to, so that its neighborhood can
+/// be walked without passing the mesh (and the face's ID) around separately.
+#[derive(Clone, Copy)]
+pub struct FaceRef<'l> {
+    mesh: &'l HalfEdgeMesh,
+    id: FaceIndex,
+}
+
+impl<'l> FaceRef<'l> {
+    pub fn new(mesh: &'l HalfEdgeMesh, id: FaceIndex) -> FaceRef<'l> {
+        FaceRef { mesh, id }
+    }
+
+    /// Returns the ID of this face.
+    pub fn id(&self) -> FaceIndex {
+        self.id
+    }
+
+    /// Returns the underlying face.
+    pub fn face(&self) -> &'l Face {
+        self.mesh.face(self.id)
+    }
+
+    /// Returns an iterator over the half-edges that bound this face, in CCW order.
+    pub fn half_edges(&self) -> FaceEdgeLoop<'l> {
+        self.mesh.adjacent_half_edges_to_face(self.id)
+    }
+
+    /// Returns an iterator over the vertices that bound this face, in CCW order.
+    pub fn vertices(&self) -> impl Iterator<Item = VertexIndex> + 'l {
+        self.mesh.adjacent_vertices_to_face(self.id)
+    }
+
+    /// Returns an iterator over the faces across each of the half-edges that bound this face, in
+    /// CCW order (`None` stands for the border of the mesh).
+    pub fn neighbors(&self) -> impl Iterator<Item = Option<FaceIndex>> + 'l {
+        self.mesh.adjacent_faces_to_face(self.id)
+    }
+}
+
+/// A vertex of a half-edge mesh, along with the mesh that it belongs to (see `FaceRef`).
+#[derive(Clone, Copy)]
+pub struct VertexRef<'l> {
+    mesh: &'l HalfEdgeMesh,
+    id: VertexIndex,
+}
+
+impl<'l> VertexRef<'l> {
+    pub fn new(mesh: &'l HalfEdgeMesh, id: VertexIndex) -> VertexRef<'l> {
+        VertexRef { mesh, id }
+    }
+
+    /// Returns the ID of this vertex.
+    pub fn id(&self) -> VertexIndex {
+        self.id
+    }
+
+    /// Returns the underlying vertex.
+    pub fn vertex(&self) -> &'l Vertex {
+        self.mesh.vertex(self.id)
+    }
+
+    /// Returns the position of this vertex.
+    pub fn coordinates(&self) -> &'l Vec3 {
+        self.vertex().coordinates()
+    }
+
+    /// Returns an iterator over the half-edges that originate from this vertex, in CCW order.
+    pub fn outgoing_half_edges(&self) -> VertexEdgeLoop<'l> {
+        self.mesh.adjacent_half_edges_to_vertex(self.id)
+    }
+
+    /// Returns an iterator over the faces around this vertex, in CCW order (`None` stands for the
+    /// border of the mesh).
+    pub fn faces(&self) -> impl Iterator<Item = Option<FaceIndex>> + 'l {
+        self.mesh.adjacent_faces_to_vertex(self.id)
+    }
+}
@@ -2,9 +2,10 @@ use glam::Vec3;
 use log::{info, warn};
 
 use crate::half_edge::ids::{FaceIndex, HalfEdgeIndex, VertexIndex};
-use crate::half_edge::iterators::{FaceEdgeLoop, VertexEdgeLoop};
+use crate::half_edge::iterators::{FaceEdgeLoop, FaceRef, VertexEdgeLoop, VertexRef};
 
 use core::fmt;
+use std::collections::{HashMap, HashSet};
 
 /// Assuming a triangle mesh (i.e. one where all faces are triangles), each face
 /// can be represented as a 3-tuple of vertex indices in CCW winding order. The
@@ -14,7 +15,7 @@ use core::fmt;
 /// For example, say a particular face is specified by the vertex indices `[0, 2, 4]`.
 /// This function would return an array of arrays:
 ///
-/// ```
+/// ```text
 /// [
 ///     [0, 2],
 ///     [2, 4],
@@ -30,6 +31,87 @@ fn get_edge_indices_for_face_indices(face_vids: &[usize; 3]) -> [[usize; 2]; 3]
     ]
 }
 
+/// A problem with the topology of a half-edge mesh, as reported by `HalfEdgeMesh::validate()`.
+#[derive(Clone, Debug, PartialEq)]
+pub enum TopologyError {
+    // The edge between these two vertices only has a single incident face, i.e. the mesh has a hole
+    BoundaryEdge {
+        vertices: [VertexIndex; 2],
+        face: FaceIndex,
+    },
+
+    // The edge between these two vertices is shared by more than two faces (or by two faces with
+    // opposite winding orders)
+    NonManifoldEdge {
+        vertices: [VertexIndex; 2],
+        faces: Vec<FaceIndex>,
+    },
+
+    // The mesh consists of more than one connected piece, and this face can't be reached from the
+    // first face of the mesh
+    Disconnected {
+        face: FaceIndex,
+    },
+}
+
+impl fmt::Display for TopologyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TopologyError::BoundaryEdge { vertices, face } => write!(
+                f,
+                "The edge between vertices {} and {} (part of face {}) only has one incident face: the mesh is not closed",
+                vertices[0], vertices[1], face
+            ),
+            TopologyError::NonManifoldEdge { vertices, faces } => write!(
+                f,
+                "The edge between vertices {} and {} is shared by faces {:?}: the mesh is not a manifold (or its faces have inconsistent winding orders)",
+                vertices[0], vertices[1], faces
+            ),
+            TopologyError::Disconnected { face } => write!(
+                f,
+                "Face {} is not connected to the rest of the mesh",
+                face
+            ),
+        }
+    }
+}
+
+/// The number of vertices, edges, and faces of a half-edge mesh.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Topology {
+    pub vertices: usize,
+    pub edges: usize,
+    pub faces: usize,
+
+    // The number of edges that only have a single incident face
+    pub border_edges: usize,
+
+    // The number of connected pieces that make up the mesh
+    pub components: usize,
+}
+
+impl Topology {
+    /// Returns the Euler characteristic (V - E + F).
+    pub fn euler_characteristic(&self) -> isize {
+        self.vertices as isize - self.edges as isize + self.faces as isize
+    }
+
+    /// Returns the genus (i.e. the number of "handles") of the surface, which is only
+    /// well-defined for closed, orientable surfaces: for example, a sphere has genus 0 and a torus
+    /// has genus 1. If the surface consists of several connected pieces, the genus of each piece is
+    /// summed. `None` is returned if the surface has a border or if its Euler characteristic is
+    /// not consistent with any closed surface.
+    pub fn genus(&self) -> Option<usize> {
+        let euler_characteristic = self.euler_characteristic();
+        let spheres = 2 * self.components as isize;
+        if self.border_edges > 0 || euler_characteristic > spheres || euler_characteristic % 2 != 0
+        {
+            return None;
+        }
+        Some(((spheres - euler_characteristic) / 2) as usize)
+    }
+}
+
 /// A simple half-edge data structure.
 ///
 /// Note that face indices are assumed to be in CCW winding
@@ -85,6 +167,12 @@ impl HalfEdge {
     }
 }
 
+impl Default for HalfEdge {
+    fn default() -> Self {
+        HalfEdge::new()
+    }
+}
+
 #[derive(Clone, Copy, Debug)]
 pub struct Vertex {
     // The XYZ-coordinates of this vertex, in 3-space
@@ -92,6 +180,9 @@ pub struct Vertex {
 
     // The ID of the half-edge that this vertex "belongs" to (i.e. is the origin vertex of)
     half_edge_id: HalfEdgeIndex,
+
+    // The (unit) normal that was loaded along with this vertex, if any
+    normal: Option<Vec3>,
 }
 
 impl Vertex {
@@ -99,6 +190,7 @@ impl Vertex {
         Vertex {
             coordinates,
             half_edge_id,
+            normal: None,
         }
     }
 
@@ -106,6 +198,14 @@ impl Vertex {
         &self.coordinates
     }
 
+    pub fn normal(&self) -> Option<Vec3> {
+        self.normal
+    }
+
+    pub fn set_normal(&mut self, normal: Vec3) {
+        self.normal = Some(normal);
+    }
+
     pub fn half_edge(&self) -> HalfEdgeIndex {
         self.half_edge_id
     }
@@ -149,6 +249,50 @@ impl HalfEdgeMesh {
         }
     }
 
+    /// Checks that the given (triangular) faces can be built into a half-edge mesh at all, i.e. that
+    /// no two faces run along the same edge in the same direction and that no edge is shared by more
+    /// than two faces: otherwise, the pairs of the half-edges along that edge would be ambiguous, and
+    /// `from_faces()` would fail (or build a broken mesh). The first offending edge is returned as
+    /// an error, along with all of the faces that share it.
+    pub fn validate_faces(base_faces: &[[usize; 3]]) -> Result<(), TopologyError> {
+        let edges_of = |&face: &[usize; 3]| (0..3).map(move |i| (face[i], face[(i + 1) % 3]));
+
+        // The faces along each (undirected) edge, and whether they run from its smaller vertex
+        let mut edge_faces: HashMap<[usize; 2], Vec<(usize, bool)>> = HashMap::new();
+        for (fid, face) in base_faces.iter().enumerate() {
+            for (a, b) in edges_of(face) {
+                edge_faces
+                    .entry([a.min(b), a.max(b)])
+                    .or_default()
+                    .push((fid, a < b));
+            }
+        }
+
+        for face in base_faces.iter() {
+            for (a, b) in edges_of(face) {
+                let incident = &edge_faces[&[a.min(b), a.max(b)]];
+                let same_direction = incident
+                    .iter()
+                    .filter(|&&(_, forward)| forward == (a < b))
+                    .count();
+                if incident.len() > 2 || same_direction > 1 {
+                    let mut faces = incident
+                        .iter()
+                        .map(|&(fid, _)| FaceIndex(fid))
+                        .collect::<Vec<_>>();
+                    faces.sort();
+                    faces.dedup();
+                    return Err(TopologyError::NonManifoldEdge {
+                        vertices: [VertexIndex(a), VertexIndex(b)],
+                        faces,
+                    });
+                }
+            }
+        }
+
+        Ok(())
+    }
+
     /// Build a list of half-edges from the given face description. Currently, half-edge meshes
     /// can only represent triangular meshes, although this restriction can certainly be lifted
     /// in the future.
@@ -379,6 +523,33 @@ impl HalfEdgeMesh {
             .collect()
     }
 
+    /// Returns each closed loop of half-edges along the border of the mesh, where the half-edges of
+    /// each loop are in order (i.e. each one is the `next` of the one before it). For a mesh that
+    /// represents an unfolded net, these are the outlines of the net, and a net that is a single,
+    /// connected piece without any holes has exactly one loop. A closed mesh has no loops at all.
+    pub fn boundary_loops(&self) -> Vec<Vec<HalfEdgeIndex>> {
+        let mut visited = vec![false; self.half_edges.len()];
+        let mut loops = vec![];
+
+        for start in self.boundary_edges() {
+            if visited[start.0] {
+                continue;
+            }
+
+            // Walk along the border until we arrive back at the half-edge that we started with
+            let mut boundary_loop = vec![];
+            let mut current = start;
+            while !visited[current.0] {
+                visited[current.0] = true;
+                boundary_loop.push(current);
+                current = self.half_edge(current).next_id;
+            }
+            loops.push(boundary_loop);
+        }
+
+        loops
+    }
+
     /// Returns the degree / valency of the specified vertex, i.e. the numbering of outgoing
     /// edges.
     pub fn vertex_degree(&self, vid: VertexIndex) -> usize {
@@ -493,10 +664,330 @@ impl HalfEdgeMesh {
         normal
     }
 
-    /// TODO: probably need a different `Edge` struct to find unique edges
-    pub fn euler_characteristic(&self) {
-        // V - E + F
-        unimplemented!();
+    /// Returns `-1.0` if the winding order of the specified face disagrees with the normals stored on
+    /// its vertices (i.e. if the face normal points away from them) and `1.0` otherwise. Faces whose
+    /// vertices don't all have normals are assumed to be wound correctly.
+    pub fn face_orientation(&self, fid: FaceIndex) -> f32 {
+        let stored_normals = self
+            .adjacent_vertices_to_face(fid)
+            .map(|vid| self.vertex(vid).normal())
+            .collect::<Option<Vec<_>>>();
+
+        match stored_normals {
+            Some(normals) => {
+                let stored_normal = normals.iter().fold(Vec3::zero(), |sum, &next| sum + next);
+                if stored_normal.dot(self.face_normal(fid)) < 0.0 {
+                    -1.0
+                } else {
+                    1.0
+                }
+            }
+            None => 1.0,
+        }
+    }
+
+    /// Returns the signed dihedral angle along the specified half-edge, i.e. the angle between the
+    /// normals of the two faces on either side of it, in the range -π..π. The angle is positive if
+    /// the edge is convex, negative if the edge is reflex (concave), and zero if the two faces are
+    /// coplanar. `None` is returned if the half-edge is part of the boundary of the mesh.
+    ///
+    /// If the vertices of the first face have normals, they determine which side of the surface is
+    /// the "outside" (see `face_orientation`); otherwise, the winding order of the faces does.
+    pub fn dihedral_angle(&self, eid: HalfEdgeIndex) -> Option<f32> {
+        match self.adjacent_faces_to_half_edge(eid) {
+            [Some(a), Some(b)] => {
+                let normal_a = self.face_normal(a);
+                let normal_b = self.face_normal(b);
+                let along = self.edge_vector(eid).normalize();
+
+                // The edge is convex if the normals "turn" in the same direction as the edge
+                // (which is oriented CCW w.r.t. the first face). Flipping the orientation of both
+                // faces reverses the edge but leaves the normals' cross product untouched, so the
+                // sign of the angle has to be flipped as well
+                Some(
+                    self.face_orientation(a)
+                        * normal_a
+                            .cross(normal_b)
+                            .dot(along)
+                            .atan2(normal_a.dot(normal_b)),
+                )
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns the (interior) dihedral angle along the specified half-edge, i.e. the angle between
+    /// the two faces on either side of it, measured through the inside of the mesh: this is the
+    /// supplement of the angle between their normals (see `dihedral_angle`), in the range 0..2π.
+    /// The angle is less than π if the edge is convex, more than π if the edge is reflex, and
+    /// exactly π if the two faces are coplanar. `None` is returned if the half-edge is part of the
+    /// boundary of the mesh.
+    pub fn interior_angle(&self, eid: HalfEdgeIndex) -> Option<f32> {
+        self.dihedral_angle(eid)
+            .map(|angle| std::f32::consts::PI - angle)
+    }
+
+    /// Returns the reflex (concave) edges of this mesh, i.e. the edges whose dihedral angle is less
+    /// than zero (beyond `flat_tolerance` radians, so that edges between coplanar faces aren't
+    /// counted). Each edge is only returned once, as the half-edge with the smaller index.
+    pub fn reflex_edges(&self, flat_tolerance: f32) -> Vec<HalfEdgeIndex> {
+        self.half_edge_id_iter()
+            .filter(|&eid| eid < self.half_edge(eid).pair())
+            .filter(|&eid| match self.dihedral_angle(eid) {
+                Some(angle) => angle < -flat_tolerance,
+                None => false,
+            })
+            .collect()
+    }
+
+    /// Returns `true` if none of the edges of this mesh are reflex, i.e. if every dihedral angle
+    /// is at most π (beyond `flat_tolerance` radians, see `reflex_edges()`). For closed meshes that
+    /// are topologically a sphere, this means that the mesh is a convex polyhedron.
+    pub fn is_convex(&self, flat_tolerance: f32) -> bool {
+        self.reflex_edges(flat_tolerance).is_empty()
+    }
+
+    /// Returns the outline of a region of faces of this mesh, i.e. the vertices along its border in
+    /// CCW order (w.r.t. the faces), starting at the first half-edge of the first face that lies
+    /// along the border. `None` is returned if the region is not topologically a disk: for example,
+    /// if it isn't connected, if it has holes, or if its border touches itself at a vertex.
+    pub fn region_outline(&self, faces: &[FaceIndex]) -> Option<Vec<VertexIndex>> {
+        let region = faces.iter().copied().collect::<HashSet<_>>();
+
+        // Gather the half-edges of the region whose pairs lie outside of it: along the border of a
+        // disk, each vertex is the origin of exactly one of these
+        let mut border = HashMap::new();
+        let mut first = None;
+        let mut half_edges = 0;
+        for &fid in faces.iter() {
+            for eid in self.adjacent_half_edges_to_face(fid) {
+                half_edges += 1;
+                let outside = match self.half_edge(self.half_edge(eid).pair_id).face_id {
+                    Some(neighbor) => !region.contains(&neighbor),
+                    None => true,
+                };
+                if outside {
+                    if border
+                        .insert(self.half_edge(eid).origin_vertex_id, eid)
+                        .is_some()
+                    {
+                        return None;
+                    }
+                    first.get_or_insert(eid);
+                }
+            }
+        }
+
+        // Walk along the border until we arrive back at the half-edge that we started with: if
+        // it has more than one loop, some of the half-edges won't be visited
+        let first = first?;
+        let mut outline = vec![];
+        let mut current = first;
+        loop {
+            outline.push(self.half_edge(current).origin_vertex_id);
+            current = *border.get(&self.get_terminating_vertex_along_half_edge(current))?;
+            if current == first || outline.len() > border.len() {
+                break;
+            }
+        }
+        if outline.len() != border.len() {
+            return None;
+        }
+
+        // A single loop isn't enough on its own (e.g. a torus with a disk cut out of it also has a
+        // single border loop), so check that the Euler characteristic is that of a disk as well
+        let vertices = faces
+            .iter()
+            .flat_map(|&fid| self.adjacent_vertices_to_face(fid))
+            .collect::<HashSet<_>>()
+            .len();
+        let edges = (half_edges - border.len()) / 2 + border.len();
+        if vertices as isize - edges as isize + faces.len() as isize != 1 {
+            return None;
+        }
+
+        Some(outline)
+    }
+
+    /// Merges groups of faces of this mesh wherever they meet along an edge whose dihedral angle
+    /// is within `tolerance` (in radians) of zero, so that (nearly) coplanar faces can be treated
+    /// as a single polygon. `groups` holds the group that each face starts out in (e.g. the polygon
+    /// that it was triangulated from), and the merged group of each face is returned: merged
+    /// groups are numbered in the order of the first group that they contain. Groups are only
+    /// merged if the resulting region is topologically a disk (see `region_outline`), so a ring of
+    /// coplanar faces around a hole (for example) is left as it is.
+    pub fn merge_coplanar_faces(&self, groups: &[usize], tolerance: f32) -> Vec<usize> {
+        debug_assert_eq!(groups.len(), self.faces.len());
+        let group_count = groups.iter().max().map_or(0, |&group| group + 1);
+
+        // Keep track of which groups should be merged with a disjoint-set forest
+        fn find_root(parents: &mut [usize], mut index: usize) -> usize {
+            while parents[index] != index {
+                parents[index] = parents[parents[index]];
+                index = parents[index];
+            }
+            index
+        }
+        let mut parents = (0..group_count).collect::<Vec<_>>();
+        for eid in self.half_edge_id_iter() {
+            match (
+                self.adjacent_faces_to_half_edge(eid),
+                self.dihedral_angle(eid),
+            ) {
+                ([Some(a), Some(b)], Some(angle)) if angle.abs() < tolerance => {
+                    let a = find_root(&mut parents, groups[a.0]);
+                    let b = find_root(&mut parents, groups[b.0]);
+                    parents[a.max(b)] = a.min(b);
+                }
+                _ => (),
+            }
+        }
+
+        // Split up any merged groups that don't form a disk again
+        let mut regions: HashMap<usize, Vec<FaceIndex>> = HashMap::new();
+        for fid in self.face_id_iter() {
+            let root = find_root(&mut parents, groups[fid.0]);
+            regions.entry(root).or_default().push(fid);
+        }
+        for (_, faces) in regions.iter() {
+            let merged = faces
+                .iter()
+                .any(|&fid| find_root(&mut parents, groups[fid.0]) != groups[fid.0]);
+            if merged && self.region_outline(faces).is_none() {
+                for &fid in faces.iter() {
+                    parents[groups[fid.0]] = groups[fid.0];
+                }
+            }
+        }
+
+        // Number the merged groups in the order that they first appear
+        let mut numbers = HashMap::new();
+        let numbered = (0..group_count)
+            .map(|group| {
+                let root = find_root(&mut parents, group);
+                let next = numbers.len();
+                *numbers.entry(root).or_insert(next)
+            })
+            .collect::<Vec<_>>();
+        groups.iter().map(|&group| numbered[group]).collect()
+    }
+
+    /// Checks that this mesh is a closed, connected 2-manifold, i.e. that every edge has exactly
+    /// two incident faces and that every face can be reached from every other face. The first
+    /// problem that is found is returned as an error.
+    pub fn validate(&self) -> Result<(), TopologyError> {
+        self.validate_surface(false)
+    }
+
+    /// Checks that this mesh is a connected 2-manifold that may have a border (i.e. an open
+    /// surface, such as a bowl): this is the same as `validate()`, except that edges with a single
+    /// incident face are allowed.
+    pub fn validate_open(&self) -> Result<(), TopologyError> {
+        self.validate_surface(true)
+    }
+
+    fn validate_surface(&self, allow_border: bool) -> Result<(), TopologyError> {
+        // Every (directed) half-edge should appear in exactly one face: if it appears in more than
+        // one, the edge is shared by more than two faces (or the faces are oriented inconsistently)
+        let mut directed_edges: HashMap<[VertexIndex; 2], Vec<HalfEdgeIndex>> = HashMap::new();
+        for eid in self.half_edge_id_iter() {
+            if self.half_edge(eid).face().is_some() {
+                directed_edges
+                    .entry(self.adjacent_vertices_to_half_edge(eid))
+                    .or_insert(vec![])
+                    .push(eid);
+            }
+        }
+
+        for eid in self.half_edge_id_iter() {
+            let face = match self.half_edge(eid).face() {
+                Some(face) => face,
+                None => continue,
+            };
+            let vertices = self.adjacent_vertices_to_half_edge(eid);
+            let reversed = [vertices[1], vertices[0]];
+
+            let pair = self.half_edge(eid).pair();
+            if directed_edges[&vertices].len() > 1 || self.half_edge(pair).pair() != eid {
+                // Gather all of the faces that touch this edge, in either direction
+                let mut faces = directed_edges[&vertices]
+                    .iter()
+                    .chain(directed_edges.get(&reversed).into_iter().flatten())
+                    .filter_map(|&other| self.half_edge(other).face())
+                    .collect::<Vec<_>>();
+                faces.sort();
+                faces.dedup();
+
+                return Err(TopologyError::NonManifoldEdge { vertices, faces });
+            }
+
+            if !allow_border && self.half_edge(pair).face().is_none() {
+                return Err(TopologyError::BoundaryEdge { vertices, face });
+            }
+        }
+
+        // Finally, make sure that every face can be reached from the first one
+        if let Some(face) = self.connected_components().iter().position(|&c| c != 0) {
+            return Err(TopologyError::Disconnected {
+                face: FaceIndex(face),
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Returns the index of the connected component (i.e. the separate piece of the mesh) that
+    /// each face belongs to, where faces are connected if they share an edge. Components are
+    /// numbered in the order that they first appear, so the first face always belongs to component
+    /// 0.
+    pub fn connected_components(&self) -> Vec<usize> {
+        let mut components = vec![usize::MAX; self.faces.len()];
+        let mut count = 0;
+
+        for first in self.face_id_iter() {
+            if components[first.0] != usize::MAX {
+                continue;
+            }
+
+            let mut stack = vec![first];
+            components[first.0] = count;
+            while let Some(fid) = stack.pop() {
+                for neighbor in self.adjacent_faces_to_face(fid).flatten() {
+                    if components[neighbor.0] == usize::MAX {
+                        components[neighbor.0] = count;
+                        stack.push(neighbor);
+                    }
+                }
+            }
+            count += 1;
+        }
+
+        components
+    }
+
+    /// Returns the number of vertices, edges, and faces of this mesh.
+    pub fn topology(&self) -> Topology {
+        Topology {
+            vertices: self.vertices.len(),
+            // Every edge is made up of exactly two half-edges (including the ones along the border)
+            edges: self.half_edges.len() / 2,
+            faces: self.faces.len(),
+            border_edges: self
+                .half_edges
+                .iter()
+                .filter(|half_edge| half_edge.face_id.is_none())
+                .count(),
+            components: self
+                .connected_components()
+                .iter()
+                .max()
+                .map_or(0, |&component| component + 1),
+        }
+    }
+
+    /// Returns the Euler characteristic (V - E + F) of this mesh.
+    pub fn euler_characteristic(&self) -> isize {
+        self.topology().euler_characteristic()
     }
 
     /// Returns the index of the half-edge that joins the vertices at `a` and `b` or `None`
@@ -602,6 +1093,26 @@ impl HalfEdgeMesh {
             .map(|i| HalfEdgeIndex(i))
     }
 
+    /// Constructs an iterator over the IDs of all (undirected) edges of the mesh, i.e. one of the two
+    /// half-edges of each edge (the one with the lower ID, which may be a border half-edge).
+    pub fn edge_id_iter(&self) -> impl Iterator<Item = HalfEdgeIndex> + '_ {
+        self.half_edge_id_iter()
+            .filter(move |&eid| eid < self.half_edge(eid).pair_id)
+    }
+
+    /// Constructs an iterator over all faces of the mesh, each of which can be used to walk the
+    /// half-edges, vertices, and neighbors of the face (see `FaceRef`).
+    pub fn face_refs(&self) -> impl Iterator<Item = FaceRef<'_>> + '_ {
+        self.face_id_iter().map(move |fid| FaceRef::new(self, fid))
+    }
+
+    /// Constructs an iterator over all vertices of the mesh, each of which can be used to walk the
+    /// half-edges and faces around the vertex (see `VertexRef`).
+    pub fn vertex_refs(&self) -> impl Iterator<Item = VertexRef<'_>> + '_ {
+        self.vertex_id_iter()
+            .map(move |vid| VertexRef::new(self, vid))
+    }
+
     /// Convenience function that constructs an iterator over all face IDs, essentially mapping a
     /// numeric index to a `FaceIndex` unit struct.
     pub fn face_id_iter(&self) -> impl Iterator<Item = FaceIndex> {
@@ -662,7 +1173,7 @@ impl HalfEdgeMesh {
     /// or more times during iteration.
     ///
     /// This is a convenience function that provides functionality equivalent to:
-    /// ```
+    /// ```ignore
     /// let face_loop_iterator = half_edge_mesh
     ///     .adjacent_half_edges_to_face(face_index)
     ///     .map(|eid| mesh.half_edge(mesh.half_edge(eid).pair).face_id);
@@ -679,7 +1190,7 @@ impl HalfEdgeMesh {
     /// The vertices are guaranteed to be in *CCW order*.
     ///
     /// This is a convenience function that provides functionality equivalent to:
-    /// ```
+    /// ```ignore
     /// let vertex_loop_iterator = half_edge_mesh
     ///     .adjacent_half_edges_to_face(face_index)
     ///     .map(|eid| mesh.half_edge(eid).origin_vertex_id);
@@ -708,7 +1219,7 @@ impl HalfEdgeMesh {
     /// indicating the "infinite face" that surrounds the mesh.
     ///
     /// This is a convenience function that provides functionality equivalent to:
-    /// ```
+    /// ```ignore
     /// let face_loop_iterator = half_edge_mesh
     ///     .adjacent_half_edges_to_vertex(vertex_index)
     ///     .map(|eid| half_edge_mesh.get_half_edge(eid).face_id);
@@ -725,7 +1236,7 @@ impl HalfEdgeMesh {
     /// The vertices are guaranteed to be in *CCW order*.
     ///
     /// This is a convenience function that provides functionality equivalent to:
-    /// ```
+    /// ```ignore
     /// let vertex_loop_iterator = half_edge_mesh
     ///     .adjacent_half_edges_to_vertex(vertex_index)
     ///     .map(|eid| half_edge_mesh.get_terminating_vertex_along_half_edge(eid));
@@ -771,6 +1282,7 @@ impl fmt::Debug for HalfEdgeMesh {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::goal_mesh::DEFAULT_FLAT_TOLERANCE;
 
     #[test]
     fn test_from_faces() {
@@ -796,12 +1308,12 @@ mod tests {
         let hem = HalfEdgeMesh::from_faces(&base_faces, &base_vertices).unwrap();
         println!("{:?}", hem);
 
-        // Print pre-computed properties
-        for face in hem.faces() {
-            println!("Face normal vector: {:?}", face.normal);
+        // Print computed properties
+        for fid in hem.face_id_iter() {
+            println!("Face normal vector: {:?}", hem.face_normal(fid));
         }
-        for vertex in hem.vertices() {
-            println!("Vertex normal vector: {:?}", vertex.normal);
+        for vid in hem.vertex_id_iter() {
+            println!("Vertex normal vector: {:?}", hem.vertex_normal(vid));
         }
 
         // First, some basic tests
@@ -991,5 +1503,482 @@ mod tests {
 
         // Test gather methods
         //println!("\n{:?}", half_edge_mesh.gather_triangles());
+
+        // The two triangles are coplanar, and the rest of the edges are along the border
+        assert_eq!(Some(0.0), hem.dihedral_angle(HalfEdgeIndex(2)));
+        assert_eq!(None, hem.dihedral_angle(HalfEdgeIndex(0)));
+    }
+
+    #[test]
+    fn test_dihedral_angle() {
+        //    3
+        //   /|\
+        //  / | \
+        // 0--|--2
+        //  \ | /
+        //   \|/
+        //    1
+        let base_vertices = vec![
+            Vec3::new(-1.0, 0.0, 0.0), // Vertex #0
+            Vec3::new(0.0, -1.0, 0.0), // Vertex #1
+            Vec3::new(1.0, 0.0, 0.0),  // Vertex #2
+            Vec3::new(0.0, 0.0, 1.0),  // Vertex #3
+        ];
+
+        // A closed tetrahedron (every edge is convex)
+        let base_faces = vec![[0, 2, 1], [0, 1, 3], [1, 2, 3], [2, 0, 3]];
+        let hem = HalfEdgeMesh::from_faces(&base_faces, &base_vertices).unwrap();
+        for eid in hem.half_edge_id_iter() {
+            let angle = hem.dihedral_angle(eid).unwrap();
+            assert!(angle > 0.0 && angle < std::f32::consts::PI);
+
+            // Both half-edges that make up an edge should agree
+            assert!(
+                (angle - hem.dihedral_angle(hem.half_edge(eid).pair()).unwrap()).abs() < 1.0e-6
+            );
+        }
+
+        // Reversing the winding order of every face turns the tetrahedron "inside out," so every
+        // edge should now be reflex
+        let base_faces = base_faces
+            .iter()
+            .map(|f_vids| [f_vids[0], f_vids[2], f_vids[1]])
+            .collect::<Vec<_>>();
+        let mut hem = HalfEdgeMesh::from_faces(&base_faces, &base_vertices).unwrap();
+        for eid in hem.half_edge_id_iter() {
+            let angle = hem.dihedral_angle(eid).unwrap();
+            assert!(angle < 0.0 && angle > -std::f32::consts::PI);
+        }
+
+        // ...unless the vertices have (outward-facing) normals, which take precedence over the
+        // winding order
+        let center = base_vertices.iter().fold(Vec3::zero(), |sum, &v| sum + v) / 4.0;
+        for (vid, coordinates) in base_vertices.iter().enumerate() {
+            hem.vertex_mut(VertexIndex(vid))
+                .set_normal((*coordinates - center).normalize());
+        }
+        for fid in hem.face_id_iter() {
+            assert_eq!(hem.face_orientation(fid), -1.0);
+        }
+        for eid in hem.half_edge_id_iter() {
+            let angle = hem.dihedral_angle(eid).unwrap();
+            assert!(angle > 0.0 && angle < std::f32::consts::PI);
+        }
+    }
+
+    #[test]
+    fn test_interior_angle() {
+        // Two triangles that meet at a right angle along the x-axis (0-1), like a partially open
+        // book: the first lies in the xy-plane and the second in the xz-plane
+        let base_vertices = vec![
+            Vec3::new(0.0, 0.0, 0.0), // Vertex #0
+            Vec3::new(1.0, 0.0, 0.0), // Vertex #1
+            Vec3::new(0.0, 1.0, 0.0), // Vertex #2
+            Vec3::new(0.0, 0.0, 1.0), // Vertex #3
+        ];
+
+        // The normals (+z and +y) point into the space between the triangles, so the edge is reflex
+        let base_faces = vec![[0, 1, 2], [1, 0, 3]];
+        let hem = HalfEdgeMesh::from_faces(&base_faces, &base_vertices).unwrap();
+        let hinge = hem
+            .half_edge_id_iter()
+            .find(|&eid| {
+                hem.adjacent_faces_to_half_edge(eid)
+                    .iter()
+                    .all(Option::is_some)
+            })
+            .unwrap();
+        let angle = hem.interior_angle(hinge).unwrap();
+        assert!((angle - std::f32::consts::PI * 1.5).abs() < 1.0e-6);
+        assert_eq!(hem.interior_angle(hem.half_edge(hinge).pair()), Some(angle));
+
+        // Reversing the winding order of both faces makes the edge convex
+        let base_faces = vec![[0, 2, 1], [1, 3, 0]];
+        let hem = HalfEdgeMesh::from_faces(&base_faces, &base_vertices).unwrap();
+        let interior = hem
+            .half_edge_id_iter()
+            .filter_map(|eid| hem.interior_angle(eid))
+            .collect::<Vec<_>>();
+        assert_eq!(interior.len(), 2);
+        for angle in interior {
+            assert!((angle - std::f32::consts::FRAC_PI_2).abs() < 1.0e-6);
+        }
+    }
+
+    #[test]
+    fn test_reflex_edges() {
+        // An octahedron, whose top vertex (#4) can be pushed down below the equator
+        let mut base_vertices = vec![
+            Vec3::new(1.0, 0.0, 0.0),  // Vertex #0
+            Vec3::new(0.0, 1.0, 0.0),  // Vertex #1
+            Vec3::new(-1.0, 0.0, 0.0), // Vertex #2
+            Vec3::new(0.0, -1.0, 0.0), // Vertex #3
+            Vec3::new(0.0, 0.0, 1.0),  // Vertex #4
+            Vec3::new(0.0, 0.0, -1.0), // Vertex #5
+        ];
+        let base_faces = vec![
+            [0, 1, 4],
+            [1, 2, 4],
+            [2, 3, 4],
+            [3, 0, 4],
+            [1, 0, 5],
+            [2, 1, 5],
+            [3, 2, 5],
+            [0, 3, 5],
+        ];
+        let hem = HalfEdgeMesh::from_faces(&base_faces, &base_vertices).unwrap();
+        assert!(hem.reflex_edges(DEFAULT_FLAT_TOLERANCE).is_empty());
+        assert!(hem.is_convex(DEFAULT_FLAT_TOLERANCE));
+
+        // Denting the top of the octahedron inwards makes the 4 edges around the dent reflex,
+        // while the edges along the equator become sharper (but remain convex)
+        base_vertices[4] = Vec3::new(0.0, 0.0, -0.5);
+        let hem = HalfEdgeMesh::from_faces(&base_faces, &base_vertices).unwrap();
+        let reflex_edges = hem.reflex_edges(DEFAULT_FLAT_TOLERANCE);
+        assert_eq!(reflex_edges.len(), 4);
+        for &eid in reflex_edges.iter() {
+            let vertices = hem.adjacent_vertices_to_half_edge(eid);
+            assert!(vertices.contains(&VertexIndex(4)));
+        }
+        assert!(!hem.is_convex(DEFAULT_FLAT_TOLERANCE));
+    }
+
+    #[test]
+    fn test_merge_coplanar_faces() {
+        // 3 -- 2
+        // | \  |
+        // |  \ | (folded along the 0-4 edge)
+        // 0 -- 1 -- 4
+        let base_vertices = vec![
+            Vec3::new(0.0, 0.0, 0.0),  // Vertex #0
+            Vec3::new(1.0, 0.0, 0.0),  // Vertex #1
+            Vec3::new(1.0, 1.0, 0.0),  // Vertex #2
+            Vec3::new(0.0, 1.0, 0.0),  // Vertex #3
+            Vec3::new(1.0, 0.0, -1.0), // Vertex #4
+        ];
+        let base_faces = vec![[0, 1, 2], [0, 2, 3], [0, 4, 1]];
+        let hem = HalfEdgeMesh::from_faces(&base_faces, &base_vertices).unwrap();
+
+        // The two triangles of the square are merged, but the folded triangle is not
+        let tolerance = 1.0f32.to_radians();
+        assert_eq!(
+            hem.merge_coplanar_faces(&[0, 1, 2], tolerance),
+            vec![0, 0, 1]
+        );
+        assert_eq!(hem.merge_coplanar_faces(&[0, 1, 2], 0.0), vec![0, 1, 2]);
+        assert_eq!(
+            hem.merge_coplanar_faces(&[1, 0, 2], tolerance),
+            vec![0, 0, 1]
+        );
+        assert_eq!(
+            hem.region_outline(&[FaceIndex(0), FaceIndex(1)]),
+            Some(vec![0.into(), 1.into(), 2.into(), 3.into()])
+        );
+
+        // A flat ring of triangles around a square hole is coplanar, but it isn't a disk, so it
+        // can't be drawn as a single polygon
+        let base_vertices = vec![
+            Vec3::new(-2.0, -2.0, 0.0),
+            Vec3::new(2.0, -2.0, 0.0),
+            Vec3::new(2.0, 2.0, 0.0),
+            Vec3::new(-2.0, 2.0, 0.0),
+            Vec3::new(-1.0, -1.0, 0.0),
+            Vec3::new(1.0, -1.0, 0.0),
+            Vec3::new(1.0, 1.0, 0.0),
+            Vec3::new(-1.0, 1.0, 0.0),
+        ];
+        let base_faces = (0..4)
+            .flat_map(|k| {
+                let (outer, inner) = ([k, (k + 1) % 4], [k + 4, (k + 1) % 4 + 4]);
+                vec![
+                    [outer[0], outer[1], inner[1]],
+                    [outer[0], inner[1], inner[0]],
+                ]
+            })
+            .collect::<Vec<_>>();
+        let hem = HalfEdgeMesh::from_faces(&base_faces, &base_vertices).unwrap();
+        let faces = hem.face_id_iter().collect::<Vec<_>>();
+        assert_eq!(hem.region_outline(&faces), None);
+        assert_eq!(
+            hem.region_outline(&faces[0..2])
+                .map(|outline| outline.len()),
+            Some(4)
+        );
+        let groups = (0..faces.len()).collect::<Vec<_>>();
+        assert_eq!(hem.merge_coplanar_faces(&groups, tolerance), groups);
+    }
+
+    #[test]
+    fn test_validate() {
+        let base_vertices = vec![
+            Vec3::new(-1.0, 0.0, 0.0), // Vertex #0
+            Vec3::new(0.0, -1.0, 0.0), // Vertex #1
+            Vec3::new(1.0, 0.0, 0.0),  // Vertex #2
+            Vec3::new(0.0, 0.0, 1.0),  // Vertex #3
+        ];
+
+        // A closed tetrahedron is valid
+        let base_faces = vec![[0, 2, 1], [0, 1, 3], [1, 2, 3], [2, 0, 3]];
+        let hem = HalfEdgeMesh::from_faces(&base_faces, &base_vertices).unwrap();
+        assert_eq!(hem.validate(), Ok(()));
+
+        // Removing one of its faces leaves a hole
+        let hem = HalfEdgeMesh::from_faces(&base_faces[1..].to_vec(), &base_vertices).unwrap();
+        match hem.validate() {
+            Err(TopologyError::BoundaryEdge { .. }) => (),
+            other => panic!("Expected a boundary edge, found: {:?}", other),
+        }
+        assert_eq!(hem.validate_open(), Ok(()));
+
+        // Flipping one of its faces means that each of that face's edges runs in the same
+        // direction as the edge of a neighboring face
+        let flipped_faces = vec![[0, 1, 2], [0, 1, 3], [1, 2, 3], [2, 0, 3]];
+        let hem = HalfEdgeMesh::from_faces(&flipped_faces, &base_vertices).unwrap();
+        match hem.validate() {
+            Err(TopologyError::NonManifoldEdge { faces, .. }) => {
+                assert!(faces.contains(&FaceIndex(0)))
+            }
+            other => panic!("Expected a non-manifold edge, found: {:?}", other),
+        }
+        assert!(matches!(
+            hem.validate_open(),
+            Err(TopologyError::NonManifoldEdge { .. })
+        ));
+        assert_eq!(HalfEdgeMesh::validate_faces(&base_faces), Ok(()));
+        assert_eq!(
+            HalfEdgeMesh::validate_faces(&flipped_faces),
+            Err(TopologyError::NonManifoldEdge {
+                vertices: [VertexIndex(0), VertexIndex(1)],
+                faces: vec![FaceIndex(0), FaceIndex(1)],
+            })
+        );
+
+        // Two tetrahedra that share a single edge (between vertices 0 and 1) are caught before the
+        // half-edge mesh is built, since that edge has 4 incident faces
+        let mut shared_vertices = base_vertices.clone();
+        shared_vertices.push(Vec3::new(0.0, -2.0, -1.0)); // Vertex #4
+        shared_vertices.push(Vec3::new(-1.0, -1.0, -1.0)); // Vertex #5
+        let mut shared_faces = base_faces.clone();
+        shared_faces.extend_from_slice(&[[0, 4, 1], [0, 1, 5], [1, 4, 5], [4, 0, 5]]);
+        match HalfEdgeMesh::validate_faces(&shared_faces) {
+            Err(TopologyError::NonManifoldEdge { vertices, faces }) => {
+                assert!(vertices.contains(&VertexIndex(0)) && vertices.contains(&VertexIndex(1)));
+                assert_eq!(faces.len(), 4);
+            }
+            other => panic!("Expected a non-manifold edge, found: {:?}", other),
+        }
+
+        // Two separate tetrahedra are not connected to each other
+        let mut two_vertices = base_vertices.clone();
+        two_vertices.extend(base_vertices.iter().map(|v| *v + Vec3::new(5.0, 0.0, 0.0)));
+        let mut two_faces = base_faces.clone();
+        two_faces.extend(base_faces.iter().map(|f| [f[0] + 4, f[1] + 4, f[2] + 4]));
+        let hem = HalfEdgeMesh::from_faces(&two_faces, &two_vertices).unwrap();
+        assert_eq!(
+            hem.validate(),
+            Err(TopologyError::Disconnected { face: FaceIndex(4) })
+        );
+        assert_eq!(hem.connected_components(), vec![0, 0, 0, 0, 1, 1, 1, 1]);
+
+        // Each of them is still a sphere
+        let topology = hem.topology();
+        assert_eq!(topology.components, 2);
+        assert_eq!(topology.euler_characteristic(), 4);
+        assert_eq!(topology.genus(), Some(0));
+    }
+
+    #[test]
+    fn test_iterators() {
+        // The triangulated cube has 8 vertices, 18 edges (12 along the sides of its squares and one
+        // across each square), and 12 faces
+        let (models, _) = tobj::load_obj("tests/fixtures/unit_cube.obj", true).unwrap();
+        let mesh = &models[0].mesh;
+        let faces = mesh
+            .indices
+            .chunks(3)
+            .map(|face| [face[0] as usize, face[1] as usize, face[2] as usize])
+            .collect::<Vec<_>>();
+        let vertices = mesh
+            .positions
+            .chunks(3)
+            .map(|position| Vec3::new(position[0], position[1], position[2]))
+            .collect::<Vec<_>>();
+        let hem = HalfEdgeMesh::from_faces(&faces, &vertices).unwrap();
+        assert_eq!(hem.face_refs().count(), 12);
+        assert_eq!(hem.vertex_refs().count(), 8);
+        assert_eq!(hem.edge_id_iter().count(), 18);
+
+        // Every edge is listed exactly once (on either side)
+        let mut edges = hem
+            .edge_id_iter()
+            .map(|eid| {
+                let [a, b] = hem.adjacent_vertices_to_half_edge(eid);
+                (a.min(b), a.max(b))
+            })
+            .collect::<Vec<_>>();
+        edges.sort();
+        edges.dedup();
+        assert_eq!(edges.len(), 18);
+
+        for face in hem.face_refs() {
+            assert_eq!(face.half_edges().count(), 3);
+            assert!(face
+                .half_edges()
+                .all(|eid| hem.half_edge(eid).face_id == Some(face.id())));
+            assert!(face.neighbors().all(|neighbor| neighbor.is_some()));
+            assert!(face
+                .vertices()
+                .zip(face.half_edges())
+                .all(|(vid, eid)| hem.half_edge(eid).origin_vertex_id == vid));
+        }
+
+        // Each corner of the cube is where 3 sides of its squares meet (plus the diagonals of any
+        // squares that are split at that corner), so every edge leaves from both of its endpoints
+        let mut degrees = 0;
+        for vertex in hem.vertex_refs() {
+            let outgoing = vertex.outgoing_half_edges().collect::<Vec<_>>();
+            assert!(outgoing
+                .iter()
+                .all(|&eid| hem.half_edge(eid).origin_vertex_id == vertex.id()));
+            assert_eq!(vertex.faces().count(), outgoing.len());
+            assert!(vertex.coordinates().abs().max_element() <= 1.0);
+            degrees += outgoing.len();
+        }
+        assert_eq!(degrees, 18 * 2);
+    }
+
+    #[test]
+    fn test_topology() {
+        let base_vertices = vec![
+            Vec3::new(-1.0, 0.0, 0.0), // Vertex #0
+            Vec3::new(0.0, -1.0, 0.0), // Vertex #1
+            Vec3::new(1.0, 0.0, 0.0),  // Vertex #2
+            Vec3::new(0.0, 0.0, 1.0),  // Vertex #3
+        ];
+
+        // A closed tetrahedron is topologically a sphere
+        let base_faces = vec![[0, 2, 1], [0, 1, 3], [1, 2, 3], [2, 0, 3]];
+        let hem = HalfEdgeMesh::from_faces(&base_faces, &base_vertices).unwrap();
+        let topology = hem.topology();
+        assert_eq!(
+            topology,
+            Topology {
+                vertices: 4,
+                edges: 6,
+                faces: 4,
+                border_edges: 0,
+                components: 1
+            }
+        );
+        assert_eq!(hem.euler_characteristic(), 2);
+        assert_eq!(topology.genus(), Some(0));
+
+        // Removing one of its faces leaves a disk, which doesn't have a genus
+        let hem = HalfEdgeMesh::from_faces(&base_faces[1..].to_vec(), &base_vertices).unwrap();
+        assert_eq!(hem.euler_characteristic(), 1);
+        assert_eq!(hem.topology().genus(), None);
+
+        // A torus made up of a 3x3 grid of quads (each split into two triangles)
+        let n = 3;
+        let torus_vertices = (0..n * n)
+            .map(|i| {
+                let u = (i / n) as f32 / n as f32 * 2.0 * std::f32::consts::PI;
+                let v = (i % n) as f32 / n as f32 * 2.0 * std::f32::consts::PI;
+                Vec3::new(
+                    (2.0 + v.cos()) * u.cos(),
+                    (2.0 + v.cos()) * u.sin(),
+                    v.sin(),
+                )
+            })
+            .collect::<Vec<_>>();
+        let mut torus_faces = vec![];
+        for i in 0..n {
+            for j in 0..n {
+                let a = i * n + j;
+                let b = ((i + 1) % n) * n + j;
+                let c = ((i + 1) % n) * n + (j + 1) % n;
+                let d = i * n + (j + 1) % n;
+                torus_faces.push([a, b, c]);
+                torus_faces.push([a, c, d]);
+            }
+        }
+        let hem = HalfEdgeMesh::from_faces(&torus_faces, &torus_vertices).unwrap();
+        assert_eq!(hem.validate(), Ok(()));
+        assert_eq!(hem.euler_characteristic(), 0);
+        assert_eq!(hem.topology().genus(), Some(1));
+    }
+
+    #[test]
+    fn test_boundary_loops() {
+        // A cube unfolded into a cross, where each square is split into two triangles:
+        //
+        //     +--+
+        //     |  |
+        //     +--+
+        //     |  |
+        //  +--+--+--+
+        //  |  |  |  |
+        //  +--+--+--+
+        //     |  |
+        //     +--+
+        let squares = [(1, 0), (0, 1), (1, 1), (2, 1), (1, 2), (1, 3)];
+
+        // Squares that are next to each other share vertices (i.e. they are joined by a fold)
+        let mut base_vertices = vec![];
+        let mut vertex_at = |x: usize, y: usize| {
+            let position = Vec3::new(x as f32, y as f32, 0.0);
+            match base_vertices.iter().position(|&v| v == position) {
+                Some(vid) => vid,
+                None => {
+                    base_vertices.push(position);
+                    base_vertices.len() - 1
+                }
+            }
+        };
+        let mut base_faces = vec![];
+        for &(x, y) in squares.iter() {
+            let a = vertex_at(x, y);
+            let b = vertex_at(x + 1, y);
+            let c = vertex_at(x + 1, y + 1);
+            let d = vertex_at(x, y + 1);
+            base_faces.push([a, b, c]);
+            base_faces.push([a, c, d]);
+        }
+        let hem = HalfEdgeMesh::from_faces(&base_faces, &base_vertices).unwrap();
+
+        // The net is a single piece, so its outline is a single loop made up of the 14 edges that
+        // are cut in order to unfold the cube
+        let loops = hem.boundary_loops();
+        assert_eq!(loops.len(), 1);
+        assert_eq!(loops[0].len(), 14);
+        for (&eid, &next) in loops[0].iter().zip(loops[0].iter().cycle().skip(1)) {
+            assert!(hem.is_border_half_edge(eid));
+            assert_eq!(
+                hem.get_terminating_vertex_along_half_edge(eid),
+                hem.half_edge(next).origin_vertex()
+            );
+        }
+
+        // Removing the square at the end of the cross (along with the two vertices that only it
+        // uses) leaves a single, shorter loop
+        let hem = HalfEdgeMesh::from_faces(
+            &base_faces[..10].to_vec(),
+            &base_vertices[..base_vertices.len() - 2].to_vec(),
+        )
+        .unwrap();
+        let loops = hem.boundary_loops();
+        assert_eq!(loops.len(), 1);
+        assert_eq!(loops[0].len(), 12);
+
+        // Whereas a closed mesh doesn't have any border at all
+        let base_vertices = vec![
+            Vec3::new(-1.0, 0.0, 0.0), // Vertex #0
+            Vec3::new(0.0, -1.0, 0.0), // Vertex #1
+            Vec3::new(1.0, 0.0, 0.0),  // Vertex #2
+            Vec3::new(0.0, 0.0, 1.0),  // Vertex #3
+        ];
+        let base_faces = vec![[0, 2, 1], [0, 1, 3], [1, 2, 3], [2, 0, 3]];
+        let hem = HalfEdgeMesh::from_faces(&base_faces, &base_vertices).unwrap();
+        assert!(hem.boundary_loops().is_empty());
     }
 }
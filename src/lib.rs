@@ -0,0 +1,736 @@
+//! The unfolding core of `durer`: loading goal meshes, unfolding them into (flat) nets, and
+//! exporting the resulting nets. None of this depends on Bevy, so it can be embedded in other
+//! tools (the `durer` binary is just a thin frontend around this library).
+//!
+//! Most users only need [`unfold_obj`], which loads an .obj file and returns the unfolded net:
+//!
+//! ```no_run
+//! use durer::{unfold_obj, UnfoldOptions};
+//! use std::path::Path;
+//!
+//! let net = unfold_obj(Path::new("goal_meshes/icosahedron.obj"), &UnfoldOptions::default())
+//!     .expect("Failed to unfold goal mesh");
+//! for edge in net.edges.iter() {
+//!     println!("{:?} ({:?})", edge.kind, edge.indices);
+//! }
+//! ```
+//!
+//! To unfold the same goal mesh several times (e.g. from different root faces), load it once with
+//! [`load_obj`] and pass it to [`unfold`] instead.
+
+pub mod color_palette;
+pub mod decimate;
+pub mod export;
+pub mod goal_mesh;
+pub mod half_edge;
+pub mod stl;
+pub mod utils;
+
+pub use crate::goal_mesh::{
+    generate_joints, generate_tabs, EdgeKind, GoalMesh, GoalMeshError, Joinery, LoadOptions,
+    NetEdge, TabOptions, TabStyle, Tolerances, UnfoldResult, UnfoldStrategy,
+    DEFAULT_COPLANAR_TOLERANCE, DEFAULT_TAB_ANGLE, DEFAULT_TAB_MITER_LIMIT, DEFAULT_TAB_WIDTH,
+    DEFAULT_WELD_TOLERANCE,
+};
+
+use crate::utils::find_oriented_bounding_box;
+
+use glam::{Mat3, Vec3};
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+
+use std::io::BufRead;
+use std::path::Path;
+
+/// The default maximum number of (triangular) faces of a goal mesh, see
+/// `UnfoldOptions::max_faces`.
+pub const DEFAULT_MAX_FACES: usize = 100_000;
+
+/// Controls how [`unfold_obj`] loads and unfolds a goal mesh. The options can be (de)serialized,
+/// e.g. to reproduce a particular net later on: missing fields are filled in with their defaults.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct UnfoldOptions {
+    // The face (i.e. polygon) of the goal mesh that the net is unfolded around (which stays fixed)
+    pub root_face: usize,
+
+    // The traversal used to decide which edges are folded and which are cut
+    pub strategy: UnfoldStrategy,
+
+    // How the goal mesh is loaded (i.e. how strictly, how coplanar faces are merged, and with
+    // which tolerances), see `GoalMesh::from_obj()`: its fields are (de)serialized alongside the
+    // rest of the options
+    #[serde(flatten)]
+    pub load: LoadOptions,
+
+    // Goal meshes with more (triangular) faces than this are simplified by collapsing their
+    // shortest edges until they have (at most) this many faces, before they are unfolded (0
+    // disables decimation)
+    pub decimate: usize,
+
+    // Goal meshes with more (triangular) faces than this (after decimation) are rejected right
+    // after they are loaded, before any of the (much slower) work on the half-edge mesh is done (0
+    // disables the limit)
+    pub max_faces: usize,
+
+    // Whether to rotate the net so that its minimum-area bounding box is aligned with the axes
+    pub auto_rotate: bool,
+
+    // If set, the net is rotated so that the edge between these vertices of the goal mesh points
+    // along the positive x-axis instead (which overrides `auto_rotate`)
+    pub align_edge: Option<(usize, usize)>,
+
+    // Whether to mirror the net (and swap its mountain and valley folds), for printing on the
+    // other side of the paper
+    pub flip: bool,
+
+    // Positions of the net that lie within this distance of each other (in the units of the goal
+    // mesh) are snapped together, which closes the gaps left by floating point error (0 disables
+    // welding)
+    pub weld_tolerance: f32,
+
+    // If the net overlaps, up to this many random spanning trees are tried as well (based on
+    // `seed`), and the net with the fewest overlaps is kept (0 disables the search)
+    pub search_attempts: usize,
+    pub seed: u64,
+
+    // Pieces of the goal mesh whose nets would be larger than this across (in the units of the goal
+    // mesh) are split into several smaller nets, i.e. islands (0 disables splitting)
+    pub max_island_size: f32,
+}
+
+impl Default for UnfoldOptions {
+    fn default() -> Self {
+        UnfoldOptions {
+            root_face: 0,
+            strategy: UnfoldStrategy::default(),
+            load: LoadOptions::default(),
+            decimate: 0,
+            max_faces: DEFAULT_MAX_FACES,
+            auto_rotate: true,
+            align_edge: None,
+            flip: false,
+            weld_tolerance: DEFAULT_WELD_TOLERANCE,
+            search_attempts: 0,
+            seed: 0,
+            max_island_size: 0.0,
+        }
+    }
+}
+
+/// Loads the goal mesh from the .obj file at the specified path (following `options.load`,
+/// `options.decimate`, and `options.max_faces`), and logs its topology (and whether it is convex),
+/// since both are good indicators of whether it will unfold into a net without overlaps. The goal
+/// mesh can then be unfolded any number of times with [`unfold`].
+pub fn load_obj(path: &Path, options: &UnfoldOptions) -> Result<GoalMesh, GoalMeshError> {
+    let goal_mesh = GoalMesh::from_obj(path, &options.load)?;
+    finish_loading(goal_mesh, options)
+}
+
+/// Loads the goal mesh from the contents of an .obj file that are read from `reader` (e.g. stdin),
+/// just like [`load_obj`]. Any .mtl files that it references are loaded relative to
+/// `material_directory`.
+pub fn load_obj_reader<R: BufRead>(
+    reader: &mut R,
+    material_directory: &Path,
+    options: &UnfoldOptions,
+) -> Result<GoalMesh, GoalMeshError> {
+    let goal_mesh = GoalMesh::from_obj_reader(reader, material_directory, &options.load)?;
+    finish_loading(goal_mesh, options)
+}
+
+/// Loads the goal mesh from the .stl file at the specified path, just like [`load_obj`] (the
+/// duplicated vertices of the .stl file are welded together, see `GoalMesh::from_stl()`).
+pub fn load_stl(path: &Path, options: &UnfoldOptions) -> Result<GoalMesh, GoalMeshError> {
+    let goal_mesh = GoalMesh::from_stl(path, &options.load)?;
+    finish_loading(goal_mesh, options)
+}
+
+/// Builds the goal mesh from triangles that are already in memory (e.g. generated procedurally),
+/// just like [`load_obj`] (see `GoalMesh::from_vertices_faces()`).
+pub fn load_vertices_faces(
+    vertices: &[Vec3],
+    faces: &[[usize; 3]],
+    options: &UnfoldOptions,
+) -> Result<GoalMesh, GoalMeshError> {
+    let goal_mesh = GoalMesh::from_vertices_faces(vertices, faces, &options.load)?;
+    finish_loading(goal_mesh, options)
+}
+
+/// Decimates the goal mesh that was just loaded (if it has more than `options.decimate` faces),
+/// then rejects it if it has more (triangular) faces than `options.max_faces`, which would take
+/// (far) too long to unfold and draw, and logs it otherwise.
+fn finish_loading(
+    mut goal_mesh: GoalMesh,
+    options: &UnfoldOptions,
+) -> Result<GoalMesh, GoalMeshError> {
+    if options.decimate > 0 && goal_mesh.half_edge_mesh().faces().len() > options.decimate {
+        goal_mesh = goal_mesh.decimate(options.decimate, options.load.coplanar_tolerance)?;
+    }
+
+    let faces = goal_mesh.half_edge_mesh().faces().len();
+    if options.max_faces > 0 && faces > options.max_faces {
+        return Err(GoalMeshError::TooManyFaces {
+            faces,
+            max_faces: options.max_faces,
+        });
+    }
+
+    log_goal_mesh(&goal_mesh);
+    Ok(goal_mesh)
+}
+
+fn log_goal_mesh(goal_mesh: &GoalMesh) {
+    // Log the topology of the goal mesh: anything that isn't topologically a sphere (e.g. a torus)
+    // is unlikely to unfold into a net without overlaps
+    let topology = goal_mesh.half_edge_mesh().topology();
+    info!(
+        "Goal mesh topology: {} vertices, {} edges, {} faces (Euler characteristic: {})",
+        topology.vertices,
+        topology.edges,
+        topology.faces,
+        topology.euler_characteristic()
+    );
+    match topology.genus() {
+        Some(0) => info!("Goal mesh genus: 0"),
+        Some(genus) => warn!(
+            "Goal mesh genus: {} - the net is likely to overlap, since the mesh is not topologically a sphere",
+            genus
+        ),
+        None if topology.border_edges > 0 => info!(
+            "Goal mesh is an open surface: its {} edges along the border are cut",
+            topology.border_edges
+        ),
+        None => warn!("The genus of the goal mesh is undefined (it is not a closed surface)"),
+    }
+
+    // Log where the goal mesh is and how big it is, which shows whether it was exported at the
+    // expected scale (or far from the origin) before it is unfolded
+    let (min, max) = goal_mesh.bounding_box();
+    let centroid = goal_mesh.centroid();
+    info!(
+        "Goal mesh bounding box: ({}, {}, {}) to ({}, {}, {})",
+        min.x(),
+        min.y(),
+        min.z(),
+        max.x(),
+        max.y(),
+        max.z()
+    );
+    let size = max - min;
+    info!(
+        "Goal mesh dimensions: {} x {} x {}",
+        size.x(),
+        size.y(),
+        size.z()
+    );
+    info!(
+        "Goal mesh centroid: ({}, {}, {})",
+        centroid.x(),
+        centroid.y(),
+        centroid.z()
+    );
+
+    // Non-convex goal meshes are unfolded just the same, but they often don't have a net at all,
+    // so set expectations up front
+    let reflex_edges = goal_mesh
+        .half_edge_mesh()
+        .reflex_edges(goal_mesh.tolerances().flat);
+    if reflex_edges.is_empty() {
+        info!("Goal mesh is convex");
+    } else {
+        warn!(
+            "Goal mesh is not convex ({} of {} edges are reflex) - the net is likely to overlap",
+            reflex_edges.len(),
+            topology.edges
+        );
+    }
+}
+
+/// Unfolds a goal mesh that was already loaded (see [`load_obj`]), following the root face,
+/// strategy, search, islands, and orientation of the provided options (the rest of the options only
+/// apply to loading).
+/// The returned net is in the same units as the goal mesh (it isn't scaled or centered), and
+/// contains the positions of every triangle, the classification of every edge (cut or mountain /
+/// valley fold), and the labels of matching cut edges.
+pub fn unfold(
+    goal_mesh: &GoalMesh,
+    options: &UnfoldOptions,
+) -> Result<UnfoldResult, GoalMeshError> {
+    let mut unfold_result = goal_mesh.unfold_best(
+        options.root_face,
+        options.strategy,
+        options.search_attempts,
+        options.seed,
+        options.max_island_size,
+    )?;
+    info!(
+        "Number of overlapping faces: {:?}",
+        unfold_result.overlaps.len()
+    );
+    if !unfold_result.overlaps.is_empty()
+        && !goal_mesh
+            .half_edge_mesh()
+            .is_convex(goal_mesh.tolerances().flat)
+    {
+        warn!(
+            "The net overlaps, which is expected for non-convex goal meshes: try a different strategy or root face"
+        );
+    }
+
+    // Nets are often oriented diagonally, which wastes space: rotate the net so that its
+    // minimum-area bounding box is aligned with the axes (with its longer side horizontal), unless
+    // a particular edge should be horizontal instead (which makes the orientation predictable)
+    if let Some((a, b)) = options.align_edge {
+        let half_edge = goal_mesh.find_edge(a, b)?;
+        // The edge belongs to the face on its left (if it is cut, the other copy belongs to the face
+        // on the other side, and runs backwards), unless it lies along the border of the goal mesh
+        let edges = &unfold_result.edges;
+        let [start, end] = match edges.iter().find(|edge| edge.half_edge == half_edge) {
+            Some(edge) => edge.indices,
+            None => {
+                let edge = edges.iter().find(|edge| edge.pair == half_edge).unwrap();
+                [edge.indices[1], edge.indices[0]]
+            }
+        };
+        let direction = unfold_result.positions[end] - unfold_result.positions[start];
+        let angle = -direction.y().atan2(direction.x());
+        rotate_net(&mut unfold_result, angle);
+        info!(
+            "Rotated net by {:?} degrees to align the edge between vertices {} and {}",
+            angle.to_degrees(),
+            a,
+            b
+        );
+    } else if options.auto_rotate {
+        let (angle, width, height) = find_oriented_bounding_box(&unfold_result.positions);
+        rotate_net(&mut unfold_result, angle);
+        info!(
+            "Rotated net by {:?} degrees to fit its bounding box ({:?} x {:?})",
+            angle.to_degrees(),
+            width,
+            height
+        );
+    }
+
+    if options.flip {
+        unfold_result.flip();
+        info!("Mirrored net (mountain and valley folds are swapped)");
+    }
+
+    let welded = unfold_result.weld_positions(options.weld_tolerance);
+    info!(
+        "Snapped {:?} positions of the net to their neighbors (within {:?})",
+        welded, options.weld_tolerance
+    );
+
+    Ok(unfold_result)
+}
+
+/// Rotates every position of the net by the specified angle (in radians, CCW) around the origin.
+fn rotate_net(unfold_result: &mut UnfoldResult, angle: f32) {
+    let rotation = Mat3::from_rotation_z(angle);
+    for point in unfold_result.positions.iter_mut() {
+        *point = rotation.mul_vec3(*point);
+    }
+}
+
+/// Loads the goal mesh from the .obj file at the specified path and unfolds it: this is a
+/// shorthand for [`load_obj`] followed by [`unfold`].
+pub fn unfold_obj(path: &Path, options: &UnfoldOptions) -> Result<UnfoldResult, GoalMeshError> {
+    let goal_mesh = load_obj(path, options)?;
+    unfold(&goal_mesh, options)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use glam::Vec3;
+
+    #[test]
+    fn test_unfold_obj() {
+        let net = unfold_obj(
+            Path::new("goal_meshes/tetrahedron.obj"),
+            &UnfoldOptions::default(),
+        )
+        .unwrap();
+        assert_eq!(net.polygons.len(), 4);
+        assert_eq!(net.positions.len(), 4 * 3);
+        assert_eq!(net.obj_faces, vec![0, 1, 2, 3]);
+        assert!(net.overlaps.is_empty());
+
+        // A spanning tree of the tetrahedron's 4 faces folds 3 of its 6 edges, and every edge
+        // contributes one entry per side
+        let folds = net.edges.iter().filter(|edge| edge.kind.is_fold()).count();
+        assert_eq!(folds, 3 * 2);
+        assert_eq!(net.edges.len(), 6 * 2);
+
+        // Every face of the (regular) tetrahedron has the same area, which scales with the square
+        // of the scale of the net
+        let areas = net.polygon_areas(10.0);
+        assert_eq!(areas.len(), 4);
+        for area in areas.iter() {
+            assert!((area - 115.47).abs() < 0.01);
+        }
+
+        // Flipping the net mirrors it, but every triangle (and every edge) should still be CCW
+        let options = UnfoldOptions {
+            flip: true,
+            ..Default::default()
+        };
+        let flipped = unfold_obj(Path::new("goal_meshes/tetrahedron.obj"), &options).unwrap();
+        let signed_area = |a: &Vec3, b: &Vec3, c: &Vec3| (*b - *a).cross(*c - *a).z();
+        for (triangle, flipped_triangle) in net.positions.chunks(3).zip(flipped.positions.chunks(3))
+        {
+            assert_eq!(triangle[0].x(), -flipped_triangle[0].x());
+            assert_eq!(triangle[1].x(), -flipped_triangle[2].x());
+            assert!(
+                signed_area(
+                    &flipped_triangle[0],
+                    &flipped_triangle[1],
+                    &flipped_triangle[2]
+                ) > 0.0
+            );
+        }
+        for (edge, flipped_edge) in net.edges.iter().zip(flipped.edges.iter()) {
+            assert_eq!(edge.kind.flipped(), flipped_edge.kind);
+            let polygon = &flipped.polygons[flipped.triangle_polygons[usize::from(edge.face)]];
+            let start = polygon
+                .iter()
+                .position(|&index| index == flipped_edge.indices[0])
+                .unwrap();
+            assert_eq!(
+                polygon[(start + 1) % polygon.len()],
+                flipped_edge.indices[1]
+            );
+        }
+        assert_eq!(flipped.welded_indices[0], 0);
+
+        // Out-of-range root faces are rejected
+        let options = UnfoldOptions {
+            root_face: 4,
+            ..Default::default()
+        };
+        assert!(unfold_obj(Path::new("goal_meshes/tetrahedron.obj"), &options).is_err());
+    }
+
+    #[test]
+    fn test_unfold_repeatedly() {
+        // A single goal mesh can be unfolded around every one of its faces, and unfolding it
+        // again around the same face gives the same net
+        let goal_mesh = load_obj(
+            Path::new("goal_meshes/hexahedron.obj"),
+            &UnfoldOptions::default(),
+        )
+        .unwrap();
+        let first = goal_mesh.unfold(0, UnfoldStrategy::default()).unwrap();
+        for root in 0..goal_mesh.face_count() {
+            let net = goal_mesh.unfold(root, UnfoldStrategy::default()).unwrap();
+            assert_eq!(net.polygons.len(), first.polygons.len());
+            assert_eq!(net.edges.len(), first.edges.len());
+        }
+        let again = goal_mesh.unfold(0, UnfoldStrategy::default()).unwrap();
+        assert_eq!(again.positions, first.positions);
+        assert_eq!(again.obj_faces, first.obj_faces);
+
+        // Scanning every root face agrees with the list of overlap-free root faces
+        let overlaps = goal_mesh.count_overlaps_by_reference_face(UnfoldStrategy::default());
+        assert_eq!(overlaps.len(), goal_mesh.face_count());
+        let overlap_free = goal_mesh.find_overlap_free_reference_faces(UnfoldStrategy::default());
+        assert_eq!(
+            overlap_free,
+            (0..overlaps.len())
+                .filter(|&face| overlaps[face] == 0)
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_align_edge() {
+        let goal_mesh = load_obj(
+            Path::new("goal_meshes/hexahedron.obj"),
+            &UnfoldOptions::default(),
+        )
+        .unwrap();
+
+        // The aligned edge runs from left to right, whichever side of it is used (and regardless of
+        // whether it is cut or folded)
+        for &(a, b) in [(0, 1), (1, 0), (7, 4)].iter() {
+            let options = UnfoldOptions {
+                align_edge: Some((a, b)),
+                ..Default::default()
+            };
+            let net = unfold(&goal_mesh, &options).unwrap();
+            let half_edge = goal_mesh.find_edge(a, b).unwrap();
+            for edge in net.edges.iter() {
+                let [start, end] = if edge.half_edge == half_edge {
+                    edge.indices
+                } else if edge.pair == half_edge && edge.kind.is_fold() {
+                    [edge.indices[1], edge.indices[0]]
+                } else {
+                    continue;
+                };
+                let direction = net.positions[end] - net.positions[start];
+                assert!(direction.y().abs() < 1.0e-5);
+                assert!((direction.x() - 1.1547).abs() < 1.0e-4);
+            }
+        }
+
+        // The diagonals that split the faces into triangles aren't edges of the net (and neither are
+        // vertices that don't exist)
+        for &(a, b) in [(0, 2), (0, 8)].iter() {
+            let options = UnfoldOptions {
+                align_edge: Some((a, b)),
+                ..Default::default()
+            };
+            assert!(matches!(
+                unfold(&goal_mesh, &options),
+                Err(GoalMeshError::InvalidEdge { .. })
+            ));
+        }
+    }
+
+    #[test]
+    fn test_max_faces() {
+        // The dodecahedron's 12 pentagons are split into 36 triangles, which count towards the limit
+        let path = Path::new("goal_meshes/dodecahedron.obj");
+        let options = UnfoldOptions {
+            max_faces: 35,
+            ..Default::default()
+        };
+        match load_obj(path, &options) {
+            Err(GoalMeshError::TooManyFaces { faces, max_faces }) => {
+                assert_eq!((faces, max_faces), (36, 35))
+            }
+            _ => panic!("Expected the goal mesh to have too many faces"),
+        }
+
+        for &max_faces in [36, 0].iter() {
+            let options = UnfoldOptions {
+                max_faces,
+                ..Default::default()
+            };
+            assert!(load_obj(path, &options).is_ok());
+        }
+    }
+
+    #[test]
+    fn test_unfold_options_json() {
+        // Options survive a round trip through JSON (with strategies named like on the command
+        // line), so that the same net can be unfolded again later on
+        let options = UnfoldOptions {
+            root_face: 3,
+            strategy: UnfoldStrategy::SteepestEdge,
+            flip: true,
+            ..UnfoldOptions::default()
+        };
+        let json = serde_json::to_string(&options).unwrap();
+        assert!(json.contains("\"strategy\":\"steepest-edge\""));
+
+        let goal_mesh = load_obj(Path::new("goal_meshes/dodecahedron.obj"), &options).unwrap();
+        let restored: UnfoldOptions = serde_json::from_str(&json).unwrap();
+        assert_eq!(
+            unfold(&goal_mesh, &restored).unwrap().positions,
+            unfold(&goal_mesh, &options).unwrap().positions
+        );
+
+        // Missing fields fall back to their defaults
+        let partial: UnfoldOptions =
+            serde_json::from_str("{\"root_face\": 2, \"strategy\": \"depth-first\"}").unwrap();
+        assert_eq!(partial.root_face, 2);
+        assert_eq!(partial.strategy, UnfoldStrategy::DepthFirst);
+        assert!(partial.auto_rotate && !partial.flip);
+        assert_eq!(partial.load.coplanar_tolerance, DEFAULT_COPLANAR_TOLERANCE);
+    }
+
+    #[test]
+    fn test_coplanar_faces() {
+        // The faces of the dodecahedron are split into triangles, which are merged back into
+        // pentagons (unless merging is disabled)
+        let path = Path::new("goal_meshes/dodecahedron.obj");
+        let net = unfold_obj(path, &UnfoldOptions::default()).unwrap();
+        assert_eq!(net.polygons.len(), 12);
+        assert!(net.polygons.iter().all(|polygon| polygon.len() == 5));
+        assert_eq!(net.positions.len(), 36 * 3);
+        assert_eq!(net.edges.len(), 30 * 2);
+        assert!(net.overlaps.is_empty());
+
+        let options = UnfoldOptions {
+            load: LoadOptions {
+                coplanar_tolerance: 0.0,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let net = unfold_obj(path, &options).unwrap();
+        assert_eq!(net.polygons.len(), 36);
+        assert_eq!(net.edges.len(), 54 * 2);
+    }
+
+    #[test]
+    fn test_material_colors() {
+        // A unit cube whose bottom face is split into two triangles: the first face doesn't have a
+        // material, and the triangles of the bottom face have the material given by `bottom`
+        let write_cube = |bottom: [&str; 2]| {
+            let directory = std::env::temp_dir();
+            std::fs::write(
+                directory.join("durer_materials.mtl"),
+                "newmtl red\nKd 1 0 0\nnewmtl blue\nKd 0 0 1\n",
+            )
+            .unwrap();
+            let contents = format!(
+                "mtllib durer_materials.mtl\n\
+                 v 0 0 0\nv 1 0 0\nv 1 1 0\nv 0 1 0\nv 0 0 1\nv 1 0 1\nv 1 1 1\nv 0 1 1\n\
+                 f 5 6 7 8\nusemtl blue\nf 1 2 6 5\nf 4 8 7 3\nf 1 5 8 4\nf 2 3 7 6\n\
+                 usemtl {}\nf 1 4 3\nusemtl {}\nf 1 3 2\n",
+                bottom[0], bottom[1]
+            );
+            let path = directory.join(format!("durer_materials_{}.obj", bottom.join("_")));
+            std::fs::write(&path, contents).unwrap();
+            path
+        };
+
+        let (red, blue) = (Vec3::new(1.0, 0.0, 0.0), Vec3::new(0.0, 0.0, 1.0));
+        let is_color = |color: Option<Vec3>, expected: Vec3| matches!(color, Some(color) if (color - expected).length() < 1.0e-4);
+
+        let net = unfold_obj(&write_cube(["red", "red"]), &UnfoldOptions::default()).unwrap();
+        assert_eq!(net.polygons.len(), 6);
+        assert_eq!(net.obj_faces, vec![0, 1, 2, 3, 4, 5]);
+        assert_eq!(net.material_colors[0], None);
+        assert!(is_color(net.material_colors[1], blue));
+        assert!(is_color(net.material_colors[5], red));
+
+        // Coplanar faces with different materials are kept apart
+        let net = unfold_obj(&write_cube(["red", "blue"]), &UnfoldOptions::default()).unwrap();
+        assert_eq!(net.polygons.len(), 7);
+        assert!(is_color(net.material_colors[5], red));
+        assert!(is_color(net.material_colors[6], blue));
+    }
+
+    #[test]
+    fn test_separate_solids() {
+        // Write a cube and a tetrahedron (next to it) as two objects of the same .obj file: note
+        // that the vertex indices of .obj files are shared by all of the objects
+        let mut contents = String::from("o hexahedron\n");
+        for &(x, y, z) in [
+            (-1, -1, 1),
+            (-1, -1, -1),
+            (1, -1, -1),
+            (1, -1, 1),
+            (1, 1, 1),
+            (-1, 1, 1),
+            (-1, 1, -1),
+            (1, 1, -1),
+        ]
+        .iter()
+        {
+            contents.push_str(&format!("v {} {} {}\n", x, y, z));
+        }
+        contents.push_str("f 1 2 3 4\nf 1 4 5 6\nf 1 6 7 2\nf 8 5 4 3\nf 8 3 2 7\nf 8 7 6 5\n");
+        contents
+            .push_str("o tetrahedron\nv 5 1 0\nv 5.9 -0.3 0\nv 4.5 -0.3 -0.8\nv 4.5 -0.3 0.8\n");
+        contents.push_str("f 9 10 11\nf 9 11 12\nf 9 12 10\nf 10 12 11\n");
+        let path = std::env::temp_dir().join("durer_separate_solids.obj");
+        std::fs::write(&path, contents).unwrap();
+
+        let goal_mesh = load_obj(&path, &UnfoldOptions::default()).unwrap();
+        assert_eq!(goal_mesh.face_count(), 10);
+        assert_eq!(goal_mesh.half_edge_mesh().topology().genus(), Some(0));
+
+        // Each solid is unfolded into its own net (whichever face the net is unfolded around), and
+        // the nets are laid out side by side
+        for root in 0..goal_mesh.face_count() {
+            let net = goal_mesh.unfold(root, UnfoldStrategy::default()).unwrap();
+            assert_eq!(net.component_count(), 2);
+            assert_eq!(net.polygon_components, vec![0, 0, 0, 0, 0, 0, 1, 1, 1, 1]);
+            assert_eq!(net.obj_faces, (0..10).collect::<Vec<_>>());
+            assert!(net.overlaps.is_empty());
+
+            let right_of_cube = net.triangle_polygons.iter().enumerate().fold(
+                f32::NEG_INFINITY,
+                |right, (triangle, &polygon)| match net.polygon_components[polygon] {
+                    0 => (0..3).fold(right, |right, i| {
+                        right.max(net.positions[triangle * 3 + i].x())
+                    }),
+                    _ => right,
+                },
+            );
+            assert!(net.positions[12 * 3..]
+                .iter()
+                .all(|point| point.x() > right_of_cube));
+
+            // The labels of each solid are numbered separately (and prefixed with its letter)
+            let labels = net
+                .edges
+                .iter()
+                .filter_map(|edge| net.cut_label(edge))
+                .collect::<Vec<_>>();
+            assert_eq!(labels.len(), (7 + 3) * 2);
+            assert!(labels.contains(&String::from("A1")));
+            assert!(labels.contains(&String::from("B3")));
+            assert!(!labels.contains(&String::from("B4")));
+        }
+    }
+
+    #[test]
+    fn test_pack_separate_solids() {
+        // Write one large cube and three small ones (all of them far apart), whose nets are 24 x 18
+        // and 8 x 6 units, respectively
+        let mut contents = String::new();
+        for (solid, &(size, offset)) in [(3.0, 0.0), (1.0, 20.0), (1.0, 40.0), (1.0, 60.0)]
+            .iter()
+            .enumerate()
+        {
+            contents.push_str(&format!("o cube_{}\n", solid));
+            for &(x, y, z) in [
+                (-1.0, -1.0, 1.0),
+                (-1.0, -1.0, -1.0),
+                (1.0, -1.0, -1.0),
+                (1.0, -1.0, 1.0),
+                (1.0, 1.0, 1.0),
+                (-1.0, 1.0, 1.0),
+                (-1.0, 1.0, -1.0),
+                (1.0, 1.0, -1.0),
+            ]
+            .iter()
+            {
+                contents.push_str(&format!(
+                    "v {} {} {}\n",
+                    x * size + offset,
+                    y * size,
+                    z * size
+                ));
+            }
+            for face in [
+                [1, 2, 3, 4],
+                [1, 4, 5, 6],
+                [1, 6, 7, 2],
+                [8, 5, 4, 3],
+                [8, 3, 2, 7],
+                [8, 7, 6, 5],
+            ]
+            .iter()
+            {
+                let indices = face
+                    .iter()
+                    .map(|index| (index + solid * 8).to_string())
+                    .collect::<Vec<_>>();
+                contents.push_str(&format!("f {}\n", indices.join(" ")));
+            }
+        }
+        let path = std::env::temp_dir().join("durer_packed_solids.obj");
+        std::fs::write(&path, contents).unwrap();
+
+        // Side by side, the nets would cover 55.2 x 18 units (with gaps of 2.4 units between them),
+        // but the small nets fit into less space when they are stacked next to the large one
+        let net = unfold_obj(&path, &UnfoldOptions::default()).unwrap();
+        assert_eq!(net.component_count(), 4);
+        assert!(net.overlaps.is_empty());
+        let (_, width, height) = find_oriented_bounding_box(&net.positions);
+        assert!(width * height < 55.2 * 18.0 * 0.9);
+
+        // Each net is only moved, so its faces keep their sizes
+        let area = net.polygon_areas(1.0).iter().sum::<f32>();
+        assert!((area - (6.0 * 36.0 + 3.0 * 6.0 * 4.0)).abs() < 1.0e-2);
+    }
+}
@@ -1,40 +1,191 @@
-mod color_palette;
-mod goal_mesh;
-mod half_edge;
-mod utils;
+use std::path::{Path, PathBuf};
 
-use std::path::Path;
-
-use crate::color_palette::ColorPalette;
-use crate::goal_mesh::GoalMesh;
-use crate::utils::*;
+use durer::color_palette::{
+    component_coloring, random_coloring, ColorMode, ColorPalette, ColorSpace,
+};
+use durer::export::dot::write_dot;
+use durer::export::dxf::write_dxf;
+use durer::export::instructions::write_instructions;
+use durer::export::obj::write_obj;
+use durer::export::page::{sheet_count, PageSize, Units};
+use durer::export::pdf::write_pdf;
+use durer::export::png::{write_png, write_png_at_dpi, write_png_frame};
+use durer::export::svg::{write_outline_svg, write_svg, write_svg_pages, SvgOptions};
+use durer::export::{NetStyle, OUTLINE_COLOR};
+use durer::utils::*;
+use durer::{
+    generate_joints, generate_tabs, load_obj, load_obj_reader, load_stl, unfold, GoalMesh,
+    GoalMeshError, Joinery, LoadOptions, TabOptions, TabStyle, Tolerances, UnfoldOptions,
+    UnfoldResult, UnfoldStrategy,
+};
 
+use bevy::input::mouse::{MouseScrollUnit, MouseWheel};
 use bevy::prelude::*;
+use bevy::render::camera::PerspectiveProjection;
+use bevy::render::mesh::VertexAttribute;
 use bevy::render::pass::ClearColor;
+use bevy::render::pipeline::PrimitiveTopology;
+use bevy::window::{CursorMoved, WindowCloseRequested, WindowId};
+use bevy::winit::WinitWindows;
 use bevy_prototype_lyon::prelude::*;
 use clap;
-use log::info;
+use log::{error, info, warn};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
 use std::fs::File;
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
 
 struct InputArgs {
     path_to_obj: String,
     resolution: u32,
+    msaa_samples: u32,
     color_palette: ColorPalette,
+    color_mode: ColorMode,
+    gradient_space: ColorSpace,
+    component_colors: bool,
+    seed: Option<u64>,
+    stroke_color: Option<Vec3>,
+    stroke_width: f32,
+    fill_opacity: f32,
+    line_join: LineJoin,
+    line_cap: LineCap,
+    fold_dash: Option<(f32, f32)>,
+    fold_widths: Option<(f32, f32)>,
     wireframe: bool,
+    outline: bool,
+    triangulated: bool,
+    labels: bool,
+    face_labels: bool,
+    group_faces: bool,
+    annotate_angles: bool,
+    debug_overlay: bool,
+    headless: bool,
+    strict: bool,
+    fix_winding: bool,
+    allow_open: bool,
+    coplanar_tolerance: f32,
+    weld_tolerance: f32,
+    tolerances: Tolerances,
+    max_island_size: f32,
+    decimate: usize,
+    max_faces: usize,
+    auto_rotate: bool,
+    align_edge: Option<(usize, usize)>,
+    flip: bool,
+    export_svg: Option<String>,
+    export_outline: Option<String>,
+    export_overlaps: Option<String>,
+    export_dxf: Option<String>,
+    export_png: Option<String>,
+    export_pdf: Option<String>,
+    export_net_obj: Option<String>,
+    export_dual: Option<String>,
+    export_instructions: Option<String>,
+    export_resolution: u32,
+    dpi: Option<f32>,
+    animate: Option<usize>,
+    scale: f32,
+    target_size: Option<f32>,
+    units: Units,
+    page_size: Option<PageSize>,
+    margin: Option<f32>,
+    estimate_sheets: Option<PageSize>,
+    scale_bar: bool,
+    legend: bool,
+    tab_width: Option<f32>,
+    tab_style: TabStyle,
+    tab_angle: f32,
+    tab_miter_limit: f32,
+    joinery: Joinery,
+    min_face_area: Option<f32>,
+    suggest_scale: Option<f32>,
+    strategy: UnfoldStrategy,
+    search_attempts: usize,
+    root_face: usize,
+    center_on_face: Option<usize>,
+    save_session: Option<String>,
+}
+
+/// The size and position of the window when it was last closed, which are written to a small .json
+/// file in the user's config directory (see `window_geometry_path()`) so that the window opens the
+/// same way the next time, until `--reset-window` is passed.
+#[derive(Serialize, Deserialize)]
+struct WindowGeometry {
+    // The size of the inside of the window, in (physical) pixels
+    width: u32,
+    height: u32,
+
+    // The position of the top-left corner of the window on the desktop (including its title bar),
+    // if the platform reports it
+    position: Option<(i32, i32)>,
+}
+
+/// Restores the position of the window once it has been created, and saves its geometry whenever
+/// it is closed, see `window_geometry_system()`.
+struct WindowGeometryState {
+    window_close_requested_event_reader: EventReader<WindowCloseRequested>,
+
+    // The position to move the window to on the first frame, if any
+    position: Option<(i32, i32)>,
+}
+
+/// The settings that reproduce a particular net: these are written to a .json file by
+/// `--save-session` and restored by `--load-session`.
+#[derive(Serialize, Deserialize)]
+struct Session {
+    // The .obj (or .stl) file that the goal mesh is loaded from
+    input: String,
+
+    // The number of millimeters per unit of the goal mesh
+    scale: f32,
+
+    color_mode: ColorMode,
+
+    // The seed passed to `--seed`, if any (random colors can only be reproduced with a seed)
+    seed: Option<u64>,
+
+    // How the goal mesh is loaded and unfolded, including the root face of the net
+    unfold_options: UnfoldOptions,
+}
+
+/// Writes log messages to stderr: messages from `durer` itself are shown up to the maximum level
+/// (see `log::max_level()`), while the libraries that it depends on (which are much noisier) only
+/// show their warnings and errors.
+struct StderrLogger;
+
+impl log::Log for StderrLogger {
+    fn enabled(&self, metadata: &log::Metadata) -> bool {
+        let max_level = if metadata.target().starts_with("durer") {
+            log::max_level()
+        } else {
+            log::max_level().min(log::LevelFilter::Warn)
+        };
+        metadata.level() <= max_level
+    }
+
+    fn log(&self, record: &log::Record) {
+        if self.enabled(record.metadata()) {
+            eprintln!("[{}] {}", record.level(), record.args());
+        }
+    }
+
+    fn flush(&self) {}
 }
 
+static LOGGER: StderrLogger = StderrLogger;
+
 fn main() {
     // Parse all of the commandline args
     let matches = clap::App::new("Unfold")
         .version("0.1")
         .author("Michael Walczyk")
-        .about("📦 A program for unfolding arbitrary convex objects.")
+        .about("📦 A program for unfolding polyhedral objects (ideally convex ones) into flat nets.")
         .short_flag('w')
         .long_flag("wireframe")
         .arg(
             clap::Arg::new("INPUT")
-                .about("Sets the input .obj file, i.e. the goal mesh")
-                .required(true),
+                .about("Sets the input .obj (or .stl) file, i.e. the goal mesh, or - to read an .obj file from stdin")
+                .required_unless_present("LOAD_SESSION"),
         )
         .arg(
             clap::Arg::new("RESOLUTION")
@@ -45,6 +196,20 @@ fn main() {
                 .default_value("1024")
                 .takes_value(true),
         )
+        .arg(
+            clap::Arg::new("RESET_WINDOW")
+                .about("Forgets the size and position that the window had when it was last closed, and opens it at --resolution")
+                .long("reset-window"),
+        )
+        .arg(
+            clap::Arg::new("MSAA")
+                .about("Sets the number of samples per pixel that the renderer uses for anti-aliasing")
+                .long("msaa")
+                .value_name("SAMPLES")
+                .possible_values(&["1", "2", "4", "8"])
+                .default_value("8")
+                .takes_value(true),
+        )
         .arg(
             clap::Arg::new("COLOR_PALETTE")
                 .about("Sets the color palette based on the contents of the provided .json file")
@@ -53,20 +218,607 @@ fn main() {
                 .value_name("COLOR_PALETTE")
                 .takes_value(true),
         )
+        .arg(
+            clap::Arg::new("COLOR_MODE")
+                .about("Sets how the faces of the net are colored")
+                .long("color-mode")
+                .value_name("MODE")
+                .possible_values(&["palette", "gradient", "random", "material", "normal", "vertex"])
+                .default_value("palette")
+                .takes_value(true),
+        )
+        .arg(
+            clap::Arg::new("GRADIENT_SPACE")
+                .about("Sets the color space that the gradient of the gradient color mode is interpolated in")
+                .long("gradient-space")
+                .value_name("SPACE")
+                .possible_values(&["rgb", "hsv", "lch"])
+                .default_value("rgb")
+                .takes_value(true),
+        )
+        .arg(
+            clap::Arg::new("COMPONENT_COLORS")
+                .about("Gives each separate piece of the goal mesh its own range of hues (with the colors of the color mode as variations within each piece), so that the faces of each piece are grouped together")
+                .long("component-colors"),
+        )
+        .arg(
+            clap::Arg::new("SEED")
+                .about("Sets the seed used to pick the colors of the random color mode (and the spanning trees of --search), so that they can be reproduced [default: a random seed]")
+                .long("seed")
+                .value_name("SEED")
+                .takes_value(true),
+        )
+        .arg(
+            clap::Arg::new("BACKGROUND")
+                .about("Sets the background color (as hex, i.e. #RRGGBB), overriding the color palette")
+                .long("background")
+                .value_name("COLOR")
+                .takes_value(true),
+        )
+        .arg(
+            clap::Arg::new("STROKE_COLOR")
+                .about("Sets the color (as hex, i.e. #RRGGBB) of the edges in wireframe mode and the outlines of glue tabs")
+                .long("stroke-color")
+                .value_name("COLOR")
+                .takes_value(true),
+        )
+        .arg(
+            clap::Arg::new("STROKE_WIDTH")
+                .about("Sets the width (in pixels) of the edges in wireframe mode and the outlines of glue tabs")
+                .long("stroke-width")
+                .value_name("PIXELS")
+                .default_value("2")
+                .takes_value(true),
+        )
+        .arg(
+            clap::Arg::new("FILL_OPACITY")
+                .about("Sets the opacity of the faces in filled mode, from 0 (transparent) to 1 (opaque), which makes overlapping faces easy to spot")
+                .long("fill-opacity")
+                .value_name("OPACITY")
+                .default_value("1")
+                .takes_value(true),
+        )
+        .arg(
+            clap::Arg::new("LINE_JOIN")
+                .about("Sets how the lines of the net (edges in wireframe mode, outlines, and glue tabs) are joined at their corners in the renderer")
+                .long("line-join")
+                .value_name("JOIN")
+                .possible_values(&["round", "miter", "bevel"])
+                .default_value("round")
+                .takes_value(true),
+        )
+        .arg(
+            clap::Arg::new("LINE_CAP")
+                .about("Sets how the ends of the edges in wireframe mode (and of their dashes) are drawn in the renderer")
+                .long("line-cap")
+                .value_name("CAP")
+                .possible_values(&["round", "butt", "square"])
+                .default_value("round")
+                .takes_value(true),
+        )
+        .arg(
+            clap::Arg::new("FOLD_DASH")
+                .about("Sets the length of each dash and gap along the folds (in millimeters, scaled like --scale), e.g. 3,2 [default: a different pattern for mountain and valley folds]")
+                .long("fold-dash")
+                .value_name("ON,OFF")
+                .takes_value(true),
+        )
+        .arg(
+            clap::Arg::new("FOLD_WIDTH_BY_ANGLE")
+                .about("Scales the width (in pixels) of each fold in wireframe mode (and in exported .pdf files) with its dihedral angle, from MIN for folds between nearly coplanar faces to MAX for folds that turn all the way back, e.g. 1,6")
+                .long("fold-width-by-angle")
+                .value_name("MIN,MAX")
+                .takes_value(true),
+        )
         .arg(
             clap::Arg::new("WIREFRAME")
                 .about("Sets the draw mode to wireframe (instead of filled)")
                 .short('w')
                 .long("wireframe"),
         )
+        .arg(
+            clap::Arg::new("OUTLINE")
+                .about("Outlines each face of the net in filled mode, using the stroke color and width")
+                .long("outline")
+                .conflicts_with("WIREFRAME"),
+        )
+        .arg(
+            clap::Arg::new("FACES")
+                .about("Sets whether each face of the net is drawn (and exported) as a single polygon, or with the lines between the triangles that it was split into")
+                .long("faces")
+                .value_name("FACES")
+                .possible_values(&["polygon", "triangulated"])
+                .default_value("polygon")
+                .takes_value(true),
+        )
+        .arg(
+            clap::Arg::new("LABELS")
+                .about("Labels each pair of matching cut edges with a shared number")
+                .short('l')
+                .long("labels"),
+        )
+        .arg(
+            clap::Arg::new("LABEL_FACES")
+                .about("Labels each face of the net with the index of the corresponding face of the .obj file")
+                .long("label-faces"),
+        )
+        .arg(
+            clap::Arg::new("GROUP_FACES")
+                .about("Writes each face of exported .svg files into its own group (with the index of the face of the .obj file in its id), with its cut and fold edges in nested groups")
+                .long("group-faces"),
+        )
+        .arg(
+            clap::Arg::new("ANNOTATE_ANGLES")
+                .about("Labels each fold with the dihedral angle (in degrees) that its faces meet at, prefixed with M or V for mountain and valley folds")
+                .long("annotate-angles"),
+        )
+        .arg(
+            clap::Arg::new("QUIET")
+                .about("Only logs errors")
+                .short('q')
+                .long("quiet")
+                .conflicts_with("VERBOSE"),
+        )
+        .arg(
+            clap::Arg::new("VERBOSE")
+                .about("Also logs the details of each step, e.g. which face each face of the net is unfolded from [default: warnings and progress, or the level in RUST_LOG]")
+                .short('v')
+                .long("verbose"),
+        )
+        .arg(
+            clap::Arg::new("DEBUG_OVERLAY")
+                .about("Draws an arrow in each face of the net along the edge that it was unfolded across (i.e. its \"up\" direction), along with the axes of the net's coordinate system")
+                .long("debug-overlay"),
+        )
+        .arg(
+            clap::Arg::new("HEADLESS")
+                .about("Writes the requested export file(s) (or logs the --estimate-sheets estimate) and exits, without opening a window")
+                .long("headless")
+                .requires("EXPORT"),
+        )
+        .arg(
+            clap::Arg::new("PREVIEW_3D")
+                .about("Shows the goal mesh itself as a 3D solid (which can be orbited with the mouse) instead of unfolding it, to check that the right model was loaded")
+                .long("preview-3d")
+                .conflicts_with_all(&["HEADLESS", "EXPORT"]),
+        )
+        .arg(
+            clap::Arg::new("STRICT")
+                .about("Rejects goal meshes with degenerate (zero-area) faces, instead of skipping those faces")
+                .long("strict"),
+        )
+        .arg(
+            clap::Arg::new("FIX_WINDING")
+                .about("Flips faces whose winding order is inconsistent with their neighbors' (and goal meshes that are inside out), instead of rejecting the goal mesh")
+                .long("fix-winding"),
+        )
+        .arg(
+            clap::Arg::new("ALLOW_OPEN")
+                .about("Unfolds open surfaces (i.e. goal meshes with holes in them, such as a bowl) by cutting the edges along their borders, instead of rejecting them")
+                .long("allow-open"),
+        )
+        .arg(
+            clap::Arg::new("CHECK_GEOMETRY")
+                .about("Reports faces of the goal mesh that intersect each other in 3D (tests every pair of faces, so this can be slow for large goal meshes)")
+                .long("check-geometry"),
+        )
+        .arg(
+            clap::Arg::new("COPLANAR_TOLERANCE")
+                .about("Merges neighboring faces of the goal mesh that meet at an angle of less than this (in degrees), so that no folds are drawn between them (0 disables merging)")
+                .long("coplanar-tol")
+                .value_name("DEGREES")
+                .default_value("0.1")
+                .takes_value(true),
+        )
+        .arg(
+            clap::Arg::new("WELD_TOLERANCE")
+                .about("Snaps together the corners of the net that lie within this distance of each other (in millimeters, following --scale), which closes hairline gaps between faces (0 disables welding)")
+                .long("weld-tol")
+                .value_name("MM")
+                .default_value("0.01")
+                .takes_value(true),
+        )
+        .arg(
+            clap::Arg::new("EPSILON")
+                .about("Sets all of the relative tolerances at once (--degenerate-tol, --overlap-tol and --stl-weld-tol), e.g. to make noisy scans load and unfold cleanly; each of them can still be overridden")
+                .long("epsilon")
+                .value_name("RATIO")
+                .takes_value(true),
+        )
+        .arg(
+            clap::Arg::new("DEGENERATE_TOLERANCE")
+                .about("Skips (or, with --strict, rejects) faces of the goal mesh whose area is at most this fraction of the square of their longest side [default: 1e-6]")
+                .long("degenerate-tol")
+                .value_name("RATIO")
+                .takes_value(true),
+        )
+        .arg(
+            clap::Arg::new("OVERLAP_TOLERANCE")
+                .about("Ignores overlaps between faces of the net that are at most this fraction of the size of the net [default: 1e-4]")
+                .long("overlap-tol")
+                .value_name("RATIO")
+                .takes_value(true),
+        )
+        .arg(
+            clap::Arg::new("STL_WELD_TOLERANCE")
+                .about("Welds together the vertices of .stl files that lie within this fraction of the diagonal of the goal mesh [default: 1e-6]")
+                .long("stl-weld-tol")
+                .value_name("RATIO")
+                .takes_value(true),
+        )
+        .arg(
+            clap::Arg::new("FLAT_TOLERANCE")
+                .about("Treats edges of the goal mesh whose dihedral angle is within this many degrees of zero as flat, i.e. as neither reflex nor mountain folds [default: 0.0057]")
+                .long("flat-tol")
+                .value_name("DEGREES")
+                .takes_value(true),
+        )
+        .arg(
+            clap::Arg::new("MAX_ISLAND_SIZE")
+                .about("Splits the net into several smaller nets (islands) that are each at most this large across, in millimeters (following --scale), with matching labels along the cuts between them")
+                .long("max-island-size")
+                .value_name("MM")
+                .takes_value(true),
+        )
+        .arg(
+            clap::Arg::new("DECIMATE")
+                .about("Simplifies goal meshes with more (triangular) faces than this by collapsing their shortest edges, until they have at most this many faces")
+                .long("decimate")
+                .value_name("FACES")
+                .takes_value(true),
+        )
+        .arg(
+            clap::Arg::new("MAX_FACES")
+                .about("Refuses to load goal meshes with more (triangular) faces than this, which would take too long to unfold and draw (0 disables the limit)")
+                .long("max-faces")
+                .value_name("N")
+                .default_value("100000")
+                .takes_value(true),
+        )
+        .arg(
+            clap::Arg::new("NO_AUTO_ROTATE")
+                .about("Disables rotating the net to fit its (minimum-area) bounding box to the canvas")
+                .long("no-auto-rotate"),
+        )
+        .arg(
+            clap::Arg::new("ALIGN_EDGE")
+                .about("Rotates the net so that the edge between these vertices of the goal mesh (numbered like --list-faces) runs from left to right, instead of fitting its bounding box, e.g. 0,1")
+                .long("align-edge")
+                .value_name("A,B")
+                .takes_value(true),
+        )
+        .arg(
+            clap::Arg::new("FLIP")
+                .about("Mirrors the net (swapping its mountain and valley folds), for printing on the back of the paper")
+                .long("flip"),
+        )
+        .arg(
+            clap::Arg::new("STRATEGY")
+                .about("Sets the strategy used to build the spanning tree of the net")
+                .short('s')
+                .long("strategy")
+                .value_name("STRATEGY")
+                .possible_values(&[
+                    "breadth-first",
+                    "depth-first",
+                    "steepest-edge",
+                    "min-weight-spanning-tree",
+                    "strip",
+                ])
+                .default_value("breadth-first")
+                .takes_value(true),
+        )
+        .arg(
+            clap::Arg::new("ROOT_FACE")
+                .about("Sets the face of the goal mesh that the net is unfolded from (starting at 0)")
+                .long("root-face")
+                .value_name("INDEX")
+                .default_value("0")
+                .takes_value(true),
+        )
+        .arg(
+            clap::Arg::new("CENTER_ON_FACE")
+                .about("Centers the view (and exported .png files) on this face of the goal mesh (starting at 0) instead of the middle of the net, shrinking the net as needed to keep all of it in frame")
+                .long("center-on-face")
+                .value_name("INDEX")
+                .takes_value(true),
+        )
+        .arg(
+            clap::Arg::new("SEARCH")
+                .about("If the net overlaps, tries up to this many random spanning trees (following --seed, or 0) and keeps the net with the fewest overlaps")
+                .long("search")
+                .value_name("ATTEMPTS")
+                .takes_value(true),
+        )
+        .arg(
+            clap::Arg::new("FIND_ROOT_FACES")
+                .about("Prints every root face that results in a net without overlaps and exits")
+                .long("find-root-faces"),
+        )
+        .arg(
+            clap::Arg::new("SCAN_ROOTS")
+                .about("Prints the number of overlapping pairs of faces in the net unfolded from every root face (fewest first) and exits")
+                .long("scan-roots")
+                .conflicts_with("FIND_ROOT_FACES"),
+        )
+        .arg(
+            clap::Arg::new("LIST_FACES")
+                .about("Prints the vertices, normal, and neighboring faces of every face of the goal mesh and exits")
+                .long("list-faces")
+                .conflicts_with_all(&["FIND_ROOT_FACES", "SCAN_ROOTS"]),
+        )
+        .arg(
+            clap::Arg::new("OUT")
+                .about("Writes the results of --scan-roots to the provided .csv file as well")
+                .long("out")
+                .value_name("PATH")
+                .requires("SCAN_ROOTS")
+                .takes_value(true),
+        )
+        .arg(
+            clap::Arg::new("MIN_FACE_AREA")
+                .about("Warns about the faces of the net whose printed area (following --scale) is smaller than this, in square millimeters")
+                .long("min-face-area")
+                .value_name("MM2")
+                .takes_value(true),
+        )
+        .arg(
+            clap::Arg::new("SUGGEST_SCALE")
+                .about("Logs the smallest scale at which every face of the net (and every tab) is at least this wide, in millimeters")
+                .long("suggest-scale")
+                .value_name("MM")
+                .takes_value(true),
+        )
+        .arg(
+            clap::Arg::new("TABS")
+                .about("Adds glue tabs of the specified width (in millimeters) to the cut edges of the net")
+                .long("tabs")
+                .value_name("WIDTH")
+                .takes_value(true),
+        )
+        .arg(
+            clap::Arg::new("TAB_STYLE")
+                .about("Sets the shape of the glue tabs")
+                .long("tab-style")
+                .value_name("STYLE")
+                .possible_values(&["trapezoid", "rectangle", "rounded"])
+                .default_value("trapezoid")
+                .requires("TABS")
+                .takes_value(true),
+        )
+        .arg(
+            clap::Arg::new("TAB_ANGLE")
+                .about("Sets the angle (in degrees) between the slanted sides of trapezoidal glue tabs and the edges that they are attached to")
+                .long("tab-angle")
+                .value_name("DEGREES")
+                .default_value("45")
+                .requires("TABS")
+                .takes_value(true),
+        )
+        .arg(
+            clap::Arg::new("TAB_MITER_LIMIT")
+                .about("Sets how far (as a multiple of the tab width) the miter between the overlapping glue tabs at a corner of the net can reach before the tabs are cut back square instead")
+                .long("tab-miter-limit")
+                .value_name("RATIO")
+                .default_value("4")
+                .requires("TABS")
+                .takes_value(true),
+        )
+        .arg(
+            clap::Arg::new("JOINERY")
+                .about("Sets how matching cut edges are joined: with glue tabs, or with tabs that fit into slots in the faces on the other side, so that the net can be assembled without glue")
+                .long("joinery")
+                .value_name("JOINERY")
+                .possible_values(&["glue", "slot"])
+                .default_value("glue")
+                .requires("TABS")
+                .takes_value(true),
+        )
+        .arg(
+            clap::Arg::new("EXPORT_SVG")
+                .about("Writes the unfolded net to the provided .svg file")
+                .long("export-svg")
+                .value_name("PATH")
+                .takes_value(true),
+        )
+        .arg(
+            clap::Arg::new("EXPORT_OUTLINE")
+                .about("Writes only the outline of the unfolded net (including any tabs) to the provided .svg file, as a single path at its physical size (for vinyl cutters)")
+                .long("export-outline")
+                .value_name("PATH")
+                .takes_value(true),
+        )
+        .arg(
+            clap::Arg::new("EXPORT_OVERLAPS")
+                .about("Writes the unfolded net to the provided .svg file with only its overlapping faces highlighted (in red), for sharing nets that overlap")
+                .long("export-overlaps")
+                .value_name("PATH")
+                .takes_value(true),
+        )
+        .arg(
+            clap::Arg::new("EXPORT_DXF")
+                .about("Writes the unfolded net to the provided .dxf file (for laser cutting)")
+                .long("export-dxf")
+                .value_name("PATH")
+                .takes_value(true),
+        )
+        .arg(
+            clap::Arg::new("EXPORT_PNG")
+                .about("Writes the unfolded net to the provided .png file")
+                .long("export-png")
+                .value_name("PATH")
+                .takes_value(true),
+        )
+        .arg(
+            clap::Arg::new("EXPORT_PDF")
+                .about("Writes the unfolded net to the provided .pdf file (at its physical size)")
+                .long("export-pdf")
+                .value_name("PATH")
+                .takes_value(true),
+        )
+        .arg(
+            clap::Arg::new("EXPORT_RESOLUTION")
+                .about("Sets the resolution (width and height) of the exported .png file [default: the resolution of the renderer]")
+                .long("export-resolution")
+                .value_name("PIXELS")
+                .requires("EXPORT_PNG")
+                .takes_value(true),
+        )
+        .arg(
+            clap::Arg::new("DPI")
+                .about("Rasterizes the exported .png file at the physical size of the net (following --scale or --target-size) with this many pixels per inch (stored in the file), instead of matching the window")
+                .long("dpi")
+                .value_name("DPI")
+                .requires("EXPORT_PNG")
+                .conflicts_with("EXPORT_RESOLUTION")
+                .takes_value(true),
+        )
+        .arg(
+            clap::Arg::new("ANIMATE")
+                .about("Also writes this many frames of the goal mesh unfolding into the net, as .png files that are numbered after the exported .png file (at the export resolution)")
+                .long("animate")
+                .value_name("FRAMES")
+                .requires("EXPORT_PNG")
+                .takes_value(true),
+        )
+        .arg(
+            clap::Arg::new("EXPORT_NET_OBJ")
+                .about("Writes the unfolded net to the provided .obj file (for importing into other software)")
+                .long("export-net-obj")
+                .value_name("PATH")
+                .takes_value(true),
+        )
+        .arg(
+            clap::Arg::new("EXPORT_DUAL")
+                .about("Writes the dual graph of the goal mesh (with its cuts and folds) to the provided Graphviz .dot file")
+                .long("export-dual")
+                .value_name("PATH")
+                .takes_value(true),
+        )
+        .arg(
+            clap::Arg::new("EXPORT_INSTRUCTIONS")
+                .about("Writes the order in which the matching cut edges of the net should be glued together to the provided text file")
+                .long("export-instructions")
+                .value_name("PATH")
+                .takes_value(true),
+        )
+        .arg(
+            clap::Arg::new("SCALE")
+                .about("Sets the physical size of exported .dxf, .pdf, and paged files, in millimeters per unit of the goal mesh")
+                .long("scale")
+                .value_name("FACTOR")
+                .default_value("1")
+                .takes_value(true),
+        )
+        .arg(
+            clap::Arg::new("TARGET_SIZE")
+                .about("Sets the physical size of the largest dimension of the goal mesh (in --units), instead of setting --scale directly")
+                .long("target-size")
+                .value_name("SIZE")
+                .conflicts_with("SCALE")
+                .takes_value(true),
+        )
+        .arg(
+            clap::Arg::new("UNITS")
+                .about("Sets the units of --target-size, and of the physical sizes that are logged")
+                .long("units")
+                .value_name("UNITS")
+                .possible_values(&["mm", "cm", "in"])
+                .default_value("mm")
+                .takes_value(true),
+        )
+        .arg(
+            clap::Arg::new("PAGE_SIZE")
+                .about("Splits the exported .svg or .pdf file into multiple pages of the specified size (A4, Letter, or WxH in millimeters)")
+                .long("page-size")
+                .value_name("SIZE")
+                .requires("PAGED_EXPORT")
+                .takes_value(true),
+        )
+        .arg(
+            clap::Arg::new("MARGIN")
+                .about("Leaves a blank border of this width (in millimeters) along the edges of every page of the exported .svg or .pdf file(s) [default: 0 with --page-size]")
+                .long("margin")
+                .value_name("MM")
+                .requires("PAGED_EXPORT")
+                .takes_value(true),
+        )
+        .arg(
+            clap::Arg::new("ESTIMATE_SHEETS")
+                .about("Logs how many pages of the specified size (A4, Letter, or WxH in millimeters) the net would be split across, and how much of their area it covers, without exporting it")
+                .long("estimate-sheets")
+                .value_name("SIZE")
+                .takes_value(true),
+        )
+        .arg(
+            clap::Arg::new("SCALE_BAR")
+                .about("Draws a labeled reference bar (e.g. 50 mm, following --scale) in a corner of the exported .svg or .pdf file(s)")
+                .long("scale-bar")
+                .requires("PAGED_EXPORT"),
+        )
+        .arg(
+            clap::Arg::new("LEGEND")
+                .about("Draws a key in a corner of the exported .svg or .pdf file(s) that explains the solid (cut), dashed (valley fold), and dash-dotted (mountain fold) lines")
+                .long("legend")
+                .requires("PAGED_EXPORT"),
+        )
+        .arg(
+            clap::Arg::new("SAVE_SESSION")
+                .about("Writes the input file, root face, strategy, scale, color mode, and the rest of the unfolding options to the provided .json file (which is updated whenever the net is re-rolled in the viewer)")
+                .long("save-session")
+                .value_name("FILE")
+                .takes_value(true),
+        )
+        .arg(
+            clap::Arg::new("LOAD_SESSION")
+                .about("Restores the settings from a .json file written by --save-session (any of them can still be overridden on the command line)")
+                .long("load-session")
+                .value_name("FILE")
+                .takes_value(true),
+        )
+        .group(
+            clap::ArgGroup::new("EXPORT")
+                .args(&[
+                    "EXPORT_SVG",
+                    "EXPORT_OUTLINE",
+                    "EXPORT_OVERLAPS",
+                    "EXPORT_DXF",
+                    "EXPORT_PNG",
+                    "EXPORT_PDF",
+                    "EXPORT_NET_OBJ",
+                    "EXPORT_DUAL",
+                    "EXPORT_INSTRUCTIONS",
+                    "ESTIMATE_SHEETS",
+                ])
+                .multiple(true),
+        )
+        .group(
+            clap::ArgGroup::new("PAGED_EXPORT")
+                .args(&["EXPORT_SVG", "EXPORT_PDF"])
+                .multiple(true),
+        )
         .get_matches();
 
-    // This arg is required, so we can safely unwrap
+    // Set up logging before anything else is logged: without either flag, the level can also be
+    // set with the `RUST_LOG` environment variable (e.g. `RUST_LOG=debug`)
+    let log_level = if matches.is_present("QUIET") {
+        log::LevelFilter::Error
+    } else if matches.is_present("VERBOSE") {
+        log::LevelFilter::Debug
+    } else {
+        std::env::var("RUST_LOG")
+            .ok()
+            .and_then(|level| level.parse().ok())
+            .unwrap_or(log::LevelFilter::Info)
+    };
+    log::set_logger(&LOGGER).expect("Failed to set up logging");
+    log::set_max_level(log_level);
+
+    // This arg is required (unless it is restored from a session below)
     let path_to_obj = matches
         .value_of("INPUT")
-        .expect("This parameter should always be provided")
-        .to_owned();
-    info!("Unfolding .obj: {:?}", path_to_obj);
+        .map(|path| path.to_owned())
+        .unwrap_or_default();
 
     let resolution = matches
         .value_of("RESOLUTION")
@@ -79,7 +831,7 @@ fn main() {
     );
 
     // Parse and construct the color palette (or return the default color palette if none was provided)
-    let color_palette = match matches.value_of("COLOR_PALETTE") {
+    let mut color_palette = match matches.value_of("COLOR_PALETTE") {
         Some(path) => {
             let json_file_path = Path::new(path);
             let json_file = File::open(json_file_path).expect("File not found");
@@ -105,116 +857,2561 @@ fn main() {
         }
     };
 
-    // Aggregate args
-    let input_args = InputArgs {
-        path_to_obj,
-        resolution,
-        color_palette,
-        wireframe: matches.is_present("WIREFRAME"),
-    };
+    if let Some(hex) = matches.value_of("BACKGROUND") {
+        color_palette.background = hex_to_rgb(hex).unwrap_or_else(|e| panic!("{}", e));
+    }
 
-    App::build()
-        .add_resource(WindowDescriptor {
-            width: resolution,
-            height: resolution,
-            title: String::from("unfold"),
-            ..Default::default()
-        })
-        .add_resource(ClearColor(Color::from(
-            input_args.color_palette.background.extend(1.0),
-        )))
-        .add_resource(Msaa { samples: 8 })
-        .add_resource(input_args)
-        .add_default_plugins()
-        .add_startup_system(setup.system())
-        .run();
+    let seed = matches
+        .value_of("SEED")
+        .map(|seed| seed.parse::<u64>().expect("Invalid seed"));
+
+    let stroke_color = matches
+        .value_of("STROKE_COLOR")
+        .map(|hex| hex_to_rgb(hex).unwrap_or_else(|e| panic!("{}", e)));
+
+    let stroke_width = matches
+        .value_of("STROKE_WIDTH")
+        .unwrap()
+        .parse::<f32>()
+        .expect("Invalid stroke width");
+
+    let fill_opacity = matches
+        .value_of("FILL_OPACITY")
+        .unwrap()
+        .parse::<f32>()
+        .ok()
+        .filter(|opacity| (0.0..=1.0).contains(opacity))
+        .expect("Invalid fill opacity (expected a number between 0 and 1)");
+
+    let fold_dash = matches.value_of("FOLD_DASH").map(|pattern| {
+        let lengths = pattern
+            .split(',')
+            .map(|length| length.trim().parse::<f32>().expect("Invalid fold dash pattern"))
+            .collect::<Vec<_>>();
+        match lengths[..] {
+            [dash, gap] if dash > 0.0 && gap >= 0.0 => (dash, gap),
+            _ => panic!(
+                "Invalid fold dash pattern: expected the (positive) lengths of a dash and a gap, separated by a comma"
+            ),
+        }
+    });
+
+    let fold_widths = matches.value_of("FOLD_WIDTH_BY_ANGLE").map(|range| {
+        let widths = range
+            .split(',')
+            .map(|width| width.trim().parse::<f32>().expect("Invalid fold width range"))
+            .collect::<Vec<_>>();
+        match widths[..] {
+            [min, max] if min > 0.0 && max > 0.0 => (min, max),
+            _ => panic!(
+                "Invalid fold width range: expected the (positive) widths of the shallowest and sharpest folds, separated by a comma"
+            ),
+        }
+    });
+
+    let align_edge = matches.value_of("ALIGN_EDGE").map(|edge| {
+        let vertices = edge
+            .split(',')
+            .map(|vertex| vertex.trim().parse::<usize>().expect("Invalid edge to align"))
+            .collect::<Vec<_>>();
+        match vertices[..] {
+            [a, b] if a != b => (a, b),
+            _ => panic!(
+                "Invalid edge to align: expected the indices of two different vertices, separated by a comma"
+            ),
+        }
+    });
+
+    // These args have default values (and are checked against their lists of possible values), so
+    // we can safely unwrap
+    let color_mode = matches
+        .value_of("COLOR_MODE")
+        .unwrap()
+        .parse::<ColorMode>()
+        .unwrap();
+
+    let gradient_space = matches
+        .value_of("GRADIENT_SPACE")
+        .unwrap()
+        .parse::<ColorSpace>()
+        .unwrap();
+
+    let msaa_samples = matches.value_of("MSAA").unwrap().parse::<u32>().unwrap();
+
+    let line_join = match matches.value_of("LINE_JOIN").unwrap() {
+        "miter" => LineJoin::Miter,
+        "bevel" => LineJoin::Bevel,
+        _ => LineJoin::Round,
+    };
+
+    let line_cap = match matches.value_of("LINE_CAP").unwrap() {
+        "butt" => LineCap::Butt,
+        "square" => LineCap::Square,
+        _ => LineCap::Round,
+    };
+
+    let strategy = matches
+        .value_of("STRATEGY")
+        .unwrap()
+        .parse::<UnfoldStrategy>()
+        .unwrap();
+    info!("Unfolding with strategy: {:?}", strategy);
+
+    let search_attempts = matches.value_of("SEARCH").map_or(0, |attempts| {
+        attempts
+            .parse::<usize>()
+            .expect("Invalid number of attempts")
+    });
+
+    let root_face = matches
+        .value_of("ROOT_FACE")
+        .unwrap()
+        .parse::<usize>()
+        .expect("Invalid root face");
+
+    let center_on_face = matches.value_of("CENTER_ON_FACE").map(|face| {
+        face.parse::<usize>()
+            .expect("Invalid face to center on (expected the index of a face)")
+    });
+
+    let coplanar_tolerance = matches
+        .value_of("COPLANAR_TOLERANCE")
+        .unwrap()
+        .parse::<f32>()
+        .expect("Invalid coplanar tolerance")
+        .to_radians();
+
+    let weld_tolerance = matches
+        .value_of("WELD_TOLERANCE")
+        .unwrap()
+        .parse::<f32>()
+        .expect("Invalid weld tolerance");
+
+    // The tolerances of the individual checks take precedence over `--epsilon`, and none of them
+    // can be zero (or negative), since floating point error alone would then make neighboring
+    // faces overlap
+    let mut tolerances = matches
+        .value_of("EPSILON")
+        .map_or_else(Tolerances::default, |epsilon| {
+            epsilon
+                .parse::<f32>()
+                .ok()
+                .and_then(Tolerances::from_epsilon)
+                .expect("Invalid epsilon (expected a positive number)")
+        });
+    let tolerance = |name: &str, message: &str| {
+        matches.value_of(name).map(|tolerance| {
+            tolerance
+                .parse::<f32>()
+                .ok()
+                .filter(|&tolerance| tolerance > 0.0 && tolerance.is_finite())
+                .expect(message)
+        })
+    };
+    if let Some(degenerate) = tolerance(
+        "DEGENERATE_TOLERANCE",
+        "Invalid degenerate tolerance (expected a positive number)",
+    ) {
+        tolerances.degenerate = degenerate;
+    }
+    if let Some(overlap) = tolerance(
+        "OVERLAP_TOLERANCE",
+        "Invalid overlap tolerance (expected a positive number)",
+    ) {
+        tolerances.overlap = overlap;
+    }
+    if let Some(stl_weld) = tolerance(
+        "STL_WELD_TOLERANCE",
+        "Invalid .stl weld tolerance (expected a positive number)",
+    ) {
+        tolerances.stl_weld = stl_weld;
+    }
+    if let Some(flat) = tolerance(
+        "FLAT_TOLERANCE",
+        "Invalid flat tolerance (expected a positive number)",
+    ) {
+        tolerances.flat = flat.to_radians();
+    }
+
+    let max_island_size = matches.value_of("MAX_ISLAND_SIZE").map_or(0.0, |size| {
+        size.parse::<f32>().expect("Invalid maximum island size")
+    });
+
+    let decimate = matches.value_of("DECIMATE").map_or(0, |faces| {
+        faces
+            .parse::<usize>()
+            .expect("Invalid number of faces to decimate to")
+    });
+
+    let max_faces = matches
+        .value_of("MAX_FACES")
+        .unwrap()
+        .parse::<usize>()
+        .expect("Invalid maximum number of faces");
+
+    let tab_width = matches
+        .value_of("TABS")
+        .map(|width| width.parse::<f32>().expect("Invalid tab width"));
+
+    let tab_style = matches
+        .value_of("TAB_STYLE")
+        .unwrap()
+        .parse::<TabStyle>()
+        .unwrap();
+
+    let tab_angle = matches
+        .value_of("TAB_ANGLE")
+        .unwrap()
+        .parse::<f32>()
+        .expect("Invalid tab angle");
+    if tab_angle <= 0.0 || tab_angle > 90.0 {
+        panic!(
+            "Invalid tab angle: {} degrees (it must be between 0 and 90)",
+            tab_angle
+        );
+    }
+    let tab_angle = tab_angle.to_radians();
+
+    let tab_miter_limit = matches
+        .value_of("TAB_MITER_LIMIT")
+        .unwrap()
+        .parse::<f32>()
+        .expect("Invalid tab miter limit");
+    if tab_miter_limit < 1.0 {
+        panic!(
+            "Invalid tab miter limit: {} (it must be at least 1)",
+            tab_miter_limit
+        );
+    }
+
+    let joinery = matches
+        .value_of("JOINERY")
+        .unwrap()
+        .parse::<Joinery>()
+        .unwrap();
+
+    let min_face_area = matches
+        .value_of("MIN_FACE_AREA")
+        .map(|area| area.parse::<f32>().expect("Invalid minimum face area"));
+
+    let suggest_scale = matches
+        .value_of("SUGGEST_SCALE")
+        .map(|size| size.parse::<f32>().expect("Invalid minimum face size"));
+
+    let scale = matches
+        .value_of("SCALE")
+        .unwrap()
+        .parse::<f32>()
+        .expect("Invalid scale");
+
+    let target_size = matches
+        .value_of("TARGET_SIZE")
+        .map(|size| size.parse::<f32>().expect("Invalid target size"));
+
+    let units = matches.value_of("UNITS").unwrap().parse::<Units>().unwrap();
+
+    let export_resolution = matches
+        .value_of("EXPORT_RESOLUTION")
+        .map(|pixels| pixels.parse::<u32>().expect("Invalid export resolution"))
+        .unwrap_or(resolution);
+
+    let dpi = matches.value_of("DPI").map(|dpi| {
+        dpi.parse::<f32>()
+            .ok()
+            .filter(|dpi| dpi.is_finite() && *dpi > 0.0)
+            .expect("Invalid DPI (expected a positive number)")
+    });
+
+    let animate = matches.value_of("ANIMATE").map(|frames| {
+        frames
+            .parse::<usize>()
+            .ok()
+            .filter(|&frames| frames >= 2)
+            .expect("Invalid number of frames (expected at least 2)")
+    });
+
+    let page_size = matches
+        .value_of("PAGE_SIZE")
+        .map(|size| size.parse::<PageSize>().unwrap_or_else(|e| panic!("{}", e)));
+
+    let margin = matches
+        .value_of("MARGIN")
+        .map(|margin| margin.parse::<f32>().expect("Invalid margin"));
+    let estimate_sheets = matches
+        .value_of("ESTIMATE_SHEETS")
+        .map(|size| size.parse::<PageSize>().unwrap_or_else(|e| panic!("{}", e)));
+
+    for page_size in page_size.iter().chain(estimate_sheets.iter()) {
+        let margin = margin.unwrap_or(0.0);
+        if margin < 0.0 || margin * 2.0 >= page_size.width.min(page_size.height) {
+            panic!(
+                "Invalid margin: {} mm doesn't leave any room on a {} x {} mm page",
+                margin, page_size.width, page_size.height
+            );
+        }
+    }
+
+    // Aggregate args
+    let mut input_args = InputArgs {
+        path_to_obj,
+        resolution,
+        msaa_samples,
+        color_palette,
+        color_mode,
+        gradient_space,
+        component_colors: matches.is_present("COMPONENT_COLORS"),
+        seed,
+        stroke_color,
+        stroke_width,
+        fill_opacity,
+        line_join,
+        line_cap,
+        fold_dash,
+        fold_widths,
+        wireframe: matches.is_present("WIREFRAME"),
+        outline: matches.is_present("OUTLINE"),
+        triangulated: matches.value_of("FACES").unwrap() == "triangulated",
+        labels: matches.is_present("LABELS"),
+        face_labels: matches.is_present("LABEL_FACES"),
+        group_faces: matches.is_present("GROUP_FACES"),
+        annotate_angles: matches.is_present("ANNOTATE_ANGLES"),
+        debug_overlay: matches.is_present("DEBUG_OVERLAY"),
+        headless: matches.is_present("HEADLESS"),
+        strict: matches.is_present("STRICT"),
+        fix_winding: matches.is_present("FIX_WINDING"),
+        allow_open: matches.is_present("ALLOW_OPEN"),
+        coplanar_tolerance,
+        weld_tolerance,
+        tolerances,
+        max_island_size,
+        decimate,
+        max_faces,
+        auto_rotate: !matches.is_present("NO_AUTO_ROTATE"),
+        align_edge,
+        flip: matches.is_present("FLIP"),
+        export_svg: matches.value_of("EXPORT_SVG").map(|path| path.to_owned()),
+        export_outline: matches
+            .value_of("EXPORT_OUTLINE")
+            .map(|path| path.to_owned()),
+        export_overlaps: matches
+            .value_of("EXPORT_OVERLAPS")
+            .map(|path| path.to_owned()),
+        export_dxf: matches.value_of("EXPORT_DXF").map(|path| path.to_owned()),
+        export_png: matches.value_of("EXPORT_PNG").map(|path| path.to_owned()),
+        export_pdf: matches.value_of("EXPORT_PDF").map(|path| path.to_owned()),
+        export_net_obj: matches
+            .value_of("EXPORT_NET_OBJ")
+            .map(|path| path.to_owned()),
+        export_dual: matches.value_of("EXPORT_DUAL").map(|path| path.to_owned()),
+        export_instructions: matches
+            .value_of("EXPORT_INSTRUCTIONS")
+            .map(|path| path.to_owned()),
+        export_resolution,
+        dpi,
+        animate,
+        scale,
+        target_size,
+        units,
+        page_size,
+        margin,
+        estimate_sheets,
+        scale_bar: matches.is_present("SCALE_BAR"),
+        legend: matches.is_present("LEGEND"),
+        tab_width,
+        tab_style,
+        tab_angle,
+        tab_miter_limit,
+        joinery,
+        min_face_area,
+        suggest_scale,
+        strategy,
+        search_attempts,
+        root_face,
+        center_on_face,
+        save_session: matches.value_of("SAVE_SESSION").map(|path| path.to_owned()),
+    };
+
+    if let Some(path) = matches.value_of("LOAD_SESSION") {
+        let json_file = File::open(Path::new(path)).expect("Session file not found");
+        let session: Session =
+            serde_json::from_reader(json_file).expect("Error while reading session");
+        restore_session(&mut input_args, &matches, session);
+        info!("Restored session from .json: {:?}", path);
+    }
+    info!("Unfolding .obj: {:?}", input_args.path_to_obj);
+
+    // Try every root face and report the ones that work, without unfolding (or drawing) the net
+    if matches.is_present("FIND_ROOT_FACES") {
+        let goal_mesh = load_goal_mesh(&input_args).unwrap_or_else(|e| {
+            eprintln!("Failed to load {:?}: {}", input_args.path_to_obj, e);
+            std::process::exit(1)
+        });
+        let faces = goal_mesh.find_overlap_free_reference_faces(input_args.strategy);
+        println!(
+            "{} of {} root faces result in a net without overlaps (with strategy {:?}): {:?}",
+            faces.len(),
+            goal_mesh.face_count(),
+            input_args.strategy,
+            faces
+        );
+        return;
+    }
+
+    // The goal mesh is only loaded once: the viewer can unfold it again from other root faces
+    let goal_mesh = load_goal_mesh(&input_args).unwrap_or_else(|e| {
+        eprintln!("Failed to load {:?}: {}", input_args.path_to_obj, e);
+        std::process::exit(1)
+    });
+
+    // A goal mesh whose faces pass through each other can still be unfolded, but the net won't
+    // fold back up, so this is worth fixing in the source
+    if matches.is_present("CHECK_GEOMETRY") {
+        let start = Instant::now();
+        let intersections = goal_mesh.find_self_intersections();
+        if intersections.is_empty() {
+            info!(
+                "None of the faces of the goal mesh intersect each other (checked in {:?})",
+                start.elapsed()
+            );
+        } else {
+            warn!(
+                "{} pairs of faces of the goal mesh intersect each other, so the net can't be folded back up: {:?}",
+                intersections.len(),
+                intersections
+            );
+        }
+    }
+
+    // List the faces of the goal mesh (numbered like --root-face) along with the faces across each
+    // of their sides, which is useful for tracking down problems with the input
+    if matches.is_present("LIST_FACES") {
+        println!(
+            "{:>6}  {:>8}  {:<24}  {:<26}  neighbors",
+            "face", "obj_face", "vertices", "normal"
+        );
+        for face in 0..goal_mesh.face_count() {
+            let vertices = goal_mesh
+                .face_vertices(face)
+                .iter()
+                .map(|vid| vid.to_string())
+                .collect::<Vec<_>>();
+            let normal = goal_mesh.face_normal(face);
+            let neighbors = goal_mesh
+                .adjacent_faces(face)
+                .iter()
+                .map(|neighbor| match neighbor {
+                    Some(neighbor) => neighbor.to_string(),
+                    None => String::from("-"),
+                })
+                .collect::<Vec<_>>();
+            println!(
+                "{:>6}  {:>8}  {:<24}  {:<26}  {}",
+                face,
+                goal_mesh.obj_face(face),
+                vertices.join(" "),
+                format!("({:.3}, {:.3}, {:.3})", normal.x(), normal.y(), normal.z()),
+                neighbors.join(" ")
+            );
+        }
+        return;
+    }
+
+    // The scale follows from the size of the goal mesh, so it can only be computed after loading it
+    if let Some(target_size) = input_args.target_size {
+        let largest_dimension = goal_mesh.size().max_element();
+        input_args.scale = target_size * input_args.units.millimeters() / largest_dimension;
+        info!(
+            "Scaling the largest dimension of the goal mesh ({:?} units) to {:?} {}: {:?} mm per unit",
+            largest_dimension,
+            target_size,
+            input_args.units.abbreviation(),
+            input_args.scale
+        );
+    }
+
+    if let Some(path) = &input_args.save_session {
+        save_session(path, &input_args, input_args.root_face);
+    }
+
+    // Unfold the goal mesh from every root face and report how badly each net overlaps, sorted so
+    // that the best root faces come first (the goal mesh is shared by every unfolding)
+    if matches.is_present("SCAN_ROOTS") {
+        let mut scan = goal_mesh
+            .count_overlaps_by_reference_face(input_args.strategy)
+            .into_iter()
+            .enumerate()
+            .collect::<Vec<_>>();
+        scan.sort_by_key(|&(root_face, overlaps)| (overlaps, root_face));
+
+        println!(
+            "Overlapping pairs of faces by root face (with strategy {:?}):",
+            input_args.strategy
+        );
+        println!("{:>10}  {:>8}", "root_face", "overlaps");
+        for (root_face, overlaps) in scan.iter() {
+            println!("{:>10}  {:>8}", root_face, overlaps);
+        }
+
+        if let Some(path) = matches.value_of("OUT") {
+            let mut csv = String::from("root_face,overlaps\n");
+            for (root_face, overlaps) in scan.iter() {
+                csv.push_str(&format!("{},{}\n", root_face, overlaps));
+            }
+            std::fs::write(path, csv).expect("Failed to write root scan");
+            info!("Wrote root scan to .csv: {:?}", path);
+        }
+        return;
+    }
+
+    // In headless mode, skip the renderer entirely and only write the requested file(s)
+    if input_args.headless {
+        let start = Instant::now();
+        let net = unfold_net(&input_args, &goal_mesh, input_args.root_face);
+        export_net(&input_args, &goal_mesh, &net).expect("Failed to export net");
+        info!("Unfolded and exported net in {:?}", start.elapsed());
+        return;
+    }
+
+    // Open the window with the geometry that it had when it was last closed, unless that is
+    // explicitly forgotten
+    let window_geometry = if matches.is_present("RESET_WINDOW") {
+        reset_window_geometry();
+        None
+    } else {
+        load_window_geometry()
+    };
+
+    let mut app = App::build();
+    app.add_resource(WindowDescriptor {
+        width: window_geometry
+            .as_ref()
+            .map_or(resolution, |geometry| geometry.width),
+        height: window_geometry
+            .as_ref()
+            .map_or(resolution, |geometry| geometry.height),
+        title: String::from("unfold"),
+        ..Default::default()
+    })
+    .add_resource(ClearColor(linear_color(
+        &input_args.color_palette.background,
+    )))
+    .add_resource(Msaa {
+        samples: input_args.msaa_samples,
+    })
+    .add_resource(WindowGeometryState {
+        window_close_requested_event_reader: Default::default(),
+        position: window_geometry.and_then(|geometry| geometry.position),
+    });
+
+    // The 3D preview shows the goal mesh as it was loaded, so nothing is unfolded (or exported)
+    if matches.is_present("PREVIEW_3D") {
+        app.add_resource(input_args)
+            .add_resource(goal_mesh)
+            .add_default_plugins()
+            .init_resource::<OrbitState>()
+            .add_startup_system(preview_setup.system())
+            .add_system(orbit_system.system())
+            .add_system(window_geometry_system.system())
+            .run();
+        return;
+    }
+
+    app.add_resource(input_args)
+        .add_resource(goal_mesh)
+        .add_default_plugins()
+        .init_resource::<HoverState>()
+        .init_resource::<CameraState>()
+        .init_resource::<NetScene>()
+        .add_startup_system(setup.system())
+        .add_system(camera_system.system())
+        .add_system(label_system.system())
+        .add_system(hover_system.system())
+        .add_system(reroll_system.system())
+        .add_system(wireframe_system.system())
+        .add_system(snapshot_system.system())
+        .add_system(window_geometry_system.system())
+        .run();
+}
+
+/// The padding (in pixels) between the net and the edges of the canvas.
+const PADDING: f32 = 100.0;
+
+/// The unfolded net, scaled and centered so that it fits the canvas, along with its glue tabs.
+struct Net {
+    unfold_result: UnfoldResult,
+
+    // The face of the goal mesh that the net was unfolded from
+    root_face: usize,
+
+    // The outlines of the glue tabs (and of the slots that they fit into, with slot joinery)
+    tabs: Vec<Vec<Vec3>>,
+
+    // One (SRGB) color per polygon of the net, based on the color mode
+    colors: Vec<Vec3>,
+
+    // The factor that the unfolded positions were multiplied by to fit the canvas
+    scale: f32,
+
+    // Where the origin of the unfolded net ended up after scaling and centering it
+    origin: Vec3,
+
+    // The length of each dash and gap along the folds (in the same units as the scaled net), if
+    // a custom pattern was requested
+    fold_dash: Option<(f32, f32)>,
+}
+
+/// Links each primitive that is spawned for the net back to the polygon of the goal mesh that it
+/// was unfolded from, along with the material that it is normally drawn with.
+struct NetPolygon {
+    polygon: usize,
+    material: Handle<ColorMaterial>,
+}
+
+/// Keeps track of the face of the net that is under the mouse cursor, so that it can be highlighted.
+#[derive(Default)]
+struct HoverState {
+    cursor_moved_event_reader: EventReader<CursorMoved>,
+
+    // The (scaled and centered) vertices of the net, 3 per triangle
+    positions: Vec<Vec3>,
+
+    // The index of the polygon that each triangle belongs to
+    triangle_polygons: Vec<usize>,
+
+    // The index of the face of the .obj file that each polygon was loaded from
+    obj_faces: Vec<usize>,
+
+    // The material that the hovered polygon is drawn with
+    material: Handle<ColorMaterial>,
+
+    // The index of the triangle under the cursor (which is also the ID of the corresponding face
+    // of the half-edge mesh), if any
+    hovered: Option<usize>,
+}
+
+/// Marks the camera that the net is drawn with (as opposed to the camera used for the UI).
+struct NetCamera;
+
+/// Marks every entity that is spawned to draw the current net (i.e. its polygons or edges, tabs,
+/// labels, and status text), so that they can be despawned when the net is replaced.
+struct NetEntity;
+
+/// Everything that outlives a single net: the face of the goal mesh that the current net was
+/// unfolded from, along with the materials that were created for it (which are removed when the
+/// net is replaced), the font that labels are drawn with, and the current draw mode.
+#[derive(Default)]
+struct NetScene {
+    root_face: usize,
+    materials: Vec<Handle<ColorMaterial>>,
+    font: Handle<Font>,
+
+    // The net that is currently drawn, so that it can be drawn again (in the other draw mode)
+    // without unfolding the goal mesh again
+    net: Option<Net>,
+
+    // Whether the net is drawn as a wireframe (starting with `--wireframe`), which can be toggled
+    // by pressing `W`
+    wireframe: bool,
+}
+
+/// Remembers where each label (of a cut edge or face) should be drawn (in the same coordinate system as the net),
+/// so that the labels can follow the camera as it moves.
+struct NetLabel {
+    position: Vec2,
+
+    // The (approximate) size of the text, in pixels
+    size: Vec2,
+}
+
+/// The current pan and zoom of the camera, which is controlled with the mouse: scrolling zooms in
+/// and out (around the cursor), and dragging with the left mouse button pans the view.
+struct CameraState {
+    cursor_moved_event_reader: EventReader<CursorMoved>,
+    mouse_wheel_event_reader: EventReader<MouseWheel>,
+
+    // The most recent position of the cursor, relative to the bottom-left corner of the window
+    cursor: Option<Vec2>,
+
+    // The point of the net that is at the center of the window
+    center: Vec2,
+
+    // The number of units of the net that are covered by a single pixel, i.e. values larger than 1
+    // zoom out and values smaller than 1 zoom in
+    zoom: f32,
+}
+
+impl Default for CameraState {
+    fn default() -> Self {
+        CameraState {
+            cursor_moved_event_reader: Default::default(),
+            mouse_wheel_event_reader: Default::default(),
+            cursor: None,
+            center: Vec2::zero(),
+            zoom: 1.0,
+        }
+    }
+}
+
+impl CameraState {
+    /// Converts a point in window space (i.e. a cursor position) to the coordinate system of the
+    /// net, where `window_size` is the size of the window (see `window_size()`).
+    fn window_to_net(&self, point: Vec2, window_size: Vec2) -> Vec2 {
+        self.center + (point - window_size * 0.5) * self.zoom
+    }
+
+    /// Converts a point in the coordinate system of the net to window space.
+    fn net_to_window(&self, point: Vec2, window_size: Vec2) -> Vec2 {
+        (point - self.center) / self.zoom + window_size * 0.5
+    }
+}
+
+/// Marks the camera of the 3D preview, along with the light that moves with it (see
+/// `--preview-3d`).
+struct PreviewCamera;
+
+/// The current orientation and distance of the camera of the 3D preview, which orbits around the
+/// goal mesh: dragging with the left mouse button turns it around the goal mesh, and scrolling
+/// moves it closer or further away.
+struct OrbitState {
+    cursor_moved_event_reader: EventReader<CursorMoved>,
+    mouse_wheel_event_reader: EventReader<MouseWheel>,
+
+    // The most recent position of the cursor, relative to the bottom-left corner of the window
+    cursor: Option<Vec2>,
+
+    // The angles (in radians) of the camera around the vertical axis and above the horizontal plane
+    yaw: f32,
+    pitch: f32,
+
+    // The distance of the camera from the center of the goal mesh, where the goal mesh is scaled to
+    // fit in a sphere with a radius of 1 (see `preview_meshes()`)
+    distance: f32,
+}
+
+/// The initial angles (in radians) of the camera of the 3D preview, which looks down at the goal
+/// mesh from the front right.
+const ORBIT_YAW: f32 = std::f32::consts::FRAC_PI_6;
+const ORBIT_PITCH: f32 = std::f32::consts::FRAC_PI_6;
+
+/// The initial distance of the camera of the 3D preview, which fits the goal mesh in the window.
+const ORBIT_DISTANCE: f32 = 3.0;
+
+impl Default for OrbitState {
+    fn default() -> Self {
+        OrbitState {
+            cursor_moved_event_reader: Default::default(),
+            mouse_wheel_event_reader: Default::default(),
+            cursor: None,
+            yaw: ORBIT_YAW,
+            pitch: ORBIT_PITCH,
+            distance: ORBIT_DISTANCE,
+        }
+    }
+}
+
+impl OrbitState {
+    /// Returns the transform of the camera, which looks at the center of the goal mesh (i.e. the
+    /// origin) from its current orientation and distance.
+    fn transform(&self) -> Transform {
+        let eye = Vec3::new(
+            self.pitch.cos() * self.yaw.sin(),
+            self.pitch.sin(),
+            self.pitch.cos() * self.yaw.cos(),
+        ) * self.distance;
+        Transform::new(Mat4::face_toward(eye, Vec3::zero(), Vec3::unit_y()))
+    }
+}
+
+/// Returns the size of the window in pixels, which is `--resolution` unless the window has been
+/// resized (or was opened at the size that it had in a previous run, see `WindowGeometry`), or
+/// `None` if the window hasn't been created yet.
+fn window_size(windows: &Windows) -> Option<Vec2> {
+    windows
+        .get_primary()
+        .map(|window| Vec2::new(window.width as f32, window.height as f32))
+}
+
+/// Loads the goal mesh from the input file, which is parsed as an .stl file if it has the .stl
+/// extension, and as an .obj file otherwise. An input of `-` reads an .obj file from stdin instead
+/// (with any .mtl files relative to the working directory).
+fn load_goal_mesh(args: &InputArgs) -> Result<GoalMesh, GoalMeshError> {
+    let path = Path::new(&args.path_to_obj);
+    let options = unfold_options(args, args.root_face);
+    if args.path_to_obj == "-" {
+        let stdin = std::io::stdin();
+        return load_obj_reader(&mut stdin.lock(), Path::new(""), &options);
+    }
+    match path.extension() {
+        Some(extension) if extension.eq_ignore_ascii_case("stl") => load_stl(path, &options),
+        _ => load_obj(path, &options),
+    }
+}
+
+/// Collects the args that control how the goal mesh is loaded and unfolded (from the specified
+/// root face).
+fn unfold_options(args: &InputArgs, root_face: usize) -> UnfoldOptions {
+    UnfoldOptions {
+        root_face,
+        strategy: args.strategy,
+        load: LoadOptions {
+            strict: args.strict,
+            coplanar_tolerance: args.coplanar_tolerance,
+            fix_winding: args.fix_winding,
+            allow_open: args.allow_open,
+            tolerances: args.tolerances,
+        },
+        decimate: args.decimate,
+        max_faces: args.max_faces,
+        auto_rotate: args.auto_rotate,
+        align_edge: args.align_edge,
+        flip: args.flip,
+        // The tolerance is given in millimeters, but the net is unfolded in the units of the goal
+        // mesh
+        weld_tolerance: args.weld_tolerance / args.scale,
+        max_island_size: args.max_island_size / args.scale,
+        search_attempts: args.search_attempts,
+        seed: args.seed.unwrap_or(0),
+    }
+}
+
+/// Overwrites the args with the settings of a saved session, except for the ones that were
+/// provided on the command line (which take precedence).
+fn restore_session(args: &mut InputArgs, matches: &clap::ArgMatches, session: Session) {
+    let given = |arg: &str| matches.occurrences_of(arg) > 0;
+    let options = session.unfold_options;
+
+    if !given("INPUT") {
+        args.path_to_obj = session.input;
+    }
+    if !given("SCALE") && !given("TARGET_SIZE") {
+        args.scale = session.scale;
+    }
+    if !given("COLOR_MODE") {
+        args.color_mode = session.color_mode;
+    }
+    if !given("SEED") {
+        args.seed = session.seed;
+    }
+    if !given("ROOT_FACE") {
+        args.root_face = options.root_face;
+    }
+    if !given("STRATEGY") {
+        args.strategy = options.strategy;
+    }
+    if !given("STRICT") {
+        args.strict = options.load.strict;
+    }
+    if !given("FIX_WINDING") {
+        args.fix_winding = options.load.fix_winding;
+    }
+    if !given("ALLOW_OPEN") {
+        args.allow_open = options.load.allow_open;
+    }
+    if !given("DECIMATE") {
+        args.decimate = options.decimate;
+    }
+    if !given("MAX_FACES") {
+        args.max_faces = options.max_faces;
+    }
+    if !given("COPLANAR_TOLERANCE") {
+        args.coplanar_tolerance = options.load.coplanar_tolerance;
+    }
+    if !given("NO_AUTO_ROTATE") {
+        args.auto_rotate = options.auto_rotate;
+    }
+    if !given("ALIGN_EDGE") {
+        args.align_edge = options.align_edge;
+    }
+    if !given("FLIP") {
+        args.flip = options.flip;
+    }
+    if !given("WELD_TOLERANCE") {
+        // The tolerance is saved in the units of the goal mesh, but given in millimeters
+        args.weld_tolerance = options.weld_tolerance * session.scale;
+    }
+    if !given("DEGENERATE_TOLERANCE") && !given("EPSILON") {
+        args.tolerances.degenerate = options.load.tolerances.degenerate;
+    }
+    if !given("OVERLAP_TOLERANCE") && !given("EPSILON") {
+        args.tolerances.overlap = options.load.tolerances.overlap;
+    }
+    if !given("STL_WELD_TOLERANCE") && !given("EPSILON") {
+        args.tolerances.stl_weld = options.load.tolerances.stl_weld;
+    }
+    if !given("FLAT_TOLERANCE") {
+        args.tolerances.flat = options.load.tolerances.flat;
+    }
+    if !given("MAX_ISLAND_SIZE") {
+        args.max_island_size = options.max_island_size * session.scale;
+    }
+    if !given("SEARCH") {
+        args.search_attempts = options.search_attempts;
+    }
+}
+
+/// Writes the current settings (with the specified root face) to a session file at the specified
+/// path, see `--save-session`. Failures are logged rather than fatal, since they don't affect the
+/// net itself.
+fn save_session(path: &str, args: &InputArgs, root_face: usize) {
+    let session = Session {
+        input: args.path_to_obj.clone(),
+        scale: args.scale,
+        color_mode: args.color_mode,
+        seed: args.seed,
+        unfold_options: unfold_options(args, root_face),
+    };
+    let result = File::create(path)
+        .map_err(serde_json::Error::io)
+        .and_then(|file| serde_json::to_writer_pretty(file, &session));
+    match result {
+        Ok(()) => info!("Saved session to .json: {:?}", path),
+        Err(e) => error!("Failed to save session to {:?}: {}", path, e),
+    }
+}
+
+/// Returns the path of the file that the geometry of the window is saved to, i.e. `durer/window.json`
+/// in the user's config directory (`%APPDATA%` on Windows, `~/Library/Application Support` on
+/// macOS, and `$XDG_CONFIG_HOME` or `~/.config` elsewhere), or `None` if it can't be found.
+fn window_geometry_path() -> Option<PathBuf> {
+    let home = || std::env::var_os("HOME").map(PathBuf::from);
+    let config_dir = if cfg!(target_os = "windows") {
+        std::env::var_os("APPDATA").map(PathBuf::from)
+    } else if cfg!(target_os = "macos") {
+        home().map(|home| home.join("Library").join("Application Support"))
+    } else {
+        std::env::var_os("XDG_CONFIG_HOME")
+            .filter(|dir| !dir.is_empty())
+            .map(PathBuf::from)
+            .or_else(|| home().map(|home| home.join(".config")))
+    };
+    config_dir.map(|dir| dir.join("durer").join("window.json"))
+}
+
+/// Loads the geometry of the window from the last run, if it was saved. A file that can't be read
+/// is ignored (with a warning), since the window can always be opened at its default size instead.
+fn load_window_geometry() -> Option<WindowGeometry> {
+    let path = window_geometry_path()?;
+    let file = File::open(&path).ok()?;
+    match serde_json::from_reader(file) {
+        Ok(geometry) => {
+            info!("Restored window geometry from .json: {:?}", path);
+            Some(geometry)
+        }
+        Err(e) => {
+            warn!("Ignoring window geometry in {:?}: {}", path, e);
+            None
+        }
+    }
+}
+
+/// Saves the geometry of the window for the next run. Like `save_session()`, failures are logged
+/// rather than fatal.
+fn save_window_geometry(geometry: &WindowGeometry) {
+    let path = match window_geometry_path() {
+        Some(path) => path,
+        None => return,
+    };
+    let result = path
+        .parent()
+        .map_or(Ok(()), std::fs::create_dir_all)
+        .and_then(|()| File::create(&path))
+        .map_err(serde_json::Error::io)
+        .and_then(|file| serde_json::to_writer_pretty(file, geometry));
+    match result {
+        Ok(()) => info!("Saved window geometry to .json: {:?}", path),
+        Err(e) => error!("Failed to save window geometry to {:?}: {}", path, e),
+    }
+}
+
+/// Deletes the saved geometry of the window (if any), see `--reset-window`.
+fn reset_window_geometry() {
+    let path = match window_geometry_path() {
+        Some(path) => path,
+        None => return,
+    };
+    match std::fs::remove_file(&path) {
+        Ok(()) => info!("Reset window geometry: removed {:?}", path),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+        Err(e) => error!("Failed to remove window geometry {:?}: {}", path, e),
+    }
+}
+
+/// Unfolds the goal mesh from the specified root face, then scales the resulting net to fit the
+/// canvas. This doesn't rely on Bevy, so it can run with or without a window.
+fn unfold_net(args: &InputArgs, goal_mesh: &GoalMesh, root_face: usize) -> Net {
+    let mut unfold_result =
+        unfold(goal_mesh, &unfold_options(args, root_face)).unwrap_or_else(|e| panic!("{}", e));
+
+    // Make sure that the unfolded net always fits into the specified canvas size
+    // (with PADDING): if the net is centered on one of its faces, rather than on its own centroid,
+    // the canvas has to reach the far side of the net in every direction
+    let (net_size_x, net_size_y, net_center) = match args.center_on_face {
+        Some(face) => {
+            let polygon = unfold_result.polygons.get(face).unwrap_or_else(|| {
+                panic!(
+                    "Invalid face to center on: {} (the goal mesh has {} faces)",
+                    face,
+                    unfold_result.polygons.len()
+                )
+            });
+            let center = find_centroid(
+                &polygon
+                    .iter()
+                    .map(|&index| unfold_result.positions[index])
+                    .collect(),
+            );
+            let (size_x, size_y) = find_extents_around(&unfold_result.positions, &center);
+            (size_x, size_y, center)
+        }
+        None => {
+            let (size_x, size_y) = find_extents(&unfold_result.positions);
+            (size_x, size_y, find_centroid(&unfold_result.positions))
+        }
+    };
+    let net_scale = (args.resolution as f32 - PADDING) / net_size_x.max(net_size_y);
+    info!("Net size: {:?} x {:?}", net_size_x, net_size_y);
+    info!("Net center: {:?}", net_center);
+    info!(
+        "Total cut length: {:?} ({:?} mm), total fold length: {:?} ({:?} mm)",
+        unfold_result.cut_length(),
+        unfold_result.cut_length() * args.scale,
+        unfold_result.fold_length(),
+        unfold_result.fold_length() * args.scale
+    );
+    for point in unfold_result.positions.iter_mut() {
+        *point = (*point - net_center) * net_scale;
+    }
+    debug_assert!(unfold_result.positions.len() % 3 == 0);
+
+    // The tab width and the fold dash pattern are given in millimeters, so convert them to the
+    // units of the net in the same way as paged exports
+    let units_per_mm = net_scale / args.scale;
+
+    // Generate glue tabs along the cut edges: slots are drawn (and cut) just like tabs, so they are
+    // kept in the same list
+    let tabs = match (
+        args.tab_width.map(|width| width * units_per_mm),
+        args.joinery,
+    ) {
+        (Some(width), Joinery::Glue) => generate_tabs(
+            &unfold_result.positions,
+            &unfold_result.edges,
+            &TabOptions {
+                width,
+                style: args.tab_style,
+                angle: args.tab_angle,
+                miter_limit: args.tab_miter_limit,
+            },
+        ),
+        (Some(width), Joinery::Slot) => {
+            let (mut tabs, slots) =
+                generate_joints(&unfold_result, width, args.tab_style, args.tab_angle);
+            tabs.extend(slots);
+            tabs
+        }
+        (None, _) => vec![],
+    };
+
+    let colors = polygon_colors(args, goal_mesh, &unfold_result);
+
+    // Report the physical size of the net (and of its faces) in the requested units, so that it's
+    // clear whether the net fits on the material before exporting it
+    let units = args.units.millimeters();
+    info!(
+        "Physical size of the net: {:?} x {:?} {}",
+        net_size_x * args.scale / units,
+        net_size_y * args.scale / units,
+        args.units.abbreviation()
+    );
+    let areas = unfold_result
+        .polygon_areas(1.0 / units_per_mm)
+        .iter()
+        .map(|area| area / (units * units))
+        .collect::<Vec<_>>();
+    info!(
+        "Physical area of each face of the net: {:?} to {:?} {}^2 (by face of the .obj file: {:?})",
+        areas.iter().cloned().fold(f32::INFINITY, f32::min),
+        areas.iter().cloned().fold(0.0, f32::max),
+        args.units.abbreviation(),
+        unfold_result
+            .obj_faces
+            .iter()
+            .zip(areas.iter())
+            .collect::<Vec<_>>()
+    );
+    let fold_dash = args
+        .fold_dash
+        .map(|(dash, gap)| (dash * units_per_mm, gap * units_per_mm));
+
+    // Faces that are too small are hard to cut out (and their tabs won't fit), so list them along
+    // with the scale that would make all of them large enough
+    if let Some(min_face_area) = args.min_face_area {
+        let areas = unfold_result.polygon_areas(1.0 / units_per_mm);
+        let small_faces = (0..areas.len())
+            .filter(|&polygon| areas[polygon] < min_face_area)
+            .collect::<Vec<_>>();
+        if small_faces.is_empty() {
+            info!("Every face of the net is at least {:?} mm^2", min_face_area);
+        } else {
+            let smallest = small_faces
+                .iter()
+                .map(|&polygon| areas[polygon])
+                .fold(f32::INFINITY, f32::min);
+            warn!(
+                "{} faces of the net are smaller than {:?} mm^2 (the smallest is {:?} mm^2) - a scale of at least {:?} would fit all of them: {:?}",
+                small_faces.len(),
+                min_face_area,
+                smallest,
+                args.scale * (min_face_area / smallest).sqrt(),
+                small_faces
+                    .iter()
+                    .map(|&polygon| (unfold_result.obj_faces[polygon], areas[polygon]))
+                    .collect::<Vec<_>>()
+            );
+        }
+    }
+
+    // The width of a face is taken to be the side of a square with the same area, and the width of
+    // a tab is the length of its edge: both grow along with the scale, so the smallest one
+    // determines the scale that makes everything large enough. The depth of the tabs is given in
+    // millimeters, so it is the same at any scale
+    if let Some(min_size) = args.suggest_scale {
+        let smallest_face = unfold_result
+            .polygon_areas(1.0 / units_per_mm)
+            .iter()
+            .map(|area| area.sqrt())
+            .fold(f32::INFINITY, f32::min);
+        let smallest_tab = args.tab_width.filter(|_| !tabs.is_empty()).map(|_| {
+            tabs.iter()
+                .map(|tab| (tab[1] - tab[0]).length())
+                .fold(f32::INFINITY, f32::min)
+                / units_per_mm
+        });
+        if let Some(width) = args.tab_width.filter(|&width| width < min_size) {
+            warn!(
+                "The glue tabs are {:?} mm deep at any scale, which is less than {:?} mm: increase --tabs to make them deeper",
+                width, min_size
+            );
+        }
+        let suggested_scale =
+            args.scale * min_size / smallest_face.min(smallest_tab.unwrap_or(f32::INFINITY));
+        info!(
+            "Suggested scale for faces (and tabs) that are at least {:?} mm wide: {:?} (or a --target-size of {:?} {}), since the smallest face is {:?} mm wide{} at the current scale of {:?}",
+            min_size,
+            suggested_scale,
+            goal_mesh.size().max_element() * suggested_scale / units,
+            args.units.abbreviation(),
+            smallest_face,
+            smallest_tab
+                .map(|width| format!(" and the smallest tab is {:?} mm wide", width))
+                .unwrap_or_default(),
+            args.scale
+        );
+    }
+
+    // The paper that the net takes up is its faces along with its tabs: with slot joinery, half of
+    // the outlines in `tabs` are slots (each the same shape as its tab), which are cut out of the
+    // faces rather than adding to them
+    if let Some(page_size) = args.estimate_sheets {
+        let margin = args.margin.unwrap_or(0.0);
+        let sheets = sheet_count(&unfold_result, &tabs, &page_size, units_per_mm, margin);
+        let tab_area =
+            tabs.iter().map(|tab| polygon_area(tab)).sum::<f32>() / (units_per_mm * units_per_mm);
+        let net_area = unfold_result
+            .polygon_areas(1.0 / units_per_mm)
+            .iter()
+            .sum::<f32>()
+            + match args.joinery {
+                Joinery::Glue => tab_area,
+                Joinery::Slot => tab_area * 0.5,
+            };
+        let paper_area = sheets as f32 * page_size.width * page_size.height;
+        info!(
+            "Estimated sheets of {:?} x {:?} mm paper: {} (the net covers {:.1}% of their area) at a scale of {:?}",
+            page_size.width,
+            page_size.height,
+            sheets,
+            net_area / paper_area * 100.0,
+            args.scale
+        );
+    }
+
+    Net {
+        unfold_result,
+        root_face,
+        tabs,
+        colors,
+        scale: net_scale,
+        origin: -net_center * net_scale,
+        fold_dash,
+    }
+}
+
+/// Picks an (SRGB) color for each polygon of the net, based on the color mode (and, optionally,
+/// the separate piece of the goal mesh that the polygon belongs to).
+fn polygon_colors(
+    args: &InputArgs,
+    goal_mesh: &GoalMesh,
+    unfold_result: &UnfoldResult,
+) -> Vec<Vec3> {
+    let colors = mode_colors(args, goal_mesh, unfold_result);
+    if args.component_colors {
+        component_coloring(&colors, &unfold_result.polygon_components)
+    } else {
+        colors
+    }
+}
+
+/// Picks an (SRGB) color for each polygon of the net, based on the color mode alone.
+fn mode_colors(args: &InputArgs, goal_mesh: &GoalMesh, unfold_result: &UnfoldResult) -> Vec<Vec3> {
+    let palette = &args.color_palette;
+    let palette_color =
+        |polygon_index: usize| palette.polygons[polygon_index % palette.polygons.len()];
+    match args.color_mode {
+        ColorMode::Palette => (0..unfold_result.polygons.len())
+            .map(palette_color)
+            .collect(),
+        ColorMode::Gradient => {
+            // Map the depth of each polygon in the spanning tree to the range `0..1`, so that the
+            // reference face is at the start of the gradient and the deepest polygon is at the end
+            let gradient = palette.gradient(args.gradient_space);
+            let max_depth = unfold_result
+                .polygon_depths
+                .iter()
+                .max()
+                .copied()
+                .unwrap_or(0)
+                .max(1);
+            unfold_result
+                .polygon_depths
+                .iter()
+                .map(|&depth| gradient.color_at(depth as f32 / max_depth as f32))
+                .collect()
+        }
+        ColorMode::Material => unfold_result
+            .material_colors
+            .iter()
+            .enumerate()
+            .map(|(polygon_index, color)| color.unwrap_or_else(|| palette_color(polygon_index)))
+            .collect(),
+        ColorMode::Normal => {
+            // The polygons of the net are numbered like the faces of the goal mesh, and each
+            // component of a (unit) normal is in the range `-1..1`
+            (0..unfold_result.polygons.len())
+                .map(|polygon_index| (goal_mesh.face_normal(polygon_index) + Vec3::one()) * 0.5)
+                .collect()
+        }
+        ColorMode::Vertex => (0..unfold_result.polygons.len())
+            .map(|polygon_index| {
+                goal_mesh
+                    .face_vertex_color(polygon_index)
+                    .unwrap_or_else(|| palette_color(polygon_index))
+            })
+            .collect(),
+        ColorMode::Random => {
+            // Log the seed, so that a good set of colors can be reproduced later
+            let seed = args.seed.unwrap_or_else(|| rand::thread_rng().gen());
+            info!("Coloring the net randomly with seed: {}", seed);
+            random_coloring(&unfold_result.polygon_neighbors(), seed)
+        }
+    }
+}
+
+/// Writes the net to each of the files requested on the commandline (if any), stopping at
+/// the first one that fails.
+fn export_net(args: &InputArgs, goal_mesh: &GoalMesh, net: &Net) -> std::io::Result<()> {
+    // Attaches the path of the file to any errors, so that it's clear which export failed
+    let with_path = |path: &String| {
+        let path = path.clone();
+        move |e: std::io::Error| std::io::Error::new(e.kind(), format!("{:?}: {}", path, e))
+    };
+
+    let style = net_style(args, net, args.wireframe);
+
+    // Optionally, write the (scaled and centered) net to an .svg file, so that it matches the
+    // on-screen render exactly
+    if let Some(path) = &args.export_svg {
+        match &args.page_size {
+            // Split the net across multiple pages, at its physical size (the same as the .dxf file)
+            Some(page_size) => {
+                let pages = write_svg_pages(
+                    Path::new(path),
+                    &net.unfold_result,
+                    &net.tabs,
+                    &style,
+                    page_size,
+                    net.scale / args.scale,
+                    args.margin.unwrap_or(0.0),
+                )
+                .map_err(with_path(path))?;
+                info!("Exported net to {} .svg pages: {:?}", pages.len(), pages);
+            }
+            None => {
+                write_svg(
+                    Path::new(path),
+                    &net.unfold_result,
+                    &net.tabs,
+                    &style,
+                    &SvgOptions {
+                        units_per_mm: net.scale / args.scale,
+                        padding: export_padding(args, net),
+                    },
+                )
+                .map_err(with_path(path))?;
+                info!("Exported net to .svg: {:?}", path);
+            }
+        }
+    }
+
+    // Optionally, write only the outline of the net to an .svg file, at its physical size
+    if let Some(path) = &args.export_outline {
+        write_outline_svg(
+            Path::new(path),
+            &net.unfold_result,
+            &net.tabs,
+            net.scale / args.scale,
+            export_padding(args, net),
+        )
+        .map_err(with_path(path))?;
+        info!("Exported outline of net to .svg: {:?}", path);
+    }
+
+    // Optionally, write a copy of the net (without its tabs) where only the overlapping faces
+    // stand out, which can be shared without the viewer: they are filled in red (and see-through,
+    // so that the overlapping areas are darker), and every other face is gray. Each face is labeled
+    // with the index of its face in the .obj file, so that the overlaps can be tracked down
+    if let Some(path) = &args.export_overlaps {
+        const OVERLAP_COLOR: [f32; 3] = [1.0, 0.0, 0.0];
+        const FACE_COLOR: f32 = 0.8;
+        const FILL_OPACITY: f32 = 0.6;
+
+        let overlapping_polygons = net.unfold_result.overlapping_polygons();
+        let colors = (0..net.unfold_result.polygons.len())
+            .map(|polygon| {
+                if overlapping_polygons.binary_search(&polygon).is_ok() {
+                    Vec3::from(OVERLAP_COLOR)
+                } else {
+                    Vec3::splat(FACE_COLOR)
+                }
+            })
+            .collect::<Vec<_>>();
+        let overlap_style = NetStyle {
+            colors: &colors,
+            background: Vec3::one(),
+            stroke_color: None,
+            fill_opacity: FILL_OPACITY,
+            wireframe: false,
+            outline: true,
+            labels: false,
+            face_labels: true,
+            fold_angles: false,
+            scale_bar: false,
+            legend: false,
+            ..style
+        };
+        write_svg(
+            Path::new(path),
+            &net.unfold_result,
+            &[],
+            &overlap_style,
+            &SvgOptions {
+                units_per_mm: net.scale / args.scale,
+                padding: export_padding(args, net),
+            },
+        )
+        .map_err(with_path(path))?;
+        info!(
+            "Exported {} overlapping faces of net to .svg: {:?}",
+            overlapping_polygons.len(),
+            path
+        );
+    }
+
+    // Optionally, write the net to a .dxf file: since the positions have already been scaled to fit
+    // the canvas, undo that scaling before converting to millimeters
+    if let Some(path) = &args.export_dxf {
+        write_dxf(
+            Path::new(path),
+            &net.unfold_result,
+            &net.tabs,
+            args.scale / net.scale,
+            args.fold_dash,
+            args.triangulated,
+        )
+        .map_err(with_path(path))?;
+        info!("Exported net to .dxf: {:?}", path);
+    }
+
+    // Optionally, rasterize the net into a .png file, which can have a different resolution than
+    // the window
+    if let Some(path) = &args.export_png {
+        match args.dpi {
+            // Rasterize the net at its physical size (the same as the .pdf file) instead
+            Some(dpi) => {
+                let (width, height) = write_png_at_dpi(
+                    Path::new(path),
+                    &net.unfold_result,
+                    &net.tabs,
+                    &style,
+                    net.scale / args.scale,
+                    dpi,
+                    export_padding(args, net),
+                )
+                .map_err(with_path(path))?;
+                info!(
+                    "Exported net to {}x{} .png at {} DPI: {:?}",
+                    width, height, dpi, path
+                );
+            }
+            None => {
+                write_png(
+                    Path::new(path),
+                    &net.unfold_result,
+                    &net.tabs,
+                    &style,
+                    args.resolution as f32,
+                    args.export_resolution,
+                )
+                .map_err(with_path(path))?;
+                info!(
+                    "Exported net to {}x{} .png: {:?}",
+                    args.export_resolution, args.export_resolution, path
+                );
+            }
+        }
+
+        // Each frame opens the folds a little further (easing in and out), and the frames are
+        // numbered after the .png file, e.g. net-000.png, net-001.png, ...
+        if let Some(frames) = args.animate {
+            let digits = (frames - 1).to_string().len().max(3);
+            let file = Path::new(path);
+            let stem = file
+                .file_stem()
+                .and_then(|stem| stem.to_str())
+                .unwrap_or("net");
+            for frame in 0..frames {
+                let t = frame as f32 / (frames - 1) as f32;
+                let progress = t * t * (3.0 - 2.0 * t);
+                let frame_path =
+                    file.with_file_name(format!("{}-{:0width$}.png", stem, frame, width = digits));
+                write_png_frame(
+                    &frame_path,
+                    &net.unfold_result,
+                    &goal_mesh.fold_positions(&net.unfold_result, progress),
+                    progress,
+                    &style,
+                    args.resolution as f32,
+                    args.export_resolution,
+                )
+                .map_err(with_path(&frame_path.to_string_lossy().into_owned()))?;
+            }
+            info!(
+                "Exported {} frames of the goal mesh unfolding to .png: {:?}",
+                frames,
+                file.with_file_name(format!("{}-{}.png", stem, "#".repeat(digits)))
+            );
+        }
+    }
+
+    // Optionally, write the net to a .pdf file at its physical size (the same as the .dxf file),
+    // split across multiple pages if a page size was provided
+    if let Some(path) = &args.export_pdf {
+        let pages = write_pdf(
+            Path::new(path),
+            &net.unfold_result,
+            &net.tabs,
+            &style,
+            args.page_size.as_ref(),
+            net.scale / args.scale,
+            export_padding(args, net),
+            args.margin.unwrap_or(0.0),
+        )
+        .map_err(with_path(path))?;
+        info!("Exported net to .pdf with {} pages: {:?}", pages, path);
+    }
+
+    // Optionally, write the net to an .obj file, in the units of the goal mesh (i.e. without the
+    // scaling that was applied to fit the canvas)
+    if let Some(path) = &args.export_net_obj {
+        write_obj(
+            Path::new(path),
+            &net.unfold_result,
+            1.0 / net.scale,
+            args.triangulated,
+        )
+        .map_err(with_path(path))?;
+        info!("Exported net to .obj: {:?}", path);
+    }
+
+    if let Some(path) = &args.export_dual {
+        write_dot(Path::new(path), &net.unfold_result).map_err(with_path(path))?;
+        info!("Exported dual graph to .dot: {:?}", path);
+    }
+
+    if let Some(path) = &args.export_instructions {
+        write_instructions(Path::new(path), &net.unfold_result).map_err(with_path(path))?;
+        info!("Exported assembly instructions: {:?}", path);
+    }
+
+    Ok(())
 }
 
+/// Writes the net that is currently drawn in the viewer to an .svg and a .png file in the working
+/// directory, which are named after the input file, the root face of the net, and the current
+/// time (so that earlier snapshots are never overwritten). Unlike `export_net()`, the files follow
+/// the current draw mode, which may have been toggled since startup. Returns the paths of the files.
+fn export_snapshot(args: &InputArgs, net: &Net, wireframe: bool) -> std::io::Result<[String; 2]> {
+    let stem = Path::new(&args.path_to_obj)
+        .file_stem()
+        .and_then(|stem| stem.to_str())
+        .filter(|&stem| stem != "-")
+        .unwrap_or("net");
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs());
+    let name = format!("{}-root{}-{}", stem, net.root_face, timestamp);
+    let (svg, png) = (format!("{}.svg", name), format!("{}.png", name));
+
+    let style = net_style(args, net, wireframe);
+    write_svg(
+        Path::new(&svg),
+        &net.unfold_result,
+        &net.tabs,
+        &style,
+        &SvgOptions {
+            units_per_mm: net.scale / args.scale,
+            padding: export_padding(args, net),
+        },
+    )?;
+    write_png(
+        Path::new(&png),
+        &net.unfold_result,
+        &net.tabs,
+        &style,
+        args.resolution as f32,
+        args.export_resolution,
+    )?;
+    Ok([svg, png])
+}
+
+/// Returns the style that exported images of the net are drawn with, which matches the on-screen
+/// render (in the provided draw mode).
+fn net_style<'a>(args: &InputArgs, net: &'a Net, wireframe: bool) -> NetStyle<'a> {
+    NetStyle {
+        colors: &net.colors,
+        background: args.color_palette.background,
+        stroke_color: args.stroke_color,
+        stroke_width: args.stroke_width,
+        fill_opacity: args.fill_opacity,
+        fold_dash: net.fold_dash,
+        fold_widths: args.fold_widths,
+        scale_bar: args.scale_bar,
+        legend: args.legend,
+        wireframe,
+        outline: args.outline,
+        triangulated: args.triangulated,
+        labels: args.labels,
+        face_labels: args.face_labels,
+        face_groups: args.group_faces,
+        fold_angles: args.annotate_angles,
+    }
+}
+
+/// Returns the padding (in the units of the net) around the net in exported .svg and .pdf files
+/// that fit a single page: the margin if one was provided (which is in millimeters), or the same
+/// padding as the renderer otherwise.
+fn export_padding(args: &InputArgs, net: &Net) -> f32 {
+    match args.margin {
+        Some(margin) => margin * net.scale / args.scale,
+        None => PADDING * 0.5,
+    }
+}
+
+/// Converts an SRGB color to a (linear) Bevy color, to compensate for Bevy's internal color system.
+fn linear_color(color: &Vec3) -> Color {
+    linear_color_with_alpha(color, 1.0)
+}
+
+/// Like `linear_color()`, but with the specified opacity: only the color channels are converted,
+/// since alpha is already linear (and is blended as such by the renderer).
+fn linear_color_with_alpha(color: &Vec3, alpha: f32) -> Color {
+    Color::rgba(
+        srgb_to_linear(color.x()),
+        srgb_to_linear(color.y()),
+        srgb_to_linear(color.z()),
+        alpha,
+    )
+}
+
+#[allow(clippy::too_many_arguments)]
 fn setup(
     mut commands: Commands,
     mut meshes: ResMut<Assets<Mesh>>,
     mut materials: ResMut<Assets<ColorMaterial>>,
+    asset_server: Res<AssetServer>,
     args: Res<InputArgs>,
+    goal_mesh: Res<GoalMesh>,
+    mut hover: ResMut<HoverState>,
+    mut scene: ResMut<NetScene>,
 ) {
-    // First, construct the goal mesh (and half-edge data structure)
-    let mut goal_mesh = GoalMesh::from_obj(&Path::new(&args.path_to_obj[..]), 0.into());
-    let mut unfolded_positions = goal_mesh.unfold();
+    // Unfolding (and exporting) a large goal mesh can take a while before the window shows anything,
+    // so log how long each step takes
+    let start = Instant::now();
+    let net = unfold_net(&args, &goal_mesh, args.root_face);
+    info!("Unfolded net in {:?}", start.elapsed());
 
-    // Make sure that the unfolded net always fits into the specified canvas size
-    // (with PADDING)
-    const PADDING: f32 = 100.0;
-    let (net_size_x, net_size_y) = find_extents(&unfolded_positions);
-    let net_center = find_centroid(&unfolded_positions);
-    let net_scale = (args.resolution as f32 - PADDING) / net_size_x.max(net_size_y);
-    info!("Net size: {:?} x {:?}", net_size_x, net_size_y);
-    info!("Net center: {:?}", net_center);
-    for point in unfolded_positions.iter_mut() {
-        *point = (*point - net_center) * net_scale;
+    let export_start = Instant::now();
+    if let Err(e) = export_net(&args, &goal_mesh, &net) {
+        error!("Failed to export net: {}", e);
+    }
+    info!("Exported net in {:?}", export_start.elapsed());
+
+    scene.font = asset_server
+        .load("assets/fonts/DejaVuSansMono.ttf")
+        .expect("Failed to load font");
+    hover.material = materials.add(
+        Color::rgb(
+            srgb_to_linear(1.0),
+            srgb_to_linear(0.85),
+            srgb_to_linear(0.3),
+        )
+        .into(),
+    );
+
+    scene.wireframe = args.wireframe;
+
+    let spawn_start = Instant::now();
+    spawn_net(
+        &mut commands,
+        &mut meshes,
+        &mut materials,
+        &args,
+        net,
+        &mut hover,
+        &mut scene,
+    );
+    info!(
+        "Spawned net in {:?} (set up in {:?} in total)",
+        spawn_start.elapsed(),
+        start.elapsed()
+    );
+
+    // Add the cameras (labels and the status text are drawn as part of the UI)
+    commands
+        .spawn(Camera2dComponents::default())
+        .with(NetCamera)
+        .spawn(UiCameraComponents::default());
+}
+
+/// Spawns the goal mesh (see `preview_meshes()`), along with the camera that orbits around it and a
+/// light that follows the camera, so that the faces that are turned towards the viewer are lit.
+fn preview_setup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    args: Res<InputArgs>,
+    goal_mesh: Res<GoalMesh>,
+    orbit: Res<OrbitState>,
+) {
+    let colors = &args.color_palette.polygons;
+    for (mesh, color) in preview_meshes(&goal_mesh, colors.len())
+        .into_iter()
+        .zip(colors.iter())
+    {
+        commands.spawn(PbrComponents {
+            mesh: meshes.add(mesh),
+            material: materials.add(linear_color(color).into()),
+            ..Default::default()
+        });
     }
-    debug_assert!(unfolded_positions.len() % 3 == 0);
+    info!(
+        "Previewing the goal mesh with {} faces (drag to orbit, scroll to zoom, and press F to reset the view)",
+        goal_mesh.face_count()
+    );
+
+    // The goal mesh always fits in the unit sphere, so the clipping planes don't depend on its units
+    commands
+        .spawn(Camera3dComponents {
+            perspective_projection: PerspectiveProjection {
+                near: 0.01,
+                far: 100.0,
+                ..Default::default()
+            },
+            transform: orbit.transform(),
+            ..Default::default()
+        })
+        .with(PreviewCamera)
+        .spawn(LightComponents {
+            transform: orbit.transform(),
+            ..Default::default()
+        })
+        .with(PreviewCamera);
+}
 
-    // Create materials based on the provided color palette
-    let mats = args
-        .color_palette
-        .polygons
+/// Builds the meshes that draw the goal mesh in the 3D preview from the vertices and faces of its
+/// half-edge mesh, where the faces are split into the specified number of meshes in turn (one per
+/// color of the palette, like `ColorMode::Palette`). Each face is fan triangulated around its first
+/// vertex (like when the goal mesh was loaded), and every face has its own copy of its vertices, so
+/// that it is shaded flat. The goal mesh is centered on its centroid and scaled to fit in a sphere
+/// with a radius of 1, so that the camera doesn't depend on the units of the goal mesh.
+fn preview_meshes(goal_mesh: &GoalMesh, count: usize) -> Vec<Mesh> {
+    let half_edge_mesh = goal_mesh.half_edge_mesh();
+    let centroid = goal_mesh.centroid();
+    let radius = half_edge_mesh
+        .vertices()
         .iter()
-        .map(|color| {
-            // Convert SRGB to linear (to compensate for Bevy's internal color system)
-            let color = Vec3::new(
-                srgb_to_linear(color.x()),
-                srgb_to_linear(color.y()),
-                srgb_to_linear(color.z()),
-            );
-            materials.add(Color::rgb(color.x(), color.y(), color.z()).into())
+        .map(|vertex| (*vertex.coordinates() - centroid).length())
+        .fold(f32::MIN_POSITIVE, f32::max);
+
+    let mut positions = vec![vec![]; count];
+    let mut normals = vec![vec![]; count];
+    for face in 0..goal_mesh.face_count() {
+        let normal = goal_mesh.face_normal(face);
+        let corners = goal_mesh
+            .face_vertices(face)
+            .iter()
+            .map(|&vid| (*half_edge_mesh.vertex(vid).coordinates() - centroid) / radius)
+            .collect::<Vec<_>>();
+        for i in 1..corners.len() - 1 {
+            for corner in [corners[0], corners[i], corners[i + 1]].iter() {
+                positions[face % count].push([corner.x(), corner.y(), corner.z()]);
+                normals[face % count].push([normal.x(), normal.y(), normal.z()]);
+            }
+        }
+    }
+
+    positions
+        .into_iter()
+        .zip(normals)
+        .map(|(positions, normals)| {
+            let uvs = vec![[0.0, 0.0]; positions.len()];
+            let indices = (0..positions.len() as u32).collect();
+            Mesh {
+                primitive_topology: PrimitiveTopology::TriangleList,
+                attributes: vec![
+                    VertexAttribute::position(positions),
+                    VertexAttribute::normal(normals),
+                    VertexAttribute::uv(uvs),
+                ],
+                indices: Some(indices),
+            }
         })
+        .collect()
+}
+
+/// Spawns the primitives (and labels) that draw the net, along with a line of text that shows the
+/// root face of the net and whether it overlaps. Every entity is marked with `NetEntity`, and
+/// every material (along with the net itself) is recorded in the `NetScene`, so that the net can
+/// be replaced or redrawn later.
+fn spawn_net(
+    commands: &mut Commands,
+    meshes: &mut ResMut<Assets<Mesh>>,
+    materials: &mut ResMut<Assets<ColorMaterial>>,
+    args: &InputArgs,
+    net: Net,
+    hover: &mut HoverState,
+    scene: &mut NetScene,
+) {
+    let Net {
+        unfold_result,
+        root_face,
+        tabs,
+        colors,
+        origin,
+        fold_dash,
+        ..
+    } = &net;
+
+    // Create one material per polygon, based on the color mode: in filled mode, the polygons may be
+    // translucent (in wireframe mode, the same materials are used for the edges, which are not)
+    let fill_opacity = if scene.wireframe {
+        1.0
+    } else {
+        args.fill_opacity
+    };
+    let mats = colors
+        .iter()
+        .map(|color| materials.add(linear_color_with_alpha(color, fill_opacity).into()))
         .collect::<Vec<_>>();
 
-    for triangle_index in 0..unfolded_positions.len() / 3 {
-        // Grab the 3 vertices that make up this triangle
-        let a = unfolded_positions[triangle_index * 3 + 0];
-        let b = unfolded_positions[triangle_index * 3 + 1];
-        let c = unfolded_positions[triangle_index * 3 + 2];
-
-        // Select one of the materials to use based on this triangle's index
-        let material = mats[triangle_index % mats.len()];
-
-        // Convert the triangle into a polyline primitive
-        let shape_type = ShapeType::Polyline {
-            points: vec![
-                (a.x(), a.y()).into(),
-                (b.x(), b.y()).into(),
-                (c.x(), c.y()).into(),
-            ],
-            closed: true,
-        };
+    // If a stroke color was provided, every edge is drawn with it (instead of the color of its
+    // polygon) in wireframe mode
+    let stroke_material = args
+        .stroke_color
+        .map(|color| materials.add(linear_color(&color).into()));
+
+    // Outlines are drawn on top of the filled polygons, so they need a color that stands out
+    let outline_material = match stroke_material {
+        Some(stroke_material) if args.outline => Some(stroke_material),
+        None if args.outline => {
+            Some(materials.add(linear_color(&Vec3::splat(OUTLINE_COLOR)).into()))
+        }
+        _ => None,
+    };
+
+    // In filled mode, the diagonals of each polygon are drawn like its outline (even if the outline
+    // itself isn't drawn)
+    let diagonal_material = outline_material
+        .or(stroke_material)
+        .unwrap_or_else(|| materials.add(linear_color(&Vec3::splat(OUTLINE_COLOR)).into()));
+
+    // Overlapping faces are drawn in red, so that the problem is visible
+    let overlap_material = materials.add(Color::rgba(1.0, 0.0, 0.0, fill_opacity).into());
+    let overlapping_polygons = unfold_result.overlapping_polygons();
+
+    // Select one of the materials to use based on a polygon's index
+    let polygon_material = |polygon_index: usize| {
+        if overlapping_polygons.contains(&polygon_index) {
+            overlap_material
+        } else {
+            mats[polygon_index]
+        }
+    };
+
+    if scene.wireframe {
+        // Draw each side of each polygon separately, so that cuts, mountain folds, and valley folds
+        // can be told apart by their dash patterns (and folds by their widths, see
+        // `NetStyle::edge_width()`)
+        let style = net_style(args, &net, true);
+        for edge in unfold_result.edges.iter() {
+            // Folds are shared by two polygons, so only draw them once
+            if edge.kind.is_fold() && edge.half_edge > edge.pair {
+                continue;
+            }
+
+            let a = unfold_result.positions[edge.indices[0]];
+            let b = unfold_result.positions[edge.indices[1]];
+
+            let mut builder = PathBuilder::new();
+            for (start, end) in dash_segments(&a, &b, &edge.kind.dash_pattern(*fold_dash)).iter() {
+                builder.move_to(point(start.x(), start.y()));
+                builder.line_to(point(end.x(), end.y()));
+            }
+
+            let polygon_index = unfold_result.triangle_polygons[usize::from(edge.face)];
+            let material = match stroke_material {
+                Some(stroke_material) if !overlapping_polygons.contains(&polygon_index) => {
+                    stroke_material
+                }
+                _ => polygon_material(polygon_index),
+            };
+            commands
+                .spawn(builder.build().stroke(
+                    material,
+                    meshes,
+                    Vec3::zero(),
+                    &stroke_options(args).with_line_width(style.edge_width(edge)),
+                ))
+                .with(NetPolygon {
+                    polygon: polygon_index,
+                    material,
+                })
+                .with(NetEntity);
+        }
+    } else {
+        // Draw each polygon (rather than each triangle), so that no lines are drawn between triangles
+        // that belong to the same polygon
+        for (polygon_index, polygon) in unfold_result.polygons.iter().enumerate() {
+            // Convert the polygon into a polyline primitive
+            let shape_type = ShapeType::Polyline {
+                points: polygon
+                    .iter()
+                    .map(|&index| unfold_result.positions[index])
+                    .map(|point| (point.x(), point.y()).into())
+                    .collect(),
+                closed: true,
+            };
+
+            let material = polygon_material(polygon_index);
+            commands
+                .spawn(primitive(
+                    material,
+                    meshes,
+                    shape_type,
+                    TessellationMode::Fill(&FillOptions::default()),
+                    Vec3::zero(),
+                ))
+                .with(NetPolygon {
+                    polygon: polygon_index,
+                    material,
+                })
+                .with(NetEntity);
+        }
+
+        // Outline the polygons with a single path, where each side of each polygon is a separate
+        // line: folds are shared by two polygons, so they are only drawn once (rather than once per
+        // polygon, which would double their width wherever the two strokes don't line up exactly).
+        // The outline isn't part of any polygon as far as hovering is concerned, since it keeps its
+        // color when a polygon is highlighted
+        if let Some(outline_material) = outline_material {
+            let mut builder = PathBuilder::new();
+            for edge in unfold_result.edges.iter() {
+                if edge.kind.is_fold() && edge.half_edge > edge.pair {
+                    continue;
+                }
+                let a = unfold_result.positions[edge.indices[0]];
+                let b = unfold_result.positions[edge.indices[1]];
+                builder.move_to(point(a.x(), a.y()));
+                builder.line_to(point(b.x(), b.y()));
+            }
+            commands
+                .spawn(builder.build().stroke(
+                    outline_material,
+                    meshes,
+                    Vec3::new(0.0, 0.0, 1.0),
+                    &stroke_options(args),
+                ))
+                .with(NetEntity);
+        }
+    }
+
+    // Draw the lines between the triangles of each polygon, which are solid since they are neither
+    // cut nor folded: in wireframe mode, they belong to their polygon like its sides, and in filled
+    // mode, they are drawn on top of the polygons along with the outline (in the same color)
+    if args.triangulated {
+        let diagonals = unfold_result.triangulation_edges();
+        if scene.wireframe {
+            for &[a, b] in diagonals.iter() {
+                let polygon_index = unfold_result.triangle_polygons[a / 3];
+                let a = unfold_result.positions[a];
+                let b = unfold_result.positions[b];
+                let mut builder = PathBuilder::new();
+                builder.move_to(point(a.x(), a.y()));
+                builder.line_to(point(b.x(), b.y()));
+
+                let material = match stroke_material {
+                    Some(stroke_material) if !overlapping_polygons.contains(&polygon_index) => {
+                        stroke_material
+                    }
+                    _ => polygon_material(polygon_index),
+                };
+                commands
+                    .spawn(builder.build().stroke(
+                        material,
+                        meshes,
+                        Vec3::zero(),
+                        &stroke_options(args),
+                    ))
+                    .with(NetPolygon {
+                        polygon: polygon_index,
+                        material,
+                    })
+                    .with(NetEntity);
+            }
+        } else if !diagonals.is_empty() {
+            let mut builder = PathBuilder::new();
+            for &[a, b] in diagonals.iter() {
+                let a = unfold_result.positions[a];
+                let b = unfold_result.positions[b];
+                builder.move_to(point(a.x(), a.y()));
+                builder.line_to(point(b.x(), b.y()));
+            }
+            commands
+                .spawn(builder.build().stroke(
+                    diagonal_material,
+                    meshes,
+                    Vec3::new(0.0, 0.0, 1.0),
+                    &stroke_options(args),
+                ))
+                .with(NetEntity);
+        }
+    }
+
+    // Draw the glue tabs as (closed) polylines, in gray unless a stroke color was provided
+    let tab_material =
+        stroke_material.unwrap_or_else(|| materials.add(linear_color(&Vec3::splat(0.5)).into()));
+    for tab in tabs.iter() {
+        commands
+            .spawn(primitive(
+                tab_material,
+                meshes,
+                ShapeType::Polyline {
+                    points: tab
+                        .iter()
+                        .map(|point| (point.x(), point.y()).into())
+                        .collect(),
+                    closed: true,
+                },
+                TessellationMode::Stroke(&stroke_options(args)),
+                Vec3::zero(),
+            ))
+            .with(NetEntity);
+    }
+
+    // Draw an arrow inside of each triangle along its incoming edge, which is the direction that
+    // the triangle was unfolded in (see `UnfoldResult::incoming_edges`), on top of everything else:
+    // if the orientation of the reference face was propagated correctly, every arrow (except the
+    // arrow of the reference face) is parallel to the fold that joins its triangle to the rest of
+    // the net, or to the edge between two triangles of the same polygon
+    let mut debug_materials = vec![];
+    if args.debug_overlay {
+        let arrow_material = materials.add(Color::rgb(1.0, 0.0, 1.0).into());
+        let mut builder = PathBuilder::new();
+        for (triangle, &[a, b]) in unfold_result
+            .positions
+            .chunks(3)
+            .zip(unfold_result.incoming_edges.iter())
+        {
+            let centroid = (triangle[0] + triangle[1] + triangle[2]) / 3.0;
+            let along = (unfold_result.positions[b] - unfold_result.positions[a]) / 3.0;
+            arrow_to(&mut builder, centroid - along * 0.5, centroid + along * 0.5);
+        }
+
+        // The axes of the net's coordinate system, with the x-axis in red and the y-axis in green
+        let x_material = materials.add(Color::rgb(1.0, 0.0, 0.0).into());
+        let y_material = materials.add(Color::rgb(0.0, 0.8, 0.0).into());
+        let mut x_builder = PathBuilder::new();
+        arrow_to(
+            &mut x_builder,
+            *origin,
+            *origin + Vec3::unit_x() * PADDING * 0.5,
+        );
+        let mut y_builder = PathBuilder::new();
+        arrow_to(
+            &mut y_builder,
+            *origin,
+            *origin + Vec3::unit_y() * PADDING * 0.5,
+        );
+
+        for (builder, material) in [
+            (builder, arrow_material),
+            (x_builder, x_material),
+            (y_builder, y_material),
+        ] {
+            commands
+                .spawn(builder.build().stroke(
+                    material,
+                    meshes,
+                    Vec3::new(0.0, 0.0, 2.0),
+                    &stroke_options(args),
+                ))
+                .with(NetEntity);
+        }
+        debug_materials = vec![arrow_material, x_material, y_material];
+    }
+
+    // Number each pair of matching cut edges (and label each fold with its dihedral angle, and
+    // each polygon with its face of the .obj file): since text can only be drawn as part of the
+    // UI, each label is positioned in screen space (relative to the bottom-left corner of the
+    // window)
+    if args.labels || args.face_labels || args.annotate_angles {
+        const FONT_SIZE: f32 = 14.0;
+        const LABEL_INSET: f32 = 12.0;
+        let font = scene.font;
+
+        let mut labels = vec![];
+        if args.labels {
+            for edge in unfold_result.edges.iter() {
+                if let Some(label) = unfold_result.cut_label(edge) {
+                    labels.push((label, unfold_result.label_position(edge, LABEL_INSET)));
+                }
+            }
+        }
+        if args.annotate_angles {
+            for edge in unfold_result.edges.iter() {
+                if let Some(label) = unfold_result.fold_angle_label(edge) {
+                    labels.push((label, unfold_result.label_position(edge, LABEL_INSET)));
+                }
+            }
+        }
+        if args.face_labels {
+            for (polygon, face) in unfold_result.obj_faces.iter().enumerate() {
+                labels.push((face.to_string(), unfold_result.polygon_center(polygon)));
+            }
+        }
+
+        for (text, position) in labels {
+            // The font is monospaced, so we can center the text without measuring it (each
+            // glyph is roughly 0.6 times as wide as it is tall)
+            let label = NetLabel {
+                position: position.truncate(),
+                size: Vec2::new(text.chars().count() as f32 * FONT_SIZE * 0.6, FONT_SIZE),
+            };
+            commands
+                .spawn(TextComponents {
+                    style: Style {
+                        position_type: PositionType::Absolute,
+                        // Moved along with the camera (and the size of the window) by
+                        // `label_system()` from the next frame on
+                        position: label_rect(
+                            &label,
+                            &CameraState::default(),
+                            Vec2::splat(args.resolution as f32),
+                        ),
+                        ..Default::default()
+                    },
+                    text: Text {
+                        value: text,
+                        font,
+                        style: TextStyle {
+                            font_size: FONT_SIZE,
+                            color: Color::rgb(0.1, 0.1, 0.1),
+                        },
+                    },
+                    ..Default::default()
+                })
+                .with(label)
+                .with(NetEntity);
+        }
+    }
 
-        let translation = Vec3::zero();
-
-        // Draw either filled or wireframe polygons, based on the provided flag
-        if args.wireframe {
-            commands.spawn(primitive(
-                material,
-                &mut meshes,
-                shape_type,
-                TessellationMode::Stroke(
-                    &StrokeOptions::default()
-                        .with_line_width(2.0)
-                        .with_line_join(LineJoin::Round)
-                        .with_line_cap(LineCap::Round),
+    // Show the root face of the net, and whether it overlaps (in the same red as the overlapping
+    // faces), in the top-left corner of the window
+    let status = if overlapping_polygons.is_empty() {
+        String::from("no overlaps")
+    } else {
+        format!("{} overlapping faces", overlapping_polygons.len())
+    };
+    commands
+        .spawn(TextComponents {
+            style: Style {
+                position_type: PositionType::Absolute,
+                position: Rect {
+                    left: Val::Px(10.0),
+                    top: Val::Px(10.0),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            text: Text {
+                value: format!(
+                    "Root face: {} ({}) - press R for a random root face, W to toggle wireframe, S to export",
+                    root_face, status
                 ),
-                translation,
-            ));
+                font: scene.font,
+                style: TextStyle {
+                    font_size: 16.0,
+                    color: if overlapping_polygons.is_empty() {
+                        Color::rgb(0.1, 0.1, 0.1)
+                    } else {
+                        Color::rgb(1.0, 0.0, 0.0)
+                    },
+                },
+            },
+            ..Default::default()
+        })
+        .with(NetEntity);
+
+    scene.root_face = *root_face;
+    scene.materials = mats;
+    scene.materials.push(overlap_material);
+    scene.materials.extend(stroke_material);
+    if stroke_material.is_none() {
+        scene.materials.push(tab_material);
+        scene.materials.extend(outline_material);
+        if outline_material.is_none() {
+            scene.materials.push(diagonal_material);
+        }
+    }
+    scene.materials.extend(debug_materials);
+
+    // Keep the (triangles of the) net around, so that the face under the cursor can be picked
+    hover.positions = unfold_result.positions.clone();
+    hover.triangle_polygons = unfold_result.triangle_polygons.clone();
+    hover.obj_faces = unfold_result.obj_faces.clone();
+    hover.hovered = None;
+    scene.net = Some(net);
+}
+
+/// Despawns every entity of the current net, along with the meshes and materials that were
+/// created for it.
+fn despawn_net(
+    commands: &mut Commands,
+    meshes: &mut ResMut<Assets<Mesh>>,
+    materials: &mut ResMut<Assets<ColorMaterial>>,
+    scene: &mut NetScene,
+    query: &mut Query<(Entity, &NetEntity, Option<&Handle<Mesh>>)>,
+) {
+    for (entity, _, mesh) in &mut query.iter() {
+        if let Some(mesh) = mesh {
+            meshes.remove(mesh);
+        }
+        commands.despawn(entity);
+    }
+    for material in scene.materials.drain(..) {
+        materials.remove(&material);
+    }
+}
+
+/// Returns the options that every line of the net is drawn with in the renderer.
+fn stroke_options(args: &InputArgs) -> StrokeOptions {
+    StrokeOptions::default()
+        .with_line_width(args.stroke_width)
+        .with_line_join(args.line_join)
+        .with_line_cap(args.line_cap)
+}
+
+/// Adds an arrow from `tail` to `head` (in the xy-plane) to the path, whose head is made of two short
+/// lines on either side of its tip.
+fn arrow_to(builder: &mut PathBuilder, tail: Vec3, head: Vec3) {
+    const HEAD_ANGLE: f32 = 5.0 * std::f32::consts::PI / 6.0;
+    let barb = (head - tail) * 0.3;
+
+    builder.move_to(point(tail.x(), tail.y()));
+    builder.line_to(point(head.x(), head.y()));
+    for &angle in [HEAD_ANGLE, -HEAD_ANGLE].iter() {
+        let end = head + Mat3::from_rotation_z(angle).mul_vec3(barb);
+        builder.move_to(point(head.x(), head.y()));
+        builder.line_to(point(end.x(), end.y()));
+    }
+}
+
+/// Returns the position of a label (in screen space) that centers it on its cut edge (or face).
+fn label_rect(label: &NetLabel, camera: &CameraState, window_size: Vec2) -> Rect<Val> {
+    let position = camera.net_to_window(label.position, window_size) - label.size * 0.5;
+    Rect {
+        left: Val::Px(position.x()),
+        bottom: Val::Px(position.y()),
+        ..Default::default()
+    }
+}
+
+/// Pans and zooms the camera based on the mouse, and resets the view so that the entire net fits
+/// the window (which is how the net is initially drawn) whenever `F` is pressed.
+fn camera_system(
+    mut camera: ResMut<CameraState>,
+    windows: Res<Windows>,
+    mouse_button_input: Res<Input<MouseButton>>,
+    keyboard_input: Res<Input<KeyCode>>,
+    cursor_moved_events: Res<Events<CursorMoved>>,
+    mouse_wheel_events: Res<Events<MouseWheel>>,
+    mut cameras: Query<(&NetCamera, &mut Transform)>,
+) {
+    const ZOOM_PER_LINE: f32 = 1.1;
+    const PIXELS_PER_LINE: f32 = 16.0;
+    const MIN_ZOOM: f32 = 0.05;
+    const MAX_ZOOM: f32 = 20.0;
+
+    let camera = &mut *camera;
+    let window_size = match window_size(&windows) {
+        Some(window_size) => window_size,
+        None => return,
+    };
+    let (center, zoom) = (camera.center, camera.zoom);
+
+    // Dragging moves the net along with the cursor
+    let mut cursor = camera.cursor;
+    for event in camera.cursor_moved_event_reader.iter(&cursor_moved_events) {
+        if let Some(previous) = cursor {
+            if mouse_button_input.pressed(MouseButton::Left) {
+                camera.center -= (event.position - previous) * camera.zoom;
+            }
+        }
+        cursor = Some(event.position);
+    }
+    camera.cursor = cursor;
+
+    // Zoom around the cursor (or the center of the window, if the cursor hasn't moved yet), so
+    // that whatever is under it stays in place
+    let anchor = cursor.unwrap_or(window_size * 0.5);
+    for event in camera.mouse_wheel_event_reader.iter(&mouse_wheel_events) {
+        let lines = match event.unit {
+            MouseScrollUnit::Line => event.y,
+            MouseScrollUnit::Pixel => event.y / PIXELS_PER_LINE,
+        };
+        let before = camera.window_to_net(anchor, window_size);
+        camera.zoom = (camera.zoom * ZOOM_PER_LINE.powf(-lines)).clamp(MIN_ZOOM, MAX_ZOOM);
+        let after = camera.window_to_net(anchor, window_size);
+        camera.center += before - after;
+    }
+
+    if keyboard_input.just_pressed(KeyCode::F) {
+        camera.center = Vec2::zero();
+        camera.zoom = 1.0;
+    }
+
+    if camera.center == center && camera.zoom == zoom {
+        return;
+    }
+
+    // Only scale the camera along the x- and y-axes, so that the depth of the scene doesn't change
+    for (_, mut transform) in &mut cameras.iter() {
+        let z = transform.translation().z();
+        *transform = Transform::new(Mat4::from_scale_rotation_translation(
+            Vec3::new(camera.zoom, camera.zoom, 1.0),
+            Quat::identity(),
+            camera.center.extend(z),
+        ));
+    }
+}
+
+/// Turns the camera of the 3D preview (and its light) around the goal mesh based on the mouse, and
+/// resets the view whenever `F` is pressed, like `camera_system()` does for the net.
+fn orbit_system(
+    mut orbit: ResMut<OrbitState>,
+    mouse_button_input: Res<Input<MouseButton>>,
+    keyboard_input: Res<Input<KeyCode>>,
+    cursor_moved_events: Res<Events<CursorMoved>>,
+    mouse_wheel_events: Res<Events<MouseWheel>>,
+    mut cameras: Query<(&PreviewCamera, &mut Transform)>,
+) {
+    const RADIANS_PER_PIXEL: f32 = 0.01;
+    const ZOOM_PER_LINE: f32 = 1.1;
+    const PIXELS_PER_LINE: f32 = 16.0;
+    const MIN_DISTANCE: f32 = 1.1;
+    const MAX_DISTANCE: f32 = 20.0;
+
+    // Looking straight down (or up) would leave the direction of the camera undefined
+    const MAX_PITCH: f32 = std::f32::consts::FRAC_PI_2 - 0.01;
+
+    let orbit = &mut *orbit;
+    let (yaw, pitch, distance) = (orbit.yaw, orbit.pitch, orbit.distance);
+
+    // Dragging turns the goal mesh along with the cursor
+    let mut cursor = orbit.cursor;
+    for event in orbit.cursor_moved_event_reader.iter(&cursor_moved_events) {
+        if let Some(previous) = cursor {
+            if mouse_button_input.pressed(MouseButton::Left) {
+                let delta = event.position - previous;
+                orbit.yaw -= delta.x() * RADIANS_PER_PIXEL;
+                orbit.pitch =
+                    (orbit.pitch - delta.y() * RADIANS_PER_PIXEL).clamp(-MAX_PITCH, MAX_PITCH);
+            }
+        }
+        cursor = Some(event.position);
+    }
+    orbit.cursor = cursor;
+
+    for event in orbit.mouse_wheel_event_reader.iter(&mouse_wheel_events) {
+        let lines = match event.unit {
+            MouseScrollUnit::Line => event.y,
+            MouseScrollUnit::Pixel => event.y / PIXELS_PER_LINE,
+        };
+        orbit.distance =
+            (orbit.distance * ZOOM_PER_LINE.powf(-lines)).clamp(MIN_DISTANCE, MAX_DISTANCE);
+    }
+
+    if keyboard_input.just_pressed(KeyCode::F) {
+        orbit.yaw = ORBIT_YAW;
+        orbit.pitch = ORBIT_PITCH;
+        orbit.distance = ORBIT_DISTANCE;
+    }
+
+    if orbit.yaw == yaw && orbit.pitch == pitch && orbit.distance == distance {
+        return;
+    }
+    for (_, mut transform) in &mut cameras.iter() {
+        *transform = orbit.transform();
+    }
+}
+
+/// Moves the labels (which are drawn in screen space) along with the camera.
+fn label_system(
+    camera: Res<CameraState>,
+    windows: Res<Windows>,
+    mut query: Query<(&NetLabel, &mut Style)>,
+) {
+    let window_size = match window_size(&windows) {
+        Some(window_size) => window_size,
+        None => return,
+    };
+    for (label, mut style) in &mut query.iter() {
+        style.position = label_rect(label, &camera, window_size);
+    }
+}
+
+/// Highlights the polygon of the net that is under the mouse cursor, and logs the face of the goal
+/// mesh that it came from.
+fn hover_system(
+    mut hover: ResMut<HoverState>,
+    camera: Res<CameraState>,
+    windows: Res<Windows>,
+    cursor_moved_events: Res<Events<CursorMoved>>,
+    mut query: Query<(&NetPolygon, &mut Handle<ColorMaterial>)>,
+) {
+    let cursor = match hover.cursor_moved_event_reader.latest(&cursor_moved_events) {
+        Some(event) => event.position,
+        None => return,
+    };
+
+    // The cursor is relative to the bottom-left corner of the window, while the net is centered
+    // on the origin (before the camera is panned or zoomed)
+    let window_size = match window_size(&windows) {
+        Some(window_size) => window_size,
+        None => return,
+    };
+    let point = camera.window_to_net(cursor, window_size);
+    let hovered = find_triangle_containing(&hover.positions, &point);
+    if hovered == hover.hovered {
+        return;
+    }
+    hover.hovered = hovered;
+
+    let hovered_polygon = hovered.map(|triangle_index| {
+        let polygon_index = hover.triangle_polygons[triangle_index];
+        info!(
+            "Hovering over face {} of the half-edge mesh (face {} of the .obj file)",
+            triangle_index, hover.obj_faces[polygon_index]
+        );
+        polygon_index
+    });
+
+    for (net_polygon, mut material) in &mut query.iter() {
+        *material = if Some(net_polygon.polygon) == hovered_polygon {
+            hover.material
+        } else {
+            net_polygon.material
+        };
+    }
+}
+
+/// Unfolds the goal mesh again from a random root face (other than the current one) whenever `R`
+/// is pressed, replacing the net that is drawn: this makes it easy to hunt for a root face that
+/// results in a net without overlaps.
+#[allow(clippy::too_many_arguments)]
+fn reroll_system(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    keyboard_input: Res<Input<KeyCode>>,
+    args: Res<InputArgs>,
+    goal_mesh: Res<GoalMesh>,
+    mut hover: ResMut<HoverState>,
+    mut scene: ResMut<NetScene>,
+    mut query: Query<(Entity, &NetEntity, Option<&Handle<Mesh>>)>,
+) {
+    if !keyboard_input.just_pressed(KeyCode::R) {
+        return;
+    }
+
+    let faces = goal_mesh.face_count();
+    let root_face = if faces > 1 {
+        (scene.root_face + rand::thread_rng().gen_range(1, faces)) % faces
+    } else {
+        scene.root_face
+    };
+    info!("Unfolding from random root face: {}", root_face);
+    if let Some(path) = &args.save_session {
+        save_session(path, &args, root_face);
+    }
+
+    despawn_net(
+        &mut commands,
+        &mut meshes,
+        &mut materials,
+        &mut scene,
+        &mut query,
+    );
+    let net = unfold_net(&args, &goal_mesh, root_face);
+    spawn_net(
+        &mut commands,
+        &mut meshes,
+        &mut materials,
+        &args,
+        net,
+        &mut hover,
+        &mut scene,
+    );
+}
+
+/// Switches between drawing the net filled and as a wireframe whenever `W` is pressed, by drawing
+/// the current net again (without unfolding the goal mesh again).
+#[allow(clippy::too_many_arguments)]
+fn wireframe_system(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    keyboard_input: Res<Input<KeyCode>>,
+    args: Res<InputArgs>,
+    mut hover: ResMut<HoverState>,
+    mut scene: ResMut<NetScene>,
+    mut query: Query<(Entity, &NetEntity, Option<&Handle<Mesh>>)>,
+) {
+    if !keyboard_input.just_pressed(KeyCode::W) {
+        return;
+    }
+
+    let net = match scene.net.take() {
+        Some(net) => net,
+        None => return,
+    };
+    scene.wireframe = !scene.wireframe;
+    info!(
+        "Drawing the net {}",
+        if scene.wireframe {
+            "as a wireframe"
         } else {
-            commands.spawn(primitive(
-                material,
-                &mut meshes,
-                shape_type,
-                TessellationMode::Fill(&FillOptions::default()),
-                translation,
-            ));
+            "filled"
+        }
+    );
+
+    despawn_net(
+        &mut commands,
+        &mut meshes,
+        &mut materials,
+        &mut scene,
+        &mut query,
+    );
+    spawn_net(
+        &mut commands,
+        &mut meshes,
+        &mut materials,
+        &args,
+        net,
+        &mut hover,
+        &mut scene,
+    );
+}
+
+/// Exports the net that is currently drawn (i.e. after any re-rolls, in the current draw mode) to
+/// timestamped .svg and .png files whenever `S` is pressed, without restarting the program.
+fn snapshot_system(
+    keyboard_input: Res<Input<KeyCode>>,
+    args: Res<InputArgs>,
+    scene: Res<NetScene>,
+) {
+    if !keyboard_input.just_pressed(KeyCode::S) {
+        return;
+    }
+
+    let net = match &scene.net {
+        Some(net) => net,
+        None => return,
+    };
+    match export_snapshot(&args, net, scene.wireframe) {
+        Ok(paths) => info!("Exported the current net to: {:?}", paths),
+        Err(e) => error!("Failed to export the current net: {}", e),
+    }
+}
+
+/// Moves the window to where it was when it was last closed (on the first frame, since the window
+/// doesn't exist before then), and saves its size and position as soon as it is closed again.
+fn window_geometry_system(
+    mut state: ResMut<WindowGeometryState>,
+    windows: Res<Windows>,
+    winit_windows: Res<WinitWindows>,
+    window_close_requested_events: Res<Events<WindowCloseRequested>>,
+) {
+    let window = match winit_windows.get_window(WindowId::primary()) {
+        Some(window) => window,
+        None => return,
+    };
+
+    if let Some((x, y)) = state.position.take() {
+        // The position is set by modifying the current one, which has the right (physical) type
+        if let Ok(mut position) = window.outer_position() {
+            position.x = x;
+            position.y = y;
+            window.set_outer_position(position);
         }
     }
 
-    // Add the camera
-    commands.spawn(Camera2dComponents::default());
+    if state
+        .window_close_requested_event_reader
+        .iter(&window_close_requested_events)
+        .next()
+        .is_none()
+    {
+        return;
+    }
+    if let Some(primary) = windows.get_primary() {
+        save_window_geometry(&WindowGeometry {
+            width: primary.width,
+            height: primary.height,
+            position: window
+                .outer_position()
+                .ok()
+                .map(|position| (position.x, position.y)),
+        });
+    }
 }
@@ -0,0 +1,364 @@
+use glam::Vec3;
+
+use std::collections::HashMap;
+use std::fmt;
+use std::path::Path;
+
+/// The size (in bytes) of the header of a binary .stl file, which is followed by the number of
+/// triangles (as a 32-bit integer).
+const BINARY_HEADER_SIZE: usize = 80;
+
+/// The size (in bytes) of each triangle of a binary .stl file: a normal and 3 vertices (each made
+/// of 3 32-bit floats), followed by a 16-bit "attribute byte count."
+const BINARY_TRIANGLE_SIZE: usize = 50;
+
+/// The reasons why an .stl file might fail to load.
+#[derive(Debug)]
+pub enum StlError {
+    // The file couldn't be read
+    Io(std::io::Error),
+
+    // The binary .stl file is shorter than its header says it should be
+    Truncated { triangles: usize, size: usize },
+
+    // The ASCII .stl file contains something other than a number or keyword on this line (starting
+    // at 1), or a facet that isn't a triangle
+    Syntax { line: usize },
+
+    // The .stl file is empty (or only contains whitespace), or doesn't contain any triangles
+    Empty,
+
+    // A vertex of the triangle at this index (starting at 0) has a coordinate that is NaN or
+    // infinite
+    NonFinite { triangle: usize },
+}
+
+impl fmt::Display for StlError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StlError::Io(e) => write!(f, "{}", e),
+            StlError::Truncated { triangles, size } => write!(
+                f,
+                "The binary .stl file should contain {} triangles, but it is only {} bytes long",
+                triangles, size
+            ),
+            StlError::Syntax { line } => write!(f, "Invalid ASCII .stl file on line {}", line),
+            StlError::Empty => write!(f, "The .stl file doesn't contain any triangles"),
+            StlError::NonFinite { triangle } => write!(
+                f,
+                "Triangle {} of the .stl file has coordinates that aren't finite numbers (NaN or infinity)",
+                triangle
+            ),
+        }
+    }
+}
+
+impl From<std::io::Error> for StlError {
+    fn from(e: std::io::Error) -> Self {
+        StlError::Io(e)
+    }
+}
+
+/// Reads the triangles of an .stl file, in either the binary or the ASCII format. Since every
+/// triangle of an .stl file lists its own vertices, the triangles aren't connected to one another:
+/// see `weld_vertices()`. The facet normals of the file are ignored, since the vertices of each
+/// triangle are in CCW order (when viewed from the outside of the mesh) anyway.
+pub fn read_stl(path: &Path) -> Result<Vec<[Vec3; 3]>, StlError> {
+    let bytes = std::fs::read(path)?;
+
+    // Otherwise, an empty file would be mistaken for a binary file that is cut short
+    if bytes.iter().all(u8::is_ascii_whitespace) {
+        return Err(StlError::Empty);
+    }
+
+    // ASCII files start with "solid", but so do some binary files (since their header is arbitrary),
+    // so binary files are recognized by their size instead
+    let binary_size = |triangles: usize| BINARY_HEADER_SIZE + 4 + triangles * BINARY_TRIANGLE_SIZE;
+    let triangles = if bytes.len() >= BINARY_HEADER_SIZE + 4 {
+        let count = &bytes[BINARY_HEADER_SIZE..BINARY_HEADER_SIZE + 4];
+        Some(u32::from_le_bytes([count[0], count[1], count[2], count[3]]) as usize)
+    } else {
+        None
+    };
+
+    let triangles = match triangles {
+        Some(triangles) if bytes.len() == binary_size(triangles) => read_binary(&bytes, triangles),
+        _ if bytes.starts_with(b"solid") => read_ascii(&String::from_utf8_lossy(&bytes))?,
+        Some(triangles) => {
+            return Err(StlError::Truncated {
+                triangles,
+                size: bytes.len(),
+            })
+        }
+        None => {
+            return Err(StlError::Truncated {
+                triangles: 0,
+                size: bytes.len(),
+            })
+        }
+    };
+
+    // Both formats parse NaNs and infinities just like any other number, so reject them here (like
+    // `check_obj_syntax()` does for .obj files) rather than unfolding them
+    match triangles.iter().position(|triangle| {
+        !triangle.iter().all(|vertex| {
+            vertex.x().is_finite() && vertex.y().is_finite() && vertex.z().is_finite()
+        })
+    }) {
+        Some(triangle) => Err(StlError::NonFinite { triangle }),
+        None if triangles.is_empty() => Err(StlError::Empty),
+        None => Ok(triangles),
+    }
+}
+
+/// Parses the triangles of a binary .stl file, whose size has already been checked.
+fn read_binary(bytes: &[u8], triangles: usize) -> Vec<[Vec3; 3]> {
+    let read_f32 = |offset: usize| {
+        f32::from_le_bytes([
+            bytes[offset],
+            bytes[offset + 1],
+            bytes[offset + 2],
+            bytes[offset + 3],
+        ])
+    };
+    let read_vec3 =
+        |offset: usize| Vec3::new(read_f32(offset), read_f32(offset + 4), read_f32(offset + 8));
+
+    (0..triangles)
+        .map(|triangle| {
+            // Skip the normal of the triangle, which comes before its vertices
+            let start = BINARY_HEADER_SIZE + 4 + triangle * BINARY_TRIANGLE_SIZE + 12;
+            [
+                read_vec3(start),
+                read_vec3(start + 12),
+                read_vec3(start + 24),
+            ]
+        })
+        .collect()
+}
+
+/// Parses the triangles of an ASCII .stl file, i.e. everything between each `outer loop` and
+/// `endloop`. The names of solids and the facet normals are ignored, so a file with several
+/// solids is read as a single list of triangles.
+fn read_ascii(text: &str) -> Result<Vec<[Vec3; 3]>, StlError> {
+    let mut triangles = vec![];
+    let mut vertices = vec![];
+    for (line_index, line) in text.lines().enumerate() {
+        let error = || StlError::Syntax {
+            line: line_index + 1,
+        };
+        let mut tokens = line.split_whitespace();
+        match tokens.next() {
+            Some("vertex") => {
+                let coordinates = tokens
+                    .map(|token| token.parse::<f32>())
+                    .collect::<Result<Vec<_>, _>>()
+                    .map_err(|_| error())?;
+                match coordinates[..] {
+                    [x, y, z] => vertices.push(Vec3::new(x, y, z)),
+                    _ => return Err(error()),
+                }
+            }
+            Some("endloop") => match vertices[..] {
+                [a, b, c] => {
+                    triangles.push([a, b, c]);
+                    vertices.clear();
+                }
+                _ => return Err(error()),
+            },
+            Some("solid") | Some("endsolid") | Some("facet") | Some("outer") | Some("endfacet")
+            | None => {}
+            Some(_) => return Err(error()),
+        }
+    }
+    Ok(triangles)
+}
+
+/// Welds together the vertices of the specified triangles that lie within `tolerance` units of one
+/// another, and returns the welded vertices along with the indices of the vertices of each
+/// triangle. Nearby vertices are found with a spatial hash (i.e. a sparse grid whose cells are
+/// `tolerance` units wide), so each vertex is only compared against the vertices in the 27 cells
+/// around it. A tolerance of zero (or less) only welds vertices that are exactly the same.
+pub fn weld_vertices(triangles: &[[Vec3; 3]], tolerance: f32) -> (Vec<Vec3>, Vec<[usize; 3]>) {
+    if tolerance <= 0.0 {
+        let mut vertices: Vec<Vec3> = vec![];
+        let mut indices: HashMap<[u32; 3], usize> = HashMap::new();
+        let mut weld = |point: &Vec3| {
+            let key = [
+                point.x().to_bits(),
+                point.y().to_bits(),
+                point.z().to_bits(),
+            ];
+            *indices.entry(key).or_insert_with(|| {
+                vertices.push(*point);
+                vertices.len() - 1
+            })
+        };
+        let faces = triangles
+            .iter()
+            .map(|[a, b, c]| [weld(a), weld(b), weld(c)])
+            .collect::<Vec<_>>();
+        return (vertices, faces);
+    }
+
+    let cell_of = |point: &Vec3| {
+        [
+            (point.x() / tolerance).floor() as i64,
+            (point.y() / tolerance).floor() as i64,
+            (point.z() / tolerance).floor() as i64,
+        ]
+    };
+
+    let mut vertices: Vec<Vec3> = vec![];
+    let mut grid: HashMap<[i64; 3], Vec<usize>> = HashMap::new();
+    let mut weld = |point: &Vec3| {
+        let [x, y, z] = cell_of(point);
+        for neighbor in (x - 1..=x + 1).flat_map(|x| {
+            (y - 1..=y + 1).flat_map(move |y| (z - 1..=z + 1).map(move |z| [x, y, z]))
+        }) {
+            if let Some(indices) = grid.get(&neighbor) {
+                if let Some(&index) = indices
+                    .iter()
+                    .find(|&&index| (vertices[index] - *point).length() <= tolerance)
+                {
+                    return index;
+                }
+            }
+        }
+
+        vertices.push(*point);
+        grid.entry([x, y, z]).or_default().push(vertices.len() - 1);
+        vertices.len() - 1
+    };
+
+    let faces = triangles
+        .iter()
+        .map(|[a, b, c]| [weld(a), weld(b), weld(c)])
+        .collect::<Vec<_>>();
+    (vertices, faces)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_stl() {
+        // The same pair of triangles (i.e. a unit square, split along its diagonal) in both
+        // formats, where the ASCII vertices are slightly off, like the output of many exporters
+        let directory = std::env::temp_dir();
+        let ascii_path = directory.join("durer_test_square_ascii.stl");
+        std::fs::write(
+            &ascii_path,
+            "solid square\n\
+             facet normal 0 0 1\n  outer loop\n    vertex 0 0 0\n    vertex 1 0 0\n    vertex 1 1 0\n  endloop\nendfacet\n\
+             facet normal 0 0 1\n  outer loop\n    vertex 0.0000001 0 0\n    vertex 1 1 0\n    vertex 0 1.0 0\n  endloop\nendfacet\n\
+             endsolid square\n",
+        )
+        .unwrap();
+
+        let binary_path = directory.join("durer_test_square_binary.stl");
+        let mut bytes = vec![0; BINARY_HEADER_SIZE];
+        bytes.extend_from_slice(&2u32.to_le_bytes());
+        for triangle in [
+            [0.0, 0.0, 1.0, 0.0, 1.0, 1.0],
+            [0.0, 0.0, 1.0, 1.0, 0.0, 1.0],
+        ]
+        .iter()
+        {
+            for value in [0.0, 0.0, 1.0].iter() {
+                bytes.extend_from_slice(&(*value as f32).to_le_bytes());
+            }
+            for vertex in triangle.chunks(2) {
+                for value in [vertex[0], vertex[1], 0.0].iter() {
+                    bytes.extend_from_slice(&(*value as f32).to_le_bytes());
+                }
+            }
+            bytes.extend_from_slice(&[0, 0]);
+        }
+        std::fs::write(&binary_path, &bytes).unwrap();
+
+        for path in [ascii_path, binary_path].iter() {
+            let triangles = read_stl(path).unwrap();
+            assert_eq!(triangles.len(), 2);
+
+            // The diagonal (and the vertex that is slightly off) are shared by both triangles
+            let (vertices, faces) = weld_vertices(&triangles, 1.0e-5);
+            assert_eq!(vertices.len(), 4);
+            assert_eq!(faces, vec![[0, 1, 2], [0, 2, 3]]);
+        }
+
+        // A binary file that is cut short isn't mistaken for an ASCII file
+        let truncated_path = directory.join("durer_test_square_truncated.stl");
+        std::fs::write(&truncated_path, &bytes[..bytes.len() - 1]).unwrap();
+        assert!(matches!(
+            read_stl(&truncated_path),
+            Err(StlError::Truncated { triangles: 2, .. })
+        ));
+
+        // Empty files (in either format) are reported as such, rather than as truncated binary files
+        let empty_path = directory.join("durer_test_empty.stl");
+        let mut empty_binary = vec![0; BINARY_HEADER_SIZE];
+        empty_binary.extend_from_slice(&0u32.to_le_bytes());
+        for contents in [
+            &b""[..],
+            &b" \n\t\n"[..],
+            &b"solid empty\nendsolid empty\n"[..],
+            &empty_binary,
+        ]
+        .iter()
+        {
+            std::fs::write(&empty_path, contents).unwrap();
+            assert!(matches!(read_stl(&empty_path), Err(StlError::Empty)));
+        }
+
+        // NaNs and infinities are rejected in both formats, rather than being unfolded
+        let nan_path = directory.join("durer_test_square_nan.stl");
+        std::fs::write(
+            &nan_path,
+            "solid square\n\
+             facet normal 0 0 1\n  outer loop\n    vertex 0 0 0\n    vertex 1 0 0\n    vertex 1 1 0\n  endloop\nendfacet\n\
+             facet normal 0 0 1\n  outer loop\n    vertex 0 0 0\n    vertex 1 1 0\n    vertex nan 1 0\n  endloop\nendfacet\n\
+             endsolid square\n",
+        )
+        .unwrap();
+        assert!(matches!(
+            read_stl(&nan_path),
+            Err(StlError::NonFinite { triangle: 1 })
+        ));
+        let start = BINARY_HEADER_SIZE + 4 + 12;
+        bytes[start..start + 4].copy_from_slice(&f32::INFINITY.to_le_bytes());
+        std::fs::write(&nan_path, &bytes).unwrap();
+        assert!(matches!(
+            read_stl(&nan_path),
+            Err(StlError::NonFinite { triangle: 0 })
+        ));
+    }
+
+    #[test]
+    fn test_weld_vertices() {
+        let triangles = [
+            [
+                Vec3::new(0.0, 0.0, 0.0),
+                Vec3::new(1.0, 0.0, 0.0),
+                Vec3::new(1.0, 1.0, 0.0),
+            ],
+            [
+                Vec3::new(1.0e-7, 0.0, 0.0),
+                Vec3::new(1.0, 1.0, 0.0),
+                Vec3::new(0.0, 1.0, 0.0),
+            ],
+        ];
+        let (vertices, faces) = weld_vertices(&triangles, 1.0e-5);
+        assert_eq!(vertices.len(), 4);
+        assert_eq!(faces, vec![[0, 1, 2], [0, 2, 3]]);
+
+        // Without a tolerance, only the vertices that are exactly the same are welded (instead of
+        // overflowing the cells of the spatial hash)
+        for &tolerance in [0.0, -1.0].iter() {
+            let (vertices, faces) = weld_vertices(&triangles, tolerance);
+            assert_eq!(vertices.len(), 5);
+            assert_eq!(faces, vec![[0, 1, 2], [3, 2, 4]]);
+        }
+    }
+}
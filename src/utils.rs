@@ -17,7 +17,7 @@ pub fn angle_with_e1(vector: &Vec3) -> f32 {
 }
 
 /// Find the minimum and maximum x- and y-coordinates of a list of vertices.
-pub fn find_extents(points: &Vec<Vec3>) -> (f32, f32) {
+pub fn find_extents(points: &[Vec3]) -> (f32, f32) {
     let mut min_x = points
         .iter()
         .map(|&v| v.x())
@@ -38,6 +38,189 @@ pub fn find_extents(points: &Vec<Vec3>) -> (f32, f32) {
     ((max_x - min_x).abs(), (max_y - min_y).abs())
 }
 
+/// Finds the width (along the x-axis) and height (along the y-axis) of the smallest axis-aligned
+/// box that is centered at `center` and encloses a list of vertices, i.e. twice the largest
+/// distance from `center` to any of the vertices along each axis.
+pub fn find_extents_around(points: &[Vec3], center: &Vec3) -> (f32, f32) {
+    let half = points.iter().fold(Vec3::zero(), |half, &point| {
+        half.max((point - *center).abs())
+    });
+
+    (half.x() * 2.0, half.y() * 2.0)
+}
+
+/// Find the corners of the axis-aligned bounding box that encloses a list of vertices. The
+/// first returned vector holds the minimum coordinates and the second the maximum coordinates.
+pub fn find_bounding_box(points: &[Vec3]) -> (Vec3, Vec3) {
+    let min = points
+        .iter()
+        .fold(Vec3::splat(f32::INFINITY), |a, &b| a.min(b));
+    let max = points
+        .iter()
+        .fold(Vec3::splat(f32::NEG_INFINITY), |a, &b| a.max(b));
+
+    (min, max)
+}
+
+/// Packs rectangles of the specified sizes (width and height) onto horizontal shelves, with `spacing`
+/// units between neighboring rectangles (and shelves), and returns the position of the bottom-left
+/// corner of each rectangle. The rectangles are placed from left to right, tallest first, and a new
+/// shelf is started above the previous one whenever the next rectangle would make the shelf wider
+/// than a certain limit. Each sensible limit (i.e. the width of the first few rectangles side by
+/// side) is tried in turn, and the one that results in the smallest bounding box is kept.
+pub fn pack_shelves(sizes: &[Vec2], spacing: f32) -> Vec<Vec2> {
+    let mut order = (0..sizes.len()).collect::<Vec<_>>();
+    order.sort_by(|&a, &b| {
+        sizes[b]
+            .y()
+            .partial_cmp(&sizes[a].y())
+            .unwrap()
+            .then(a.cmp(&b))
+    });
+
+    // Returns the area of the bounding box of the packed rectangles, along with their corners
+    let pack = |limit: f32| {
+        let mut corners = vec![Vec2::zero(); sizes.len()];
+        let (mut x, mut y, mut shelf_height, mut width) = (0.0, 0.0, 0.0, 0.0f32);
+        for &index in order.iter() {
+            let size = sizes[index];
+            if x > 0.0 && x + size.x() > limit {
+                x = 0.0;
+                y += shelf_height + spacing;
+                shelf_height = 0.0;
+            }
+            corners[index] = Vec2::new(x, y);
+            width = width.max(x + size.x());
+            shelf_height = shelf_height.max(size.y());
+            x += size.x() + spacing;
+        }
+        (width * (y + shelf_height), corners)
+    };
+
+    // A shelf is never narrower than the widest rectangle, so start with that
+    let mut best = pack(sizes.iter().map(|size| size.x()).fold(0.0, f32::max));
+    let mut limit = 0.0;
+    for &index in order.iter() {
+        limit += sizes[index].x();
+        let candidate = pack(limit);
+        if candidate.0 < best.0 {
+            best = candidate;
+        }
+        limit += spacing;
+    }
+    best.1
+}
+
+/// Finds the convex hull of a list of vertices that lie in the xy-plane, using Andrew's monotone
+/// chain algorithm. The corners of the hull are returned in CCW order, without any collinear points.
+pub fn convex_hull_2d(points: &[Vec3]) -> Vec<Vec2> {
+    let mut sorted = points
+        .iter()
+        .map(|point| Vec2::new(point.x(), point.y()))
+        .collect::<Vec<_>>();
+    sorted.sort_by(|a, b| {
+        a.x()
+            .partial_cmp(&b.x())
+            .unwrap()
+            .then(a.y().partial_cmp(&b.y()).unwrap())
+    });
+    sorted.dedup();
+    if sorted.len() < 3 {
+        return sorted;
+    }
+
+    // Walks along the points in order, dropping any point that doesn't form a CCW turn with its
+    // neighbors: the result is one half of the hull
+    fn half_hull<'a>(points: impl Iterator<Item = &'a Vec2>) -> Vec<Vec2> {
+        let mut half: Vec<Vec2> = vec![];
+        for point in points {
+            while half.len() >= 2 {
+                let (o, a) = (half[half.len() - 2], half[half.len() - 1]);
+                let cross =
+                    (a.x() - o.x()) * (point.y() - o.y()) - (a.y() - o.y()) * (point.x() - o.x());
+                if cross > 0.0 {
+                    break;
+                }
+                half.pop();
+            }
+            half.push(*point);
+        }
+
+        // The last point of each half is the first point of the other half
+        half.pop();
+        half
+    }
+
+    // The lower half of the hull runs from left to right, and the upper half from right to left
+    let mut hull = half_hull(sorted.iter());
+    hull.extend(half_hull(sorted.iter().rev()));
+    hull
+}
+
+/// Finds the minimum-area bounding box of a list of vertices that lie in the xy-plane, which (unlike
+/// `find_extents`) doesn't have to be aligned with the axes. One side of this box is always collinear
+/// with an edge of the convex hull of the vertices, so each edge of the hull is tried in turn.
+///
+/// Returns the angle (in radians) that the vertices should be rotated by (about the z-axis) so that
+/// the box becomes axis-aligned, with its longer side along the x-axis, along with the width and
+/// height of the (rotated) box.
+pub fn find_oriented_bounding_box(points: &[Vec3]) -> (f32, f32, f32) {
+    let hull = convex_hull_2d(points);
+
+    // Start with the axis-aligned box, which is only replaced by boxes that are noticeably smaller
+    // (so that nets that are already aligned aren't rotated because of floating point noise)
+    const RELATIVE_TOLERANCE: f32 = 1.0e-4;
+    let (width, height) = find_extents(points);
+    let mut best = if width >= height {
+        (0.0, width, height)
+    } else {
+        (std::f32::consts::FRAC_PI_2, height, width)
+    };
+    for i in 0..hull.len() {
+        let edge = hull[(i + 1) % hull.len()] - hull[i];
+        if edge.length() == 0.0 {
+            continue;
+        }
+
+        // Project the hull onto the edge and its perpendicular, which are the axes of the box
+        let along = edge.normalize();
+        let across = Vec2::new(-along.y(), along.x());
+        let extent = |axis: Vec2| {
+            let (min, max) = hull
+                .iter()
+                .map(|point| point.dot(axis))
+                .fold((f32::INFINITY, f32::NEG_INFINITY), |(min, max), p| {
+                    (min.min(p), max.max(p))
+                });
+            max - min
+        };
+        let (width, height) = (extent(along), extent(across));
+
+        if width * height < best.1 * best.2 * (1.0 - RELATIVE_TOLERANCE) {
+            // Rotating by the negative of the edge's angle aligns the edge with the x-axis, and
+            // a further quarter turn puts the longer side of the box along the x-axis
+            let angle = -along.y().atan2(along.x());
+            best = if width >= height {
+                (angle, width, height)
+            } else {
+                (angle + std::f32::consts::FRAC_PI_2, height, width)
+            };
+        }
+    }
+
+    // Turning the box by half a revolution doesn't change it, so use the smallest rotation
+    // (in the range -π/2..π/2) that aligns it with the axes
+    let (mut angle, width, height) = best;
+    while angle > std::f32::consts::FRAC_PI_2 {
+        angle -= std::f32::consts::PI;
+    }
+    while angle <= -std::f32::consts::FRAC_PI_2 {
+        angle += std::f32::consts::PI;
+    }
+
+    (angle, width, height)
+}
+
 /// Finds the center (i.e. average) of the given set of 3D points.
 pub fn find_centroid(points: &Vec<Vec3>) -> Vec3 {
     let mut centroid = Vec3::zero();
@@ -47,11 +230,314 @@ pub fn find_centroid(points: &Vec<Vec3>) -> Vec3 {
     centroid / points.len() as f32
 }
 
+/// Returns the area of the (planar) polygon with the specified vertices, which should be in order
+/// (either CW or CCW). This works in 3-space, regardless of the plane that the polygon lies in.
+pub fn polygon_area(points: &[Vec3]) -> f32 {
+    // The sum of the cross products of the polygon's fan triangles points along the polygon's
+    // normal and has twice the area of the polygon as its length
+    let mut sum = Vec3::zero();
+    for i in 1..points.len().saturating_sub(1) {
+        sum += (points[i] - points[0]).cross(points[i + 1] - points[0]);
+    }
+    sum.length() * 0.5
+}
+
 /// Finds the area of the specified triangle.
 pub fn triangle_area_2d(a: &Vec2, b: &Vec2, c: &Vec2) -> f32 {
     (a.x() * (b.y() - c.y()) + b.x() * (c.y() - a.y()) + c.x() * (a.y() * b.y()) / 2.0).abs()
 }
 
+/// Returns `true` if the interiors of the two specified triangles overlap and `false` otherwise.
+///
+/// This is implemented via the separating axis theorem: the triangles are disjoint if their
+/// projections onto one of the (6) edge normals don't overlap. Projections that overlap by
+/// less than `epsilon` are treated as disjoint, so that triangles that merely share an edge
+/// or a vertex (as neighboring faces in a net always do) are not reported.
+pub fn triangles_overlap_2d(t0: &[Vec2; 3], t1: &[Vec2; 3], epsilon: f32) -> bool {
+    for triangle in [t0, t1].iter() {
+        for i in 0..3 {
+            let edge = triangle[(i + 1) % 3] - triangle[i];
+            let axis = Vec2::new(-edge.y(), edge.x()).normalize();
+
+            let project = |t: &[Vec2; 3]| {
+                t.iter()
+                    .map(|point| point.dot(axis))
+                    .fold((f32::INFINITY, f32::NEG_INFINITY), |(min, max), p| {
+                        (min.min(p), max.max(p))
+                    })
+            };
+            let (min0, max0) = project(t0);
+            let (min1, max1) = project(t1);
+
+            // Found a separating axis
+            if max0.min(max1) - min0.max(min1) <= epsilon {
+                return false;
+            }
+        }
+    }
+
+    true
+}
+
+/// Returns the tolerance that should be used when testing the specified set of vertices for overlaps,
+/// which is the specified fraction of their overall size (so that it doesn't depend on the units of
+/// the goal mesh).
+pub fn overlap_tolerance(points: &[Vec3], relative_tolerance: f32) -> f32 {
+    let (size_x, size_y) = find_extents(points);
+    size_x.max(size_y) * relative_tolerance
+}
+
+/// Converts a list of vertices (3 per triangle) that lie in the xy-plane to a list of 2D triangles.
+fn triangles_2d(points: &[Vec3]) -> Vec<[Vec2; 3]> {
+    points
+        .chunks(3)
+        .map(|t| {
+            [
+                Vec2::new(t[0].x(), t[0].y()),
+                Vec2::new(t[1].x(), t[1].y()),
+                Vec2::new(t[2].x(), t[2].y()),
+            ]
+        })
+        .collect()
+}
+
+/// Finds all pairs of triangles whose interiors overlap, given a list of vertices (3 per triangle)
+/// that lie in the xy-plane. Each pair `(i, j)` holds the indices of the two triangles, where `i < j`,
+/// and the pairs are sorted. Triangles that overlap by no more than `relative_tolerance` times the
+/// size of the vertices (see `overlap_tolerance()`) are considered to be touching instead.
+///
+/// Testing every pair of triangles is quadratic in the number of faces, which is too slow for large
+/// nets (especially when many nets are tried, i.e. when scanning reference faces or searching for a
+/// net without overlaps), so this uses "sweep and prune" instead: the triangles are sorted by the
+/// left side of their bounding boxes, and each triangle is only tested against the triangles whose
+/// bounding boxes start before its own ends (and overlap it vertically). Triangles whose bounding
+/// boxes are disjoint are always separated by one of their edge normals, so this finds exactly the
+/// same pairs as `find_overlapping_triangles_brute_force()`.
+pub fn find_overlapping_triangles(points: &[Vec3], relative_tolerance: f32) -> Vec<(usize, usize)> {
+    let epsilon = overlap_tolerance(points, relative_tolerance);
+    let triangles = triangles_2d(points);
+
+    let bounds = triangles
+        .iter()
+        .map(|t| (t[0].min(t[1]).min(t[2]), t[0].max(t[1]).max(t[2])))
+        .collect::<Vec<_>>();
+    let mut order = (0..triangles.len()).collect::<Vec<_>>();
+    order.sort_by(|&i, &j| bounds[i].0.x().partial_cmp(&bounds[j].0.x()).unwrap());
+
+    let mut overlaps = vec![];
+    for (sorted_index, &i) in order.iter().enumerate() {
+        let (min_i, max_i) = bounds[i];
+        for &j in order[sorted_index + 1..].iter() {
+            let (min_j, max_j) = bounds[j];
+
+            // Every remaining triangle starts to the right of this one
+            if min_j.x() > max_i.x() {
+                break;
+            }
+            if min_j.y() > max_i.y() || min_i.y() > max_j.y() {
+                continue;
+            }
+
+            if triangles_overlap_2d(&triangles[i], &triangles[j], epsilon) {
+                overlaps.push((i.min(j), i.max(j)));
+            }
+        }
+    }
+
+    overlaps.sort();
+    overlaps
+}
+
+/// Finds all pairs of triangles whose interiors overlap (like `find_overlapping_triangles()`) by
+/// testing every pair of triangles against each other. This is only useful as a reference (e.g.
+/// for benchmarks), since it takes time quadratic in the number of triangles.
+pub fn find_overlapping_triangles_brute_force(
+    points: &[Vec3],
+    relative_tolerance: f32,
+) -> Vec<(usize, usize)> {
+    let epsilon = overlap_tolerance(points, relative_tolerance);
+    let triangles = triangles_2d(points);
+
+    let mut overlaps = vec![];
+    for i in 0..triangles.len() {
+        for j in (i + 1)..triangles.len() {
+            if triangles_overlap_2d(&triangles[i], &triangles[j], epsilon) {
+                overlaps.push((i, j));
+            }
+        }
+    }
+
+    overlaps
+}
+
+/// Returns `true` if the segment `pq` passes through the triangle `t`, i.e. if its endpoints lie
+/// on opposite sides of the triangle's plane (by more than `epsilon`) and the point where it
+/// crosses the plane lies inside of the triangle (or within `epsilon` of it). Crossings on the
+/// border count, since the border of a triangle is usually shared with another triangle (e.g.
+/// the diagonal of a square face, which the segment would otherwise slip through).
+fn segment_crosses_triangle_3d(p: &Vec3, q: &Vec3, t: &[Vec3; 3], epsilon: f32) -> bool {
+    let normal = (t[1] - t[0]).cross(t[2] - t[0]);
+    if normal.length() <= f32::EPSILON {
+        return false;
+    }
+    let normal = normal.normalize();
+
+    let distance_p = normal.dot(*p - t[0]);
+    let distance_q = normal.dot(*q - t[0]);
+    if !(distance_p > epsilon && distance_q < -epsilon
+        || distance_p < -epsilon && distance_q > epsilon)
+    {
+        return false;
+    }
+
+    let crossing = *p + (*q - *p) * (distance_p / (distance_p - distance_q));
+    (0..3).all(|i| {
+        let edge = t[(i + 1) % 3] - t[i];
+        normal.dot(edge.cross(crossing - t[i])) / edge.length() >= -epsilon
+    })
+}
+
+/// Returns `true` if the two specified triangles intersect in 3D and `false` otherwise.
+///
+/// Two triangles that cross each other always have (at least) one side that passes through the
+/// interior of the other triangle, so this tests each of the (6) sides against the other triangle.
+/// Triangles that lie in the same plane are projected onto it and tested like the faces of a net
+/// instead (see `triangles_overlap_2d()`). Like there, contact within `epsilon` isn't reported,
+/// so that triangles that merely share a side or a vertex (as neighboring faces always do) aren't
+/// treated as intersecting.
+pub fn triangles_intersect_3d(t0: &[Vec3; 3], t1: &[Vec3; 3], epsilon: f32) -> bool {
+    let normal = (t0[1] - t0[0]).cross(t0[2] - t0[0]);
+    if normal.length() > f32::EPSILON {
+        let normal = normal.normalize();
+        if t1
+            .iter()
+            .all(|point| normal.dot(*point - t0[0]).abs() <= epsilon)
+        {
+            // Drop the coordinate along which the normal is largest, which keeps the projected
+            // triangles from degenerating
+            let abs = normal.abs();
+            let project = |point: &Vec3| {
+                if abs.x() >= abs.y() && abs.x() >= abs.z() {
+                    Vec2::new(point.y(), point.z())
+                } else if abs.y() >= abs.z() {
+                    Vec2::new(point.z(), point.x())
+                } else {
+                    Vec2::new(point.x(), point.y())
+                }
+            };
+            return triangles_overlap_2d(
+                &[project(&t0[0]), project(&t0[1]), project(&t0[2])],
+                &[project(&t1[0]), project(&t1[1]), project(&t1[2])],
+                epsilon,
+            );
+        }
+    }
+
+    (0..3).any(|i| {
+        segment_crosses_triangle_3d(&t0[i], &t0[(i + 1) % 3], t1, epsilon)
+            || segment_crosses_triangle_3d(&t1[i], &t1[(i + 1) % 3], t0, epsilon)
+    })
+}
+
+/// Finds all pairs of the specified triangles that intersect in 3D (see `triangles_intersect_3d()`),
+/// with a tolerance that is relative to their overall size. Each pair `(i, j)` holds the indices of
+/// the two triangles, where `i < j`, and the pairs are sorted.
+///
+/// Like `find_overlapping_triangles()`, this sorts the triangles by the left side of their bounding
+/// boxes and only tests the ones whose bounding boxes overlap, but it still takes time quadratic in
+/// the number of triangles in the worst case.
+pub fn find_intersecting_triangles_3d(triangles: &[[Vec3; 3]]) -> Vec<(usize, usize)> {
+    const RELATIVE_TOLERANCE: f32 = 1.0e-4;
+    let points = triangles.iter().flatten().cloned().collect::<Vec<_>>();
+    let (min, max) = find_bounding_box(&points);
+    let epsilon = (max - min).max_element() * RELATIVE_TOLERANCE;
+
+    let bounds = triangles
+        .iter()
+        .map(|t| (t[0].min(t[1]).min(t[2]), t[0].max(t[1]).max(t[2])))
+        .collect::<Vec<_>>();
+    let mut order = (0..triangles.len()).collect::<Vec<_>>();
+    order.sort_by(|&i, &j| bounds[i].0.x().partial_cmp(&bounds[j].0.x()).unwrap());
+
+    let mut intersections = vec![];
+    for (sorted_index, &i) in order.iter().enumerate() {
+        let (min_i, max_i) = bounds[i];
+        for &j in order[sorted_index + 1..].iter() {
+            let (min_j, max_j) = bounds[j];
+
+            // Every remaining triangle starts to the right of this one
+            if min_j.x() > max_i.x() + epsilon {
+                break;
+            }
+            if (min_j - max_i).max_element() > epsilon || (min_i - max_j).max_element() > epsilon {
+                continue;
+            }
+
+            if triangles_intersect_3d(&triangles[i], &triangles[j], epsilon) {
+                intersections.push((i.min(j), i.max(j)));
+            }
+        }
+    }
+
+    intersections.sort();
+    intersections
+}
+
+/// Returns `true` if the specified point lies inside of (or on the border of) the triangle
+/// `abc`, regardless of the triangle's winding order.
+pub fn point_in_triangle_2d(point: &Vec2, a: &Vec2, b: &Vec2, c: &Vec2) -> bool {
+    // Which side of the (directed) line through `u` and `v` the point lies on
+    let side = |u: &Vec2, v: &Vec2| {
+        (v.x() - u.x()) * (point.y() - u.y()) - (v.y() - u.y()) * (point.x() - u.x())
+    };
+    let (d0, d1, d2) = (side(a, b), side(b, c), side(c, a));
+
+    let has_negative = d0 < 0.0 || d1 < 0.0 || d2 < 0.0;
+    let has_positive = d0 > 0.0 || d1 > 0.0 || d2 > 0.0;
+    !(has_negative && has_positive)
+}
+
+/// Finds the first triangle that contains the specified point, given a list of vertices (3 per
+/// triangle) that lie in the xy-plane. Returns the index of the triangle, if any.
+pub fn find_triangle_containing(points: &[Vec3], point: &Vec2) -> Option<usize> {
+    points.chunks(3).position(|t| {
+        point_in_triangle_2d(
+            point,
+            &Vec2::new(t[0].x(), t[0].y()),
+            &Vec2::new(t[1].x(), t[1].y()),
+            &Vec2::new(t[2].x(), t[2].y()),
+        )
+    })
+}
+
+/// Splits the line segment from `a` to `b` into a list of dashes, based on the specified
+/// pattern of alternating dash and gap lengths (which repeats until the end of the segment).
+/// An empty pattern (or one of zero length) results in a single, solid segment.
+pub fn dash_segments(a: &Vec3, b: &Vec3, pattern: &[f32]) -> Vec<(Vec3, Vec3)> {
+    let length = (*b - *a).length();
+    if pattern.iter().sum::<f32>() <= 0.0 || length == 0.0 {
+        return vec![(*a, *b)];
+    }
+    let direction = (*b - *a) / length;
+
+    let mut dashes = vec![];
+    let mut traveled = 0.0;
+    for (i, &step) in pattern.iter().cycle().enumerate() {
+        if traveled >= length {
+            break;
+        }
+        let next = (traveled + step).min(length);
+
+        // Even entries are dashes, odd entries are gaps
+        if i % 2 == 0 {
+            dashes.push((*a + direction * traveled, *a + direction * next));
+        }
+        traveled = next;
+    }
+
+    dashes
+}
+
 /// Remaps `s` from the first range to the second.
 pub fn remap(from_range: (f32, f32), to_range: (f32, f32), s: f32) -> f32 {
     to_range.0 + (s - from_range.0) * (to_range.1 - to_range.0) / (from_range.1 - from_range.0)
@@ -64,3 +550,270 @@ pub fn srgb_to_linear(val: f32) -> f32 {
     }
     ((val + 0.055) / 1.055).powf(2.4)
 }
+
+/// Convert linear to SRGB color (the inverse of `srgb_to_linear`).
+pub fn linear_to_srgb(val: f32) -> f32 {
+    if val <= 0.0031308 {
+        return val * 12.92;
+    }
+    1.055 * val.powf(1.0 / 2.4) - 0.055
+}
+
+/// Converts an HSV color to RGB, where all of the components (including the hue) are in the
+/// range `0..1`.
+pub fn hsv_to_rgb(hue: f32, saturation: f32, value: f32) -> Vec3 {
+    let channel = |n: f32| {
+        let k = (n + hue * 6.0) % 6.0;
+        value - value * saturation * k.min(4.0 - k).clamp(0.0, 1.0)
+    };
+    Vec3::new(channel(5.0), channel(3.0), channel(1.0))
+}
+
+/// Converts an RGB color (with components in the range `0..1`) to HSV, where all of the components
+/// (including the hue) are in the range `0..1`. This is the inverse of `hsv_to_rgb`. The hue of a
+/// gray is undefined, so it is set to 0.
+pub fn rgb_to_hsv(color: &Vec3) -> (f32, f32, f32) {
+    let max = color.x().max(color.y()).max(color.z());
+    let min = color.x().min(color.y()).min(color.z());
+    let delta = max - min;
+
+    let sector = if delta <= 0.0 {
+        0.0
+    } else if max == color.x() {
+        ((color.y() - color.z()) / delta).rem_euclid(6.0)
+    } else if max == color.y() {
+        (color.z() - color.x()) / delta + 2.0
+    } else {
+        (color.x() - color.y()) / delta + 4.0
+    };
+    let saturation = if max > 0.0 { delta / max } else { 0.0 };
+
+    (sector / 6.0, saturation, max)
+}
+
+/// The (D65) reference white of the CIELAB color space, in XYZ coordinates.
+const REFERENCE_WHITE: [f32; 3] = [0.950_47, 1.0, 1.088_83];
+
+/// Converts an SRGB color to CIE LCh, i.e. the cylindrical form of the (perceptually uniform)
+/// CIELAB color space. The lightness is in the range `0..100`, the chroma starts at 0 (for grays)
+/// and is at most ~130 for SRGB colors, and the hue is in the range `0..1` (just like in HSV).
+pub fn srgb_to_lch(color: &Vec3) -> Vec3 {
+    let (r, g, b) = (
+        srgb_to_linear(color.x()),
+        srgb_to_linear(color.y()),
+        srgb_to_linear(color.z()),
+    );
+
+    // Linear SRGB to XYZ (relative to the reference white)
+    let x = (0.412_456_4 * r + 0.357_576_1 * g + 0.180_437_5 * b) / REFERENCE_WHITE[0];
+    let y = (0.212_672_9 * r + 0.715_152_2 * g + 0.072_175 * b) / REFERENCE_WHITE[1];
+    let z = (0.019_333_9 * r + 0.119_192 * g + 0.950_304_1 * b) / REFERENCE_WHITE[2];
+
+    // XYZ to CIELAB
+    const DELTA: f32 = 6.0 / 29.0;
+    let f = |t: f32| {
+        if t > DELTA * DELTA * DELTA {
+            t.cbrt()
+        } else {
+            t / (3.0 * DELTA * DELTA) + 4.0 / 29.0
+        }
+    };
+    let (fx, fy, fz) = (f(x), f(y), f(z));
+    let lightness = 116.0 * fy - 16.0;
+    let a = 500.0 * (fx - fy);
+    let b = 200.0 * (fy - fz);
+
+    Vec3::new(
+        lightness,
+        (a * a + b * b).sqrt(),
+        b.atan2(a).rem_euclid(std::f32::consts::PI * 2.0) / (std::f32::consts::PI * 2.0),
+    )
+}
+
+/// Converts a CIE LCh color to SRGB (the inverse of `srgb_to_lch`). Colors that lie outside of the
+/// SRGB gamut are clamped to it.
+pub fn lch_to_srgb(color: &Vec3) -> Vec3 {
+    let (lightness, chroma) = (color.x(), color.y());
+    let hue = color.z() * std::f32::consts::PI * 2.0;
+    let (a, b) = (chroma * hue.cos(), chroma * hue.sin());
+
+    // CIELAB to XYZ
+    const DELTA: f32 = 6.0 / 29.0;
+    let f_inverse = |t: f32| {
+        if t > DELTA {
+            t * t * t
+        } else {
+            3.0 * DELTA * DELTA * (t - 4.0 / 29.0)
+        }
+    };
+    let fy = (lightness + 16.0) / 116.0;
+    let x = f_inverse(fy + a / 500.0) * REFERENCE_WHITE[0];
+    let y = f_inverse(fy) * REFERENCE_WHITE[1];
+    let z = f_inverse(fy - b / 200.0) * REFERENCE_WHITE[2];
+
+    // XYZ to linear SRGB
+    let r = 3.240_454_2 * x - 1.537_138_5 * y - 0.498_531_4 * z;
+    let g = -0.969_266 * x + 1.876_010_8 * y + 0.041_556 * z;
+    let b = 0.055_643_4 * x - 0.204_025_9 * y + 1.057_225_2 * z;
+
+    let to_srgb = |val: f32| linear_to_srgb(val.clamp(0.0, 1.0));
+    Vec3::new(to_srgb(r), to_srgb(g), to_srgb(b))
+}
+
+/// Converts an RGB color (with components in the range `0..1`) to a hex string of the
+/// form `#RRGGBB`, suitable for use in .svg files.
+pub fn rgb_to_hex(color: &Vec3) -> String {
+    let to_byte = |val: f32| (val.clamp(0.0, 1.0) * 255.0).round() as u8;
+    format!(
+        "#{:02x}{:02x}{:02x}",
+        to_byte(color.x()),
+        to_byte(color.y()),
+        to_byte(color.z())
+    )
+}
+
+/// Parses a hex string of the form `#RRGGBB` (where the `#` is optional) into an RGB color, with
+/// components in the range `0..1`. This is the inverse of `rgb_to_hex`.
+pub fn hex_to_rgb(hex: &str) -> Result<Vec3, String> {
+    let digits = hex.trim().trim_start_matches('#');
+    if digits.len() != 6 || !digits.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(format!(
+            "Invalid color: {:?} (expected a hex color of the form #RRGGBB)",
+            hex
+        ));
+    }
+
+    let component = |i: usize| u8::from_str_radix(&digits[i..i + 2], 16).unwrap() as f32 / 255.0;
+    Ok(Vec3::new(component(0), component(2), component(4)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::goal_mesh::DEFAULT_OVERLAP_TOLERANCE;
+
+    #[test]
+    fn test_find_extents_around() {
+        let points = vec![
+            Vec3::new(0.0, 0.0, 0.0),
+            Vec3::new(4.0, 0.0, 0.0),
+            Vec3::new(4.0, 2.0, 0.0),
+            Vec3::new(0.0, 2.0, 0.0),
+        ];
+
+        // Around the center of the rectangle, the box is the rectangle itself, but around any other
+        // point, it has to grow to reach the far side
+        assert_eq!(
+            find_extents_around(&points, &Vec3::new(2.0, 1.0, 0.0)),
+            find_extents(&points)
+        );
+        assert_eq!(
+            find_extents_around(&points, &Vec3::new(1.0, 1.5, 0.0)),
+            (6.0, 3.0)
+        );
+        assert_eq!(
+            find_extents_around(&points, &Vec3::new(-1.0, 0.0, 0.0)),
+            (10.0, 4.0)
+        );
+    }
+
+    #[test]
+    fn test_find_overlapping_triangles() {
+        // A row of right triangles that share their edges with their neighbors (like the faces of a
+        // net), along with a few triangles that are placed on top of them
+        let mut points = vec![];
+        for i in 0..10 {
+            let x = i as f32;
+            points.extend_from_slice(&[
+                Vec3::new(x, 0.0, 0.0),
+                Vec3::new(x + 1.0, 0.0, 0.0),
+                Vec3::new(x, 1.0, 0.0),
+            ]);
+            points.extend_from_slice(&[
+                Vec3::new(x + 1.0, 0.0, 0.0),
+                Vec3::new(x + 1.0, 1.0, 0.0),
+                Vec3::new(x, 1.0, 0.0),
+            ]);
+        }
+        points.extend_from_slice(&[
+            // Overlaps the two triangles of the first square
+            Vec3::new(0.25, 0.25, 0.0),
+            Vec3::new(0.75, 0.25, 0.0),
+            Vec3::new(0.5, 0.75, 0.0),
+            // Spans the last two squares, but only touches the upper triangle of the last one
+            Vec3::new(8.0, 0.0, 0.0),
+            Vec3::new(10.0, 0.0, 0.0),
+            Vec3::new(9.0, 1.0, 0.0),
+            // Lies above the row, only touching it
+            Vec3::new(4.0, 1.0, 0.0),
+            Vec3::new(5.0, 1.0, 0.0),
+            Vec3::new(4.5, 2.0, 0.0),
+        ]);
+
+        let overlaps = find_overlapping_triangles(&points, DEFAULT_OVERLAP_TOLERANCE);
+        assert_eq!(
+            overlaps,
+            find_overlapping_triangles_brute_force(&points, DEFAULT_OVERLAP_TOLERANCE)
+        );
+        assert_eq!(
+            overlaps,
+            vec![(0, 20), (1, 20), (16, 21), (17, 21), (18, 21)]
+        );
+
+        // A tolerance of a tenth of the size of the row (i.e. of a whole square) hides the
+        // small triangle on top of the first square
+        let overlaps = find_overlapping_triangles(&points, 0.1);
+        assert_eq!(
+            overlaps,
+            find_overlapping_triangles_brute_force(&points, 0.1)
+        );
+        assert_eq!(overlaps, vec![]);
+    }
+
+    #[test]
+    fn test_find_intersecting_triangles_3d() {
+        let triangles = [
+            // Two faces of a tetrahedron, which share a side
+            [
+                Vec3::new(0.0, 0.0, 0.0),
+                Vec3::new(1.0, 0.0, 0.0),
+                Vec3::new(0.0, 1.0, 0.0),
+            ],
+            [
+                Vec3::new(0.0, 0.0, 0.0),
+                Vec3::new(0.0, 1.0, 0.0),
+                Vec3::new(0.0, 0.0, 1.0),
+            ],
+            // Passes through the first triangle (but not the second)
+            [
+                Vec3::new(0.25, 0.25, -0.5),
+                Vec3::new(0.25, 0.25, 0.5),
+                Vec3::new(0.5, -0.5, 0.0),
+            ],
+            // Lies in the plane of the first triangle and overlaps it, which also cuts through the
+            // second triangle along the side that they share
+            [
+                Vec3::new(0.5, 0.5, 0.0),
+                Vec3::new(-0.5, 0.5, 0.0),
+                Vec3::new(0.5, -0.5, 0.0),
+            ],
+            // Lies in the plane of the first triangle and only shares its corner
+            [
+                Vec3::new(1.0, 0.0, 0.0),
+                Vec3::new(2.0, 0.0, 0.0),
+                Vec3::new(2.0, 1.0, 0.0),
+            ],
+            // Lies above the first triangle, only touching the second one at its tip
+            [
+                Vec3::new(0.0, 0.0, 1.0),
+                Vec3::new(1.0, 0.0, 1.0),
+                Vec3::new(0.0, 1.0, 1.0),
+            ],
+        ];
+
+        assert_eq!(
+            find_intersecting_triangles_3d(&triangles),
+            vec![(0, 2), (0, 3), (1, 3), (2, 3)]
+        );
+    }
+}